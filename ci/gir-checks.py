@@ -4,7 +4,7 @@ import sys
 from pathlib import Path as P
 from subprocess import check_call as exec
 
-NATIVE_CRATES = ["gstreamer-utils"]
+NATIVE_CRATES = ["gstreamer-utils", "gstreamer-derive"]
 
 def git(*args):
     exec(["git"] + list(args))
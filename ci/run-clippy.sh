@@ -13,7 +13,7 @@ CARGO_FLAGS="--color=always -j${FDO_CI_CONCURRENT:-$cpus}"
 get_features() {
     crate=$1
     case "$crate" in
-        gstreamer-audio|gstreamer-editing-services|gstreamer-gl|gstreamer-pbutils|gstreamer-rtp|gstreamer-rtsp|gstreamer-video|gstreamer)
+        gstreamer-audio|gstreamer-editing-services|gstreamer-gl|gstreamer-pbutils|gstreamer-rtp|gstreamer-rtsp|gstreamer-sdp|gstreamer-video|gstreamer)
             echo "--features=serde,v1_26"
             ;;
         gstreamer-mse)
@@ -330,6 +330,13 @@ impl Harness {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Pushes a buffer list into the harnessed element.
+    pub fn push_list(&mut self, list: gst::BufferList) -> Result<gst::FlowSuccess, gst::FlowError> {
+        let srcpad = self.srcpad().ok_or(gst::FlowError::NotLinked)?;
+        srcpad.push_list(list)
+    }
+
     #[doc(alias = "gst_harness_push_from_src")]
     pub fn push_from_src(&mut self) -> Result<gst::FlowSuccess, gst::FlowError> {
         unsafe { try_from_glib(ffi::gst_harness_push_from_src(self.0.as_ptr())) }
@@ -593,6 +600,119 @@ impl Harness {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Cranks all clock waits of the test clock up to `time`, one after another, and then
+    /// advances the test clock to `time`.
+    ///
+    /// Returns the number of cranked clock waits.
+    pub fn crank_until(&mut self, time: gst::ClockTime) -> Result<u32, glib::BoolError> {
+        let testclock = self
+            .testclock()
+            .ok_or_else(|| glib::bool_error!("Harness has no test clock"))?;
+
+        let mut cranks = 0;
+        while testclock
+            .next_entry_time()
+            .is_some_and(|entry_time| entry_time <= time)
+        {
+            self.crank_single_clock_wait()?;
+            cranks += 1;
+        }
+
+        if testclock.time() < time {
+            self.set_time(time)?;
+        }
+
+        Ok(cranks)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Pulls events received by the harness until one of type `T` arrives, e.g.
+    /// `expect_event::<gst::event::Eos>()`.
+    ///
+    /// All other events before it are dropped. Fails if no such event arrives before
+    /// [`pull_event()`](Self::pull_event) times out.
+    pub fn expect_event<T: gst::event::ConcreteEventType>(
+        &mut self,
+    ) -> Result<T::Owned, glib::BoolError> {
+        loop {
+            let event = self
+                .pull_event()
+                .map_err(|_| glib::bool_error!("Failed to pull {:?} event", T::TYPE))?;
+            if let Ok(event) = T::from_event(event) {
+                return Ok(event);
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Pulls upstream events received by the harness until one of type `T` arrives, e.g.
+    /// `expect_upstream_event::<gst::event::Qos>()`.
+    ///
+    /// All other events before it are dropped. Fails if no such event arrives before
+    /// [`pull_upstream_event()`](Self::pull_upstream_event) times out.
+    pub fn expect_upstream_event<T: gst::event::ConcreteEventType>(
+        &mut self,
+    ) -> Result<T::Owned, glib::BoolError> {
+        loop {
+            let event = self
+                .pull_upstream_event()
+                .map_err(|_| glib::bool_error!("Failed to pull upstream {:?} event", T::TYPE))?;
+            if let Ok(event) = T::from_event(event) {
+                return Ok(event);
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the bus of the harnessed element, setting a new bus on the element if it has
+    /// none yet.
+    ///
+    /// Messages posted by the element before it has a bus are dropped, so this has to be called
+    /// before messages are expected with [`expect_message()`](Self::expect_message).
+    pub fn bus(&mut self) -> Option<gst::Bus> {
+        let element = self.element()?;
+        let bus = element.bus().unwrap_or_else(|| {
+            let bus = gst::Bus::new();
+            element.set_bus(Some(&bus));
+            bus
+        });
+
+        Some(bus)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Pops messages posted by the harnessed element until one of type `T` arrives, e.g.
+    /// `expect_message::<gst::message::Latency>()`.
+    ///
+    /// All other messages before it are dropped. Fails if the element has no bus, see
+    /// [`bus()`](Self::bus), or if no such message arrives within `timeout`. With `None` this
+    /// waits forever.
+    pub fn expect_message<T: gst::message::ConcreteMessageType>(
+        &mut self,
+        timeout: impl Into<Option<gst::ClockTime>>,
+    ) -> Result<T::Owned, glib::BoolError> {
+        let bus = self
+            .element()
+            .and_then(|element| element.bus())
+            .ok_or_else(|| glib::bool_error!("Harnessed element has no bus"))?;
+
+        let deadline = timeout
+            .into()
+            .and_then(|timeout| gst::util_get_timestamp().checked_add(timeout));
+
+        loop {
+            let timeout =
+                deadline.map(|deadline| deadline.saturating_sub(gst::util_get_timestamp()));
+            let message = bus
+                .timed_pop(timeout)
+                .ok_or_else(|| glib::bool_error!("Failed to pop {:?} message", T::TYPE))?;
+            if let Ok(message) = T::from_message(message) {
+                return Ok(message);
+            }
+        }
+    }
+
     #[inline]
     unsafe fn from_glib_full(ptr: *mut ffi::GstHarness) -> Harness {
         debug_assert!(!ptr.is_null());
@@ -634,6 +754,20 @@ impl Harness {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a harness around a new element of type `element_name`.
+    pub fn builder(element_name: &str) -> HarnessBuilder<'_> {
+        assert_initialized_main_thread!();
+        HarnessBuilder::new(HarnessElement::Name(element_name))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a harness around `element`.
+    pub fn builder_with_element<'a>(element: &impl IsA<gst::Element>) -> HarnessBuilder<'a> {
+        skip_assert_initialized!();
+        HarnessBuilder::new(HarnessElement::Element(element.as_ref().clone()))
+    }
+
     #[doc(alias = "gst_harness_new_parse")]
     pub fn new_parse(launchline: &str) -> Harness {
         assert_initialized_main_thread!();
@@ -810,6 +944,149 @@ impl Harness {
     }
 }
 
+#[derive(Debug)]
+enum HarnessElement<'a> {
+    Name(&'a str),
+    Element(gst::Element),
+}
+
+#[must_use = "The builder must be built to be used"]
+#[derive(Debug)]
+pub struct HarnessBuilder<'a> {
+    element: HarnessElement<'a>,
+    sinkpad_name: Option<&'a str>,
+    srcpad_name: Option<&'a str>,
+    src_caps: Option<gst::Caps>,
+    sink_caps: Option<gst::Caps>,
+    testclock: bool,
+    live: Option<bool>,
+    forwarding: Option<bool>,
+    upstream_latency: Option<gst::ClockTime>,
+}
+
+impl<'a> HarnessBuilder<'a> {
+    fn new(element: HarnessElement<'a>) -> Self {
+        skip_assert_initialized!();
+        Self {
+            element,
+            sinkpad_name: Some("sink"),
+            srcpad_name: Some("src"),
+            src_caps: None,
+            sink_caps: None,
+            testclock: false,
+            live: None,
+            forwarding: None,
+            upstream_latency: None,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Name of the element's sink pad to link the harness to, `sink` by default.
+    ///
+    /// `None` does not link any sink pad of the element.
+    pub fn sinkpad_name(self, sinkpad_name: Option<&'a str>) -> Self {
+        Self {
+            sinkpad_name,
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Name of the element's source pad to link the harness to, `src` by default.
+    ///
+    /// `None` does not link any source pad of the element.
+    pub fn srcpad_name(self, srcpad_name: Option<&'a str>) -> Self {
+        Self {
+            srcpad_name,
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Caps of the buffers pushed into the element, see [`Harness::set_src_caps()`].
+    pub fn src_caps(self, caps: gst::Caps) -> Self {
+        Self {
+            src_caps: Some(caps),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Caps accepted by the harness from the element, see [`Harness::set_sink_caps()`].
+    pub fn sink_caps(self, caps: gst::Caps) -> Self {
+        Self {
+            sink_caps: Some(caps),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether to use the test clock for the element, see [`Harness::use_testclock()`].
+    pub fn testclock(self, testclock: bool) -> Self {
+        Self { testclock, ..self }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether the harness answers latency queries as live, see [`Harness::set_live()`].
+    pub fn live(self, live: bool) -> Self {
+        Self {
+            live: Some(live),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether to forward allocation and other queries, see [`Harness::set_forwarding()`].
+    pub fn forwarding(self, forwarding: bool) -> Self {
+        Self {
+            forwarding: Some(forwarding),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Latency reported upstream of the element, see [`Harness::set_upstream_latency()`].
+    pub fn upstream_latency(self, latency: gst::ClockTime) -> Self {
+        Self {
+            upstream_latency: Some(latency),
+            ..self
+        }
+    }
+
+    pub fn build(self) -> Harness {
+        let mut h = match self.element {
+            HarnessElement::Name(element_name) => {
+                Harness::with_padnames(element_name, self.sinkpad_name, self.srcpad_name)
+            }
+            HarnessElement::Element(element) => {
+                Harness::with_element(&element, self.sinkpad_name, self.srcpad_name)
+            }
+        };
+
+        if let Some(forwarding) = self.forwarding {
+            h.set_forwarding(forwarding);
+        }
+        if self.testclock {
+            h.use_testclock();
+        }
+        if let Some(live) = self.live {
+            h.set_live(live);
+        }
+        if let Some(latency) = self.upstream_latency {
+            h.set_upstream_latency(latency);
+        }
+
+        match (self.src_caps, self.sink_caps) {
+            (Some(src_caps), Some(sink_caps)) => h.set_caps(src_caps, sink_caps),
+            (Some(src_caps), None) => h.set_src_caps(src_caps),
+            (None, Some(sink_caps)) => h.set_sink_caps(sink_caps),
+            (None, None) => (),
+        }
+
+        h
+    }
+}
+
 #[derive(Debug)]
 pub struct Ref<'a>(&'a Harness);
 
@@ -855,4 +1132,96 @@ mod tests {
         let buf = h.push_and_pull(buf);
         assert!(buf.is_ok());
     }
+
+    #[test]
+    fn test_builder() {
+        gst::init().unwrap();
+
+        let caps = gst::Caps::new_empty_simple("application/test");
+        let mut h = Harness::builder("identity")
+            .src_caps(caps.clone())
+            .testclock(true)
+            .build();
+
+        let event = h.expect_event::<gst::event::Caps>().unwrap();
+        assert_eq!(event.caps_owned(), caps);
+
+        h.push(gst::Buffer::new()).unwrap();
+        assert!(h.pull().is_ok());
+    }
+
+    #[test]
+    fn test_push_list() {
+        gst::init().unwrap();
+
+        let mut h = Harness::new("identity");
+        h.set_src_caps_str("application/test");
+
+        let mut list = gst::BufferList::new();
+        {
+            let list = list.get_mut().unwrap();
+            for _ in 0..3 {
+                list.add(gst::Buffer::new());
+            }
+        }
+        h.push_list(list).unwrap();
+
+        assert_eq!(h.buffers_received(), 3);
+    }
+
+    #[test]
+    fn test_expect_event() {
+        gst::init().unwrap();
+
+        let mut h = Harness::new("identity");
+        h.set_src_caps_str("application/test");
+        h.push(gst::Buffer::new()).unwrap();
+        assert!(h.push_event(gst::event::Eos::new()));
+
+        h.expect_event::<gst::event::Eos>().unwrap();
+        assert!(h.try_pull_event().is_none());
+    }
+
+    #[test]
+    fn test_expect_message() {
+        gst::init().unwrap();
+
+        let mut h = Harness::new("identity");
+        assert!(h
+            .expect_message::<gst::message::Application>(gst::ClockTime::ZERO)
+            .is_err());
+
+        let bus = h.bus().unwrap();
+        let element = h.element().unwrap();
+        assert_eq!(element.bus().as_ref(), Some(&bus));
+
+        element.post_message(gst::message::Eos::new()).unwrap();
+        element
+            .post_message(gst::message::Application::new(gst::Structure::new_empty(
+                "test",
+            )))
+            .unwrap();
+
+        let message = h
+            .expect_message::<gst::message::Application>(gst::ClockTime::SECOND)
+            .unwrap();
+        assert_eq!(message.structure().unwrap().name(), "test");
+        assert!(h
+            .expect_message::<gst::message::Application>(gst::ClockTime::from_mseconds(10))
+            .is_err());
+    }
+
+    #[test]
+    fn test_crank_until() {
+        gst::init().unwrap();
+
+        let mut h = Harness::new("identity");
+        h.use_testclock();
+
+        assert_eq!(h.crank_until(gst::ClockTime::from_seconds(1)).unwrap(), 0);
+        assert_eq!(
+            h.testclock().unwrap().time(),
+            gst::ClockTime::from_seconds(1)
+        );
+    }
 }
@@ -28,7 +28,7 @@ pub use crate::auto::*;
 mod test_clock;
 
 pub mod harness;
-pub use crate::harness::Harness;
+pub use crate::harness::{Harness, HarnessBuilder};
 
 // Re-export all the traits in a prelude module, so that applications
 // can always "use gst_check::prelude::*" without getting conflicts
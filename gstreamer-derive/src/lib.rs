@@ -0,0 +1,86 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![doc = include_str!("../README.md")]
+
+mod structure;
+mod utils;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Derives `TryFrom<&gst::StructureRef>` for a struct with named fields.
+///
+/// The name of the structure must match the name given with
+/// `#[structure(name = "...")]` on the struct, which defaults to the name of
+/// the struct. Each field is retrieved from the structure field of the same
+/// name with `_` replaced by `-`, or the name given with
+/// `#[structure(rename = "...")]` on the field.
+///
+/// Fields marked with `#[structure(optional)]` must be of type `Option<T>`
+/// and are `None` if the structure has no such field.
+///
+/// Conversion fails with a `glib::BoolError` if the name of the structure
+/// doesn't match, or a field is missing or has a different type.
+///
+/// ```rust
+/// use gstreamer_derive::FromStructure;
+///
+/// #[derive(FromStructure)]
+/// #[structure(name = "stats")]
+/// struct Stats {
+///     num_buffers: u64,
+///     #[structure(optional)]
+///     error: Option<String>,
+/// }
+///
+/// gst::init().unwrap();
+///
+/// let s = gst::Structure::builder("stats")
+///     .field("num-buffers", 10u64)
+///     .build();
+/// let stats = Stats::try_from(s.as_ref()).unwrap();
+/// assert_eq!(stats.num_buffers, 10);
+/// assert_eq!(stats.error, None);
+/// ```
+#[proc_macro_derive(FromStructure, attributes(structure))]
+pub fn from_structure_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    structure::impl_from_structure(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `From<T> for gst::Structure` for a struct `T` with named fields.
+///
+/// The structure name and field names are the same as for
+/// [`FromStructure`](derive@FromStructure). Fields marked with
+/// `#[structure(optional)]` are only set if they are `Some`.
+///
+/// ```rust
+/// use gstreamer_derive::IntoStructure;
+///
+/// #[derive(IntoStructure)]
+/// #[structure(name = "stats")]
+/// struct Stats {
+///     num_buffers: u64,
+///     #[structure(optional)]
+///     error: Option<String>,
+/// }
+///
+/// gst::init().unwrap();
+///
+/// let s = gst::Structure::from(Stats {
+///     num_buffers: 10,
+///     error: None,
+/// });
+/// assert_eq!(s.get::<u64>("num-buffers"), Ok(10));
+/// assert!(!s.has_field("error"));
+/// ```
+#[proc_macro_derive(IntoStructure, attributes(structure))]
+pub fn into_structure_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    structure::impl_into_structure(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
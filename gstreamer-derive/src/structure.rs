@@ -0,0 +1,167 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{ext::IdentExt, Data, DeriveInput, Fields, LitStr};
+
+use crate::utils::crate_ident;
+
+struct StructureField {
+    ident: syn::Ident,
+    name: String,
+    optional: bool,
+}
+
+struct StructureInfo {
+    name: String,
+    fields: Vec<StructureField>,
+}
+
+fn parse_structure(input: &DeriveInput) -> syn::Result<StructureInfo> {
+    let mut name = input.ident.unraw().to_string();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("structure"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else {
+                Err(meta.error("unsupported structure attribute"))
+            }
+        })?;
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "only structs with named fields are supported",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "only structs with named fields are supported",
+            ))
+        }
+    };
+
+    let fields = fields
+        .iter()
+        .map(|field| {
+            let ident = field.ident.clone().unwrap();
+            let mut name = ident.unraw().to_string().replace('_', "-");
+            let mut optional = false;
+
+            for attr in field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("structure"))
+            {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        name = meta.value()?.parse::<LitStr>()?.value();
+                        Ok(())
+                    } else if meta.path.is_ident("optional") {
+                        optional = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("unsupported structure field attribute"))
+                    }
+                })?;
+            }
+
+            Ok(StructureField {
+                ident,
+                name,
+                optional,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(StructureInfo { name, fields })
+}
+
+pub(crate) fn impl_from_structure(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let info = parse_structure(input)?;
+    let gst = crate_ident();
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let structure_name = &info.name;
+
+    let fields = info.fields.iter().map(|field| {
+        let ident = &field.ident;
+        let name = &field.name;
+        let getter = if field.optional {
+            quote!(get_optional)
+        } else {
+            quote!(get)
+        };
+
+        quote! {
+            #ident: structure.#getter(#name).map_err(|err| {
+                #gst::glib::bool_error!(
+                    "Failed to get field '{}' of structure '{}': {}",
+                    #name,
+                    #structure_name,
+                    err,
+                )
+            })?
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::core::convert::TryFrom<&#gst::StructureRef> for #ident #ty_generics #where_clause {
+            type Error = #gst::glib::BoolError;
+
+            fn try_from(structure: &#gst::StructureRef) -> ::core::result::Result<Self, Self::Error> {
+                if structure.name().as_str() != #structure_name {
+                    return ::core::result::Result::Err(#gst::glib::bool_error!(
+                        "Expected structure '{}' but got '{}'",
+                        #structure_name,
+                        structure.name(),
+                    ));
+                }
+
+                ::core::result::Result::Ok(Self {
+                    #(#fields,)*
+                })
+            }
+        }
+    })
+}
+
+pub(crate) fn impl_into_structure(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let info = parse_structure(input)?;
+    let gst = crate_ident();
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let structure_name = &info.name;
+
+    let fields = info.fields.iter().map(|field| {
+        let ident = &field.ident;
+        let name = &field.name;
+        if field.optional {
+            quote!(.field_if_some(#name, value.#ident))
+        } else {
+            quote!(.field(#name, value.#ident))
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::core::convert::From<#ident #ty_generics> for #gst::Structure #where_clause {
+            #[allow(unused_variables)]
+            fn from(value: #ident #ty_generics) -> Self {
+                #gst::Structure::builder(#structure_name)
+                    #(#fields)*
+                    .build()
+            }
+        }
+    })
+}
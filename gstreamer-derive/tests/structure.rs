@@ -0,0 +1,114 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use gstreamer_derive::{FromStructure, IntoStructure};
+
+#[derive(Debug, PartialEq, FromStructure, IntoStructure)]
+#[structure(name = "test-message")]
+struct TestMessage {
+    count: u32,
+    running_time: gst::ClockTime,
+    #[structure(rename = "label")]
+    name: String,
+    #[structure(optional)]
+    rate: Option<f64>,
+    #[structure(optional)]
+    caps: Option<gst::Caps>,
+}
+
+#[derive(Debug, PartialEq, FromStructure, IntoStructure)]
+struct Empty {}
+
+#[test]
+fn into_structure() {
+    gst::init().unwrap();
+
+    let s = gst::Structure::from(TestMessage {
+        count: 2,
+        running_time: gst::ClockTime::SECOND,
+        name: String::from("test"),
+        rate: Some(1.5),
+        caps: None,
+    });
+
+    assert_eq!(s.name(), "test-message");
+    assert_eq!(s.get::<u32>("count"), Ok(2));
+    assert_eq!(
+        s.get::<gst::ClockTime>("running-time"),
+        Ok(gst::ClockTime::SECOND)
+    );
+    assert_eq!(s.get::<&str>("label"), Ok("test"));
+    assert_eq!(s.get::<f64>("rate"), Ok(1.5));
+    assert!(!s.has_field("caps"));
+
+    let s = gst::Structure::from(Empty {});
+    assert_eq!(s.name(), "Empty");
+    assert_eq!(s.n_fields(), 0);
+}
+
+#[test]
+fn from_structure() {
+    gst::init().unwrap();
+
+    let caps = gst::Caps::new_empty_simple("video/x-raw");
+    let s = gst::Structure::builder("test-message")
+        .field("count", 2u32)
+        .field("running-time", gst::ClockTime::SECOND)
+        .field("label", "test")
+        .field("caps", &caps)
+        .build();
+
+    assert_eq!(
+        TestMessage::try_from(s.as_ref()).unwrap(),
+        TestMessage {
+            count: 2,
+            running_time: gst::ClockTime::SECOND,
+            name: String::from("test"),
+            rate: None,
+            caps: Some(caps),
+        }
+    );
+
+    let s = gst::Structure::builder("other-message")
+        .field("count", 2u32)
+        .build();
+    assert!(TestMessage::try_from(s.as_ref()).is_err());
+
+    let s = gst::Structure::builder("test-message")
+        .field("count", "2")
+        .field("running-time", gst::ClockTime::SECOND)
+        .field("label", "test")
+        .build();
+    assert!(TestMessage::try_from(s.as_ref()).is_err());
+
+    let s = gst::Structure::builder("test-message")
+        .field("count", 2u32)
+        .build();
+    assert!(TestMessage::try_from(s.as_ref()).is_err());
+
+    let s = gst::Structure::new_empty("Empty");
+    assert_eq!(Empty::try_from(s.as_ref()).unwrap(), Empty {});
+}
+
+#[test]
+fn roundtrip() {
+    gst::init().unwrap();
+
+    let msg = TestMessage {
+        count: 10,
+        running_time: gst::ClockTime::from_mseconds(20),
+        name: String::from("roundtrip"),
+        rate: Some(0.5),
+        caps: Some(gst::Caps::new_empty_simple("audio/x-raw")),
+    };
+
+    let structure = gst::Structure::from(msg);
+    let element = gst::ElementFactory::make("identity").build().unwrap();
+    let message = gst::message::Element::builder(structure)
+        .src(&element)
+        .build();
+
+    let msg = TestMessage::try_from(message.structure().unwrap()).unwrap();
+    assert_eq!(msg.count, 10);
+    assert_eq!(msg.name, "roundtrip");
+    assert_eq!(msg.rate, Some(0.5));
+}
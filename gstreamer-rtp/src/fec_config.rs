@@ -0,0 +1,217 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use gst::prelude::*;
+
+// rustdoc-stripper-ignore-next
+/// Configuration of ULPFEC (RFC 5109) forward error correction, optionally
+/// encapsulated in RED (RFC 2198) packets, for the streams of an `rtpbin`.
+///
+/// This creates the `rtpulpfecenc`/`rtpredenc` and
+/// `rtpreddec`/`rtpulpfecdec` elements that `rtpbin` requests with its
+/// `request-fec-encoder` and `request-fec-decoder` signals, and the caps
+/// of the FEC payload types. FlexFEC is not supported as there are no
+/// GStreamer elements for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FecConfig {
+    // rustdoc-stripper-ignore-next
+    /// The payload type of the ULPFEC packets.
+    pub ulpfec_pt: u8,
+    // rustdoc-stripper-ignore-next
+    /// The payload type of the RED packets, or `None` to send ULPFEC
+    /// packets as a separate stream.
+    pub red_pt: Option<u8>,
+    // rustdoc-stripper-ignore-next
+    /// The amount of protection in percent of the media packets.
+    pub percentage: u32,
+    // rustdoc-stripper-ignore-next
+    /// The amount of protection in percent of the important media packets,
+    /// e.g. of keyframes.
+    pub percentage_important: u32,
+    // rustdoc-stripper-ignore-next
+    /// Whether to protect multiple packets with one FEC packet.
+    pub multipacket: bool,
+    // rustdoc-stripper-ignore-next
+    /// The number of previous packets to include as redundant blocks in
+    /// RED packets.
+    pub red_distance: u32,
+    // rustdoc-stripper-ignore-next
+    /// How long the decoder keeps media packets for recovering lost ones.
+    pub storage_time: gst::ClockTime,
+}
+
+impl FecConfig {
+    // rustdoc-stripper-ignore-next
+    /// Creates a configuration with ULPFEC packets of payload type `ulpfec_pt`
+    /// that protect 10% of the packets.
+    pub fn new(ulpfec_pt: u8) -> Self {
+        skip_assert_initialized!();
+        Self {
+            ulpfec_pt,
+            red_pt: None,
+            percentage: 10,
+            percentage_important: 10,
+            multipacket: true,
+            red_distance: 0,
+            storage_time: gst::ClockTime::from_mseconds(250),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the caps of the ULPFEC payload type for `media`, e.g.
+    /// `video`, with `clock_rate`.
+    pub fn ulpfec_caps(&self, media: &str, clock_rate: u32) -> gst::Caps {
+        fec_caps(media, "ULPFEC", clock_rate, self.ulpfec_pt)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the caps of the RED payload type for `media`, if enabled.
+    pub fn red_caps(&self, media: &str, clock_rate: u32) -> Option<gst::Caps> {
+        self.red_pt.map(|pt| fec_caps(media, "RED", clock_rate, pt))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates the FEC encoder for a session, which is either an
+    /// `rtpulpfecenc` or a bin of it followed by an `rtpredenc`.
+    pub fn encoder(&self) -> Result<gst::Element, glib::BoolError> {
+        let ulpfecenc = gst::ElementFactory::make("rtpulpfecenc")
+            .property("pt", self.ulpfec_pt as u32)
+            .property("percentage", self.percentage)
+            .property("percentage-important", self.percentage_important)
+            .property("multipacket", self.multipacket)
+            .build()?;
+
+        let Some(red_pt) = self.red_pt else {
+            return Ok(ulpfecenc);
+        };
+
+        let redenc = gst::ElementFactory::make("rtpredenc")
+            .property("pt", red_pt as i32)
+            .property("distance", self.red_distance)
+            .property("allow-no-red-blocks", true)
+            .build()?;
+
+        fec_bin(&ulpfecenc, &redenc)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates the FEC decoder for session `session_id` of `rtpbin`, which
+    /// is either an `rtpulpfecdec` or a bin of an `rtpreddec` followed by
+    /// it.
+    ///
+    /// The decoder recovers lost packets from the internal storage of the
+    /// session.
+    pub fn decoder(
+        &self,
+        rtpbin: &impl IsA<gst::Element>,
+        session_id: u32,
+    ) -> Result<gst::Element, glib::BoolError> {
+        let storage = rtpbin.emit_by_name::<glib::Object>("get-internal-storage", &[&session_id]);
+        storage.set_property("size-time", self.storage_time.nseconds());
+
+        let ulpfecdec = gst::ElementFactory::make("rtpulpfecdec")
+            .property("pt", self.ulpfec_pt as u32)
+            .property("storage", &storage)
+            .build()?;
+
+        let Some(red_pt) = self.red_pt else {
+            return Ok(ulpfecdec);
+        };
+
+        let reddec = gst::ElementFactory::make("rtpreddec")
+            .property("pt", red_pt as i32)
+            .build()?;
+
+        fec_bin(&reddec, &ulpfecdec)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Connects to the `request-fec-encoder` and `request-fec-decoder`
+    /// signals of `rtpbin` to use this configuration for all its sessions.
+    pub fn connect_rtpbin(&self, rtpbin: &impl IsA<gst::Element>) {
+        let config = self.clone();
+        rtpbin.connect("request-fec-encoder", false, move |args| {
+            let rtpbin = args[0].get::<gst::Element>().unwrap();
+            match config.encoder() {
+                Ok(encoder) => Some(encoder.to_value()),
+                Err(err) => {
+                    gst::warning!(
+                        gst::CAT_RUST,
+                        obj = rtpbin,
+                        "Failed to create FEC encoder: {}",
+                        err
+                    );
+                    None
+                }
+            }
+        });
+
+        let config = self.clone();
+        rtpbin.connect("request-fec-decoder", false, move |args| {
+            let rtpbin = args[0].get::<gst::Element>().unwrap();
+            let session_id = args[1].get::<u32>().unwrap();
+            match config.decoder(&rtpbin, session_id) {
+                Ok(decoder) => Some(decoder.to_value()),
+                Err(err) => {
+                    gst::warning!(
+                        gst::CAT_RUST,
+                        obj = rtpbin,
+                        "Failed to create FEC decoder for session {}: {}",
+                        session_id,
+                        err
+                    );
+                    None
+                }
+            }
+        });
+    }
+}
+
+fn fec_caps(media: &str, encoding_name: &str, clock_rate: u32, pt: u8) -> gst::Caps {
+    skip_assert_initialized!();
+    gst::Caps::builder("application/x-rtp")
+        .field("media", media)
+        .field("encoding-name", encoding_name)
+        .field("clock-rate", clock_rate as i32)
+        .field("payload", pt as i32)
+        .build()
+}
+
+// Wraps `first` followed by `second` in a bin with ghost pads
+fn fec_bin(first: &gst::Element, second: &gst::Element) -> Result<gst::Element, glib::BoolError> {
+    skip_assert_initialized!();
+    let bin = gst::Bin::new();
+    bin.add_many([first, second])?;
+    first.link(second)?;
+
+    let sinkpad = gst::GhostPad::with_target(&first.static_pad("sink").unwrap())?;
+    let srcpad = gst::GhostPad::with_target(&second.static_pad("src").unwrap())?;
+    bin.add_pad(&sinkpad)?;
+    bin.add_pad(&srcpad)?;
+
+    Ok(bin.upcast())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caps() {
+        gst::init().unwrap();
+
+        let mut config = FecConfig::new(100);
+        assert_eq!(config.red_caps("video", 90000), None);
+
+        config.red_pt = Some(101);
+        let caps = config.ulpfec_caps("video", 90000);
+        let s = caps.structure(0).unwrap();
+        assert_eq!(s.get::<&str>("encoding-name"), Ok("ULPFEC"));
+        assert_eq!(s.get::<i32>("payload"), Ok(100));
+
+        let caps = config.red_caps("video", 90000).unwrap();
+        let s = caps.structure(0).unwrap();
+        assert_eq!(s.get::<&str>("encoding-name"), Ok("RED"));
+        assert_eq!(s.get::<i32>("clock-rate"), Ok(90000));
+        assert_eq!(s.get::<i32>("payload"), Ok(101));
+    }
+}
@@ -7,6 +7,9 @@
 
 pub use glib;
 pub use gst;
+#[cfg(feature = "rtsp")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rtsp")))]
+pub use gst_rtsp;
 pub use gstreamer_rtp_sys as ffi;
 
 macro_rules! assert_initialized_main_thread {
@@ -34,6 +37,24 @@ pub mod rtp_buffer;
 pub use crate::rtp_buffer::{
     calc_header_len, calc_packet_len, calc_payload_len, compare_seqnum, ext_timestamp, RTPBuffer,
 };
+mod rtp_header_extension_map;
+mod rtp_session_builder;
+pub use crate::rtp_header_extension_map::RTPHeaderExtensionMap;
+pub use crate::rtp_session_builder::{
+    RTPSession, RTPSessionBuilder, RTPSessionTransport, RTPUdpTransport,
+};
+
+mod fec_config;
+pub use crate::fec_config::FecConfig;
+
+pub mod rtcp_buffer;
+pub use crate::rtcp_buffer::{RTCPApp, RTCPBuffer, RTCPBye, RTCPPacket};
+mod rtcp_twcc;
+pub use crate::rtcp_twcc::{
+    RTCPTwccFeedback, RTCPTwccPacketStatus, RTCPTwccPackets, TWCC_EXTENSION_URI,
+};
+mod rtcp_xr;
+pub use crate::rtcp_xr::*;
 #[cfg(feature = "v1_20")]
 #[cfg_attr(docsrs, doc(cfg(feature = "v1_20")))]
 pub mod rtp_header_extension;
@@ -64,7 +85,8 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "v1_20")))]
     pub use crate::rtp_header_extension::RTPHeaderExtensionExtManual;
     pub use crate::{
-        auto::traits::*, rtp_base_depayload::RTPBaseDepayloadExtManual, rtp_buffer::RTPBufferExt,
+        auto::traits::*, rtcp_buffer::RTCPBufferExt, rtp_base_depayload::RTPBaseDepayloadExtManual,
+        rtp_buffer::RTPBufferExt,
     };
 }
 
@@ -0,0 +1,537 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{fmt, marker::PhantomData, mem, slice, str};
+
+use glib::translate::{from_glib, mut_override, FromGlibPtrFull, IntoGlib};
+
+use crate::{
+    ffi,
+    rtcp_twcc::{RTCPTwccFeedback, TWCC_FMT},
+    rtcp_xr::{RTCPXRBlock, RTCPXRBlocks},
+    rtp_buffer::{Readable, Writable},
+    RTCPType,
+};
+
+const RTCP_VERSION: u8 = 2;
+
+pub struct RTCPBuffer<'a, T> {
+    rtcp_buffer: ffi::GstRTCPBuffer,
+    phantom: PhantomData<&'a T>,
+}
+
+unsafe impl<T> Send for RTCPBuffer<'_, T> {}
+unsafe impl<T> Sync for RTCPBuffer<'_, T> {}
+
+impl<T> fmt::Debug for RTCPBuffer<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RTCPBuffer")
+            .field("rtcp_buffer", &self.rtcp_buffer)
+            .finish()
+    }
+}
+
+impl<'a> RTCPBuffer<'a, Readable> {
+    #[inline]
+    pub fn from_buffer_readable(
+        buffer: &'a gst::BufferRef,
+    ) -> Result<RTCPBuffer<'a, Readable>, glib::BoolError> {
+        skip_assert_initialized!();
+        unsafe {
+            let mut rtcp_buffer = mem::MaybeUninit::zeroed();
+            let res: bool = from_glib(ffi::gst_rtcp_buffer_map(
+                mut_override(buffer.as_ptr()),
+                gst::ffi::GST_MAP_READ,
+                rtcp_buffer.as_mut_ptr(),
+            ));
+
+            if res {
+                Ok(RTCPBuffer {
+                    rtcp_buffer: rtcp_buffer.assume_init(),
+                    phantom: PhantomData,
+                })
+            } else {
+                Err(glib::bool_error!("Failed to map RTCP buffer readable"))
+            }
+        }
+    }
+}
+
+impl<'a> RTCPBuffer<'a, Writable> {
+    #[inline]
+    pub fn from_buffer_writable(
+        buffer: &'a mut gst::BufferRef,
+    ) -> Result<RTCPBuffer<'a, Writable>, glib::BoolError> {
+        skip_assert_initialized!();
+        unsafe {
+            let mut rtcp_buffer = mem::MaybeUninit::zeroed();
+            let res: bool = from_glib(ffi::gst_rtcp_buffer_map(
+                buffer.as_mut_ptr(),
+                gst::ffi::GST_MAP_READWRITE,
+                rtcp_buffer.as_mut_ptr(),
+            ));
+
+            if res {
+                Ok(RTCPBuffer {
+                    rtcp_buffer: rtcp_buffer.assume_init(),
+                    phantom: PhantomData,
+                })
+            } else {
+                Err(glib::bool_error!("Failed to map RTCP buffer writable"))
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Appends an extended report (RFC 3611) packet from `ssrc` with the
+    /// report `blocks`.
+    pub fn add_xr_packet(
+        &mut self,
+        ssrc: u32,
+        blocks: &[RTCPXRBlock],
+    ) -> Result<(), glib::BoolError> {
+        let mut data = vec![0; 8];
+        data[4..8].copy_from_slice(&ssrc.to_be_bytes());
+        for block in blocks {
+            block.write(&mut data)?;
+        }
+        self.append_packet(RTCPType::Xr, 0, &mut data)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Appends an application-defined packet from `ssrc` with the 4
+    /// character ASCII `name`, the `subtype` and the application-dependent
+    /// `data`, whose length must be a multiple of 4 bytes.
+    pub fn add_app_packet(
+        &mut self,
+        ssrc: u32,
+        subtype: u8,
+        name: &str,
+        data: &[u8],
+    ) -> Result<(), glib::BoolError> {
+        if subtype >= 32 {
+            return Err(glib::bool_error!("Invalid APP subtype {}", subtype));
+        }
+        if name.len() != 4 || !name.is_ascii() {
+            return Err(glib::bool_error!("Invalid APP name {}", name));
+        }
+        if data.len() % 4 != 0 {
+            return Err(glib::bool_error!(
+                "APP data length {} is not a multiple of 4",
+                data.len()
+            ));
+        }
+
+        let mut packet = Vec::with_capacity(12 + data.len());
+        packet.extend_from_slice(&[0; 4]);
+        packet.extend_from_slice(&ssrc.to_be_bytes());
+        packet.extend_from_slice(name.as_bytes());
+        packet.extend_from_slice(data);
+        self.append_packet(RTCPType::App, subtype, &mut packet)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Appends a goodbye packet for the sources `ssrcs` with an optional
+    /// `reason` for leaving.
+    pub fn add_bye_packet(
+        &mut self,
+        ssrcs: &[u32],
+        reason: Option<&str>,
+    ) -> Result<(), glib::BoolError> {
+        if ssrcs.len() >= 32 {
+            return Err(glib::bool_error!("Too many SSRCs for BYE packet"));
+        }
+
+        let mut packet = vec![0; 4];
+        for ssrc in ssrcs {
+            packet.extend_from_slice(&ssrc.to_be_bytes());
+        }
+        if let Some(reason) = reason {
+            if reason.len() > 255 {
+                return Err(glib::bool_error!("BYE reason too long"));
+            }
+            packet.push(reason.len() as u8);
+            packet.extend_from_slice(reason.as_bytes());
+            packet.resize(packet.len().next_multiple_of(4), 0);
+        }
+        self.append_packet(RTCPType::Bye, ssrcs.len() as u8, &mut packet)
+    }
+
+    // Writes the header of the packet `data`, which starts with 4 bytes of
+    // space for the header, and appends it after the last packet
+    pub(crate) fn append_packet(
+        &mut self,
+        type_: RTCPType,
+        count: u8,
+        data: &mut [u8],
+    ) -> Result<(), glib::BoolError> {
+        debug_assert!(data.len() >= 4 && data.len() % 4 == 0 && count < 32);
+        let words = data.len() / 4 - 1;
+        if words > u16::MAX as usize {
+            return Err(glib::bool_error!("RTCP packet too big"));
+        }
+
+        let map = &mut self.rtcp_buffer.map;
+        if map.size + data.len() > map.maxsize {
+            return Err(glib::bool_error!("Not enough space for RTCP packet"));
+        }
+
+        data[0] = (RTCP_VERSION << 6) | count;
+        data[1] = type_.into_glib() as u8;
+        data[2..4].copy_from_slice(&(words as u16).to_be_bytes());
+
+        unsafe {
+            let dest = slice::from_raw_parts_mut(map.data.add(map.size), data.len());
+            dest.copy_from_slice(data);
+        }
+        map.size += data.len();
+
+        Ok(())
+    }
+}
+
+impl<T> RTCPBuffer<'_, T> {
+    fn data(&self) -> &[u8] {
+        unsafe {
+            let map = &self.rtcp_buffer.map;
+            if map.size == 0 {
+                &[]
+            } else {
+                slice::from_raw_parts(map.data, map.size)
+            }
+        }
+    }
+
+    #[doc(alias = "get_packet_count")]
+    #[doc(alias = "gst_rtcp_buffer_get_packet_count")]
+    pub fn packet_count(&self) -> u32 {
+        unsafe { ffi::gst_rtcp_buffer_get_packet_count(mut_override(&self.rtcp_buffer)) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns an iterator over the packets of the compound RTCP packet.
+    pub fn packets(&self) -> RTCPPackets<'_> {
+        RTCPPackets { data: self.data() }
+    }
+
+    pub fn buffer(&self) -> &gst::BufferRef {
+        unsafe {
+            let ptr = self.rtcp_buffer.buffer;
+
+            debug_assert!(!ptr.is_null());
+
+            gst::BufferRef::from_ptr(ptr)
+        }
+    }
+
+    #[inline]
+    pub fn as_ptr(&self) -> *const ffi::GstRTCPBuffer {
+        &self.rtcp_buffer as *const ffi::GstRTCPBuffer
+    }
+
+    #[inline]
+    pub fn as_mut_ptr(&self) -> *mut ffi::GstRTCPBuffer {
+        &self.rtcp_buffer as *const ffi::GstRTCPBuffer as *mut ffi::GstRTCPBuffer
+    }
+}
+
+impl<T> Drop for RTCPBuffer<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            ffi::gst_rtcp_buffer_unmap(&mut self.rtcp_buffer);
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A single packet of a compound RTCP packet.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct RTCPPacket<'a> {
+    data: &'a [u8],
+}
+
+impl fmt::Debug for RTCPPacket<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RTCPPacket")
+            .field("type", &self.type_())
+            .field("count", &self.count())
+            .field("padding", &self.has_padding())
+            .field("length", &self.data.len())
+            .finish()
+    }
+}
+
+impl<'a> RTCPPacket<'a> {
+    #[doc(alias = "get_type")]
+    pub fn type_(&self) -> RTCPType {
+        unsafe { from_glib(self.data[1] as ffi::GstRTCPType) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The count field of the header, e.g. the number of report blocks of
+    /// a receiver report or the subtype of an APP packet.
+    #[doc(alias = "get_count")]
+    pub fn count(&self) -> u8 {
+        self.data[0] & 0x1F
+    }
+
+    #[doc(alias = "get_padding")]
+    pub fn has_padding(&self) -> bool {
+        self.data[0] & 0x20 != 0
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The whole packet including the header and padding.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The packet without the 4 byte header and the padding.
+    pub fn payload(&self) -> &'a [u8] {
+        let payload = &self.data[4..];
+        if !self.has_padding() {
+            return payload;
+        }
+
+        let padding = payload.last().copied().unwrap_or(0) as usize;
+        &payload[..payload.len().saturating_sub(padding)]
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns an iterator over the report blocks of an extended report
+    /// packet, or `None` if this is another packet type.
+    pub fn xr_blocks(&self) -> Option<(u32, RTCPXRBlocks<'a>)> {
+        if self.type_() != RTCPType::Xr {
+            return None;
+        }
+
+        let payload = self.payload();
+        let ssrc = u32::from_be_bytes(payload.get(..4)?.try_into().unwrap());
+        Some((ssrc, RTCPXRBlocks::new(&payload[4..])))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses an application-defined packet, or returns `None` if this is
+    /// another packet type or the packet is invalid.
+    pub fn app(&self) -> Option<RTCPApp<'a>> {
+        if self.type_() != RTCPType::App {
+            return None;
+        }
+
+        let payload = self.payload();
+        let name = str::from_utf8(payload.get(4..8)?).ok()?;
+        Some(RTCPApp {
+            ssrc: u32::from_be_bytes(payload[..4].try_into().unwrap()),
+            subtype: self.count(),
+            name,
+            data: &payload[8..],
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses a goodbye packet, or returns `None` if this is another packet
+    /// type or the packet is invalid.
+    pub fn bye(&self) -> Option<RTCPBye<'a>> {
+        if self.type_() != RTCPType::Bye {
+            return None;
+        }
+
+        let payload = self.payload();
+        let (ssrcs, rest) = payload.split_at_checked(self.count() as usize * 4)?;
+        let ssrcs = ssrcs
+            .chunks_exact(4)
+            .map(|ssrc| u32::from_be_bytes(ssrc.try_into().unwrap()))
+            .collect();
+        let reason = match rest.first() {
+            Some(&len) if len > 0 => Some(str::from_utf8(rest.get(1..1 + len as usize)?).ok()?),
+            _ => None,
+        };
+
+        Some(RTCPBye { ssrcs, reason })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses a transport-wide congestion control feedback packet, or
+    /// returns `None` if this is another packet type or the packet is
+    /// invalid.
+    pub fn twcc(&self) -> Option<RTCPTwccFeedback<'a>> {
+        if self.type_() != RTCPType::Rtpfb || self.count() != TWCC_FMT {
+            return None;
+        }
+
+        RTCPTwccFeedback::parse(self.payload())
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// An application-defined RTCP packet, as returned by [`RTCPPacket::app()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RTCPApp<'a> {
+    pub ssrc: u32,
+    pub subtype: u8,
+    pub name: &'a str,
+    pub data: &'a [u8],
+}
+
+// rustdoc-stripper-ignore-next
+/// A goodbye RTCP packet, as returned by [`RTCPPacket::bye()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RTCPBye<'a> {
+    pub ssrcs: Vec<u32>,
+    pub reason: Option<&'a str>,
+}
+
+// rustdoc-stripper-ignore-next
+/// Iterator over the packets of an [`RTCPBuffer`], as returned by
+/// [`RTCPBuffer::packets()`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RTCPPackets<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for RTCPPackets<'a> {
+    type Item = RTCPPacket<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let header = self.data.get(..4)?;
+        let len = (u16::from_be_bytes([header[2], header[3]]) as usize + 1) * 4;
+        if header[0] >> 6 != RTCP_VERSION || self.data.len() < len {
+            self.data = &[];
+            return None;
+        }
+
+        let (data, rest) = self.data.split_at(len);
+        self.data = rest;
+        Some(RTCPPacket { data })
+    }
+}
+
+impl std::iter::FusedIterator for RTCPPackets<'_> {}
+
+pub trait RTCPBufferExt {
+    fn new_rtcp(mtu: u32) -> gst::Buffer;
+}
+
+impl RTCPBufferExt for gst::Buffer {
+    // rustdoc-stripper-ignore-next
+    /// Creates an empty RTCP buffer with space for packets of up to `mtu`
+    /// bytes.
+    #[doc(alias = "gst_rtcp_buffer_new")]
+    fn new_rtcp(mtu: u32) -> gst::Buffer {
+        assert_initialized_main_thread!();
+        unsafe { from_glib_full(ffi::gst_rtcp_buffer_new(mtu)) }
+    }
+}
+
+#[doc(alias = "gst_rtcp_buffer_validate")]
+pub fn validate(buffer: &gst::BufferRef) -> bool {
+    skip_assert_initialized!();
+    unsafe { from_glib(ffi::gst_rtcp_buffer_validate(mut_override(buffer.as_ptr()))) }
+}
+
+#[doc(alias = "gst_rtcp_buffer_validate_reduced")]
+pub fn validate_reduced(buffer: &gst::BufferRef) -> bool {
+    skip_assert_initialized!();
+    unsafe {
+        from_glib(ffi::gst_rtcp_buffer_validate_reduced(mut_override(
+            buffer.as_ptr(),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtcp_xr::{RTCPXRDlrr, RTCPXRVoipMetrics};
+
+    #[test]
+    fn test_xr_packet() {
+        gst::init().unwrap();
+
+        let blocks = [
+            RTCPXRBlock::ReceiverReferenceTime {
+                ntp_timestamp: 0x0102_0304_0506_0708,
+            },
+            RTCPXRBlock::Dlrr(vec![RTCPXRDlrr {
+                ssrc: 0x1234,
+                last_rr: 10,
+                delay: 20,
+            }]),
+            RTCPXRBlock::VoipMetrics(RTCPXRVoipMetrics {
+                ssrc: 0x1234,
+                loss_rate: 12,
+                mos_lq: 41,
+                ..Default::default()
+            }),
+        ];
+
+        let mut buffer = gst::Buffer::new_rtcp(1400);
+        {
+            let buffer = buffer.get_mut().unwrap();
+            let mut rtcp = RTCPBuffer::from_buffer_writable(buffer).unwrap();
+            rtcp.add_xr_packet(0x5678, &blocks).unwrap();
+        }
+        assert_eq!(buffer.size(), 8 + 12 + 16 + 36);
+        assert!(validate_reduced(&buffer));
+
+        let rtcp = RTCPBuffer::from_buffer_readable(&buffer).unwrap();
+        assert_eq!(rtcp.packet_count(), 1);
+        let packet = rtcp.packets().next().unwrap();
+        assert_eq!(packet.type_(), RTCPType::Xr);
+        let (ssrc, parsed) = packet.xr_blocks().unwrap();
+        assert_eq!(ssrc, 0x5678);
+        assert_eq!(parsed.collect::<Vec<_>>(), blocks);
+    }
+
+    #[test]
+    fn test_app_bye_packets() {
+        gst::init().unwrap();
+
+        let mut buffer = gst::Buffer::new_rtcp(1400);
+        {
+            let buffer = buffer.get_mut().unwrap();
+            let mut rtcp = RTCPBuffer::from_buffer_writable(buffer).unwrap();
+            rtcp.add_app_packet(0x1234, 3, "TEST", &[1, 2, 3, 4])
+                .unwrap();
+            rtcp.add_bye_packet(&[0x1234, 0x5678], Some("done"))
+                .unwrap();
+            rtcp.add_bye_packet(&[0x9abc], None).unwrap();
+
+            assert!(rtcp.add_app_packet(0x1234, 32, "TEST", &[]).is_err());
+            assert!(rtcp.add_app_packet(0x1234, 0, "TOOLONG", &[]).is_err());
+            assert!(rtcp.add_app_packet(0x1234, 0, "TEST", &[1]).is_err());
+        }
+        assert_eq!(buffer.size(), 16 + 20 + 8);
+        assert!(validate_reduced(&buffer));
+
+        let rtcp = RTCPBuffer::from_buffer_readable(&buffer).unwrap();
+        let packets = rtcp.packets().collect::<Vec<_>>();
+        assert_eq!(packets.len(), 3);
+        assert_eq!(
+            packets[0].app(),
+            Some(RTCPApp {
+                ssrc: 0x1234,
+                subtype: 3,
+                name: "TEST",
+                data: &[1, 2, 3, 4],
+            })
+        );
+        assert_eq!(packets[0].bye(), None);
+        assert_eq!(
+            packets[1].bye(),
+            Some(RTCPBye {
+                ssrcs: vec![0x1234, 0x5678],
+                reason: Some("done"),
+            })
+        );
+        assert_eq!(
+            packets[2].bye(),
+            Some(RTCPBye {
+                ssrcs: vec![0x9abc],
+                reason: None,
+            })
+        );
+    }
+}
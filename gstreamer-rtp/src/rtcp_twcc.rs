@@ -0,0 +1,466 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::fmt;
+
+use crate::{
+    rtcp_buffer::RTCPBuffer,
+    rtp_buffer::{RTPBuffer, Writable},
+    RTCPType,
+};
+
+// rustdoc-stripper-ignore-next
+/// The URI of the transport-wide sequence number RTP header extension.
+pub const TWCC_EXTENSION_URI: &str =
+    "http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01";
+
+// The feedback message type of transport-wide congestion control feedback
+pub(crate) const TWCC_FMT: u8 = 15;
+
+// Receive deltas are in units of 250µs, the reference time in units of 64ms
+const DELTA_UNIT: u64 = 250_000;
+const REFERENCE_TIME_UNIT: u64 = 64_000_000;
+
+const STATUS_NOT_RECEIVED: u8 = 0;
+const STATUS_SMALL_DELTA: u8 = 1;
+const STATUS_LARGE_DELTA: u8 = 2;
+
+impl RTPBuffer<'_, Writable> {
+    // rustdoc-stripper-ignore-next
+    /// Adds the transport-wide sequence number `seqnum` as header extension
+    /// with `id`.
+    pub fn add_twcc_seqnum(&mut self, id: u8, seqnum: u16) -> Result<(), glib::BoolError> {
+        self.add_extension(id, &seqnum.to_be_bytes())
+    }
+}
+
+impl<T> RTPBuffer<'_, T> {
+    // rustdoc-stripper-ignore-next
+    /// Returns the transport-wide sequence number from the header extension
+    /// with `id`.
+    pub fn twcc_seqnum(&self, id: u8) -> Option<u16> {
+        let data = self.extension(id)?;
+        Some(u16::from_be_bytes(data.get(..2)?.try_into().unwrap()))
+    }
+}
+
+impl RTCPBuffer<'_, Writable> {
+    // rustdoc-stripper-ignore-next
+    /// Appends a transport-wide congestion control feedback packet from
+    /// `sender_ssrc` about the packets starting at `base_seqnum`.
+    ///
+    /// `deltas` contains the arrival time of each packet relative to the
+    /// previous received packet, or to `reference_time` for the first one,
+    /// or `None` if the packet was not received. Deltas are rounded to
+    /// multiples of 250µs and the reference time is truncated to multiples
+    /// of 64ms.
+    pub fn add_twcc_packet(
+        &mut self,
+        sender_ssrc: u32,
+        media_ssrc: u32,
+        feedback_count: u8,
+        reference_time: gst::ClockTime,
+        base_seqnum: u16,
+        deltas: &[Option<gst::Signed<gst::ClockTime>>],
+    ) -> Result<(), glib::BoolError> {
+        let status_count = u16::try_from(deltas.len())
+            .map_err(|_| glib::bool_error!("Too many packets for TWCC feedback"))?;
+
+        let mut statuses = Vec::with_capacity(deltas.len());
+        let mut delta_data = Vec::with_capacity(deltas.len());
+        for delta in deltas {
+            let Some(delta) = delta else {
+                statuses.push(STATUS_NOT_RECEIVED);
+                continue;
+            };
+
+            let ticks = match delta {
+                gst::Signed::Positive(delta) => {
+                    ((delta.nseconds() + DELTA_UNIT / 2) / DELTA_UNIT) as i64
+                }
+                gst::Signed::Negative(delta) => {
+                    -(((delta.nseconds() + DELTA_UNIT / 2) / DELTA_UNIT) as i64)
+                }
+            };
+            if let Ok(ticks) = u8::try_from(ticks) {
+                statuses.push(STATUS_SMALL_DELTA);
+                delta_data.push(ticks);
+            } else if let Ok(ticks) = i16::try_from(ticks) {
+                statuses.push(STATUS_LARGE_DELTA);
+                delta_data.extend_from_slice(&ticks.to_be_bytes());
+            } else {
+                return Err(glib::bool_error!("TWCC delta {} too large", delta));
+            }
+        }
+
+        let reference_time = (reference_time.nseconds() / REFERENCE_TIME_UNIT) as u32 & 0xFF_FFFF;
+
+        let mut data = vec![0; 4];
+        data.extend_from_slice(&sender_ssrc.to_be_bytes());
+        data.extend_from_slice(&media_ssrc.to_be_bytes());
+        data.extend_from_slice(&base_seqnum.to_be_bytes());
+        data.extend_from_slice(&status_count.to_be_bytes());
+        data.extend_from_slice(&reference_time.to_be_bytes()[1..]);
+        data.push(feedback_count);
+        for chunk in encode_chunks(&statuses) {
+            data.extend_from_slice(&chunk.to_be_bytes());
+        }
+        data.extend_from_slice(&delta_data);
+        data.resize(data.len().next_multiple_of(4), 0);
+
+        self.append_packet(RTCPType::Rtpfb, TWCC_FMT, &mut data)
+    }
+}
+
+fn encode_chunks(statuses: &[u8]) -> Vec<u16> {
+    skip_assert_initialized!();
+    let mut chunks = Vec::new();
+    let mut statuses = statuses;
+    while let Some(&status) = statuses.first() {
+        let run = statuses
+            .iter()
+            .take(0x1FFF)
+            .take_while(|s| **s == status)
+            .count();
+
+        let len = if run >= 7 {
+            chunks.push(((status as u16) << 13) | run as u16);
+            run
+        } else if statuses.iter().take(14).all(|s| *s <= STATUS_SMALL_DELTA) {
+            let len = statuses.len().min(14);
+            let chunk = statuses[..len]
+                .iter()
+                .enumerate()
+                .fold(0x8000, |chunk, (idx, s)| {
+                    chunk | ((*s as u16) << (13 - idx))
+                });
+            chunks.push(chunk);
+            len
+        } else {
+            let len = statuses.len().min(7);
+            let chunk = statuses[..len]
+                .iter()
+                .enumerate()
+                .fold(0xC000, |chunk, (idx, s)| {
+                    chunk | ((*s as u16) << (12 - 2 * idx))
+                });
+            chunks.push(chunk);
+            len
+        };
+
+        statuses = &statuses[len..];
+    }
+
+    chunks
+}
+
+// Returns the number of statuses in `chunk`
+fn chunk_len(chunk: u16) -> usize {
+    skip_assert_initialized!();
+    match chunk >> 14 {
+        0 | 1 => (chunk & 0x1FFF) as usize,
+        2 => 14,
+        _ => 7,
+    }
+}
+
+fn chunk_status(chunk: u16, idx: usize) -> u8 {
+    skip_assert_initialized!();
+    match chunk >> 14 {
+        0 | 1 => ((chunk >> 13) & 0x3) as u8,
+        2 => ((chunk >> (13 - idx)) & 0x1) as u8,
+        _ => ((chunk >> (12 - 2 * idx)) & 0x3) as u8,
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A transport-wide congestion control feedback packet, as returned by
+/// [`RTCPPacket::twcc()`](crate::RTCPPacket::twcc).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct RTCPTwccFeedback<'a> {
+    sender_ssrc: u32,
+    media_ssrc: u32,
+    base_seqnum: u16,
+    status_count: u16,
+    reference_time: u32,
+    feedback_count: u8,
+    chunks: &'a [u8],
+    deltas: &'a [u8],
+}
+
+impl fmt::Debug for RTCPTwccFeedback<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RTCPTwccFeedback")
+            .field("sender_ssrc", &self.sender_ssrc)
+            .field("media_ssrc", &self.media_ssrc)
+            .field("base_seqnum", &self.base_seqnum)
+            .field("status_count", &self.status_count)
+            .field("reference_time", &self.reference_time())
+            .field("feedback_count", &self.feedback_count)
+            .finish()
+    }
+}
+
+impl<'a> RTCPTwccFeedback<'a> {
+    pub(crate) fn parse(payload: &'a [u8]) -> Option<Self> {
+        skip_assert_initialized!();
+        let header = payload.get(..16)?;
+        let status_count = u16::from_be_bytes([header[10], header[11]]);
+
+        // The chunks are followed by the deltas
+        let mut chunks_len = 0;
+        let mut covered = 0;
+        while covered < status_count as usize {
+            let chunk = payload.get(16 + chunks_len..16 + chunks_len + 2)?;
+            let len = chunk_len(u16::from_be_bytes([chunk[0], chunk[1]]));
+            if len == 0 {
+                return None;
+            }
+            covered += len;
+            chunks_len += 2;
+        }
+
+        Some(RTCPTwccFeedback {
+            sender_ssrc: u32::from_be_bytes(header[0..4].try_into().unwrap()),
+            media_ssrc: u32::from_be_bytes(header[4..8].try_into().unwrap()),
+            base_seqnum: u16::from_be_bytes([header[8], header[9]]),
+            status_count,
+            reference_time: u32::from_be_bytes([0, header[12], header[13], header[14]]),
+            feedback_count: header[15],
+            chunks: &payload[16..16 + chunks_len],
+            deltas: &payload[16 + chunks_len..],
+        })
+    }
+
+    pub fn sender_ssrc(&self) -> u32 {
+        self.sender_ssrc
+    }
+
+    pub fn media_ssrc(&self) -> u32 {
+        self.media_ssrc
+    }
+
+    pub fn base_seqnum(&self) -> u16 {
+        self.base_seqnum
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The number of packets that this feedback reports about.
+    pub fn status_count(&self) -> u16 {
+        self.status_count
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The arrival time that the delta of the first received packet is
+    /// relative to, which wraps around after 24 bits in units of 64ms.
+    pub fn reference_time(&self) -> gst::ClockTime {
+        gst::ClockTime::from_nseconds(self.reference_time as u64 * REFERENCE_TIME_UNIT)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The counter of feedback packets sent for detecting lost feedback.
+    pub fn feedback_count(&self) -> u8 {
+        self.feedback_count
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns an iterator over the status of each reported packet.
+    pub fn packets(&self) -> RTCPTwccPackets<'a> {
+        RTCPTwccPackets {
+            chunks: self.chunks,
+            deltas: self.deltas,
+            chunk: 0,
+            chunk_pos: 0,
+            chunk_len: 0,
+            seqnum: self.base_seqnum,
+            remaining: self.status_count,
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// The reception status of a packet in an [`RTCPTwccFeedback`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RTCPTwccPacketStatus {
+    // rustdoc-stripper-ignore-next
+    /// The transport-wide sequence number of the packet.
+    pub seqnum: u16,
+    // rustdoc-stripper-ignore-next
+    /// The arrival time relative to the previous received packet, or to the
+    /// reference time for the first one, or `None` if the packet was not
+    /// received.
+    pub delta: Option<gst::Signed<gst::ClockTime>>,
+}
+
+// rustdoc-stripper-ignore-next
+/// Iterator over the packets of an [`RTCPTwccFeedback`], as returned by
+/// [`RTCPTwccFeedback::packets()`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RTCPTwccPackets<'a> {
+    chunks: &'a [u8],
+    deltas: &'a [u8],
+    chunk: u16,
+    chunk_pos: usize,
+    chunk_len: usize,
+    seqnum: u16,
+    remaining: u16,
+}
+
+impl RTCPTwccPackets<'_> {
+    fn next_delta(&mut self, status: u8) -> Option<Option<gst::Signed<gst::ClockTime>>> {
+        let ticks = match status {
+            STATUS_NOT_RECEIVED => return Some(None),
+            STATUS_SMALL_DELTA => {
+                let (ticks, rest) = self.deltas.split_first()?;
+                self.deltas = rest;
+                *ticks as i64
+            }
+            STATUS_LARGE_DELTA => {
+                let (ticks, rest) = self.deltas.split_first_chunk::<2>()?;
+                self.deltas = rest;
+                i16::from_be_bytes(*ticks) as i64
+            }
+            _ => return None,
+        };
+
+        let delta = gst::ClockTime::from_nseconds(ticks.unsigned_abs() * DELTA_UNIT);
+        Some(Some(if ticks < 0 {
+            gst::Signed::Negative(delta)
+        } else {
+            gst::Signed::Positive(delta)
+        }))
+    }
+}
+
+impl Iterator for RTCPTwccPackets<'_> {
+    type Item = RTCPTwccPacketStatus;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        if self.chunk_pos == self.chunk_len {
+            let Some((chunk, rest)) = self.chunks.split_first_chunk::<2>() else {
+                self.remaining = 0;
+                return None;
+            };
+            self.chunk = u16::from_be_bytes(*chunk);
+            self.chunks = rest;
+            self.chunk_pos = 0;
+            self.chunk_len = chunk_len(self.chunk);
+        }
+
+        let status = chunk_status(self.chunk, self.chunk_pos);
+        self.chunk_pos += 1;
+
+        let Some(delta) = self.next_delta(status) else {
+            self.remaining = 0;
+            return None;
+        };
+
+        let seqnum = self.seqnum;
+        self.seqnum = self.seqnum.wrapping_add(1);
+        self.remaining -= 1;
+
+        Some(RTCPTwccPacketStatus { seqnum, delta })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining as usize))
+    }
+}
+
+impl std::iter::FusedIterator for RTCPTwccPackets<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_chunks() {
+        let statuses = [1; 20];
+        assert_eq!(encode_chunks(&statuses), [0x2000 | 20]);
+
+        let statuses = [1, 0, 1, 1, 0];
+        assert_eq!(encode_chunks(&statuses), [0xAC00]);
+
+        let statuses = [1, 2, 0, 1];
+        assert_eq!(encode_chunks(&statuses), [0xD840]);
+
+        for chunk in [0x2000 | 20, 0xAC00, 0xD840] {
+            let statuses = (0..chunk_len(chunk))
+                .map(|idx| chunk_status(chunk, idx))
+                .collect::<Vec<_>>();
+            assert_eq!(encode_chunks(&statuses)[0], chunk);
+        }
+    }
+
+    #[test]
+    fn test_twcc_seqnum() {
+        gst::init().unwrap();
+
+        let mut buffer = gst::Buffer::new_rtp_with_sizes(0, 0, 0).unwrap();
+        {
+            let buffer = buffer.get_mut().unwrap();
+            let mut rtp = RTPBuffer::from_buffer_writable(buffer).unwrap();
+            rtp.add_twcc_seqnum(3, 0x1234).unwrap();
+        }
+
+        let rtp = RTPBuffer::from_buffer_readable(&buffer).unwrap();
+        assert_eq!(rtp.twcc_seqnum(3), Some(0x1234));
+        assert_eq!(rtp.twcc_seqnum(4), None);
+    }
+
+    #[test]
+    fn test_twcc_packet() {
+        gst::init().unwrap();
+
+        let ms = |ms: u64| gst::ClockTime::from_mseconds(ms);
+        let deltas = [
+            Some(ms(1).into_positive()),
+            None,
+            Some(ms(100).into_positive()),
+            Some(ms(2).into_negative()),
+            Some(gst::ClockTime::ZERO.into_positive()),
+        ];
+
+        let mut buffer = gst::Buffer::new_rtcp(1400);
+        {
+            let buffer = buffer.get_mut().unwrap();
+            let mut rtcp = RTCPBuffer::from_buffer_writable(buffer).unwrap();
+            rtcp.add_twcc_packet(0x1234, 0x5678, 7, ms(6400), 65534, &deltas)
+                .unwrap();
+            assert!(rtcp
+                .add_twcc_packet(
+                    0x1234,
+                    0x5678,
+                    8,
+                    ms(0),
+                    0,
+                    &[Some(ms(10000).into_positive())]
+                )
+                .is_err());
+        }
+        assert!(crate::rtcp_buffer::validate_reduced(&buffer));
+
+        let rtcp = RTCPBuffer::from_buffer_readable(&buffer).unwrap();
+        let packet = rtcp.packets().next().unwrap();
+        assert_eq!(packet.type_(), RTCPType::Rtpfb);
+        let feedback = packet.twcc().unwrap();
+        assert_eq!(feedback.sender_ssrc(), 0x1234);
+        assert_eq!(feedback.media_ssrc(), 0x5678);
+        assert_eq!(feedback.base_seqnum(), 65534);
+        assert_eq!(feedback.status_count(), 5);
+        assert_eq!(feedback.reference_time(), ms(6400));
+        assert_eq!(feedback.feedback_count(), 7);
+
+        let packets = feedback.packets().collect::<Vec<_>>();
+        assert_eq!(
+            packets.iter().map(|p| p.seqnum).collect::<Vec<_>>(),
+            [65534, 65535, 0, 1, 2]
+        );
+        assert_eq!(packets.iter().map(|p| p.delta).collect::<Vec<_>>(), deltas);
+    }
+}
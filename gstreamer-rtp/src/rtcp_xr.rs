@@ -0,0 +1,440 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+const BLOCK_TYPE_RRT: u8 = 4;
+const BLOCK_TYPE_DLRR: u8 = 5;
+const BLOCK_TYPE_SSUMM: u8 = 6;
+const BLOCK_TYPE_VOIP_METRICS: u8 = 7;
+
+const SSUMM_LOSS: u8 = 0x80;
+const SSUMM_DUPLICATES: u8 = 0x40;
+const SSUMM_JITTER: u8 = 0x20;
+
+// rustdoc-stripper-ignore-next
+/// A report block of an RTCP extended report (RFC 3611).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RTCPXRBlock {
+    // rustdoc-stripper-ignore-next
+    /// The NTP time at which a receiver sent the report, which allows
+    /// senders to calculate the round trip time to it.
+    ReceiverReferenceTime {
+        ntp_timestamp: u64,
+    },
+    // rustdoc-stripper-ignore-next
+    /// The delay since the last receiver reference time of each receiver.
+    Dlrr(Vec<RTCPXRDlrr>),
+    StatisticsSummary(RTCPXRStatisticsSummary),
+    VoipMetrics(RTCPXRVoipMetrics),
+    // rustdoc-stripper-ignore-next
+    /// A block of another type, or a block with an invalid length. `data`
+    /// is the body of the block without its 4 byte header.
+    Other {
+        block_type: u8,
+        type_specific: u8,
+        data: Vec<u8>,
+    },
+}
+
+impl RTCPXRBlock {
+    #[doc(alias = "gst_rtcp_packet_xr_get_block_type")]
+    pub fn block_type(&self) -> u8 {
+        match self {
+            Self::ReceiverReferenceTime { .. } => BLOCK_TYPE_RRT,
+            Self::Dlrr(_) => BLOCK_TYPE_DLRR,
+            Self::StatisticsSummary(_) => BLOCK_TYPE_SSUMM,
+            Self::VoipMetrics(_) => BLOCK_TYPE_VOIP_METRICS,
+            Self::Other { block_type, .. } => *block_type,
+        }
+    }
+
+    fn parse(block_type: u8, type_specific: u8, body: &[u8]) -> Self {
+        skip_assert_initialized!();
+        let mut reader = Reader(body);
+        match (block_type, body.len()) {
+            (BLOCK_TYPE_RRT, 8) => Self::ReceiverReferenceTime {
+                ntp_timestamp: reader.u64(),
+            },
+            (BLOCK_TYPE_DLRR, len) if len % 12 == 0 => Self::Dlrr(
+                (0..len / 12)
+                    .map(|_| RTCPXRDlrr {
+                        ssrc: reader.u32(),
+                        last_rr: reader.u32(),
+                        delay: reader.u32(),
+                    })
+                    .collect(),
+            ),
+            (BLOCK_TYPE_SSUMM, 36) => {
+                let ssrc = reader.u32();
+                let begin_seq = reader.u16();
+                let end_seq = reader.u16();
+                let lost_packets = reader.u32();
+                let duplicated_packets = reader.u32();
+                let jitter = RTCPXRSummary {
+                    min: reader.u32(),
+                    max: reader.u32(),
+                    mean: reader.u32(),
+                    dev: reader.u32(),
+                };
+                let hop_limit = RTCPXRSummary {
+                    min: reader.u8(),
+                    max: reader.u8(),
+                    mean: reader.u8(),
+                    dev: reader.u8(),
+                };
+
+                Self::StatisticsSummary(RTCPXRStatisticsSummary {
+                    ssrc,
+                    begin_seq,
+                    end_seq,
+                    lost_packets: (type_specific & SSUMM_LOSS != 0).then_some(lost_packets),
+                    duplicated_packets: (type_specific & SSUMM_DUPLICATES != 0)
+                        .then_some(duplicated_packets),
+                    jitter: (type_specific & SSUMM_JITTER != 0).then_some(jitter),
+                    ttl: match (type_specific >> 3) & 0x03 {
+                        1 => Some(RTCPXRHopLimit::Ipv4Ttl(hop_limit)),
+                        2 => Some(RTCPXRHopLimit::Ipv6HopLimit(hop_limit)),
+                        _ => None,
+                    },
+                })
+            }
+            (BLOCK_TYPE_VOIP_METRICS, 32) => Self::VoipMetrics(RTCPXRVoipMetrics {
+                ssrc: reader.u32(),
+                loss_rate: reader.u8(),
+                discard_rate: reader.u8(),
+                burst_density: reader.u8(),
+                gap_density: reader.u8(),
+                burst_duration: reader.u16(),
+                gap_duration: reader.u16(),
+                round_trip_delay: reader.u16(),
+                end_system_delay: reader.u16(),
+                signal_level: reader.u8() as i8,
+                noise_level: reader.u8() as i8,
+                rerl: reader.u8(),
+                gmin: reader.u8(),
+                r_factor: reader.u8(),
+                ext_r_factor: reader.u8(),
+                mos_lq: reader.u8(),
+                mos_cq: reader.u8(),
+                rx_config: {
+                    let rx_config = reader.u8();
+                    reader.u8();
+                    rx_config
+                },
+                jb_nominal: reader.u16(),
+                jb_maximum: reader.u16(),
+                jb_abs_max: reader.u16(),
+            }),
+            _ => Self::Other {
+                block_type,
+                type_specific,
+                data: body.to_vec(),
+            },
+        }
+    }
+
+    // Appends the block including its header to `data`
+    pub(crate) fn write(&self, data: &mut Vec<u8>) -> Result<(), glib::BoolError> {
+        let start = data.len();
+        data.extend_from_slice(&[self.block_type(), 0, 0, 0]);
+
+        let mut type_specific = 0;
+        match self {
+            Self::ReceiverReferenceTime { ntp_timestamp } => {
+                data.extend_from_slice(&ntp_timestamp.to_be_bytes());
+            }
+            Self::Dlrr(dlrr) => {
+                for dlrr in dlrr {
+                    data.extend_from_slice(&dlrr.ssrc.to_be_bytes());
+                    data.extend_from_slice(&dlrr.last_rr.to_be_bytes());
+                    data.extend_from_slice(&dlrr.delay.to_be_bytes());
+                }
+            }
+            Self::StatisticsSummary(summary) => {
+                let jitter = summary.jitter.unwrap_or_default();
+                let (toh, hop_limit) = match summary.ttl {
+                    None => (0, RTCPXRSummary::default()),
+                    Some(RTCPXRHopLimit::Ipv4Ttl(ttl)) => (1, ttl),
+                    Some(RTCPXRHopLimit::Ipv6HopLimit(hop_limit)) => (2, hop_limit),
+                };
+
+                if summary.lost_packets.is_some() {
+                    type_specific |= SSUMM_LOSS;
+                }
+                if summary.duplicated_packets.is_some() {
+                    type_specific |= SSUMM_DUPLICATES;
+                }
+                if summary.jitter.is_some() {
+                    type_specific |= SSUMM_JITTER;
+                }
+                type_specific |= toh << 3;
+
+                data.extend_from_slice(&summary.ssrc.to_be_bytes());
+                data.extend_from_slice(&summary.begin_seq.to_be_bytes());
+                data.extend_from_slice(&summary.end_seq.to_be_bytes());
+                data.extend_from_slice(&summary.lost_packets.unwrap_or(0).to_be_bytes());
+                data.extend_from_slice(&summary.duplicated_packets.unwrap_or(0).to_be_bytes());
+                for value in [jitter.min, jitter.max, jitter.mean, jitter.dev] {
+                    data.extend_from_slice(&value.to_be_bytes());
+                }
+                data.extend_from_slice(&[
+                    hop_limit.min,
+                    hop_limit.max,
+                    hop_limit.mean,
+                    hop_limit.dev,
+                ]);
+            }
+            Self::VoipMetrics(metrics) => {
+                data.extend_from_slice(&metrics.ssrc.to_be_bytes());
+                data.extend_from_slice(&[
+                    metrics.loss_rate,
+                    metrics.discard_rate,
+                    metrics.burst_density,
+                    metrics.gap_density,
+                ]);
+                for value in [
+                    metrics.burst_duration,
+                    metrics.gap_duration,
+                    metrics.round_trip_delay,
+                    metrics.end_system_delay,
+                ] {
+                    data.extend_from_slice(&value.to_be_bytes());
+                }
+                data.extend_from_slice(&[
+                    metrics.signal_level as u8,
+                    metrics.noise_level as u8,
+                    metrics.rerl,
+                    metrics.gmin,
+                    metrics.r_factor,
+                    metrics.ext_r_factor,
+                    metrics.mos_lq,
+                    metrics.mos_cq,
+                    metrics.rx_config,
+                    0,
+                ]);
+                for value in [metrics.jb_nominal, metrics.jb_maximum, metrics.jb_abs_max] {
+                    data.extend_from_slice(&value.to_be_bytes());
+                }
+            }
+            Self::Other {
+                type_specific: ts,
+                data: body,
+                ..
+            } => {
+                if body.len() % 4 != 0 {
+                    data.truncate(start);
+                    return Err(glib::bool_error!(
+                        "XR block size {} is not a multiple of 4",
+                        body.len()
+                    ));
+                }
+                type_specific = *ts;
+                data.extend_from_slice(body);
+            }
+        }
+
+        let words = (data.len() - start) / 4 - 1;
+        if words > u16::MAX as usize {
+            data.truncate(start);
+            return Err(glib::bool_error!("XR block too big"));
+        }
+        data[start + 1] = type_specific;
+        data[start + 2..start + 4].copy_from_slice(&(words as u16).to_be_bytes());
+
+        Ok(())
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A sub-block of a DLRR report block.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RTCPXRDlrr {
+    // rustdoc-stripper-ignore-next
+    /// The SSRC of the receiver.
+    pub ssrc: u32,
+    // rustdoc-stripper-ignore-next
+    /// The middle 32 bits of the NTP timestamp of the last receiver
+    /// reference time of the receiver.
+    pub last_rr: u32,
+    // rustdoc-stripper-ignore-next
+    /// The delay since the last receiver reference time in units of
+    /// 1/65536 seconds.
+    pub delay: u32,
+}
+
+// rustdoc-stripper-ignore-next
+/// Minimum, maximum, mean and standard deviation of a value.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RTCPXRSummary<T> {
+    pub min: T,
+    pub max: T,
+    pub mean: T,
+    pub dev: T,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RTCPXRHopLimit {
+    Ipv4Ttl(RTCPXRSummary<u8>),
+    Ipv6HopLimit(RTCPXRSummary<u8>),
+}
+
+// rustdoc-stripper-ignore-next
+/// Statistics about the packets with the sequence numbers `begin_seq` to
+/// `end_seq` (exclusive) of the source `ssrc`. Statistics that are not
+/// reported are `None`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RTCPXRStatisticsSummary {
+    pub ssrc: u32,
+    pub begin_seq: u16,
+    pub end_seq: u16,
+    pub lost_packets: Option<u32>,
+    pub duplicated_packets: Option<u32>,
+    // rustdoc-stripper-ignore-next
+    /// The jitter in RTP timestamp units.
+    pub jitter: Option<RTCPXRSummary<u32>>,
+    pub ttl: Option<RTCPXRHopLimit>,
+}
+
+// rustdoc-stripper-ignore-next
+/// VoIP call quality metrics of the source `ssrc`.
+///
+/// The values are stored as defined by RFC 3611, i.e. rates and densities
+/// are fractions in units of 1/256, durations and delays are in
+/// milliseconds, and the MOS values are multiplied by 10. The value `127`
+/// for R factors and MOS values means that they are not available.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RTCPXRVoipMetrics {
+    pub ssrc: u32,
+    pub loss_rate: u8,
+    pub discard_rate: u8,
+    pub burst_density: u8,
+    pub gap_density: u8,
+    pub burst_duration: u16,
+    pub gap_duration: u16,
+    pub round_trip_delay: u16,
+    pub end_system_delay: u16,
+    // rustdoc-stripper-ignore-next
+    /// The signal level in dBm.
+    pub signal_level: i8,
+    // rustdoc-stripper-ignore-next
+    /// The noise level in dBm.
+    pub noise_level: i8,
+    pub rerl: u8,
+    pub gmin: u8,
+    pub r_factor: u8,
+    pub ext_r_factor: u8,
+    pub mos_lq: u8,
+    pub mos_cq: u8,
+    pub rx_config: u8,
+    pub jb_nominal: u16,
+    pub jb_maximum: u16,
+    pub jb_abs_max: u16,
+}
+
+// rustdoc-stripper-ignore-next
+/// Iterator over the report blocks of an extended report packet, as returned
+/// by [`RTCPPacket::xr_blocks()`](crate::rtcp_buffer::RTCPPacket::xr_blocks).
+///
+/// The iteration stops at truncated blocks.
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RTCPXRBlocks<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> RTCPXRBlocks<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        skip_assert_initialized!();
+        RTCPXRBlocks { data }
+    }
+}
+
+impl Iterator for RTCPXRBlocks<'_> {
+    type Item = RTCPXRBlock;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let header = self.data.get(..4)?;
+        let len = (u16::from_be_bytes([header[2], header[3]]) as usize + 1) * 4;
+        if self.data.len() < len {
+            self.data = &[];
+            return None;
+        }
+
+        let (block, rest) = self.data.split_at(len);
+        self.data = rest;
+        Some(RTCPXRBlock::parse(block[0], block[1], &block[4..]))
+    }
+}
+
+impl std::iter::FusedIterator for RTCPXRBlocks<'_> {}
+
+// Big-endian reader for blocks whose size was checked before
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> [u8; N] {
+        let (value, rest) = self.0.split_at(N);
+        self.0 = rest;
+        value.try_into().unwrap()
+    }
+
+    fn u8(&mut self) -> u8 {
+        self.take::<1>()[0]
+    }
+
+    fn u16(&mut self) -> u16 {
+        u16::from_be_bytes(self.take())
+    }
+
+    fn u32(&mut self) -> u32 {
+        u32::from_be_bytes(self.take())
+    }
+
+    fn u64(&mut self) -> u64 {
+        u64::from_be_bytes(self.take())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statistics_summary() {
+        let summary = RTCPXRBlock::StatisticsSummary(RTCPXRStatisticsSummary {
+            ssrc: 1,
+            begin_seq: 100,
+            end_seq: 200,
+            lost_packets: Some(3),
+            duplicated_packets: None,
+            jitter: Some(RTCPXRSummary {
+                min: 1,
+                max: 10,
+                mean: 4,
+                dev: 2,
+            }),
+            ttl: Some(RTCPXRHopLimit::Ipv6HopLimit(RTCPXRSummary {
+                min: 60,
+                max: 64,
+                mean: 62,
+                dev: 1,
+            })),
+        });
+
+        let mut data = Vec::new();
+        summary.write(&mut data).unwrap();
+        assert_eq!(data.len(), 40);
+        assert_eq!(&data[..4], &[BLOCK_TYPE_SSUMM, 0xB0, 0, 9]);
+        assert_eq!(RTCPXRBlocks::new(&data).collect::<Vec<_>>(), [summary]);
+
+        let other = RTCPXRBlock::Other {
+            block_type: 42,
+            type_specific: 1,
+            data: vec![1, 2, 3],
+        };
+        assert!(other.write(&mut data).is_err());
+        assert_eq!(data.len(), 40);
+
+        // Truncated block
+        data.extend_from_slice(&[BLOCK_TYPE_RRT, 0, 0, 2, 0, 0]);
+        assert_eq!(RTCPXRBlocks::new(&data).count(), 1);
+    }
+}
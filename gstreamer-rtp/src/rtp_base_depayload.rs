@@ -48,6 +48,13 @@ pub trait RTPBaseDepayloadExtManual: IsA<RTPBaseDepayload> + 'static {
         }
     }
 
+    fn clock_rate(&self) -> u32 {
+        unsafe {
+            let elt = &*(self.as_ptr() as *const ffi::GstRTPBaseDepayload);
+            elt.clock_rate
+        }
+    }
+
     fn sink_pad(&self) -> &gst::Pad {
         unsafe {
             let elt = &*(self.as_ptr() as *const ffi::GstRTPBaseDepayload);
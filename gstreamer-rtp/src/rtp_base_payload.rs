@@ -1,8 +1,19 @@
 use std::ptr;
 
 use glib::{prelude::*, translate::*};
+use gst::prelude::*;
 
-use crate::{ffi, RTPBasePayload};
+use crate::{ffi, prelude::*, RTPBasePayload, RTPBuffer};
+
+// rustdoc-stripper-ignore-next
+/// Position of a packet in the packets of a payload that was split by
+/// [`RTPBasePayloadExtManual::fragment()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RTPFragment {
+    pub index: usize,
+    pub is_first: bool,
+    pub is_last: bool,
+}
 
 pub trait RTPBasePayloadExtManual: IsA<RTPBasePayload> + 'static {
     #[cfg(feature = "v1_20")]
@@ -65,6 +76,130 @@ pub trait RTPBasePayloadExtManual: IsA<RTPBasePayload> + 'static {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Splits the data of `input` into RTP packets that fit into the MTU of
+    /// the payloader.
+    ///
+    /// Each packet starts with a payload header of `header_len` bytes that
+    /// is written by `write_header`, e.g. the fragmentation units of H.264.
+    /// The packets have the timestamps of `input`, and the marker bit is set
+    /// on the last packet if `marker` is `true`, e.g. if `input` is the end
+    /// of a frame. The space needed for header extensions and CSRCs is not
+    /// accounted for.
+    fn fragment<F>(
+        &self,
+        input: &gst::BufferRef,
+        header_len: usize,
+        marker: bool,
+        mut write_header: F,
+    ) -> Result<gst::BufferList, gst::FlowError>
+    where
+        F: FnMut(&mut [u8], RTPFragment),
+    {
+        let payload = self.as_ref();
+        let max_size = (payload.mtu() as usize).saturating_sub(crate::calc_header_len(0) as usize);
+        if max_size <= header_len {
+            gst::error!(
+                gst::CAT_RUST,
+                obj = payload,
+                "MTU {} too small for payload header of {} bytes",
+                payload.mtu(),
+                header_len
+            );
+            return Err(gst::FlowError::NotNegotiated);
+        }
+
+        let map = input.map_readable().map_err(|_| gst::FlowError::Error)?;
+        let chunks = map.chunks(max_size - header_len).collect::<Vec<_>>();
+        // Empty inputs result in a single packet with only the payload header
+        let chunks = if chunks.is_empty() {
+            vec![&[][..]]
+        } else {
+            chunks
+        };
+
+        let mut list = gst::BufferList::new_sized(chunks.len());
+        {
+            let list = list.get_mut().unwrap();
+            for (index, chunk) in chunks.iter().enumerate() {
+                let fragment = RTPFragment {
+                    index,
+                    is_first: index == 0,
+                    is_last: index == chunks.len() - 1,
+                };
+
+                let mut buffer =
+                    payload.allocate_output_buffer((header_len + chunk.len()) as u32, 0, 0);
+                {
+                    let buffer = buffer.get_mut().unwrap();
+                    buffer.set_pts(input.pts());
+                    buffer.set_dts(input.dts());
+
+                    let mut rtp = RTPBuffer::from_buffer_writable(buffer)
+                        .map_err(|_| gst::FlowError::Error)?;
+                    rtp.set_marker(marker && fragment.is_last);
+                    let data = rtp.payload_mut().map_err(|_| gst::FlowError::Error)?;
+                    let (header, data) = data.split_at_mut(header_len);
+                    write_header(header, fragment);
+                    data.copy_from_slice(chunk);
+                }
+                list.add(buffer);
+            }
+        }
+
+        Ok(list)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Splits `input` with [`fragment()`](Self::fragment) and pushes the
+    /// packets downstream.
+    fn push_fragmented<F>(
+        &self,
+        input: &gst::BufferRef,
+        header_len: usize,
+        marker: bool,
+        write_header: F,
+    ) -> Result<gst::FlowSuccess, gst::FlowError>
+    where
+        F: FnMut(&mut [u8], RTPFragment),
+    {
+        let list = self.fragment(input, header_len, marker, write_header)?;
+        self.as_ref().push_list(list)
+    }
+
+    fn clock_rate(&self) -> u32 {
+        unsafe {
+            let elt = &*(self.as_ptr() as *const ffi::GstRTPBasePayload);
+            elt.clock_rate
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the RTP timestamp for `pts`, like the base class calculates
+    /// it from the running time when pushing packets, or `None` if `pts` is
+    /// outside the segment or the clock rate is not known yet.
+    ///
+    /// Rate changes are not considered, regardless of the `scale-rtptime`
+    /// property.
+    fn rtp_time(&self, pts: gst::ClockTime) -> Option<u32> {
+        let (segment, ts_base, clock_rate) = unsafe {
+            let elt = &*(self.as_ptr() as *const ffi::GstRTPBasePayload);
+            let segment: gst::Segment = from_glib_none(&elt.segment as *const _);
+            (segment, elt.ts_base, elt.clock_rate)
+        };
+        if clock_rate == 0 {
+            return None;
+        }
+
+        let running_time = segment
+            .downcast_ref::<gst::ClockTime>()?
+            .to_running_time(pts)?;
+        let rtp_time = running_time
+            .nseconds()
+            .mul_div_floor(clock_rate as u64, *gst::ClockTime::SECOND)?;
+        Some(ts_base.wrapping_add(rtp_time as u32))
+    }
+
     fn sink_pad(&self) -> &gst::Pad {
         unsafe {
             let elt = &*(self.as_ptr() as *const ffi::GstRTPBasePayload);
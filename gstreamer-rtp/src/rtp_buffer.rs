@@ -8,6 +8,11 @@ use glib::translate::{from_glib, mut_override, FromGlibPtrFull, IntoGlib};
 pub enum Readable {}
 pub enum Writable {}
 
+// Extension profiles of the one-byte and two-byte header extensions of RFC 8285
+const ONE_BYTE_PROFILE: u16 = 0xBEDE;
+const TWO_BYTES_PROFILE: u16 = 0x1000;
+const TWO_BYTES_PROFILE_MASK: u16 = 0xFFF0;
+
 pub struct RTPBuffer<'a, T> {
     rtp_buffer: ffi::GstRTPBuffer,
     phantom: PhantomData<&'a T>,
@@ -187,6 +192,49 @@ impl<'a> RTPBuffer<'a, Writable> {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Appends a header extension as defined by RFC 8285.
+    ///
+    /// The one-byte format is used if possible, the two-byte format if the
+    /// packet already contains two-byte extensions or if `id` is above 14 or
+    /// `data` is empty or longer than 16 bytes. As both formats can't be
+    /// mixed in a packet, this fails if the two-byte format is required for
+    /// a packet with one-byte extensions.
+    pub fn add_extension(&mut self, id: u8, data: &[u8]) -> Result<(), glib::BoolError> {
+        if id == 0 || data.len() > 255 {
+            return Err(glib::bool_error!(
+                "Invalid header extension with id {} and size {}",
+                id,
+                data.len()
+            ));
+        }
+
+        let fits_onebyte = id <= 14 && !data.is_empty() && data.len() <= 16;
+        let twobytes = match self.extension_data() {
+            Some((ONE_BYTE_PROFILE, _)) => false,
+            Some((bits, _)) if bits & TWO_BYTES_PROFILE_MASK == TWO_BYTES_PROFILE => true,
+            Some((bits, _)) => {
+                return Err(glib::bool_error!(
+                    "Packet has a header extension with profile {:#06x}",
+                    bits
+                ))
+            }
+            None => !fits_onebyte,
+        };
+
+        if twobytes {
+            self.add_extension_twobytes_header(0, id, data)
+        } else if fits_onebyte {
+            self.add_extension_onebyte_header(id, data)
+        } else {
+            Err(glib::bool_error!(
+                "Header extension with id {} and size {} can't be added to a packet with one-byte header extensions",
+                id,
+                data.len()
+            ))
+        }
+    }
+
     #[cfg(feature = "v1_20")]
     #[cfg_attr(docsrs, doc(cfg(feature = "v1_20")))]
     #[doc(alias = "gst_rtp_buffer_remove_extension_data")]
@@ -408,6 +456,60 @@ impl<T> RTPBuffer<'_, T> {
         }
     }
 
+    #[doc(alias = "gst_rtp_buffer_get_extension_data")]
+    fn extension_data(&self) -> Option<(u16, &[u8])> {
+        unsafe {
+            let mut bits: u16 = 0;
+            let mut data = ptr::null_mut();
+            // FIXME: Workaround for gstreamer-rtp-sys having the wrong type for this parameter
+            let data_ptr = &mut data as *mut *mut u8 as *mut u8;
+            let mut wordlen = 0;
+            let result: bool = from_glib(ffi::gst_rtp_buffer_get_extension_data(
+                glib::translate::mut_override(&self.rtp_buffer),
+                &mut bits,
+                data_ptr,
+                &mut wordlen,
+            ));
+            if !result {
+                None
+            } else if wordlen == 0 {
+                Some((bits, &[]))
+            } else {
+                Some((
+                    bits,
+                    slice::from_raw_parts(data as *const u8, wordlen as usize * 4),
+                ))
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns an iterator over the one-byte or two-byte header extensions
+    /// of RFC 8285 as `(id, data)` pairs.
+    ///
+    /// The iterator is empty if the packet has no header extension or one
+    /// with another profile.
+    pub fn extensions(&self) -> RTPHeaderExtensions<'_> {
+        let (data, twobytes) = match self.extension_data() {
+            Some((ONE_BYTE_PROFILE, data)) => (data, false),
+            Some((bits, data)) if bits & TWO_BYTES_PROFILE_MASK == TWO_BYTES_PROFILE => {
+                (data, true)
+            }
+            _ => (&[][..], false),
+        };
+
+        RTPHeaderExtensions { data, twobytes }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the data of the first one-byte or two-byte header extension
+    /// with `id`.
+    pub fn extension(&self, id: u8) -> Option<&[u8]> {
+        self.extensions()
+            .find(|(ext_id, _)| *ext_id == id)
+            .map(|(_, data)| data)
+    }
+
     #[doc(alias = "get_padding")]
     #[doc(alias = "gst_rtp_buffer_get_padding")]
     pub fn has_padding(&self) -> bool {
@@ -438,6 +540,58 @@ impl<T> Drop for RTPBuffer<'_, T> {
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// Iterator over the header extensions of an [`RTPBuffer`], as returned by
+/// [`RTPBuffer::extensions()`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RTPHeaderExtensions<'a> {
+    data: &'a [u8],
+    twobytes: bool,
+}
+
+impl<'a> Iterator for RTPHeaderExtensions<'a> {
+    type Item = (u8, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (&first, rest) = self.data.split_first()?;
+            // Padding between the extensions
+            if first == 0 {
+                self.data = rest;
+                continue;
+            }
+
+            let (id, len, rest) = if self.twobytes {
+                let Some((&len, rest)) = rest.split_first() else {
+                    self.data = &[];
+                    return None;
+                };
+                (first, len as usize, rest)
+            } else {
+                let id = first >> 4;
+                // The reserved id 15 ends the parsing of the extensions
+                if id == 0 || id == 15 {
+                    self.data = &[];
+                    return None;
+                }
+                (id, (first & 0x0F) as usize + 1, rest)
+            };
+
+            if rest.len() < len {
+                self.data = &[];
+                return None;
+            }
+
+            let (data, rest) = rest.split_at(len);
+            self.data = rest;
+            return Some((id, data));
+        }
+    }
+}
+
+impl std::iter::FusedIterator for RTPHeaderExtensions<'_> {}
+
 pub trait RTPBufferExt {
     fn new_rtp_with_sizes(
         payload_len: u32,
@@ -675,6 +829,48 @@ mod tests {
         assert_eq!(data, &extension_data);
     }
 
+    #[test]
+    fn test_extensions() {
+        gst::init().unwrap();
+
+        let mut buffer = gst::Buffer::new_rtp_with_sizes(16, 4, 0).unwrap();
+        {
+            let buffer = buffer.get_mut().unwrap();
+            let mut rtp_buffer = RTPBuffer::from_buffer_writable(buffer).unwrap();
+
+            assert_eq!(rtp_buffer.extensions().count(), 0);
+            rtp_buffer.add_extension(1, &[1, 2, 3]).unwrap();
+            rtp_buffer.add_extension(3, &[4]).unwrap();
+            // Requires the two-byte format
+            assert!(rtp_buffer.add_extension(20, &[5]).is_err());
+            assert!(rtp_buffer.add_extension(0, &[5]).is_err());
+        }
+
+        let rtp_buffer = RTPBuffer::from_buffer_readable(&buffer).unwrap();
+        assert_eq!(
+            rtp_buffer.extensions().collect::<Vec<_>>(),
+            [(1, &[1u8, 2, 3][..]), (3, &[4u8][..])]
+        );
+        assert_eq!(rtp_buffer.extension(3), Some(&[4u8][..]));
+        assert_eq!(rtp_buffer.extension(2), None);
+
+        let mut buffer = gst::Buffer::new_rtp_with_sizes(16, 4, 0).unwrap();
+        {
+            let buffer = buffer.get_mut().unwrap();
+            let mut rtp_buffer = RTPBuffer::from_buffer_writable(buffer).unwrap();
+
+            rtp_buffer.add_extension(20, &[]).unwrap();
+            // Uses the two-byte format of the packet
+            rtp_buffer.add_extension(1, &[1, 2]).unwrap();
+        }
+
+        let rtp_buffer = RTPBuffer::from_buffer_readable(&buffer).unwrap();
+        assert_eq!(
+            rtp_buffer.extensions().collect::<Vec<_>>(),
+            [(20, &[][..]), (1, &[1u8, 2][..])]
+        );
+    }
+
     #[test]
     fn test_padding() {
         gst::init().unwrap();
@@ -0,0 +1,165 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::collections::BTreeMap;
+
+// rustdoc-stripper-ignore-next
+/// Mapping between the local IDs of RTP header extensions and their URIs,
+/// as negotiated with `extmap` attributes in SDP (RFC 8285).
+///
+/// Together with [`RTPBuffer::extension()`](crate::RTPBuffer::extension), this
+/// allows looking up well-known extensions by their URI:
+///
+/// ```rust,ignore
+/// let id = extmap.id("urn:ietf:params:rtp-hdrext:sdes:mid")?;
+/// let mid = rtp_buffer.extension(id)?;
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RTPHeaderExtensionMap {
+    extensions: BTreeMap<u8, String>,
+}
+
+impl RTPHeaderExtensionMap {
+    pub fn new() -> Self {
+        skip_assert_initialized!();
+        Self::default()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a mapping from the values of `extmap` SDP attributes, e.g.
+    /// `1 urn:ietf:params:rtp-hdrext:ssrc-audio-level` or
+    /// `2/sendrecv urn:ietf:params:rtp-hdrext:toffset`.
+    pub fn from_sdp_attributes<'a>(
+        attributes: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+        let mut map = Self::new();
+        for attribute in attributes {
+            let mut tokens = attribute.split_ascii_whitespace();
+            let (Some(id), Some(uri)) = (tokens.next(), tokens.next()) else {
+                return Err(glib::bool_error!("Invalid extmap attribute {}", attribute));
+            };
+            let id = id.split_once('/').map_or(id, |(id, _direction)| id);
+            let id = parse_id(id)
+                .ok_or_else(|| glib::bool_error!("Invalid extmap attribute {}", attribute))?;
+            map.insert(id, uri);
+        }
+
+        Ok(map)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a mapping from the `extmap-<id>` fields of RTP caps, which
+    /// contain either the URI or an array of the direction, the URI and the
+    /// attributes of the extension.
+    pub fn from_caps(s: &gst::StructureRef) -> Self {
+        skip_assert_initialized!();
+        let mut map = Self::new();
+        for (name, value) in s.iter() {
+            let Some(id) = name.as_str().strip_prefix("extmap-").and_then(parse_id) else {
+                continue;
+            };
+
+            if let Ok(uri) = value.get::<&str>() {
+                map.insert(id, uri);
+            } else if let Ok(array) = value.get::<gst::Array>() {
+                if let Some(uri) = array.as_slice().get(1).and_then(|v| v.get::<&str>().ok()) {
+                    map.insert(id, uri);
+                }
+            }
+        }
+
+        map
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Maps `id` to `uri`, returning the URI that was previously mapped to
+    /// `id`.
+    pub fn insert(&mut self, id: u8, uri: &str) -> Option<String> {
+        assert_ne!(id, 0, "id 0 is not a valid header extension id");
+        self.extensions.insert(id, uri.to_owned())
+    }
+
+    pub fn remove(&mut self, id: u8) -> Option<String> {
+        self.extensions.remove(&id)
+    }
+
+    pub fn id(&self, uri: &str) -> Option<u8> {
+        self.extensions
+            .iter()
+            .find(|(_, ext_uri)| ext_uri.as_str() == uri)
+            .map(|(id, _)| *id)
+    }
+
+    pub fn uri(&self, id: u8) -> Option<&str> {
+        self.extensions.get(&id).map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &str)> {
+        self.extensions.iter().map(|(id, uri)| (*id, uri.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.extensions.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.extensions.len()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether IDs above 14 are mapped, which can only be sent in the
+    /// two-byte header extension format.
+    pub fn requires_two_bytes(&self) -> bool {
+        self.extensions.keys().any(|id| *id > 14)
+    }
+}
+
+fn parse_id(id: &str) -> Option<u8> {
+    skip_assert_initialized!();
+    id.parse::<u8>().ok().filter(|id| *id != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUDIO_LEVEL: &str = "urn:ietf:params:rtp-hdrext:ssrc-audio-level";
+    const TOFFSET: &str = "urn:ietf:params:rtp-hdrext:toffset";
+
+    #[test]
+    fn test_from_sdp_attributes() {
+        let attributes = [
+            format!("1 {AUDIO_LEVEL} vad=on"),
+            format!("20/sendrecv {TOFFSET}"),
+        ];
+        let map = RTPHeaderExtensionMap::from_sdp_attributes(attributes.iter().map(String::as_str))
+            .unwrap();
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.id(AUDIO_LEVEL), Some(1));
+        assert_eq!(map.uri(20), Some(TOFFSET));
+        assert_eq!(map.id("urn:unknown"), None);
+        assert!(map.requires_two_bytes());
+
+        assert!(RTPHeaderExtensionMap::from_sdp_attributes(["0 urn:foo"]).is_err());
+        assert!(RTPHeaderExtensionMap::from_sdp_attributes(["1"]).is_err());
+    }
+
+    #[test]
+    fn test_from_caps() {
+        gst::init().unwrap();
+
+        let s = gst::Structure::builder("application/x-rtp")
+            .field("extmap-1", AUDIO_LEVEL)
+            .field("extmap-2", gst::Array::new(["sendonly", TOFFSET, ""]))
+            .field("extmap-foo", "urn:ignored")
+            .build();
+
+        let map = RTPHeaderExtensionMap::from_caps(&s);
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            [(1, AUDIO_LEVEL), (2, TOFFSET)]
+        );
+        assert!(!map.requires_two_bytes());
+    }
+}
@@ -3,7 +3,7 @@
 use std::fmt;
 use std::ptr;
 
-use crate::ffi;
+use crate::{ffi, RTPBuffer};
 use glib::translate::*;
 use gst::prelude::*;
 
@@ -36,6 +36,20 @@ impl RTPSourceMeta {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Adds a meta with the SSRC and CSRCs of the RTP packet `rtp` to
+    /// `buffer`, e.g. to a buffer that was depayloaded from `rtp`.
+    pub fn add_from_rtp_buffer<'a, T>(
+        buffer: &'a mut gst::BufferRef,
+        rtp: &RTPBuffer<T>,
+    ) -> gst::MetaRefMut<'a, Self, gst::meta::Standalone> {
+        skip_assert_initialized!();
+        let csrc = (0..rtp.csrc_count())
+            .filter_map(|idx| rtp.csrc(idx))
+            .collect::<Vec<_>>();
+        Self::add(buffer, Some(rtp.ssrc()), &csrc)
+    }
+
     #[inline]
     pub fn ssrc(&self) -> Option<u32> {
         unsafe {
@@ -52,6 +66,21 @@ impl RTPSourceMeta {
         &self.0.csrc[0..self.0.csrc_count as usize]
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Returns the number of sources, i.e. the CSRCs and the SSRC if set.
+    #[doc(alias = "get_source_count")]
+    #[doc(alias = "gst_rtp_source_meta_get_source_count")]
+    #[inline]
+    pub fn source_count(&self) -> u32 {
+        unsafe { ffi::gst_rtp_source_meta_get_source_count(&self.0) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns an iterator over the SSRC, if set, followed by the CSRCs.
+    pub fn sources(&self) -> impl Iterator<Item = u32> + '_ {
+        self.ssrc().into_iter().chain(self.csrc().iter().copied())
+    }
+
     #[doc(alias = "gst_rtp_source_meta_set_ssrc")]
     #[inline]
     pub fn set_ssrc(&mut self, ssrc: Option<u32>) {
@@ -104,3 +133,55 @@ impl fmt::Debug for RTPSourceMeta {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_add_get_meta() {
+        gst::init().unwrap();
+
+        let mut buffer = gst::Buffer::with_size(10).unwrap();
+        {
+            let buffer = buffer.get_mut().unwrap();
+            let mut meta = RTPSourceMeta::add(buffer, None, &[1, 2]);
+            assert_eq!(meta.ssrc(), None);
+            assert_eq!(meta.source_count(), 2);
+
+            meta.set_ssrc(Some(0x1234));
+            meta.append_csrc(&[3]).unwrap();
+            assert!(meta.append_csrc(&[0; 13]).is_err());
+        }
+
+        let meta = buffer.meta::<RTPSourceMeta>().unwrap();
+        assert_eq!(meta.ssrc(), Some(0x1234));
+        assert_eq!(meta.csrc(), &[1, 2, 3]);
+        assert_eq!(meta.source_count(), 4);
+        assert_eq!(meta.sources().collect::<Vec<_>>(), [0x1234, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_add_from_rtp_buffer() {
+        gst::init().unwrap();
+
+        let mut rtp_buffer = gst::Buffer::new_rtp_with_sizes(4, 0, 2).unwrap();
+        {
+            let buffer = rtp_buffer.get_mut().unwrap();
+            let mut rtp = RTPBuffer::from_buffer_writable(buffer).unwrap();
+            rtp.set_ssrc(0x1234);
+            rtp.set_csrc(0, 5);
+            rtp.set_csrc(1, 6);
+        }
+
+        let mut buffer = gst::Buffer::new();
+        {
+            let rtp = RTPBuffer::from_buffer_readable(&rtp_buffer).unwrap();
+            RTPSourceMeta::add_from_rtp_buffer(buffer.get_mut().unwrap(), &rtp);
+        }
+
+        let meta = buffer.meta::<RTPSourceMeta>().unwrap();
+        assert_eq!(meta.sources().collect::<Vec<_>>(), [0x1234, 5, 6]);
+    }
+}
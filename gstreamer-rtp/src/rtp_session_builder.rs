@@ -0,0 +1,526 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{fmt, net::IpAddr};
+
+use gio::prelude::*;
+use gst::prelude::*;
+
+// rustdoc-stripper-ignore-next
+/// Ports and addresses of an RTP session sent over UDP.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RTPUdpTransport {
+    // rustdoc-stripper-ignore-next
+    /// The host that RTP and RTCP packets are sent to, or the multicast
+    /// group.
+    pub destination: String,
+    pub rtp_port: u16,
+    pub rtcp_port: u16,
+    pub local_rtp_port: u16,
+    pub local_rtcp_port: u16,
+    // rustdoc-stripper-ignore-next
+    /// The TTL of multicast packets, or `None` for unicast.
+    pub multicast_ttl: Option<u8>,
+}
+
+// rustdoc-stripper-ignore-next
+/// The transport of an RTP session created by [`RTPSessionBuilder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RTPSessionTransport {
+    // rustdoc-stripper-ignore-next
+    /// RTP and RTCP are sent and received with `udpsink` and `udpsrc`.
+    ///
+    /// For unicast, the `udpsink` sends from the socket that the `udpsrc`
+    /// receives on, so packets are sent from the local ports as required
+    /// for symmetric RTP.
+    Udp(RTPUdpTransport),
+    // rustdoc-stripper-ignore-next
+    /// RTP and RTCP are interleaved with another protocol, e.g. RTSP over
+    /// TCP, and are exchanged with the application through `appsrc` and
+    /// `appsink`.
+    Interleaved,
+}
+
+#[cfg(feature = "rtsp")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rtsp")))]
+impl RTPSessionTransport {
+    // rustdoc-stripper-ignore-next
+    /// Creates the transport of an RTSP client from the `transport` that
+    /// was negotiated with the server at `server_host`.
+    pub fn from_rtsp_client_transport(
+        transport: &gst_rtsp::RTSPTransport,
+        server_host: &str,
+    ) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+        Self::from_rtsp_transport(transport, server_host, false)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates the transport of an RTSP server from the `transport` that
+    /// was negotiated with the client at `client_host`.
+    pub fn from_rtsp_server_transport(
+        transport: &gst_rtsp::RTSPTransport,
+        client_host: &str,
+    ) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+        Self::from_rtsp_transport(transport, client_host, true)
+    }
+
+    fn from_rtsp_transport(
+        transport: &gst_rtsp::RTSPTransport,
+        remote_host: &str,
+        is_server: bool,
+    ) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+        let lower_transport = transport.lower_transport();
+        if lower_transport.contains(gst_rtsp::RTSPLowerTrans::TCP) {
+            return Ok(RTPSessionTransport::Interleaved);
+        }
+
+        let ports = |range: Option<gst_rtsp::RTSPRange>| {
+            range
+                .and_then(|range| range.rtp_rtcp_pair())
+                .ok_or_else(|| glib::bool_error!("No valid port pair in transport"))
+        };
+
+        if lower_transport.contains(gst_rtsp::RTSPLowerTrans::UDP_MCAST) {
+            let destination = transport
+                .destination()
+                .ok_or_else(|| glib::bool_error!("No multicast destination in transport"))?;
+            let (rtp_port, rtcp_port) = ports(transport.port())?;
+            return Ok(RTPSessionTransport::Udp(RTPUdpTransport {
+                destination: destination.to_owned(),
+                rtp_port,
+                rtcp_port,
+                local_rtp_port: rtp_port,
+                local_rtcp_port: rtcp_port,
+                multicast_ttl: Some(transport.ttl().clamp(1, 255) as u8),
+            }));
+        }
+
+        let client_ports = ports(transport.client_port())?;
+        let server_ports = ports(transport.server_port())?;
+        let (local_ports, remote_ports) = if is_server {
+            (server_ports, client_ports)
+        } else {
+            (client_ports, server_ports)
+        };
+
+        Ok(RTPSessionTransport::Udp(RTPUdpTransport {
+            destination: remote_host.to_owned(),
+            rtp_port: remote_ports.0,
+            rtcp_port: remote_ports.1,
+            local_rtp_port: local_ports.0,
+            local_rtcp_port: local_ports.1,
+            multicast_ttl: None,
+        }))
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Elements and pads of a session of an `rtpbin`, as created by
+/// [`RTPSessionBuilder`].
+#[derive(Debug, Clone)]
+pub struct RTPSession {
+    rtpbin: gst::Element,
+    session_id: u32,
+    send_rtp_sink: Option<gst::Pad>,
+    rtp_src: Option<gst::Element>,
+    rtp_sink: Option<gst::Element>,
+    rtcp_src: gst::Element,
+    rtcp_sink: gst::Element,
+}
+
+impl RTPSession {
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for session `session_id` of `rtpbin`, which must
+    /// already be added to a bin.
+    pub fn builder(
+        rtpbin: &impl IsA<gst::Element>,
+        session_id: u32,
+        transport: RTPSessionTransport,
+    ) -> RTPSessionBuilder {
+        assert_initialized_main_thread!();
+        RTPSessionBuilder {
+            rtpbin: rtpbin.as_ref().clone(),
+            session_id,
+            transport,
+            caps: None,
+            send: false,
+            receive: false,
+            recv_pad_added: None,
+        }
+    }
+
+    pub fn rtpbin(&self) -> &gst::Element {
+        &self.rtpbin
+    }
+
+    pub fn session_id(&self) -> u32 {
+        self.session_id
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The `send_rtp_sink` pad of the session that the stream to send has
+    /// to be linked to, if sending.
+    pub fn send_rtp_sink(&self) -> Option<&gst::Pad> {
+        self.send_rtp_sink.as_ref()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The `udpsrc` or `appsrc` of received RTP packets, if receiving.
+    pub fn rtp_src(&self) -> Option<&gst::Element> {
+        self.rtp_src.as_ref()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The `udpsink` or `appsink` of sent RTP packets, if sending.
+    pub fn rtp_sink(&self) -> Option<&gst::Element> {
+        self.rtp_sink.as_ref()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The `udpsrc` or `appsrc` of received RTCP packets.
+    pub fn rtcp_src(&self) -> &gst::Element {
+        &self.rtcp_src
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The `udpsink` or `appsink` of sent RTCP packets.
+    pub fn rtcp_sink(&self) -> &gst::Element {
+        &self.rtcp_sink
+    }
+}
+
+type RecvPadAddedFn = Box<dyn Fn(&gst::Pad, u32, u8) + Send + Sync + 'static>;
+
+#[must_use = "The builder must be built to be used"]
+pub struct RTPSessionBuilder {
+    rtpbin: gst::Element,
+    session_id: u32,
+    transport: RTPSessionTransport,
+    caps: Option<gst::Caps>,
+    send: bool,
+    receive: bool,
+    recv_pad_added: Option<RecvPadAddedFn>,
+}
+
+impl fmt::Debug for RTPSessionBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RTPSessionBuilder")
+            .field("rtpbin", &self.rtpbin)
+            .field("session_id", &self.session_id)
+            .field("transport", &self.transport)
+            .field("caps", &self.caps)
+            .field("send", &self.send)
+            .field("receive", &self.receive)
+            .finish()
+    }
+}
+
+impl RTPSessionBuilder {
+    // rustdoc-stripper-ignore-next
+    /// Creates a `send_rtp_sink` pad and sends its stream to the transport.
+    pub fn send(self, send: bool) -> Self {
+        Self { send, ..self }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Receives RTP packets from the transport.
+    pub fn receive(self, receive: bool) -> Self {
+        Self { receive, ..self }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The caps of the received RTP packets, which must contain at least the
+    /// clock rate unless `rtpbin` provides it with `request-pt-map`.
+    pub fn caps(self, caps: &gst::Caps) -> Self {
+        Self {
+            caps: Some(caps.clone()),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Calls `func` with the pad, the SSRC and the payload type of every
+    /// stream that `rtpbin` receives in the session.
+    pub fn recv_pad_added<F: Fn(&gst::Pad, u32, u8) + Send + Sync + 'static>(
+        self,
+        func: F,
+    ) -> Self {
+        Self {
+            recv_pad_added: Some(Box::new(func)),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates the transport elements, adds them to the bin of `rtpbin` and
+    /// links them to the session.
+    ///
+    /// RTCP packets are always sent and received, e.g. for the reception
+    /// reports about sent streams.
+    pub fn build(self) -> Result<RTPSession, glib::BoolError> {
+        let bin = self
+            .rtpbin
+            .parent()
+            .and_downcast::<gst::Bin>()
+            .ok_or_else(|| glib::bool_error!("rtpbin is not in a bin"))?;
+        let id = self.session_id;
+
+        let rtcp_socket = self.socket(false)?;
+        let rtcp_src = self.source(false, rtcp_socket.as_ref())?;
+        bin.add(&rtcp_src)?;
+        rtcp_src.link_pads(
+            Some("src"),
+            &self.rtpbin,
+            Some(&format!("recv_rtcp_sink_{id}")),
+        )?;
+
+        let rtcp_sink = self.sink(false, rtcp_socket.as_ref())?;
+        bin.add(&rtcp_sink)?;
+        self.rtpbin.link_pads(
+            Some(&format!("send_rtcp_src_{id}")),
+            &rtcp_sink,
+            Some("sink"),
+        )?;
+
+        let rtp_socket = if self.receive || self.send {
+            self.socket(true)?
+        } else {
+            None
+        };
+
+        let mut rtp_src = None;
+        if self.receive {
+            let src = self.source(true, rtp_socket.as_ref())?;
+            bin.add(&src)?;
+            src.link_pads(
+                Some("src"),
+                &self.rtpbin,
+                Some(&format!("recv_rtp_sink_{id}")),
+            )?;
+            rtp_src = Some(src);
+        }
+
+        let mut send_rtp_sink = None;
+        let mut rtp_sink = None;
+        if self.send {
+            let pad = self
+                .rtpbin
+                .request_pad_simple(&format!("send_rtp_sink_{id}"))
+                .ok_or_else(|| glib::bool_error!("Failed to request send_rtp_sink_{}", id))?;
+            let sink = self.sink(true, rtp_socket.as_ref())?;
+            bin.add(&sink)?;
+            self.rtpbin
+                .link_pads(Some(&format!("send_rtp_src_{id}")), &sink, Some("sink"))?;
+            send_rtp_sink = Some(pad);
+            rtp_sink = Some(sink);
+        }
+
+        if let Some(func) = self.recv_pad_added {
+            self.rtpbin.connect_pad_added(move |_, pad| {
+                if let Some((ssrc, pt)) = parse_recv_rtp_src(&pad.name(), id) {
+                    func(pad, ssrc, pt);
+                }
+            });
+        }
+
+        for element in [
+            Some(&rtcp_src),
+            Some(&rtcp_sink),
+            rtp_src.as_ref(),
+            rtp_sink.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            element.sync_state_with_parent()?;
+        }
+
+        Ok(RTPSession {
+            rtpbin: self.rtpbin,
+            session_id: id,
+            send_rtp_sink,
+            rtp_src,
+            rtp_sink,
+            rtcp_src,
+            rtcp_sink,
+        })
+    }
+
+    // The socket bound to the local RTP or RTCP port, shared by `udpsrc`
+    // and `udpsink` for unicast
+    fn socket(&self, rtp: bool) -> Result<Option<gio::Socket>, glib::BoolError> {
+        let udp = match &self.transport {
+            RTPSessionTransport::Udp(udp) if udp.multicast_ttl.is_none() => udp,
+            _ => return Ok(None),
+        };
+        let port = if rtp {
+            udp.local_rtp_port
+        } else {
+            udp.local_rtcp_port
+        };
+
+        let family = match udp.destination.parse::<IpAddr>() {
+            Ok(IpAddr::V6(_)) => gio::SocketFamily::Ipv6,
+            _ => gio::SocketFamily::Ipv4,
+        };
+        let socket = gio::Socket::new(family, gio::SocketType::Datagram, gio::SocketProtocol::Udp)
+            .map_err(|err| glib::bool_error!("Failed to create socket: {}", err))?;
+        socket
+            .bind(
+                &gio::InetSocketAddress::new(&gio::InetAddress::new_any(family), port),
+                true,
+            )
+            .map_err(|err| glib::bool_error!("Failed to bind port {}: {}", port, err))?;
+
+        Ok(Some(socket))
+    }
+
+    fn source(
+        &self,
+        rtp: bool,
+        socket: Option<&gio::Socket>,
+    ) -> Result<gst::Element, glib::BoolError> {
+        let caps = if rtp {
+            self.caps.clone()
+        } else {
+            Some(gst::Caps::new_empty_simple("application/x-rtcp"))
+        };
+
+        let builder = match &self.transport {
+            RTPSessionTransport::Udp(udp) => {
+                let port = if rtp {
+                    udp.local_rtp_port
+                } else {
+                    udp.local_rtcp_port
+                };
+                gst::ElementFactory::make("udpsrc")
+                    .property("port", port as i32)
+                    .property_if(
+                        "address",
+                        udp.destination.as_str(),
+                        udp.multicast_ttl.is_some(),
+                    )
+                    .property_if_some("socket", socket.cloned())
+                    .property_if("close-socket", false, socket.is_some())
+            }
+            RTPSessionTransport::Interleaved => gst::ElementFactory::make("appsrc")
+                .property("is-live", true)
+                .property("format", gst::Format::Time),
+        };
+
+        builder.property_if_some("caps", caps.as_ref()).build()
+    }
+
+    fn sink(
+        &self,
+        rtp: bool,
+        socket: Option<&gio::Socket>,
+    ) -> Result<gst::Element, glib::BoolError> {
+        let builder = match &self.transport {
+            RTPSessionTransport::Udp(udp) => {
+                let port = if rtp { udp.rtp_port } else { udp.rtcp_port };
+                let socket_property = match socket.map(|socket| socket.family()) {
+                    Some(gio::SocketFamily::Ipv6) => "socket-v6",
+                    _ => "socket",
+                };
+                gst::ElementFactory::make("udpsink")
+                    .property("host", udp.destination.as_str())
+                    .property("port", port as i32)
+                    .property_if_some("ttl-mc", udp.multicast_ttl.map(|ttl| ttl as i32))
+                    .property_if_some(socket_property, socket.cloned())
+                    .property_if("close-socket", false, socket.is_some())
+            }
+            RTPSessionTransport::Interleaved => gst::ElementFactory::make("appsink"),
+        };
+
+        // RTCP packets are sent as soon as they are produced
+        builder
+            .property_if("sync", false, !rtp)
+            .property_if("async", false, !rtp)
+            .build()
+    }
+}
+
+// Parses the SSRC and payload type from `recv_rtp_src_<session>_<ssrc>_<pt>`
+fn parse_recv_rtp_src(name: &str, session_id: u32) -> Option<(u32, u8)> {
+    skip_assert_initialized!();
+    let rest = name
+        .strip_prefix("recv_rtp_src_")?
+        .strip_prefix(&session_id.to_string())?
+        .strip_prefix('_')?;
+    let (ssrc, pt) = rest.split_once('_')?;
+    Some((ssrc.parse().ok()?, pt.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_recv_rtp_src() {
+        assert_eq!(
+            parse_recv_rtp_src("recv_rtp_src_1_12345_96", 1),
+            Some((12345, 96))
+        );
+        assert_eq!(parse_recv_rtp_src("recv_rtp_src_11_12345_96", 1), None);
+        assert_eq!(parse_recv_rtp_src("recv_rtp_src_1_12345", 1), None);
+        assert_eq!(parse_recv_rtp_src("send_rtp_src_1", 1), None);
+    }
+
+    #[test]
+    fn test_unicast_socket() {
+        gst::init().unwrap();
+
+        let rtpbin = gst::Bin::new();
+        let mut udp = RTPUdpTransport {
+            destination: "127.0.0.1".into(),
+            rtp_port: 5000,
+            rtcp_port: 5001,
+            local_rtp_port: 0,
+            local_rtcp_port: 0,
+            multicast_ttl: None,
+        };
+
+        let builder = RTPSession::builder(&rtpbin, 0, RTPSessionTransport::Udp(udp.clone()));
+        let socket = builder.socket(true).unwrap().unwrap();
+        assert_eq!(socket.family(), gio::SocketFamily::Ipv4);
+        assert!(socket.local_address().is_ok());
+
+        // Multicast packets are sent to the group from any port
+        udp.destination = "224.3.0.1".into();
+        udp.multicast_ttl = Some(1);
+        let builder = RTPSession::builder(&rtpbin, 0, RTPSessionTransport::Udp(udp));
+        assert!(builder.socket(true).unwrap().is_none());
+    }
+
+    #[cfg(feature = "rtsp")]
+    #[test]
+    fn test_from_rtsp_transport() {
+        gst::init().unwrap();
+
+        let transport = gst_rtsp::RTSPTransport::parse(
+            "RTP/AVP;unicast;client_port=5000-5001;server_port=6000-6001",
+        )
+        .unwrap();
+        assert_eq!(
+            RTPSessionTransport::from_rtsp_client_transport(&transport, "192.168.1.2").unwrap(),
+            RTPSessionTransport::Udp(RTPUdpTransport {
+                destination: "192.168.1.2".into(),
+                rtp_port: 6000,
+                rtcp_port: 6001,
+                local_rtp_port: 5000,
+                local_rtcp_port: 5001,
+                multicast_ttl: None,
+            })
+        );
+
+        let transport =
+            gst_rtsp::RTSPTransport::parse("RTP/AVP/TCP;unicast;interleaved=0-1").unwrap();
+        assert_eq!(
+            RTPSessionTransport::from_rtsp_server_transport(&transport, "192.168.1.3").unwrap(),
+            RTPSessionTransport::Interleaved
+        );
+    }
+}
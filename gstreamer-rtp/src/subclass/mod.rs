@@ -8,6 +8,9 @@ mod rtp_base_payload;
 
 mod rtp_base_depayload;
 
+mod rtp_depayload_reassembler;
+pub use self::rtp_depayload_reassembler::RTPDepayloadReassembler;
+
 #[cfg(feature = "v1_20")]
 #[cfg_attr(docsrs, doc(cfg(feature = "v1_20")))]
 mod rtp_header_extension;
@@ -0,0 +1,178 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::{rtp_buffer::Readable, RTPBuffer};
+
+// rustdoc-stripper-ignore-next
+/// Collects the payloads of consecutive RTP packets until a packet with the
+/// marker bit set, e.g. to reassemble frames that were fragmented over
+/// multiple packets, from
+/// [`RTPBaseDepayloadImpl::process_rtp_packet()`](super::prelude::RTPBaseDepayloadImpl::process_rtp_packet).
+///
+/// All packets of a frame must have the same RTP timestamp. If a packet is
+/// missing, the incomplete frame is dropped and all packets are dropped
+/// until the next marker bit, after which the next complete frame is marked
+/// with the `DISCONT` flag.
+#[derive(Debug, Default)]
+pub struct RTPDepayloadReassembler {
+    pending: Option<gst::Buffer>,
+    timestamp: u32,
+    next_seqnum: Option<u16>,
+    skip_until_marker: bool,
+    discont: bool,
+}
+
+impl RTPDepayloadReassembler {
+    pub fn new() -> Self {
+        skip_assert_initialized!();
+        Self::default()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds the payload of `rtp`, without the first `header_len` bytes of
+    /// payload specific headers, and returns the complete frame if the
+    /// marker bit of `rtp` is set.
+    pub fn push(
+        &mut self,
+        rtp: &RTPBuffer<Readable>,
+        header_len: usize,
+    ) -> Result<Option<gst::Buffer>, glib::BoolError> {
+        let seqnum = rtp.seq();
+        let timestamp = rtp.timestamp();
+
+        let continuous = self.next_seqnum.is_none_or(|next| next == seqnum);
+        self.next_seqnum = Some(seqnum.wrapping_add(1));
+
+        if !continuous {
+            self.lost();
+        } else if self.pending.is_some() && self.timestamp != timestamp {
+            // The end of the previous frame is missing
+            self.lost();
+            self.skip_until_marker = false;
+        }
+
+        if self.skip_until_marker {
+            if rtp.is_marker() {
+                self.skip_until_marker = false;
+            }
+            return Ok(None);
+        }
+
+        let payload = rtp.payload_buffer()?;
+        if payload.size() < header_len {
+            return Err(glib::bool_error!(
+                "Payload of {} bytes too small for header of {} bytes",
+                payload.size(),
+                header_len
+            ));
+        }
+        let payload = payload.copy_region(gst::BufferCopyFlags::MEMORY, header_len..)?;
+
+        self.timestamp = timestamp;
+        let pending = match self.pending.take() {
+            Some(mut pending) => {
+                pending.append(payload);
+                pending
+            }
+            None => payload,
+        };
+
+        if !rtp.is_marker() {
+            self.pending = Some(pending);
+            return Ok(None);
+        }
+
+        let mut frame = pending;
+        if std::mem::take(&mut self.discont) {
+            frame.make_mut().set_flags(gst::BufferFlags::DISCONT);
+        }
+
+        Ok(Some(frame))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Drops the incomplete frame and all packets until the next marker bit,
+    /// e.g. when the base class reports lost packets.
+    pub fn lost(&mut self) {
+        self.pending = None;
+        self.skip_until_marker = true;
+        self.discont = true;
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Drops the incomplete frame and resets the state, e.g. when flushing.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether packets of an incomplete frame were collected.
+    pub fn has_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The RTP timestamp of the incomplete frame.
+    pub fn pending_timestamp(&self) -> Option<u32> {
+        self.pending.as_ref().map(|_| self.timestamp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    fn packet(seqnum: u16, timestamp: u32, marker: bool, payload: &[u8]) -> gst::Buffer {
+        let mut buffer = gst::Buffer::new_rtp_with_sizes(payload.len() as u32, 0, 0).unwrap();
+        {
+            let buffer = buffer.get_mut().unwrap();
+            let mut rtp = RTPBuffer::from_buffer_writable(buffer).unwrap();
+            rtp.set_seq(seqnum);
+            rtp.set_timestamp(timestamp);
+            rtp.set_marker(marker);
+            rtp.payload_mut().unwrap().copy_from_slice(payload);
+        }
+        buffer
+    }
+
+    fn push(
+        reassembler: &mut RTPDepayloadReassembler,
+        buffer: &gst::Buffer,
+    ) -> Option<gst::Buffer> {
+        let rtp = RTPBuffer::from_buffer_readable(buffer).unwrap();
+        reassembler.push(&rtp, 1).unwrap()
+    }
+
+    #[test]
+    fn test_reassembly() {
+        gst::init().unwrap();
+
+        let mut reassembler = RTPDepayloadReassembler::new();
+        assert!(push(&mut reassembler, &packet(1, 100, false, &[0, 1, 2])).is_none());
+        assert!(reassembler.has_pending());
+        assert_eq!(reassembler.pending_timestamp(), Some(100));
+        let frame = push(&mut reassembler, &packet(2, 100, true, &[0, 3])).unwrap();
+        assert_eq!(frame.map_readable().unwrap().as_slice(), &[1, 2, 3]);
+        assert!(!frame.flags().contains(gst::BufferFlags::DISCONT));
+        assert!(!reassembler.has_pending());
+
+        // Packet 4 is missing, so the frame is dropped together with packet 5
+        assert!(push(&mut reassembler, &packet(3, 200, false, &[0, 4])).is_none());
+        assert!(push(&mut reassembler, &packet(5, 200, true, &[0, 5])).is_none());
+        assert!(!reassembler.has_pending());
+
+        let frame = push(&mut reassembler, &packet(6, 300, true, &[0, 6])).unwrap();
+        assert_eq!(frame.map_readable().unwrap().as_slice(), &[6]);
+        assert!(frame.flags().contains(gst::BufferFlags::DISCONT));
+
+        // The end of the frame with timestamp 400 is missing
+        assert!(push(&mut reassembler, &packet(7, 400, false, &[0, 7])).is_none());
+        let frame = push(&mut reassembler, &packet(8, 500, true, &[0, 8])).unwrap();
+        assert_eq!(frame.map_readable().unwrap().as_slice(), &[8]);
+        assert!(frame.flags().contains(gst::BufferFlags::DISCONT));
+
+        let rtp_buffer = packet(9, 600, false, &[]);
+        let rtp = RTPBuffer::from_buffer_readable(&rtp_buffer).unwrap();
+        assert!(reassembler.push(&rtp, 1).is_err());
+    }
+}
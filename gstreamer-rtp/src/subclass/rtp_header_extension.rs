@@ -4,6 +4,7 @@ use glib::{prelude::*, subclass::prelude::*, translate::*};
 
 use super::prelude::*;
 use crate::ffi;
+use crate::prelude::RTPHeaderExtensionExtManual;
 use crate::RTPHeaderExtension;
 
 pub trait RTPHeaderExtensionImpl:
@@ -11,6 +12,13 @@ pub trait RTPHeaderExtensionImpl:
 {
     const URI: &'static str;
 
+    // rustdoc-stripper-ignore-next
+    /// The maximum number of bytes written by [`write()`](Self::write), which
+    /// is used by the default implementations of
+    /// [`max_size()`](Self::max_size) and
+    /// [`supported_flags()`](Self::supported_flags).
+    const MAX_SIZE: usize = 255;
+
     fn supported_flags(&self) -> crate::RTPHeaderExtensionFlags {
         self.parent_supported_flags()
     }
@@ -55,7 +63,22 @@ pub trait RTPHeaderExtensionImpl:
     }
 
     fn set_caps_from_attributes(&self, caps: &mut gst::CapsRef) -> Result<(), gst::LoggableError> {
-        self.parent_set_caps_from_attributes(caps)
+        match self.attributes() {
+            Some(attributes) => self.set_caps_from_attributes_helper(caps, &attributes),
+            None => self.parent_set_caps_from_attributes(caps),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The attributes of the extension that are negotiated in the `extmap`
+    /// caps field, e.g. the values parsed in
+    /// [`set_attributes()`](Self::set_attributes).
+    ///
+    /// If this returns `Some`, the default implementation of
+    /// [`set_caps_from_attributes()`](Self::set_caps_from_attributes) writes
+    /// the attributes together with the URI and direction to the caps.
+    fn attributes(&self) -> Option<String> {
+        None
     }
 }
 
@@ -64,14 +87,19 @@ pub trait RTPHeaderExtensionImplExt: RTPHeaderExtensionImpl {
         unsafe {
             let data = Self::type_data();
             let parent_class = data.as_ref().parent_class() as *mut ffi::GstRTPHeaderExtensionClass;
-            let f = (*parent_class)
-                .get_supported_flags
-                .expect("no parent \"get_supported_flags\" implementation");
-            from_glib(f(self
-                .obj()
-                .unsafe_cast_ref::<RTPHeaderExtension>()
-                .to_glib_none()
-                .0))
+            match (*parent_class).get_supported_flags {
+                Some(f) => from_glib(f(self
+                    .obj()
+                    .unsafe_cast_ref::<RTPHeaderExtension>()
+                    .to_glib_none()
+                    .0)),
+                // The one-byte header format allows up to 16 bytes of data
+                None if Self::MAX_SIZE <= 16 => {
+                    crate::RTPHeaderExtensionFlags::ONE_BYTE
+                        | crate::RTPHeaderExtensionFlags::TWO_BYTE
+                }
+                None => crate::RTPHeaderExtensionFlags::TWO_BYTE,
+            }
         }
     }
 
@@ -79,16 +107,16 @@ pub trait RTPHeaderExtensionImplExt: RTPHeaderExtensionImpl {
         unsafe {
             let data = Self::type_data();
             let parent_class = data.as_ref().parent_class() as *mut ffi::GstRTPHeaderExtensionClass;
-            let f = (*parent_class)
-                .get_max_size
-                .expect("no parent \"get_max_size\" implementation");
-            f(
-                self.obj()
-                    .unsafe_cast_ref::<RTPHeaderExtension>()
-                    .to_glib_none()
-                    .0,
-                input.as_ptr(),
-            )
+            match (*parent_class).get_max_size {
+                Some(f) => f(
+                    self.obj()
+                        .unsafe_cast_ref::<RTPHeaderExtension>()
+                        .to_glib_none()
+                        .0,
+                    input.as_ptr(),
+                ),
+                None => Self::MAX_SIZE,
+            }
         }
     }
 
@@ -257,6 +285,28 @@ pub trait RTPHeaderExtensionImplExt: RTPHeaderExtensionImpl {
             )
         }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Writes the `extmap` field for `attributes` together with the URI and
+    /// direction of the extension to `caps`.
+    fn set_caps_from_attributes_helper(
+        &self,
+        caps: &mut gst::CapsRef,
+        attributes: &str,
+    ) -> Result<(), gst::LoggableError> {
+        if self
+            .obj()
+            .unsafe_cast_ref::<RTPHeaderExtension>()
+            .set_caps_from_attributes_helper(caps, attributes)
+        {
+            Ok(())
+        } else {
+            Err(gst::loggable_error!(
+                gst::CAT_RUST,
+                "Failed to set caps from attributes"
+            ))
+        }
+    }
 }
 
 impl<T: RTPHeaderExtensionImpl> RTPHeaderExtensionImplExt for T {}
@@ -442,3 +492,115 @@ unsafe extern "C" fn set_caps_from_attributes<T: RTPHeaderExtensionImpl>(
     })
     .into_glib()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    pub mod imp {
+        use std::sync::Mutex;
+
+        use super::*;
+
+        #[derive(Default)]
+        pub struct TestExtension {
+            pub attributes: Mutex<String>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for TestExtension {
+            const NAME: &'static str = "TestRTPHeaderExtension";
+            type Type = super::TestExtension;
+            type ParentType = RTPHeaderExtension;
+        }
+
+        impl ObjectImpl for TestExtension {}
+
+        impl GstObjectImpl for TestExtension {}
+
+        impl ElementImpl for TestExtension {}
+
+        impl RTPHeaderExtensionImpl for TestExtension {
+            const URI: &'static str = "urn:gstreamer-rs:test";
+            const MAX_SIZE: usize = 1;
+
+            fn write(
+                &self,
+                _input: &gst::BufferRef,
+                _write_flags: crate::RTPHeaderExtensionFlags,
+                _output: &gst::BufferRef,
+                output_data: &mut [u8],
+            ) -> Result<usize, gst::LoggableError> {
+                output_data[0] = 42;
+                Ok(1)
+            }
+
+            fn read(
+                &self,
+                _read_flags: crate::RTPHeaderExtensionFlags,
+                _input_data: &[u8],
+                _output: &mut gst::BufferRef,
+            ) -> Result<(), gst::LoggableError> {
+                Ok(())
+            }
+
+            fn set_attributes(
+                &self,
+                _direction: crate::RTPHeaderExtensionDirection,
+                attributes: &str,
+            ) -> Result<(), gst::LoggableError> {
+                *self.attributes.lock().unwrap() = attributes.to_owned();
+                Ok(())
+            }
+
+            fn attributes(&self) -> Option<String> {
+                Some(self.attributes.lock().unwrap().clone())
+            }
+        }
+    }
+
+    glib::wrapper! {
+        pub struct TestExtension(ObjectSubclass<imp::TestExtension>)
+            @extends RTPHeaderExtension, gst::Element, gst::Object;
+    }
+
+    #[test]
+    fn test_defaults() {
+        gst::init().unwrap();
+
+        let ext = glib::Object::new::<TestExtension>();
+        ext.set_id(1);
+        assert_eq!(
+            ext.supported_flags(),
+            crate::RTPHeaderExtensionFlags::ONE_BYTE | crate::RTPHeaderExtensionFlags::TWO_BYTE
+        );
+        assert_eq!(ext.max_size(&gst::Buffer::new()), 1);
+
+        let caps = gst::Caps::builder("application/x-rtp")
+            .field(
+                "extmap-1",
+                gst::Array::new([
+                    "",
+                    <imp::TestExtension as RTPHeaderExtensionImpl>::URI,
+                    "foo=1",
+                ]),
+            )
+            .build();
+        assert!(ext.set_attributes_from_caps(&caps));
+        assert_eq!(*ext.imp().attributes.lock().unwrap(), "foo=1");
+
+        let mut caps = gst::Caps::new_empty_simple("application/x-rtp");
+        assert!(ext.set_caps_from_attributes(caps.get_mut().unwrap()));
+        let extmap = caps
+            .structure(0)
+            .unwrap()
+            .get::<gst::Array>("extmap-1")
+            .unwrap();
+        assert_eq!(
+            extmap.as_slice()[1].get::<&str>(),
+            Ok(<imp::TestExtension as RTPHeaderExtensionImpl>::URI)
+        );
+        assert_eq!(extmap.as_slice()[2].get::<&str>(), Ok("foo=1"));
+    }
+}
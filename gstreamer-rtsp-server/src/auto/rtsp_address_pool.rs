@@ -75,13 +75,6 @@ pub trait RTSPAddressPoolExt: IsA<RTSPAddressPool> + 'static {
         }
     }
 
-    #[doc(alias = "gst_rtsp_address_pool_clear")]
-    fn clear(&self) {
-        unsafe {
-            ffi::gst_rtsp_address_pool_clear(self.as_ref().to_glib_none().0);
-        }
-    }
-
     #[doc(alias = "gst_rtsp_address_pool_dump")]
     fn dump(&self) {
         unsafe {
@@ -33,22 +33,40 @@ pub use crate::auto::*;
 #[cfg(feature = "serde")]
 mod flag_serde;
 
+mod object_data;
+mod rtsp_address;
 mod rtsp_address_pool;
 mod rtsp_auth;
+mod rtsp_auth_tls;
 mod rtsp_client;
 mod rtsp_context;
 mod rtsp_media;
 mod rtsp_media_factory;
+mod rtsp_media_factory_uri;
 mod rtsp_onvif_media_factory;
+mod rtsp_pattern_mount_points;
+mod rtsp_recovery;
 mod rtsp_server;
 mod rtsp_session;
 mod rtsp_session_pool;
+mod rtsp_stream;
+mod rtsp_stream_stats;
 mod rtsp_thread;
 mod rtsp_token;
 
 pub mod subclass;
 
-pub use crate::{rtsp_context::*, rtsp_thread::*, rtsp_token::*};
+pub use crate::{
+    rtsp_address_pool::{RTSPAddressPoolRange, RTSPAddressRange, RTSPAddressReservation},
+    rtsp_auth_tls::*,
+    rtsp_context::*,
+    rtsp_pattern_mount_points::*,
+    rtsp_recovery::*,
+    rtsp_session_pool::RTSPSessionPoolStats,
+    rtsp_stream_stats::*,
+    rtsp_thread::*,
+    rtsp_token::*,
+};
 
 pub static RTSP_ADDRESS_POOL_ANY_IPV4: &glib::GStr =
     unsafe { glib::GStr::from_utf8_with_nul_unchecked(ffi::GST_RTSP_ADDRESS_POOL_ANY_IPV4) };
@@ -67,6 +85,11 @@ pub static RTSP_AUTH_CHECK_TRANSPORT_CLIENT_SETTINGS: &glib::GStr = unsafe {
 };
 pub static RTSP_AUTH_CHECK_URL: &glib::GStr =
     unsafe { glib::GStr::from_utf8_with_nul_unchecked(ffi::GST_RTSP_AUTH_CHECK_URL) };
+pub static RTSP_ONVIF_BACKCHANNEL_REQUIREMENT: &glib::GStr = unsafe {
+    glib::GStr::from_utf8_with_nul_unchecked(ffi::GST_RTSP_ONVIF_BACKCHANNEL_REQUIREMENT)
+};
+pub static RTSP_ONVIF_REPLAY_REQUIREMENT: &glib::GStr =
+    unsafe { glib::GStr::from_utf8_with_nul_unchecked(ffi::GST_RTSP_ONVIF_REPLAY_REQUIREMENT) };
 pub static RTSP_PERM_MEDIA_FACTORY_ACCESS: &glib::GStr =
     unsafe { glib::GStr::from_utf8_with_nul_unchecked(ffi::GST_RTSP_PERM_MEDIA_FACTORY_ACCESS) };
 pub static RTSP_PERM_MEDIA_FACTORY_CONSTRUCT: &glib::GStr =
@@ -96,5 +119,6 @@ pub mod prelude {
         rtsp_media_factory::RTSPMediaFactoryExtManual,
         rtsp_onvif_media_factory::RTSPOnvifMediaFactoryExtManual, rtsp_server::RTSPServerExtManual,
         rtsp_session::RTSPSessionExtManual, rtsp_session_pool::RTSPSessionPoolExtManual,
+        rtsp_stream::RTSPStreamExtManual,
     };
 }
@@ -0,0 +1,48 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::ptr;
+
+use glib::{prelude::*, translate::*};
+
+// Returns the value stored on `obj` under `key`, storing `T::default()` if
+// there is none yet, and whether this call stored it.
+//
+// The value is stored with `g_object_replace_qdata()`, so concurrent callers
+// all get a clone of the same value and only one of them stores it.
+pub(crate) fn shared_data<T>(obj: &impl IsA<glib::Object>, key: &str) -> (T, bool)
+where
+    T: Default + Clone + Send + Sync + 'static,
+{
+    skip_assert_initialized!();
+
+    unsafe extern "C" fn free<T>(ptr: glib::ffi::gpointer) {
+        drop(Box::from_raw(ptr as *mut T));
+    }
+
+    let quark = glib::Quark::from_str(key).into_glib();
+    let obj: *mut glib::gobject_ffi::GObject = obj.as_ref().to_glib_none().0;
+
+    unsafe {
+        let existing = glib::gobject_ffi::g_object_get_qdata(obj, quark) as *const T;
+        if !existing.is_null() {
+            return ((*existing).clone(), false);
+        }
+
+        let value = Box::into_raw(Box::<T>::default());
+        if from_glib(glib::gobject_ffi::g_object_replace_qdata(
+            obj,
+            quark,
+            ptr::null_mut(),
+            value as glib::ffi::gpointer,
+            Some(free::<T>),
+            ptr::null_mut(),
+        )) {
+            return ((*value).clone(), true);
+        }
+
+        // Another thread stored its value first
+        drop(Box::from_raw(value));
+        let existing = glib::gobject_ffi::g_object_get_qdata(obj, quark) as *const T;
+        ((*existing).clone(), false)
+    }
+}
@@ -0,0 +1,40 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{ffi::CStr, net::IpAddr};
+
+use glib::translate::*;
+
+use crate::{RTSPAddress, RTSPAddressPool};
+
+impl RTSPAddress {
+    // rustdoc-stripper-ignore-next
+    /// Returns the IP address, or `None` if it can't be parsed.
+    pub fn address(&self) -> Option<IpAddr> {
+        unsafe {
+            let address = (*self.as_ptr()).address;
+            if address.is_null() {
+                return None;
+            }
+            CStr::from_ptr(address).to_str().ok()?.parse().ok()
+        }
+    }
+
+    pub fn port(&self) -> u16 {
+        unsafe { (*self.as_ptr()).port }
+    }
+
+    pub fn n_ports(&self) -> u32 {
+        unsafe { (*self.as_ptr()).n_ports as u32 }
+    }
+
+    pub fn ttl(&self) -> u8 {
+        unsafe { (*self.as_ptr()).ttl }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The pool the address was acquired from. It is released to it again
+    /// when the address is dropped.
+    pub fn pool(&self) -> Option<RTSPAddressPool> {
+        unsafe { from_glib_none((*self.as_ptr()).pool) }
+    }
+}
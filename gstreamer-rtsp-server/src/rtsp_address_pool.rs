@@ -1,10 +1,230 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::ptr;
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    ops::{Deref, RangeInclusive},
+    ptr, str,
+    sync::{Arc, Mutex, Weak},
+};
 
 use glib::{prelude::*, translate::*};
 
-use crate::{ffi, RTSPAddress, RTSPAddressPool, RTSPAddressPoolResult};
+use crate::{
+    ffi, object_data::shared_data, prelude::*, RTSPAddress, RTSPAddressPool, RTSPAddressPoolResult,
+};
+
+const RANGES_KEY: &str = "gstreamer-rs-rtsp-address-pool-ranges";
+const RESERVATIONS_KEY: &str = "gstreamer-rs-rtsp-address-pool-reservations";
+
+// rustdoc-stripper-ignore-next
+/// An inclusive range of IP addresses of the same family, e.g. parsed from
+/// `224.3.0.0/24`, `224.3.0.1-224.3.0.10` or a single address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RTSPAddressRange {
+    min: IpAddr,
+    max: IpAddr,
+}
+
+impl RTSPAddressRange {
+    pub fn new(min: IpAddr, max: IpAddr) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+        if min.is_ipv4() != max.is_ipv4() {
+            return Err(glib::bool_error!(
+                "Addresses {} and {} of different families",
+                min,
+                max
+            ));
+        }
+        if to_bits(min) > to_bits(max) {
+            return Err(glib::bool_error!("Address {} after {}", min, max));
+        }
+
+        Ok(Self { min, max })
+    }
+
+    pub fn single(address: IpAddr) -> Self {
+        skip_assert_initialized!();
+        Self {
+            min: address,
+            max: address,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates the range of all addresses of the network `address` with the
+    /// prefix length `prefix_len`.
+    pub fn from_cidr(address: IpAddr, prefix_len: u8) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+        let bits = if address.is_ipv4() { 32 } else { 128 };
+        if u32::from(prefix_len) > bits {
+            return Err(glib::bool_error!(
+                "Invalid prefix length {} for {}",
+                prefix_len,
+                address
+            ));
+        }
+
+        let host_mask = u128::MAX
+            .checked_shr(128 - bits + u32::from(prefix_len))
+            .unwrap_or(0);
+        let network = to_bits(address) & !host_mask;
+
+        Ok(Self {
+            min: from_bits(network, address.is_ipv6()),
+            max: from_bits(network | host_mask, address.is_ipv6()),
+        })
+    }
+
+    pub fn min(&self) -> IpAddr {
+        self.min
+    }
+
+    pub fn max(&self) -> IpAddr {
+        self.max
+    }
+
+    pub fn contains(&self, address: IpAddr) -> bool {
+        address.is_ipv4() == self.min.is_ipv4()
+            && (to_bits(self.min)..=to_bits(self.max)).contains(&to_bits(address))
+    }
+
+    pub fn n_addresses(&self) -> u128 {
+        (to_bits(self.max) - to_bits(self.min)).saturating_add(1)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = IpAddr> {
+        let is_ipv6 = self.min.is_ipv6();
+        (to_bits(self.min)..=to_bits(self.max)).map(move |bits| from_bits(bits, is_ipv6))
+    }
+}
+
+impl fmt::Display for RTSPAddressRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.min == self.max {
+            write!(f, "{}", self.min)
+        } else {
+            write!(f, "{}-{}", self.min, self.max)
+        }
+    }
+}
+
+impl str::FromStr for RTSPAddressRange {
+    type Err = glib::BoolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        skip_assert_initialized!();
+        let address = |address: &str| {
+            address
+                .trim()
+                .parse::<IpAddr>()
+                .map_err(|_| glib::bool_error!("Invalid address in range {}", s))
+        };
+
+        if let Some((network, prefix_len)) = s.split_once('/') {
+            let prefix_len = prefix_len
+                .trim()
+                .parse::<u8>()
+                .map_err(|_| glib::bool_error!("Invalid prefix length in range {}", s))?;
+            Self::from_cidr(address(network)?, prefix_len)
+        } else if let Some((min, max)) = s.split_once('-') {
+            Self::new(address(min)?, address(max)?)
+        } else {
+            Ok(Self::single(address(s)?))
+        }
+    }
+}
+
+fn to_bits(address: IpAddr) -> u128 {
+    skip_assert_initialized!();
+    match address {
+        IpAddr::V4(address) => u32::from(address).into(),
+        IpAddr::V6(address) => address.into(),
+    }
+}
+
+fn from_bits(bits: u128, is_ipv6: bool) -> IpAddr {
+    skip_assert_initialized!();
+    if is_ipv6 {
+        IpAddr::V6(Ipv6Addr::from(bits))
+    } else {
+        IpAddr::V4(Ipv4Addr::from(bits as u32))
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A range of addresses and ports added with
+/// [`RTSPAddressPoolExtManual::add_address_range()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RTSPAddressPoolRange {
+    pub addresses: RTSPAddressRange,
+    pub ports: RangeInclusive<u16>,
+    // rustdoc-stripper-ignore-next
+    /// The TTL of multicast addresses, `0` for unicast addresses.
+    pub ttl: u8,
+}
+
+type Ranges = Arc<Mutex<Vec<RTSPAddressPoolRange>>>;
+
+fn ranges(pool: &RTSPAddressPool) -> Ranges {
+    skip_assert_initialized!();
+    shared_data::<Ranges>(pool, RANGES_KEY).0
+}
+
+// The addresses and ports of a reservation, for as long as it is alive
+#[derive(Debug)]
+struct Reserved {
+    address: IpAddr,
+    ports: RangeInclusive<u16>,
+}
+
+type Reservations = Arc<Mutex<Vec<Weak<Reserved>>>>;
+
+fn reservations(pool: &RTSPAddressPool) -> Reservations {
+    skip_assert_initialized!();
+    shared_data::<Reservations>(pool, RESERVATIONS_KEY).0
+}
+
+// rustdoc-stripper-ignore-next
+/// An address reserved with [`RTSPAddressPoolExtManual::reserve()`].
+///
+/// The address is released to the pool again when this is dropped.
+#[derive(Debug)]
+pub struct RTSPAddressReservation {
+    address: RTSPAddress,
+    _reserved: Arc<Reserved>,
+}
+
+impl Deref for RTSPAddressReservation {
+    type Target = RTSPAddress;
+
+    #[inline]
+    fn deref(&self) -> &RTSPAddress {
+        &self.address
+    }
+}
+
+impl RTSPAddressPool {
+    // rustdoc-stripper-ignore-next
+    /// Creates a pool with only the multicast `address` and the RTP and
+    /// RTCP ports `port` and `port + 1`, e.g. for streaming the media of a
+    /// factory to a fixed group with
+    /// [`set_address_pool()`](crate::prelude::RTSPMediaFactoryExt::set_address_pool).
+    pub fn with_static_address(
+        address: IpAddr,
+        port: u16,
+        ttl: u8,
+    ) -> Result<Self, glib::BoolError> {
+        assert_initialized_main_thread!();
+        let max_port = port
+            .checked_add(1)
+            .ok_or_else(|| glib::bool_error!("Invalid port {}", port))?;
+
+        let pool = Self::new();
+        pool.add_address_range(&RTSPAddressRange::single(address), port..=max_port, ttl)?;
+        Ok(pool)
+    }
+}
 
 pub trait RTSPAddressPoolExtManual: IsA<RTSPAddressPool> + 'static {
     #[doc(alias = "gst_rtsp_address_pool_reserve_address")]
@@ -31,6 +251,188 @@ pub trait RTSPAddressPoolExtManual: IsA<RTSPAddressPool> + 'static {
             }
         }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds the `addresses` with the `ports` to the pool. Multicast
+    /// addresses require a `ttl` greater than `0`, unicast addresses a
+    /// `ttl` of `0`.
+    #[doc(alias = "gst_rtsp_address_pool_add_range")]
+    fn add_address_range(
+        &self,
+        addresses: &RTSPAddressRange,
+        ports: RangeInclusive<u16>,
+        ttl: u8,
+    ) -> Result<(), glib::BoolError> {
+        self.add_range(
+            &addresses.min().to_string(),
+            &addresses.max().to_string(),
+            *ports.start(),
+            *ports.end(),
+            ttl,
+        )?;
+
+        ranges(self.as_ref())
+            .lock()
+            .unwrap()
+            .push(RTSPAddressPoolRange {
+                addresses: *addresses,
+                ports,
+                ttl,
+            });
+
+        Ok(())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the ranges added with
+    /// [`add_address_range()`](Self::add_address_range) since the pool was
+    /// last cleared.
+    fn address_ranges(&self) -> Vec<RTSPAddressPoolRange> {
+        ranges(self.as_ref()).lock().unwrap().clone()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes all addresses from the pool. There should be no outstanding
+    /// reservations.
+    #[doc(alias = "gst_rtsp_address_pool_clear")]
+    fn clear(&self) {
+        unsafe {
+            ffi::gst_rtsp_address_pool_clear(self.as_ref().to_glib_none().0);
+        }
+
+        ranges(self.as_ref()).lock().unwrap().clear();
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Reserves the `ports` of `address` like
+    /// [`reserve_address()`](Self::reserve_address) and tracks the
+    /// reservation until the returned value is dropped, see
+    /// [`reserved_addresses()`](Self::reserved_addresses).
+    fn reserve(
+        &self,
+        address: IpAddr,
+        ports: RangeInclusive<u16>,
+        ttl: u8,
+    ) -> Result<RTSPAddressReservation, RTSPAddressPoolResult> {
+        if ports.is_empty() {
+            return Err(RTSPAddressPoolResult::Erange);
+        }
+
+        let n_ports = u32::from(*ports.end() - *ports.start()) + 1;
+        let rtsp_address = self.reserve_address(
+            &address.to_string(),
+            (*ports.start()).into(),
+            n_ports,
+            ttl.into(),
+        )?;
+
+        let reserved = Arc::new(Reserved { address, ports });
+        let reservations = reservations(self.as_ref());
+        let mut reservations = reservations.lock().unwrap();
+        reservations.retain(|reserved| reserved.strong_count() > 0);
+        reservations.push(Arc::downgrade(&reserved));
+
+        Ok(RTSPAddressReservation {
+            address: rtsp_address,
+            _reserved: reserved,
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Checks if `port` of `address` is reserved with
+    /// [`reserve()`](Self::reserve), or returns `None` if it is not part of
+    /// a range added with [`add_address_range()`](Self::add_address_range).
+    fn is_reserved(&self, address: IpAddr, port: u16) -> Option<bool> {
+        let in_range = ranges(self.as_ref())
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|range| range.addresses.contains(address) && range.ports.contains(&port));
+        if !in_range {
+            return None;
+        }
+
+        Some(self.reserved_addresses().contains(&(address, port)))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns all ports that are currently reserved with
+    /// [`reserve()`](Self::reserve), in the order they were reserved.
+    ///
+    /// Addresses that media reserve or acquire internally are not tracked.
+    /// Reservations that are still reported once everything using the pool
+    /// is shut down are leaked.
+    fn reserved_addresses(&self) -> Vec<(IpAddr, u16)> {
+        reservations(self.as_ref())
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .flat_map(|reserved| {
+                let address = reserved.address;
+                reserved.ports.clone().map(move |port| (address, port))
+            })
+            .collect()
+    }
 }
 
 impl<O: IsA<RTSPAddressPool>> RTSPAddressPoolExtManual for O {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range() {
+        let range = "224.3.0.0/24".parse::<RTSPAddressRange>().unwrap();
+        assert_eq!(range.min(), "224.3.0.0".parse::<IpAddr>().unwrap());
+        assert_eq!(range.max(), "224.3.0.255".parse::<IpAddr>().unwrap());
+        assert_eq!(range.n_addresses(), 256);
+        assert!(range.contains("224.3.0.7".parse().unwrap()));
+        assert!(!range.contains("224.3.1.0".parse().unwrap()));
+        assert!(!range.contains("::1".parse().unwrap()));
+
+        let range = "ff15::1-ff15::3".parse::<RTSPAddressRange>().unwrap();
+        assert_eq!(range.iter().count(), 3);
+        assert_eq!(range.to_string(), "ff15::1-ff15::3");
+
+        let range = "ff15::/0".parse::<RTSPAddressRange>().unwrap();
+        assert_eq!(range.n_addresses(), u128::MAX);
+
+        assert!("224.3.0.2-224.3.0.1".parse::<RTSPAddressRange>().is_err());
+        assert!("224.3.0.1-ff15::1".parse::<RTSPAddressRange>().is_err());
+        assert!("224.3.0.0/33".parse::<RTSPAddressRange>().is_err());
+    }
+
+    #[test]
+    fn test_reservations() {
+        gst::init().unwrap();
+
+        let pool = RTSPAddressPool::new();
+        pool.add_address_range(&"224.3.0.1-224.3.0.2".parse().unwrap(), 5000..=5003, 16)
+            .unwrap();
+        assert_eq!(pool.address_ranges().len(), 1);
+
+        let ip = "224.3.0.2".parse::<IpAddr>().unwrap();
+        let address = pool.reserve(ip, 5002..=5003, 16).unwrap();
+        assert_eq!(address.address(), Some(ip));
+        assert_eq!(address.port(), 5002);
+        assert_eq!(address.n_ports(), 2);
+
+        assert_eq!(
+            pool.reserve_address("224.3.0.2", 5003, 1, 16).unwrap_err(),
+            RTSPAddressPoolResult::Ereserved
+        );
+        assert_eq!(pool.is_reserved(ip, 5003), Some(true));
+        assert_eq!(pool.is_reserved(ip, 5000), Some(false));
+        assert_eq!(pool.is_reserved("224.3.0.3".parse().unwrap(), 5000), None);
+        assert_eq!(pool.reserved_addresses(), [(ip, 5002), (ip, 5003)]);
+
+        drop(address);
+        assert!(pool.reserved_addresses().is_empty());
+        pool.reserve_address("224.3.0.2", 5003, 1, 16).unwrap();
+
+        pool.clear();
+        assert!(pool.address_ranges().is_empty());
+    }
+}
@@ -1,6 +1,11 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::{boxed::Box as Box_, mem::transmute};
+use std::{
+    boxed::Box as Box_,
+    collections::HashMap,
+    mem::transmute,
+    sync::{Arc, Mutex},
+};
 
 use glib::{
     prelude::*,
@@ -8,9 +13,111 @@ use glib::{
     translate::*,
 };
 
-use crate::{ffi, RTSPAuth, RTSPToken};
+use crate::{ffi, object_data::shared_data, prelude::*, RTSPAuth, RTSPToken};
+
+const BASIC_USERS_KEY: &str = "gstreamer-rs-rtsp-auth-basic-users";
+// Longest htdigest line that fits into a pipe without blocking
+const MAX_HTDIGEST_LINE: usize = 512;
+
+type BasicUsers = Arc<Mutex<HashMap<String, glib::GString>>>;
+
+// The Basic credentials of the users added with `add_user()`, by user name
+fn basic_users(auth: &RTSPAuth) -> BasicUsers {
+    skip_assert_initialized!();
+    shared_data::<BasicUsers>(auth, BASIC_USERS_KEY).0
+}
+
+impl RTSPAuth {
+    // rustdoc-stripper-ignore-next
+    /// Computes the hash of the password of `user` for Digest
+    /// authentication in `realm`, as stored in `htdigest` files.
+    ///
+    /// This allows storing only the hash instead of the password, see
+    /// [`RTSPAuthExtManual::add_user_hashed()`].
+    pub fn digest_hash(user: &str, realm: &str, password: &str) -> glib::GString {
+        skip_assert_initialized!();
+        glib::compute_checksum_for_string(
+            glib::ChecksumType::Md5,
+            format!("{user}:{realm}:{password}"),
+        )
+        .expect("Failed to compute checksum")
+    }
+}
 
 pub trait RTSPAuthExtManual: IsA<RTSPAuth> + 'static {
+    // rustdoc-stripper-ignore-next
+    /// Registers `user` with `password` for both Basic and Digest
+    /// authentication. Authenticated requests of the user get `token`,
+    /// e.g. a token created with [`RTSPToken::with_role()`].
+    fn add_user(&self, user: &str, password: &str, token: &RTSPToken) {
+        let auth = self.as_ref();
+        let basic = RTSPAuth::make_basic(user, password);
+        auth.add_basic(&basic, token);
+        auth.add_digest(user, password, token);
+
+        if let Some(old) = basic_users(auth)
+            .lock()
+            .unwrap()
+            .insert(user.to_owned(), basic.clone())
+        {
+            if old != basic {
+                auth.remove_basic(&old);
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes a user that was registered with [`add_user()`](Self::add_user)
+    /// or [`add_user_hashed()`](Self::add_user_hashed).
+    fn remove_user(&self, user: &str) {
+        let auth = self.as_ref();
+        if let Some(basic) = basic_users(auth).lock().unwrap().remove(user) {
+            auth.remove_basic(&basic);
+        }
+        auth.remove_digest(user);
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Registers `user` for Digest authentication with the password hash
+    /// computed by [`RTSPAuth::digest_hash()`], so that the password itself
+    /// doesn't have to be known by the server.
+    ///
+    /// The hash must have been computed for the realm of `self`. This is
+    /// only supported on Unix platforms.
+    fn add_user_hashed(
+        &self,
+        user: &str,
+        hash: &str,
+        token: &RTSPToken,
+    ) -> Result<(), glib::BoolError> {
+        if user.contains([':', '\n']) || hash.contains([':', '\n']) {
+            return Err(glib::bool_error!("Invalid user or password hash"));
+        }
+        let realm = self.as_ref().realm();
+        let line = format!("{user}:{}:{hash}\n", realm.as_deref().unwrap_or(""));
+        if line.len() > MAX_HTDIGEST_LINE {
+            return Err(glib::bool_error!("User or password hash too long"));
+        }
+
+        parse_htdigest_line(self.as_ref(), &line, token)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Configures authentication of clients by TLS client certificates that
+    /// are verified against `database`.
+    ///
+    /// If `required` is `false`, clients without certificate can still
+    /// authenticate with another method.
+    fn set_client_certificate_auth(&self, database: &impl IsA<gio::TlsDatabase>, required: bool) {
+        let auth = self.as_ref();
+        auth.set_tls_database(Some(database));
+        auth.set_tls_authentication_mode(if required {
+            gio::TlsAuthenticationMode::Required
+        } else {
+            gio::TlsAuthenticationMode::Requested
+        });
+    }
+
     #[doc(alias = "gst_rtsp_auth_set_default_token")]
     fn set_default_token(&self, mut token: Option<&mut RTSPToken>) {
         unsafe {
@@ -51,6 +158,53 @@ pub trait RTSPAuthExtManual: IsA<RTSPAuth> + 'static {
 
 impl<O: IsA<RTSPAuth>> RTSPAuthExtManual for O {}
 
+// Hashed passwords can only be added by parsing htdigest files. Pass the line
+// through an anonymous pipe so the credentials never end up on disk.
+#[cfg(unix)]
+fn parse_htdigest_line(
+    auth: &RTSPAuth,
+    line: &str,
+    token: &RTSPToken,
+) -> Result<(), glib::BoolError> {
+    skip_assert_initialized!();
+    let mut fds = [0; 2];
+    unsafe {
+        if libc::pipe(fds.as_mut_ptr()) != 0 {
+            return Err(glib::bool_error!(
+                "Failed to create pipe: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+
+        // The line fits into the pipe buffer, so this doesn't block
+        let written = libc::write(fds[1], line.as_ptr() as *const _, line.len());
+        libc::close(fds[1]);
+
+        let res = if written != line.len() as isize {
+            Err(glib::bool_error!("Failed to write htdigest line"))
+        } else if auth.parse_htdigest(format!("/dev/fd/{}", fds[0]), token) {
+            Ok(())
+        } else {
+            Err(glib::bool_error!("Failed to parse htdigest line"))
+        };
+        libc::close(fds[0]);
+
+        res
+    }
+}
+
+#[cfg(not(unix))]
+fn parse_htdigest_line(
+    _auth: &RTSPAuth,
+    _line: &str,
+    _token: &RTSPToken,
+) -> Result<(), glib::BoolError> {
+    skip_assert_initialized!();
+    Err(glib::bool_error!(
+        "Hashed passwords are not supported on this platform"
+    ))
+}
+
 unsafe extern "C" fn accept_certificate_trampoline<
     P,
     F: Fn(
@@ -87,3 +241,35 @@ where
     }
     .into_glib()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digest_hash() {
+        assert_eq!(
+            RTSPAuth::digest_hash("Mufasa", "testrealm@host.com", "Circle Of Life"),
+            "939e7578ed9e3c518a452acee763bce9"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_add_user_hashed() {
+        gst::init().unwrap();
+
+        let auth = RTSPAuth::new();
+        auth.set_realm(Some("test"));
+        let token = RTSPToken::with_role("user");
+        let hash = RTSPAuth::digest_hash("user", "test", "pass");
+        auth.add_user_hashed("user", &hash, &token).unwrap();
+        auth.remove_user("user");
+        auth.add_user("admin", "secret", &RTSPToken::with_role("admin"));
+        auth.add_user("admin", "other", &RTSPToken::with_role("admin"));
+        auth.remove_user("admin");
+        assert!(basic_users(&auth).lock().unwrap().is_empty());
+
+        assert!(auth.add_user_hashed("a:b", &hash, &token).is_err());
+    }
+}
@@ -0,0 +1,301 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::path::PathBuf;
+
+use glib::{prelude::*, subclass::prelude::*, translate::*};
+
+use crate::{ffi, prelude::*, RTSPAuth, RTSPServer};
+
+#[derive(Debug, Clone)]
+enum CertificateSource {
+    Certificate(gio::TlsCertificate),
+    File(PathBuf),
+    Files(PathBuf, PathBuf),
+    Pem(String),
+}
+
+#[derive(Debug, Clone)]
+enum DatabaseSource {
+    Database(gio::TlsDatabase),
+    File(PathBuf),
+}
+
+impl RTSPAuth {
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for an auth object that secures the connections of
+    /// a server with TLS.
+    pub fn tls_builder() -> RTSPAuthTlsBuilder {
+        assert_initialized_main_thread!();
+        RTSPAuthTlsBuilder::new()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for the TLS configuration of an [`RTSPAuth`].
+///
+/// Certificates and databases given as files or PEM data are only loaded by
+/// [`build()`](Self::build) or [`apply()`](Self::apply), which fail if they
+/// can't be loaded.
+#[derive(Debug, Clone)]
+#[must_use = "The builder must be built to be used"]
+pub struct RTSPAuthTlsBuilder {
+    certificate: Option<CertificateSource>,
+    authentication_mode: Option<gio::TlsAuthenticationMode>,
+    database: Option<DatabaseSource>,
+    allowed_ciphersuites: Option<Vec<String>>,
+}
+
+impl RTSPAuthTlsBuilder {
+    fn new() -> Self {
+        skip_assert_initialized!();
+        RTSPAuthTlsBuilder {
+            certificate: None,
+            authentication_mode: None,
+            database: None,
+            allowed_ciphersuites: None,
+        }
+    }
+
+    pub fn certificate(self, certificate: &impl IsA<gio::TlsCertificate>) -> Self {
+        Self {
+            certificate: Some(CertificateSource::Certificate(certificate.as_ref().clone())),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Loads the certificate and private key from a single PEM file.
+    pub fn certificate_file(self, path: impl Into<PathBuf>) -> Self {
+        Self {
+            certificate: Some(CertificateSource::File(path.into())),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Loads the certificate and the private key from separate PEM files.
+    pub fn certificate_files(
+        self,
+        cert_path: impl Into<PathBuf>,
+        key_path: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            certificate: Some(CertificateSource::Files(cert_path.into(), key_path.into())),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Loads the certificate and private key from PEM data.
+    pub fn certificate_pem(self, pem: &str) -> Self {
+        Self {
+            certificate: Some(CertificateSource::Pem(pem.to_owned())),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets whether clients are asked for a certificate. By default, this
+    /// is [`Requested`](gio::TlsAuthenticationMode::Requested) if a client
+    /// CA database is set and [`None`](gio::TlsAuthenticationMode::None)
+    /// otherwise.
+    pub fn authentication_mode(self, mode: gio::TlsAuthenticationMode) -> Self {
+        Self {
+            authentication_mode: Some(mode),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the database for verifying client certificates.
+    pub fn client_ca_database(self, database: &impl IsA<gio::TlsDatabase>) -> Self {
+        Self {
+            database: Some(DatabaseSource::Database(database.as_ref().clone())),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Loads the database for verifying client certificates from a file
+    /// with the PEM encoded CA certificates.
+    pub fn client_ca_file(self, path: impl Into<PathBuf>) -> Self {
+        Self {
+            database: Some(DatabaseSource::File(path.into())),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Answers requests with `403 Forbidden` if the connection negotiated a
+    /// ciphersuite other than `allowed`, e.g. `TLS_AES_256_GCM_SHA384`.
+    ///
+    /// This is a policy check after the handshake and doesn't restrict the
+    /// ciphersuites offered during the handshake, which GIO has no API for.
+    /// These have to be restricted in the configuration of the TLS backend.
+    /// This requires GLib 2.70 or newer, otherwise all requests are
+    /// rejected.
+    pub fn allowed_ciphersuites(
+        self,
+        allowed: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            allowed_ciphersuites: Some(allowed.into_iter().map(Into::into).collect()),
+            ..self
+        }
+    }
+
+    pub fn build(self) -> Result<RTSPAuth, glib::Error> {
+        let certificate = match self.certificate {
+            Some(CertificateSource::Certificate(certificate)) => certificate,
+            Some(CertificateSource::File(path)) => gio::TlsCertificate::from_file(path)?,
+            Some(CertificateSource::Files(cert_path, key_path)) => {
+                gio::TlsCertificate::from_files(cert_path, key_path)?
+            }
+            Some(CertificateSource::Pem(pem)) => gio::TlsCertificate::from_pem(&pem)?,
+            None => {
+                return Err(glib::Error::new(
+                    gio::IOErrorEnum::InvalidArgument,
+                    "No TLS certificate configured",
+                ))
+            }
+        };
+
+        let database = match self.database {
+            Some(DatabaseSource::Database(database)) => Some(database),
+            Some(DatabaseSource::File(path)) => Some(gio::TlsFileDatabase::new(path)?.upcast()),
+            None => None,
+        };
+
+        let auth = match self.allowed_ciphersuites {
+            Some(allowed) => {
+                let auth = glib::Object::new::<TlsAuth>();
+                let _ = auth.imp().allowed_ciphersuites.set(allowed);
+                auth.upcast::<RTSPAuth>()
+            }
+            None => RTSPAuth::new(),
+        };
+
+        auth.set_tls_certificate(Some(&certificate));
+        let mode = self.authentication_mode.unwrap_or(if database.is_some() {
+            gio::TlsAuthenticationMode::Requested
+        } else {
+            gio::TlsAuthenticationMode::None
+        });
+        auth.set_tls_authentication_mode(mode);
+        if let Some(ref database) = database {
+            auth.set_tls_database(Some(database));
+        }
+
+        Ok(auth)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Builds the auth object and sets it on `server`, so that all new
+    /// client connections of the server use TLS.
+    ///
+    /// Users and default tokens have to be added to the returned auth
+    /// object, as the previous auth object of the server is replaced.
+    pub fn apply(self, server: &impl IsA<RTSPServer>) -> Result<RTSPAuth, glib::Error> {
+        let auth = self.build()?;
+        server.set_auth(Some(&auth));
+        Ok(auth)
+    }
+}
+
+mod imp {
+    use std::sync::OnceLock;
+
+    use super::*;
+    use crate::{subclass::prelude::*, RTSPContext};
+
+    #[derive(Default)]
+    pub struct TlsAuth {
+        pub(super) allowed_ciphersuites: OnceLock<Vec<String>>,
+    }
+
+    impl TlsAuth {
+        fn negotiated_ciphersuite(&self, ctx: &RTSPContext) -> Option<String> {
+            let client = ctx.client()?;
+            let tls = unsafe {
+                let conn = ffi::gst_rtsp_client_get_connection(client.to_glib_none().0);
+                if conn.is_null() {
+                    return None;
+                }
+                let mut error = std::ptr::null_mut();
+                let tls = gst_rtsp::ffi::gst_rtsp_connection_get_tls(conn, &mut error);
+                if !error.is_null() {
+                    glib::ffi::g_error_free(error);
+                    return None;
+                }
+                Option::<gio::TlsConnection>::from_glib_none(tls)?
+            };
+
+            tls.find_property("ciphersuite-name")?;
+            tls.property::<Option<String>>("ciphersuite-name")
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for TlsAuth {
+        const NAME: &'static str = "GstRsRTSPTlsAuth";
+        type Type = super::TlsAuth;
+        type ParentType = RTSPAuth;
+    }
+
+    impl ObjectImpl for TlsAuth {}
+
+    impl RTSPAuthImpl for TlsAuth {
+        fn check(&self, ctx: &RTSPContext, check: &glib::GString) -> bool {
+            // Policy check of the already negotiated ciphersuite
+            if check.as_str() == crate::RTSP_AUTH_CHECK_URL.as_str() {
+                let ciphersuites = self
+                    .allowed_ciphersuites
+                    .get()
+                    .map(Vec::as_slice)
+                    .unwrap_or(&[]);
+                let allowed = self
+                    .negotiated_ciphersuite(ctx)
+                    .is_some_and(|name| ciphersuites.iter().any(|c| c.eq_ignore_ascii_case(&name)));
+
+                if !allowed {
+                    if let Some(client) = ctx.client() {
+                        let response = gst_rtsp::RTSPMessage::new_response(
+                            gst_rtsp::RTSPStatusCode::Forbidden,
+                            ctx.request(),
+                        );
+                        let _ = client.send_message(&response, ctx.session());
+                    }
+                    return false;
+                }
+            }
+
+            self.parent_check(ctx, check)
+        }
+    }
+}
+
+glib::wrapper! {
+    struct TlsAuth(ObjectSubclass<imp::TlsAuth>) @extends RTSPAuth;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        gst::init().unwrap();
+
+        let err = RTSPAuth::tls_builder().build().unwrap_err();
+        assert!(err.matches(gio::IOErrorEnum::InvalidArgument));
+
+        assert!(RTSPAuth::tls_builder()
+            .certificate_pem("invalid")
+            .build()
+            .is_err());
+        assert!(RTSPAuth::tls_builder()
+            .certificate_file("/nonexistent/cert.pem")
+            .build()
+            .is_err());
+    }
+}
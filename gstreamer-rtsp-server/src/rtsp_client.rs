@@ -1,8 +1,42 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use crate::{ffi, RTSPClient, RTSPSession};
-use glib::{prelude::*, source::SourceId, translate::*};
-use gst_rtsp::rtsp_message::RTSPMessage;
+use std::sync::Arc;
+
+use crate::{ffi, prelude::*, RTSPClient, RTSPContext, RTSPSession};
+use glib::{prelude::*, source::SourceId, translate::*, SignalHandlerId};
+use gst_rtsp::{rtsp_message::RTSPMessage, RTSPHeaderField, RTSPStatusCode, RTSPTransport};
+
+pub(crate) type TransportPolicy =
+    dyn Fn(&RTSPClient, &RTSPContext, &RTSPTransport) -> bool + Send + Sync + 'static;
+
+pub(crate) fn connect_transport_policy(
+    client: &RTSPClient,
+    policy: Arc<TransportPolicy>,
+) -> SignalHandlerId {
+    skip_assert_initialized!();
+    client.connect_pre_setup_request(move |client, ctx| {
+        let Some(header) = ctx
+            .request()
+            .and_then(|request| request.header(RTSPHeaderField::Transport, 0))
+        else {
+            return RTSPStatusCode::Ok;
+        };
+
+        // The server uses the first alternative that is supported by the stream
+        let transports = RTSPTransport::parse_list(header);
+        let selected = transports.iter().find(|transport| {
+            ctx.stream()
+                .is_none_or(|stream| stream.is_transport_supported(transport))
+        });
+
+        match selected {
+            Some(transport) if !policy(client, ctx, transport) => {
+                RTSPStatusCode::UnsupportedTransport
+            }
+            _ => RTSPStatusCode::Ok,
+        }
+    })
+}
 
 pub trait RTSPClientExtManual: IsA<RTSPClient> + 'static {
     #[doc(alias = "gst_rtsp_client_attach")]
@@ -29,6 +63,26 @@ pub trait RTSPClientExtManual: IsA<RTSPClient> + 'static {
             ))
         }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Installs `policy`, which is consulted during SETUP requests whether
+    /// the client may use the requested transport.
+    ///
+    /// The server uses the first alternative of the `Transport` header that
+    /// is supported by the stream. If `policy` denies this transport, the
+    /// request is answered with `461 Unsupported Transport` and clients
+    /// usually retry with another transport, e.g. TCP instead of UDP.
+    ///
+    /// For modifying the transport instead, e.g. for capping the TTL of
+    /// multicast transports, implement
+    /// [`RTSPClientImpl::configure_client_transport()`](crate::subclass::prelude::RTSPClientImpl::configure_client_transport)
+    /// in a subclass.
+    fn connect_transport_policy<F>(&self, policy: F) -> SignalHandlerId
+    where
+        F: Fn(&RTSPClient, &RTSPContext, &RTSPTransport) -> bool + Send + Sync + 'static,
+    {
+        connect_transport_policy(self.upcast_ref(), Arc::new(policy))
+    }
 }
 
 impl<O: IsA<RTSPClient>> RTSPClientExtManual for O {}
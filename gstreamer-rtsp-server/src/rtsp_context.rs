@@ -5,7 +5,10 @@ use std::{marker::PhantomData, ptr};
 use glib::{prelude::*, translate::*};
 use gst_rtsp::{rtsp_message::RTSPMessage, RTSPUrl};
 
-use crate::{ffi, RTSPClient, RTSPSession, RTSPToken};
+use crate::{
+    ffi, RTSPAuth, RTSPClient, RTSPMedia, RTSPMediaFactory, RTSPServer, RTSPSession,
+    RTSPSessionMedia, RTSPStream, RTSPStreamTransport, RTSPToken,
+};
 
 #[derive(Debug, PartialEq, Eq)]
 #[doc(alias = "GstRTSPContext")]
@@ -39,6 +42,19 @@ impl RTSPContext {
         }
     }
 
+    #[inline]
+    pub fn server(&self) -> Option<&RTSPServer> {
+        unsafe {
+            let ptr = self.0.as_ptr();
+            if (*ptr).server.is_null() {
+                None
+            } else {
+                let server = RTSPServer::from_glib_ptr_borrow(&(*ptr).server);
+                Some(server)
+            }
+        }
+    }
+
     #[inline]
     pub fn client(&self) -> Option<&RTSPClient> {
         unsafe {
@@ -52,6 +68,13 @@ impl RTSPContext {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// The method of the request that is currently handled.
+    #[inline]
+    pub fn method(&self) -> gst_rtsp::RTSPMethod {
+        unsafe { from_glib((*self.0.as_ptr()).method) }
+    }
+
     #[inline]
     pub fn request(&self) -> Option<&RTSPMessage> {
         unsafe {
@@ -91,6 +114,84 @@ impl RTSPContext {
         }
     }
 
+    #[inline]
+    pub fn session_media(&self) -> Option<&RTSPSessionMedia> {
+        unsafe {
+            let ptr = self.0.as_ptr();
+            if (*ptr).sessmedia.is_null() {
+                None
+            } else {
+                let sessmedia = RTSPSessionMedia::from_glib_ptr_borrow(&(*ptr).sessmedia);
+                Some(sessmedia)
+            }
+        }
+    }
+
+    #[inline]
+    pub fn factory(&self) -> Option<&RTSPMediaFactory> {
+        unsafe {
+            let ptr = self.0.as_ptr();
+            if (*ptr).factory.is_null() {
+                None
+            } else {
+                let factory = RTSPMediaFactory::from_glib_ptr_borrow(&(*ptr).factory);
+                Some(factory)
+            }
+        }
+    }
+
+    #[inline]
+    pub fn media(&self) -> Option<&RTSPMedia> {
+        unsafe {
+            let ptr = self.0.as_ptr();
+            if (*ptr).media.is_null() {
+                None
+            } else {
+                let media = RTSPMedia::from_glib_ptr_borrow(&(*ptr).media);
+                Some(media)
+            }
+        }
+    }
+
+    #[inline]
+    pub fn stream(&self) -> Option<&RTSPStream> {
+        unsafe {
+            let ptr = self.0.as_ptr();
+            if (*ptr).stream.is_null() {
+                None
+            } else {
+                let stream = RTSPStream::from_glib_ptr_borrow(&(*ptr).stream);
+                Some(stream)
+            }
+        }
+    }
+
+    #[inline]
+    pub fn stream_transport(&self) -> Option<&RTSPStreamTransport> {
+        unsafe {
+            let ptr = self.0.as_ptr();
+            if (*ptr).trans.is_null() {
+                None
+            } else {
+                let trans = RTSPStreamTransport::from_glib_ptr_borrow(&(*ptr).trans);
+                Some(trans)
+            }
+        }
+    }
+
+    #[inline]
+    pub fn auth(&self) -> Option<&RTSPAuth> {
+        unsafe {
+            let ptr = self.0.as_ptr();
+            if (*ptr).auth.is_null() {
+                None
+            } else {
+                let auth = RTSPAuth::from_glib_ptr_borrow(&(*ptr).auth);
+                Some(auth)
+            }
+        }
+    }
+
     #[inline]
     pub fn token(&self) -> Option<RTSPToken> {
         unsafe {
@@ -111,8 +212,6 @@ impl RTSPContext {
             ffi::gst_rtsp_context_set_token(self.0.as_ptr(), token.into_glib_ptr());
         }
     }
-
-    // TODO: Add additional getters for all the contained fields as needed
 }
 
 #[doc(hidden)]
@@ -137,3 +236,26 @@ impl<'a> ToGlibPtr<'a, *mut ffi::GstRTSPContext> for RTSPContext {
         unimplemented!()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context() {
+        gst::init().unwrap();
+
+        let server = RTSPServer::new();
+        let mut ctx = unsafe { std::mem::zeroed::<ffi::GstRTSPContext>() };
+        ctx.server = server.to_glib_none().0;
+        ctx.method = gst_rtsp::ffi::GST_RTSP_SETUP;
+
+        let ctx = unsafe { RTSPContext::from_glib_borrow(&mut ctx) };
+        assert_eq!(ctx.server(), Some(&server));
+        assert_eq!(ctx.method(), gst_rtsp::RTSPMethod::SETUP);
+        assert!(ctx.client().is_none());
+        assert!(ctx.session_media().is_none());
+        assert!(ctx.stream_transport().is_none());
+        assert!(ctx.token().is_none());
+    }
+}
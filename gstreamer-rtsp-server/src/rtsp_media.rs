@@ -2,7 +2,7 @@
 
 use glib::{prelude::*, translate::*};
 
-use crate::{ffi, RTSPMedia};
+use crate::{ffi, prelude::*, RTSPMedia, RTSPStreamStats};
 
 pub trait RTSPMediaExtManual: IsA<RTSPMedia> + 'static {
     #[doc(alias = "gst_rtsp_media_take_pipeline")]
@@ -28,6 +28,16 @@ pub trait RTSPMediaExtManual: IsA<RTSPMedia> + 'static {
             }
         }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the statistics of all streams of the media that are joined
+    /// to its bin.
+    fn stats(&self) -> Vec<RTSPStreamStats> {
+        let media = self.as_ref();
+        (0..media.n_streams())
+            .filter_map(|idx| media.stream(idx)?.stats())
+            .collect()
+    }
 }
 
 impl<O: IsA<RTSPMedia>> RTSPMediaExtManual for O {}
@@ -1,8 +1,195 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use glib::{prelude::*, translate::*};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
 
-use crate::{ffi, RTSPMediaFactory};
+use glib::{prelude::*, subclass::prelude::*, translate::*};
+
+use crate::{
+    ffi, object_data::shared_data, prelude::*, RTSPMediaFactory, RTSPRecoveryConfig,
+    RTSPTransportMode,
+};
+
+const RECOVERY_CONFIG_KEY: &str = "gstreamer-rs-rtsp-recovery-config";
+
+type RecoveryConfig = Arc<Mutex<RTSPRecoveryConfig>>;
+
+// The recovery config applied to the streams of all new media of `factory`
+fn recovery_config(factory: &RTSPMediaFactory) -> RecoveryConfig {
+    skip_assert_initialized!();
+    let (config, created) = shared_data::<RecoveryConfig>(factory, RECOVERY_CONFIG_KEY);
+    if created {
+        let config = config.clone();
+        factory.connect_media_configure(move |_, media| {
+            let config = *config.lock().unwrap();
+            for idx in 0..media.n_streams() {
+                if let Some(stream) = media.stream(idx) {
+                    stream.set_recovery_config(&config);
+                }
+            }
+        });
+    }
+
+    config
+}
+
+impl RTSPMediaFactory {
+    // rustdoc-stripper-ignore-next
+    /// Creates a media factory that calls `func` for creating the element
+    /// of the media for the requested URL, instead of parsing a launch line.
+    ///
+    /// As with launch lines, the element is usually a bin containing
+    /// payloaders named `pay0`, `pay1`, etc.
+    pub fn with_create_element<F>(func: F) -> Self
+    where
+        F: Fn(&gst_rtsp::RTSPUrl) -> Option<gst::Element> + Send + Sync + 'static,
+    {
+        assert_initialized_main_thread!();
+        let factory = glib::Object::new::<ClosureMediaFactory>();
+        let _ = factory.imp().create_element.set(Box::new(func));
+        factory.upcast()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a media factory for clients publishing up to `n_streams`
+    /// streams with ANNOUNCE and RECORD, which writes all of them into a
+    /// Matroska file at `path`.
+    ///
+    /// The streams are depayloaded and parsed with `parsebin`.
+    pub fn record_to_file(path: impl Into<PathBuf>, n_streams: u32) -> Self {
+        assert_initialized_main_thread!();
+        let path = path.into();
+        let factory = Self::with_create_element(move |_url| {
+            let bin = gst::Bin::new();
+            let mux = gst::ElementFactory::make("matroskamux").build().ok()?;
+            let sink = gst::ElementFactory::make("filesink")
+                .property("location", path.to_str()?)
+                .build()
+                .ok()?;
+            bin.add_many([&mux, &sink]).ok()?;
+            mux.link(&sink).ok()?;
+
+            add_record_streams(&bin, n_streams, move |_bin, _idx, pad| {
+                let sinkpad = mux.compatible_pad(pad, None)?;
+                pad.link(&sinkpad).ok()?;
+                Some(())
+            })?;
+
+            Some(bin.upcast())
+        });
+        factory.set_transport_mode(RTSPTransportMode::RECORD);
+
+        factory
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a media factory for clients publishing up to `n_streams`
+    /// streams with ANNOUNCE and RECORD, which calls `func` with the index
+    /// and the caps of each depayloaded and parsed stream for creating the
+    /// element terminating it, e.g. an `appsink` or a `filesink`.
+    ///
+    /// The streams are depayloaded and parsed with `parsebin`. `func` is
+    /// called from the streaming threads and can return `None` if the
+    /// stream should not be recorded.
+    pub fn record_with_sink<F>(n_streams: u32, func: F) -> Self
+    where
+        F: Fn(u32, &gst::Caps) -> Option<gst::Element> + Send + Sync + 'static,
+    {
+        assert_initialized_main_thread!();
+        let func = Arc::new(func);
+        let factory = Self::with_create_element(move |_url| {
+            let bin = gst::Bin::new();
+            let func = func.clone();
+
+            add_record_streams(&bin, n_streams, move |bin, idx, pad| {
+                let caps = pad.current_caps().unwrap_or_else(|| pad.query_caps(None));
+                let sink = func(idx, &caps)?;
+                bin.add(&sink).ok()?;
+                pad.link(&sink.static_pad("sink")?).ok()?;
+                sink.sync_state_with_parent().ok()?;
+                Some(())
+            })?;
+
+            Some(bin.upcast())
+        });
+        factory.set_transport_mode(RTSPTransportMode::RECORD);
+
+        factory
+    }
+}
+
+// Adds a `parsebin` named `depay<idx>` for each stream, from which the media
+// creates its RECORD streams, and calls `link` for each parsed stream
+fn add_record_streams<F>(bin: &gst::Bin, n_streams: u32, link: F) -> Option<()>
+where
+    F: Fn(&gst::Bin, u32, &gst::Pad) -> Option<()> + Send + Sync + 'static,
+{
+    skip_assert_initialized!();
+    let link = Arc::new(link);
+
+    for idx in 0..n_streams {
+        let parsebin = gst::ElementFactory::make("parsebin")
+            .name(format!("depay{idx}"))
+            .build()
+            .ok()?;
+        bin.add(&parsebin).ok()?;
+
+        let bin = bin.downgrade();
+        let link = link.clone();
+        parsebin.connect_pad_added(move |parsebin, pad| {
+            let Some(bin) = bin.upgrade() else {
+                return;
+            };
+            if link(&bin, idx, pad).is_none() {
+                gst::warning!(
+                    gst::CAT_RUST,
+                    obj = parsebin,
+                    "Failed to record stream {} from pad {}",
+                    idx,
+                    pad.name()
+                );
+            }
+        });
+    }
+
+    Some(())
+}
+
+mod imp {
+    use std::sync::OnceLock;
+
+    use crate::subclass::prelude::*;
+
+    type CreateElementFn =
+        Box<dyn Fn(&gst_rtsp::RTSPUrl) -> Option<gst::Element> + Send + Sync + 'static>;
+
+    #[derive(Default)]
+    pub struct ClosureMediaFactory {
+        pub(super) create_element: OnceLock<CreateElementFn>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ClosureMediaFactory {
+        const NAME: &'static str = "GstRsRTSPClosureMediaFactory";
+        type Type = super::ClosureMediaFactory;
+        type ParentType = crate::RTSPMediaFactory;
+    }
+
+    impl ObjectImpl for ClosureMediaFactory {}
+
+    impl RTSPMediaFactoryImpl for ClosureMediaFactory {
+        fn create_element(&self, url: &gst_rtsp::RTSPUrl) -> Option<gst::Element> {
+            (self.create_element.get()?)(url)
+        }
+    }
+}
+
+glib::wrapper! {
+    struct ClosureMediaFactory(ObjectSubclass<imp::ClosureMediaFactory>)
+        @extends RTSPMediaFactory;
+}
 
 pub trait RTSPMediaFactoryExtManual: IsA<RTSPMediaFactory> + 'static {
     #[doc(alias = "gst_rtsp_media_factory_add_role_from_structure")]
@@ -14,6 +201,81 @@ pub trait RTSPMediaFactoryExtManual: IsA<RTSPMediaFactory> + 'static {
             );
         }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets whether requests with a token for `role` may access the media
+    /// of the factory or may construct new media, respectively.
+    ///
+    /// Use [`RTSPToken::with_role()`](crate::RTSPToken::with_role) for the
+    /// tokens of users with this role.
+    fn add_role_permissions(&self, role: &str, access: bool, construct: bool) {
+        let structure = gst::Structure::builder(role)
+            .field_with_static(crate::RTSP_PERM_MEDIA_FACTORY_ACCESS, access)
+            .field_with_static(crate::RTSP_PERM_MEDIA_FACTORY_CONSTRUCT, construct)
+            .build();
+        self.add_role_from_structure(&structure);
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Configures retransmission and ULP FEC for the streams of all new
+    /// media of the factory and enables the feedback profiles if needed.
+    ///
+    /// Requesting retransmissions of recorded streams and ULP FEC require
+    /// the `v1_16` feature.
+    fn set_recovery_config(&self, config: &RTSPRecoveryConfig) {
+        let factory = self.as_ref();
+        factory.set_retransmission_time(config.retransmission_time());
+        #[cfg(feature = "v1_16")]
+        factory.set_do_retransmission(config.do_retransmission());
+        factory.set_profiles(config.profiles(factory.profiles()));
+
+        *recovery_config(factory).lock().unwrap() = *config;
+    }
 }
 
 impl<O: IsA<RTSPMediaFactory>> RTSPMediaFactoryExtManual for O {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_with_create_element() {
+        gst::init().unwrap();
+
+        let factory = RTSPMediaFactory::with_create_element(|url| {
+            let bin = gst::Bin::with_name(url.abspath().trim_start_matches('/'));
+            Some(bin.upcast())
+        });
+
+        let url = gst_rtsp::RTSPUrl::parse("rtsp://localhost:8554/test")
+            .1
+            .unwrap();
+        let element = factory.create_element(&url).unwrap();
+        assert_eq!(element.name(), "test");
+    }
+
+    #[test]
+    fn test_record_with_sink() {
+        gst::init().unwrap();
+
+        let factory = RTSPMediaFactory::record_with_sink(2, |_, _| None);
+        assert_eq!(factory.transport_mode(), RTSPTransportMode::RECORD);
+        if gst::ElementFactory::find("parsebin").is_none() {
+            return;
+        }
+
+        let url = gst_rtsp::RTSPUrl::parse("rtsp://localhost:8554/record")
+            .1
+            .unwrap();
+        let bin = factory
+            .create_element(&url)
+            .unwrap()
+            .downcast::<gst::Bin>()
+            .unwrap();
+        assert!(bin.by_name("depay0").is_some());
+        assert!(bin.by_name("depay1").is_some());
+        assert!(bin.by_name("depay2").is_none());
+    }
+}
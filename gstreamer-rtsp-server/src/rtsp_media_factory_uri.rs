@@ -0,0 +1,74 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::{prelude::*, subclass::prelude::*};
+
+use crate::{prelude::*, RTSPMedia, RTSPMediaFactory, RTSPMediaFactoryURI};
+
+impl RTSPMediaFactoryURI {
+    // rustdoc-stripper-ignore-next
+    /// Creates a media factory for `uri` that calls `func` with the query
+    /// options of the requested URL for each new media, e.g. `speed` and
+    /// `start` for `rtsp://host/movie?speed=2&start=10`.
+    ///
+    /// The options are percent-decoded as by
+    /// [`gst_rtsp::helpers::options_from_uri()`]. `func` is called before
+    /// the media is prepared and can configure the media or its element
+    /// accordingly, e.g. by seeking once the media is prepared. If it
+    /// returns `false`, the request fails.
+    ///
+    /// Media are only shared between requests with the same query, as the
+    /// query is part of the default key of the factory.
+    pub fn with_uri_options<F>(uri: &str, func: F) -> Self
+    where
+        F: Fn(&[(String, String)], &RTSPMedia) -> bool + Send + Sync + 'static,
+    {
+        assert_initialized_main_thread!();
+        let factory = glib::Object::new::<OptionsMediaFactoryURI>();
+        let _ = factory.imp().func.set(Box::new(func));
+        factory.set_uri(uri);
+        factory.upcast()
+    }
+}
+
+mod imp {
+    use std::sync::OnceLock;
+
+    use crate::{subclass::prelude::*, RTSPMedia};
+
+    type OptionsFn = Box<dyn Fn(&[(String, String)], &RTSPMedia) -> bool + Send + Sync + 'static>;
+
+    #[derive(Default)]
+    pub struct OptionsMediaFactoryURI {
+        pub(super) func: OnceLock<OptionsFn>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for OptionsMediaFactoryURI {
+        const NAME: &'static str = "GstRsRTSPOptionsMediaFactoryURI";
+        type Type = super::OptionsMediaFactoryURI;
+        type ParentType = crate::RTSPMediaFactoryURI;
+    }
+
+    impl ObjectImpl for OptionsMediaFactoryURI {}
+
+    impl RTSPMediaFactoryImpl for OptionsMediaFactoryURI {
+        fn construct(&self, url: &gst_rtsp::RTSPUrl) -> Option<RTSPMedia> {
+            let media = self.parent_construct(url)?;
+            let options = gst_rtsp::helpers::options_from_uri(&url.request_uri());
+            if let Some(func) = self.func.get() {
+                if !func(&options, &media) {
+                    return None;
+                }
+            }
+
+            Some(media)
+        }
+    }
+
+    impl RTSPMediaFactoryURIImpl for OptionsMediaFactoryURI {}
+}
+
+glib::wrapper! {
+    struct OptionsMediaFactoryURI(ObjectSubclass<imp::OptionsMediaFactoryURI>)
+        @extends RTSPMediaFactoryURI, RTSPMediaFactory;
+}
@@ -0,0 +1,331 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::sync::Arc;
+
+use glib::{prelude::*, subclass::prelude::*, translate::*};
+
+use crate::{ffi, prelude::*, RTSPMediaFactory, RTSPMountPoints};
+
+type FactoryFn = dyn Fn(&RTSPMountParams, &gst_rtsp::RTSPUrl) -> Option<RTSPMediaFactory>
+    + Send
+    + Sync
+    + 'static;
+
+// rustdoc-stripper-ignore-next
+/// The parameters extracted from a path for a pattern of
+/// [`RTSPPatternMountPoints`], in the order of the pattern.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RTSPMountParams(Vec<(String, String)>);
+
+impl RTSPMountParams {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.0.iter().map(|(n, v)| (n.as_str(), v.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Param(String),
+}
+
+struct Pattern {
+    pattern: String,
+    segments: Vec<Segment>,
+    func: Arc<FactoryFn>,
+}
+
+impl Pattern {
+    fn parse(pattern: &str) -> Result<Vec<Segment>, glib::BoolError> {
+        let Some(path) = pattern.strip_prefix('/') else {
+            return Err(glib::bool_error!(
+                "Pattern {} doesn't start with a '/'",
+                pattern
+            ));
+        };
+
+        let mut segments = Vec::new();
+        for segment in path.split('/').filter(|s| !s.is_empty()) {
+            let segment = match segment.strip_prefix('{') {
+                Some(name) => {
+                    let name = name
+                        .strip_suffix('}')
+                        .filter(|name| !name.is_empty() && !name.contains(['{', '}']))
+                        .ok_or_else(|| {
+                            glib::bool_error!(
+                                "Invalid parameter {} in pattern {}",
+                                segment,
+                                pattern
+                            )
+                        })?;
+                    if segments.contains(&Segment::Param(name.to_string())) {
+                        return Err(glib::bool_error!(
+                            "Duplicate parameter {} in pattern {}",
+                            name,
+                            pattern
+                        ));
+                    }
+                    Segment::Param(name.to_string())
+                }
+                None if segment.contains(['{', '}']) => {
+                    return Err(glib::bool_error!(
+                        "Invalid segment {} in pattern {}",
+                        segment,
+                        pattern
+                    ));
+                }
+                None => Segment::Literal(segment.to_string()),
+            };
+            segments.push(segment);
+        }
+
+        Ok(segments)
+    }
+
+    // Matches the leading segments of `path` and returns the path of the
+    // mount point together with the parameters. Further segments are e.g.
+    // the control paths of the streams.
+    fn match_path(&self, path: &str) -> Option<(String, RTSPMountParams)> {
+        let mut parts = path.split('/').filter(|s| !s.is_empty());
+        let mut mount_path = String::new();
+        let mut params = Vec::new();
+
+        for segment in &self.segments {
+            let part = parts.next()?;
+            match segment {
+                Segment::Literal(literal) if literal != part => return None,
+                Segment::Literal(_) => (),
+                Segment::Param(name) => params.push((name.clone(), part.to_string())),
+            }
+            mount_path.push('/');
+            mount_path.push_str(part);
+        }
+
+        if mount_path.is_empty() {
+            mount_path.push('/');
+        }
+
+        Some((mount_path, RTSPMountParams(params)))
+    }
+}
+
+glib::wrapper! {
+    // rustdoc-stripper-ignore-next
+    /// Mount points that additionally serve paths matching patterns like
+    /// `/cameras/{id}`.
+    ///
+    /// When a client requests a path matching a pattern for which no media
+    /// factory is mounted yet, the function of the pattern is called with
+    /// the extracted parameters. The returned factory is then mounted at
+    /// the matched path and used for all following requests for it.
+    /// Factories that were mounted at the matched path with
+    /// [`add_factory()`](RTSPMountPointsExt::add_factory) take precedence
+    /// over patterns.
+    pub struct RTSPPatternMountPoints(ObjectSubclass<imp::RTSPPatternMountPoints>)
+        @extends RTSPMountPoints;
+}
+
+impl RTSPPatternMountPoints {
+    pub fn new() -> Self {
+        assert_initialized_main_thread!();
+        glib::Object::new()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds `pattern`, which consists of literal segments and parameters
+    /// in braces, e.g. `/cameras/{id}/{profile}`.
+    ///
+    /// Patterns are matched in the order they were added. `func` is called
+    /// from the threads of the clients and can return `None` if no media
+    /// should be served for the parameters.
+    pub fn add_pattern<F>(&self, pattern: &str, func: F) -> Result<(), glib::BoolError>
+    where
+        F: Fn(&RTSPMountParams, &gst_rtsp::RTSPUrl) -> Option<RTSPMediaFactory>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let segments = Pattern::parse(pattern)?;
+        let mut patterns = self.imp().patterns.lock().unwrap();
+        if patterns.iter().any(|p| p.segments == segments) {
+            return Err(glib::bool_error!("Pattern {} already exists", pattern));
+        }
+
+        patterns.push(Pattern {
+            pattern: pattern.to_string(),
+            segments,
+            func: Arc::new(func),
+        });
+
+        Ok(())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes `pattern`. Factories that were already mounted for it are
+    /// not removed.
+    pub fn remove_pattern(&self, pattern: &str) {
+        self.imp()
+            .patterns
+            .lock()
+            .unwrap()
+            .retain(|p| p.pattern != pattern);
+    }
+
+    fn has_factory(&self, path: &str) -> bool {
+        unsafe {
+            let mut matched = 0;
+            let factory: Option<RTSPMediaFactory> =
+                from_glib_full(ffi::gst_rtsp_mount_points_match(
+                    self.upcast_ref::<RTSPMountPoints>().to_glib_none().0,
+                    path.to_glib_none().0,
+                    &mut matched,
+                ));
+            factory.is_some() && matched as usize == path.len()
+        }
+    }
+
+    fn ensure_factory(&self, path: &str, url: &gst_rtsp::RTSPUrl) {
+        let Some((mount_path, params, func)) =
+            self.imp().patterns.lock().unwrap().iter().find_map(|p| {
+                p.match_path(path)
+                    .map(|(mount_path, params)| (mount_path, params, p.func.clone()))
+            })
+        else {
+            return;
+        };
+
+        // Serializes the creation so that a factory is only created once for
+        // concurrent requests
+        let _guard = self.imp().creation_lock.lock().unwrap();
+        if self.has_factory(&mount_path) {
+            return;
+        }
+
+        if let Some(factory) = func(&params, url) {
+            self.add_factory(&mount_path, factory);
+        }
+    }
+}
+
+impl Default for RTSPPatternMountPoints {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod imp {
+    use std::sync::Mutex;
+
+    use crate::subclass::prelude::*;
+
+    #[derive(Default)]
+    pub struct RTSPPatternMountPoints {
+        pub(super) patterns: Mutex<Vec<super::Pattern>>,
+        pub(super) creation_lock: Mutex<()>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for RTSPPatternMountPoints {
+        const NAME: &'static str = "GstRsRTSPPatternMountPoints";
+        type Type = super::RTSPPatternMountPoints;
+        type ParentType = crate::RTSPMountPoints;
+    }
+
+    impl ObjectImpl for RTSPPatternMountPoints {}
+
+    impl RTSPMountPointsImpl for RTSPPatternMountPoints {
+        fn make_path(&self, url: &gst_rtsp::RTSPUrl) -> Option<glib::GString> {
+            let path = self.parent_make_path(url)?;
+            self.obj().ensure_factory(&path, url);
+            Some(path)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn test_pattern() {
+        for pattern in [
+            "cameras",
+            "/cameras/{}",
+            "/cameras/{id",
+            "/{id}/{id}",
+            "/a{b}",
+        ] {
+            assert!(Pattern::parse(pattern).is_err(), "{pattern}");
+        }
+
+        let pattern = Pattern {
+            pattern: String::from("/cameras/{id}/{profile}"),
+            segments: Pattern::parse("/cameras/{id}/{profile}").unwrap(),
+            func: Arc::new(|_, _| None),
+        };
+        let (mount_path, params) = pattern.match_path("/cameras/5/main/stream=0").unwrap();
+        assert_eq!(mount_path, "/cameras/5/main");
+        assert_eq!(params.get("id"), Some("5"));
+        assert_eq!(params.get("profile"), Some("main"));
+        assert_eq!(
+            params.iter().collect::<Vec<_>>(),
+            [("id", "5"), ("profile", "main")]
+        );
+
+        assert!(pattern.match_path("/cameras/5").is_none());
+        assert!(pattern.match_path("/microphones/5/main").is_none());
+    }
+
+    #[test]
+    fn test_mount_points() {
+        gst::init().unwrap();
+
+        static CREATED: AtomicU32 = AtomicU32::new(0);
+
+        let mounts = RTSPPatternMountPoints::new();
+        mounts
+            .add_pattern("/cameras/{id}", |params, _url| {
+                CREATED.fetch_add(1, Ordering::SeqCst);
+                let factory = RTSPMediaFactory::new();
+                factory.set_launch(&format!(
+                    "( videotestsrc pattern={} ! rtpvrawpay name=pay0 )",
+                    params.get("id")?
+                ));
+                Some(factory)
+            })
+            .unwrap();
+        assert!(mounts.add_pattern("/cameras/{name}", |_, _| None).is_err());
+
+        for _ in 0..2 {
+            let url = gst_rtsp::RTSPUrl::parse("rtsp://localhost/cameras/1/stream=0")
+                .1
+                .unwrap();
+            assert_eq!(mounts.make_path(&url).unwrap(), "/cameras/1/stream=0");
+        }
+        assert_eq!(CREATED.load(Ordering::SeqCst), 1);
+
+        let (factory, matched) = mounts.match_("/cameras/1/stream=0");
+        assert_eq!(matched as usize, "/cameras/1".len());
+        assert_eq!(
+            factory.launch().unwrap(),
+            "( videotestsrc pattern=1 ! rtpvrawpay name=pay0 )"
+        );
+    }
+}
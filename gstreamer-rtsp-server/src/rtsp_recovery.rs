@@ -0,0 +1,142 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use gst_rtsp::RTSPProfile;
+
+// rustdoc-stripper-ignore-next
+/// Packet loss recovery settings of the streams of a server, i.e. NACK based
+/// retransmission (RFC 4588) and ULP forward error correction (RFC 5109).
+///
+/// Set them with
+/// [`RTSPMediaFactoryExtManual::set_recovery_config()`](crate::prelude::RTSPMediaFactoryExtManual::set_recovery_config)
+/// or [`RTSPStreamExtManual::set_recovery_config()`](crate::prelude::RTSPStreamExtManual::set_recovery_config).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RTSPRecoveryConfig {
+    retransmission_time: Option<gst::ClockTime>,
+    retransmission_pt: Option<u32>,
+    do_retransmission: bool,
+    ulpfec_pt: Option<u32>,
+    ulpfec_percentage: u32,
+}
+
+impl RTSPRecoveryConfig {
+    pub fn builder() -> RTSPRecoveryConfigBuilder {
+        skip_assert_initialized!();
+        RTSPRecoveryConfigBuilder::default()
+    }
+
+    pub fn retransmission_time(&self) -> Option<gst::ClockTime> {
+        self.retransmission_time
+    }
+
+    pub fn retransmission_pt(&self) -> Option<u32> {
+        self.retransmission_pt
+    }
+
+    pub fn do_retransmission(&self) -> bool {
+        self.do_retransmission
+    }
+
+    pub fn ulpfec_pt(&self) -> Option<u32> {
+        self.ulpfec_pt
+    }
+
+    pub fn ulpfec_percentage(&self) -> u32 {
+        self.ulpfec_percentage
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether any of the recovery mechanisms is enabled, which requires
+    /// the feedback profiles.
+    pub fn is_enabled(&self) -> bool {
+        self.retransmission_time.is_some()
+            || self.do_retransmission
+            || (self.ulpfec_pt.is_some() && self.ulpfec_percentage > 0)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds the feedback variants of the profiles in `profiles` if any
+    /// recovery mechanism is enabled, e.g. `AVPF` for `AVP`.
+    pub fn profiles(&self, profiles: RTSPProfile) -> RTSPProfile {
+        if !self.is_enabled() {
+            return profiles;
+        }
+
+        let mut profiles = profiles | RTSPProfile::AVPF;
+        if profiles.contains(RTSPProfile::SAVP) {
+            profiles |= RTSPProfile::SAVPF;
+        }
+        profiles
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+#[must_use = "The builder must be built to be used"]
+pub struct RTSPRecoveryConfigBuilder {
+    config: RTSPRecoveryConfig,
+}
+
+impl RTSPRecoveryConfigBuilder {
+    // rustdoc-stripper-ignore-next
+    /// Keeps sent packets for `time` for answering retransmission requests.
+    pub fn retransmission_time(mut self, time: gst::ClockTime) -> Self {
+        self.config.retransmission_time = Some(time);
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the payload type of the retransmission stream. By default, a
+    /// free payload type is selected.
+    pub fn retransmission_pt(mut self, pt: u32) -> Self {
+        self.config.retransmission_pt = Some(pt);
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Requests retransmissions of lost packets of recorded streams.
+    pub fn do_retransmission(mut self, do_retransmission: bool) -> Self {
+        self.config.do_retransmission = do_retransmission;
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sends ULP FEC packets with the payload type `pt`, protecting
+    /// `percentage` percent of the media packets.
+    pub fn ulpfec(mut self, pt: u32, percentage: u32) -> Self {
+        self.config.ulpfec_pt = Some(pt);
+        self.config.ulpfec_percentage = percentage.min(100);
+        self
+    }
+
+    pub fn build(self) -> RTSPRecoveryConfig {
+        self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles() {
+        let config = RTSPRecoveryConfig::default();
+        assert!(!config.is_enabled());
+        assert_eq!(config.profiles(RTSPProfile::AVP), RTSPProfile::AVP);
+
+        let config = RTSPRecoveryConfig::builder()
+            .retransmission_time(gst::ClockTime::from_mseconds(500))
+            .build();
+        assert!(config.is_enabled());
+        assert_eq!(
+            config.profiles(RTSPProfile::AVP),
+            RTSPProfile::AVP | RTSPProfile::AVPF
+        );
+        assert_eq!(
+            config.profiles(RTSPProfile::AVP | RTSPProfile::SAVP),
+            RTSPProfile::AVP | RTSPProfile::SAVP | RTSPProfile::AVPF | RTSPProfile::SAVPF
+        );
+
+        let config = RTSPRecoveryConfig::builder().ulpfec(122, 150).build();
+        assert_eq!(config.ulpfec_percentage(), 100);
+        assert!(config.is_enabled());
+    }
+}
@@ -1,8 +1,65 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use glib::{prelude::*, source::SourceId, translate::*};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
-use crate::{ffi, RTSPServer};
+use glib::{prelude::*, source::SourceId, translate::*, SignalHandlerId};
+
+use crate::{
+    ffi, object_data::shared_data, prelude::*, RTSPClient, RTSPContext, RTSPFilterResult,
+    RTSPServer, RTSPSession, RTSPSessionPool,
+};
+
+const SOURCES_KEY: &str = "gstreamer-rs-rtsp-server-sources";
+const VERSION_TRACKING_KEY: &str = "gstreamer-rs-rtsp-server-version-tracking";
+const SESSION_VERSION_KEY: &str = "gstreamer-rs-rtsp-session-version";
+// Interval for checking if all clients disconnected during shutdown
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+type Sources = Arc<Mutex<Vec<glib::Source>>>;
+
+// Sources created by `attach()`, for removing them again on shutdown
+fn sources(server: &RTSPServer) -> Sources {
+    skip_assert_initialized!();
+    shared_data::<Sources>(server, SOURCES_KEY).0
+}
+
+// Remembers the RTSP version of the SETUP request that created each session
+// of the clients of `server`, as only RTSP 2.0 allows sending requests to
+// clients
+fn track_session_versions(server: &RTSPServer) {
+    skip_assert_initialized!();
+    if !shared_data::<()>(server, VERSION_TRACKING_KEY).1 {
+        return;
+    }
+
+    server.connect_client_connected(|_, client| {
+        client.connect_new_session(|_, session| {
+            let version = RTSPContext::with_current_context(|ctx| {
+                ctx.request().and_then(|request| request.version())
+            })
+            .flatten();
+            if let Some(version) = version {
+                unsafe {
+                    session.set_data(SESSION_VERSION_KEY, version);
+                }
+            }
+        });
+    });
+}
+
+fn session_version(session: &RTSPSession) -> Option<gst_rtsp::RTSPVersion> {
+    skip_assert_initialized!();
+    unsafe {
+        session
+            .data::<gst_rtsp::RTSPVersion>(SESSION_VERSION_KEY)
+            .map(|version| *version.as_ref())
+    }
+}
 
 pub trait RTSPServerExtManual: IsA<RTSPServer> + 'static {
     #[doc(alias = "gst_rtsp_server_attach")]
@@ -10,6 +67,7 @@ pub trait RTSPServerExtManual: IsA<RTSPServer> + 'static {
         &self,
         context: Option<&glib::MainContext>,
     ) -> Result<SourceId, glib::error::BoolError> {
+        track_session_versions(self.as_ref());
         unsafe {
             match ffi::gst_rtsp_server_attach(
                 self.as_ref().to_glib_none().0,
@@ -18,9 +76,193 @@ pub trait RTSPServerExtManual: IsA<RTSPServer> + 'static {
                 0 => Err(glib::bool_error!(
                     "Failed to attach main context to RTSP server"
                 )),
-                id => Ok(from_glib(id)),
+                id => {
+                    let context = context.cloned().unwrap_or_else(glib::MainContext::default);
+                    if let Some(source) = context.find_source_by_id(&SourceId::from_glib(id)) {
+                        let sources = sources(self.as_ref());
+                        let mut sources = sources.lock().unwrap();
+                        sources.retain(|s| !s.is_destroyed());
+                        sources.push(source);
+                    }
+                    Ok(from_glib(id))
+                }
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Like [`attach()`](Self::attach), but calls `filter` for each new
+    /// connection before a client is created for it.
+    ///
+    /// The returned future is spawned on `context` and can e.g. look up the
+    /// address of the peer in a database without blocking the context. The
+    /// connection is only handled by the server if it resolves to `true`,
+    /// otherwise it is closed. Requests sent by the peer in the meantime are
+    /// handled once the connection is accepted.
+    fn attach_with_connect_filter<F, Fut>(
+        &self,
+        context: Option<&glib::MainContext>,
+        filter: F,
+    ) -> Result<SourceId, glib::error::BoolError>
+    where
+        F: Fn(&RTSPServer, &gio::Socket) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        let server = self.as_ref().clone();
+        track_session_versions(&server);
+        let socket = server
+            .create_socket(gio::Cancellable::NONE)
+            .map_err(|err| glib::bool_error!("Failed to create socket: {}", err))?;
+        let context = context.cloned().unwrap_or_else(glib::MainContext::default);
+        let filter = Arc::new(filter);
+
+        let source = {
+            let context = context.clone();
+            let server = server.downgrade();
+            socket.create_source(
+                glib::IOCondition::IN | glib::IOCondition::PRI,
+                gio::Cancellable::NONE,
+                None,
+                glib::Priority::DEFAULT,
+                move |socket, _condition| {
+                    let Some(server) = server.upgrade() else {
+                        return glib::ControlFlow::Break;
+                    };
+
+                    let connection = match socket.accept(gio::Cancellable::NONE) {
+                        Ok(connection) => connection,
+                        Err(err) if err.matches(gio::IOErrorEnum::WouldBlock) => {
+                            return glib::ControlFlow::Continue;
+                        }
+                        Err(err) => {
+                            gst::warning!(
+                                gst::CAT_RUST,
+                                obj = &server,
+                                "Failed to accept connection: {}",
+                                err
+                            );
+                            return glib::ControlFlow::Continue;
+                        }
+                    };
+
+                    let accept = filter(&server, &connection);
+                    let ctx = context.clone();
+                    context.spawn(async move {
+                        if !accept.await {
+                            let _ = connection.close();
+                            return;
+                        }
+
+                        let Some(address) = connection
+                            .remote_address()
+                            .ok()
+                            .and_then(|address| address.downcast::<gio::InetSocketAddress>().ok())
+                        else {
+                            let _ = connection.close();
+                            return;
+                        };
+                        let ip = address.address().to_str();
+
+                        // Clients are attached to the thread default context
+                        // if the thread pool doesn't provide client threads
+                        let res = ctx.with_thread_default(|| {
+                            server.transfer_connection(connection, &ip, address.port() as i32, None)
+                        });
+                        if let Err(err) = res.and_then(|res| res) {
+                            gst::warning!(
+                                gst::CAT_RUST,
+                                obj = &server,
+                                "Failed to handle connection from {}: {}",
+                                ip,
+                                err
+                            );
+                        }
+                    });
+
+                    glib::ControlFlow::Continue
+                },
+            )
+        };
+
+        let id = source.attach(Some(&context));
+        let sources = sources(&server);
+        let mut sources = sources.lock().unwrap();
+        sources.retain(|s| !s.is_destroyed());
+        sources.push(source);
+
+        Ok(id)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Shuts down the server gracefully.
+    ///
+    /// The sources created by [`attach()`](Self::attach) and
+    /// [`attach_with_connect_filter()`](Self::attach_with_connect_filter) are removed
+    /// immediately, so no new connections are accepted. Clients get a
+    /// `TEARDOWN` request for each of their RTSP 2.0 sessions, as servers
+    /// can't send requests to RTSP 1.0 clients, and can disconnect by
+    /// themselves for up to `grace`. Afterwards, the remaining sessions are
+    /// removed from the session pool and the remaining clients are closed.
+    ///
+    /// The version of sessions is only known for clients that connected
+    /// after the server was attached with one of the functions above.
+    ///
+    /// The returned future must be run on a main context, e.g. the one the
+    /// server is attached to.
+    fn shutdown(&self, grace: Duration) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> {
+        let server = self.as_ref().clone();
+
+        for source in sources(&server).lock().unwrap().drain(..) {
+            source.destroy();
+        }
+
+        for client in server.client_filter(None) {
+            for session in client.session_filter(None) {
+                if session_version(&session) != Some(gst_rtsp::RTSPVersion::_20) {
+                    continue;
+                }
+                let Some(session_id) = session.sessionid() else {
+                    continue;
+                };
+                let request =
+                    gst_rtsp::RTSPMessage::request_builder(gst_rtsp::RTSPMethod::Teardown, "*")
+                        .session(&session_id)
+                        .build();
+                let _ = client.send_message(&request, Some(&session));
             }
         }
+
+        Box::pin(async move {
+            let deadline = Instant::now() + grace;
+            while !server.client_filter(None).is_empty() && Instant::now() < deadline {
+                glib::timeout_future(
+                    SHUTDOWN_POLL_INTERVAL.min(deadline.saturating_duration_since(Instant::now())),
+                )
+                .await;
+            }
+
+            if let Some(pool) = server.session_pool() {
+                pool.filter(Some(&mut |_: &RTSPSessionPool, _: &RTSPSession| {
+                    RTSPFilterResult::Remove
+                }));
+            }
+            server.client_filter(Some(&mut |_: &RTSPServer, _: &RTSPClient| {
+                RTSPFilterResult::Remove
+            }));
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Installs `policy` on all clients that connect from now on, see
+    /// [`RTSPClientExtManual::connect_transport_policy()`].
+    fn connect_transport_policy<F>(&self, policy: F) -> SignalHandlerId
+    where
+        F: Fn(&RTSPClient, &RTSPContext, &gst_rtsp::RTSPTransport) -> bool + Send + Sync + 'static,
+    {
+        let policy: Arc<crate::rtsp_client::TransportPolicy> = Arc::new(policy);
+        self.connect_client_connected(move |_, client| {
+            crate::rtsp_client::connect_transport_policy(client, policy.clone());
+        })
     }
 }
 
@@ -1,6 +1,6 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::mem::transmute;
+use std::{mem::transmute, time::Duration};
 
 use glib::{
     ffi::{gboolean, gpointer},
@@ -10,7 +10,48 @@ use glib::{
     ControlFlow,
 };
 
-use crate::{ffi, RTSPSessionPool};
+use crate::{ffi, prelude::*, RTSPFilterResult, RTSPSession, RTSPSessionPool};
+
+// rustdoc-stripper-ignore-next
+/// Occupancy of an [`RTSPSessionPool`], as reported by
+/// [`RTSPSessionPoolExtManual::attach_cleanup()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RTSPSessionPoolStats {
+    pub n_sessions: u32,
+    // rustdoc-stripper-ignore-next
+    /// The maximum number of sessions, `0` if unlimited.
+    pub max_sessions: u32,
+    // rustdoc-stripper-ignore-next
+    /// The number of sessions removed by the last cleanup.
+    pub n_expired: u32,
+}
+
+impl RTSPSessionPoolStats {
+    // rustdoc-stripper-ignore-next
+    /// Returns the fraction of the maximum number of sessions that is in
+    /// use, or `None` if the number of sessions is unlimited.
+    pub fn occupancy(&self) -> Option<f64> {
+        (self.max_sessions > 0).then(|| self.n_sessions as f64 / self.max_sessions as f64)
+    }
+}
+
+// Removes the sessions that are expired at `now` like
+// `gst_rtsp_session_pool_cleanup()`, but returns them
+fn remove_expired(pool: &RTSPSessionPool, now: i64) -> Vec<RTSPSession> {
+    skip_assert_initialized!();
+    let expired = pool.filter(Some(&mut |_: &RTSPSessionPool, session: &RTSPSession| {
+        if session.is_expired_usec(now) {
+            RTSPFilterResult::Ref
+        } else {
+            RTSPFilterResult::Keep
+        }
+    }));
+
+    expired
+        .into_iter()
+        .filter(|session| pool.remove(session).is_ok())
+        .collect()
+}
 
 unsafe extern "C" fn trampoline_watch<
     F: FnMut(&RTSPSessionPool) -> ControlFlow + Send + 'static,
@@ -63,6 +104,88 @@ pub trait RTSPSessionPoolExtManual: IsA<RTSPSessionPool> + 'static {
             from_glib_full(source)
         }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the current occupancy of the pool. `n_expired` is always `0`.
+    fn stats(&self) -> RTSPSessionPoolStats {
+        RTSPSessionPoolStats {
+            n_sessions: self.n_sessions(),
+            max_sessions: self.max_sessions(),
+            n_expired: 0,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes expired sessions from the pool every `interval` on `context`,
+    /// or the default main context if `None`.
+    ///
+    /// After each cleanup, `func` is called with the removed sessions and
+    /// the occupancy of the pool. The returned source is destroyed once the
+    /// pool is disposed.
+    fn attach_cleanup<F>(
+        &self,
+        context: Option<&glib::MainContext>,
+        interval: Duration,
+        mut func: F,
+    ) -> glib::SourceId
+    where
+        F: FnMut(&RTSPSessionPool, &[RTSPSession], &RTSPSessionPoolStats) + Send + 'static,
+    {
+        let pool = self.as_ref().downgrade();
+        let source = glib::timeout_source_new(
+            interval,
+            Some("rtsp-session-pool-cleanup"),
+            Priority::DEFAULT,
+            move || {
+                let Some(pool) = pool.upgrade() else {
+                    return ControlFlow::Break;
+                };
+
+                let expired = remove_expired(&pool, glib::monotonic_time());
+                let stats = RTSPSessionPoolStats {
+                    n_expired: expired.len() as u32,
+                    ..pool.stats()
+                };
+                func(&pool, &expired, &stats);
+
+                ControlFlow::Continue
+            },
+        );
+
+        source.attach(context)
+    }
 }
 
 impl<O: IsA<RTSPSessionPool>> RTSPSessionPoolExtManual for O {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_expired() {
+        gst::init().unwrap();
+
+        let pool = RTSPSessionPool::new();
+        pool.set_max_sessions(4);
+        let session = pool.create().unwrap();
+        session.set_timeout(60);
+        pool.create().unwrap().set_timeout(7200);
+
+        let now = glib::monotonic_time();
+        assert!(remove_expired(&pool, now).is_empty());
+        assert_eq!(
+            pool.stats(),
+            RTSPSessionPoolStats {
+                n_sessions: 2,
+                max_sessions: 4,
+                n_expired: 0,
+            }
+        );
+        assert_eq!(pool.stats().occupancy(), Some(0.5));
+
+        let expired = remove_expired(&pool, now + 3600 * 1_000_000);
+        assert_eq!(expired, [session]);
+        assert_eq!(pool.n_sessions(), 1);
+    }
+}
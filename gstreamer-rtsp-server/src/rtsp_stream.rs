@@ -0,0 +1,49 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::{prelude::*, translate::*};
+
+use crate::{ffi, prelude::*, RTSPRecoveryConfig, RTSPStream, RTSPStreamStats};
+
+pub trait RTSPStreamExtManual: IsA<RTSPStream> + 'static {
+    #[doc(alias = "gst_rtsp_stream_is_transport_supported")]
+    fn is_transport_supported(&self, transport: &gst_rtsp::RTSPTransport) -> bool {
+        unsafe {
+            from_glib(ffi::gst_rtsp_stream_is_transport_supported(
+                self.as_ref().to_glib_none().0,
+                transport.as_ptr(),
+            ))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Configures retransmission and ULP FEC for the stream and enables the
+    /// feedback profiles if needed.
+    ///
+    /// This has to be called before the stream is joined to the bin of the
+    /// media. ULP FEC requires the `v1_16` feature.
+    fn set_recovery_config(&self, config: &RTSPRecoveryConfig) {
+        let stream = self.as_ref();
+        stream.set_retransmission_time(config.retransmission_time());
+        if let Some(pt) = config.retransmission_pt() {
+            stream.set_retransmission_pt(pt);
+        }
+        #[cfg(feature = "v1_16")]
+        if let Some(pt) = config.ulpfec_pt() {
+            stream.set_ulpfec_pt(pt);
+            stream.set_ulpfec_percentage(config.ulpfec_percentage());
+        }
+        stream.set_profiles(config.profiles(stream.profiles()));
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the statistics of the RTP session of the stream, or `None` if
+    /// the stream is not joined to the bin of its media yet.
+    fn stats(&self) -> Option<RTSPStreamStats> {
+        let stream = self.as_ref();
+        let session = stream.rtpsession()?;
+        let stats = session.property::<gst::Structure>("stats");
+        Some(RTSPStreamStats::from_session_stats(stream.index(), &stats))
+    }
+}
+
+impl<O: IsA<RTSPStream>> RTSPStreamExtManual for O {}
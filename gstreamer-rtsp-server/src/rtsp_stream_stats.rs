@@ -0,0 +1,245 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+/// Statistics of the RTP session of a stream, as returned by
+/// [`RTSPStreamExtManual::stats()`](crate::prelude::RTSPStreamExtManual::stats).
+///
+/// They are aggregated over all sources of the session, i.e. the streams
+/// sent by the server and all clients receiving or recording them.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RTSPStreamStats {
+    // rustdoc-stripper-ignore-next
+    /// The index of the stream in its media.
+    pub index: u32,
+    // rustdoc-stripper-ignore-next
+    /// The streams sent by the server.
+    pub senders: Vec<RTSPSenderStats>,
+    // rustdoc-stripper-ignore-next
+    /// The last reception reports of the clients.
+    pub receivers: Vec<RTSPReceiverStats>,
+    // rustdoc-stripper-ignore-next
+    /// The streams received from clients, e.g. for RECORD.
+    pub remote_senders: Vec<RTSPRemoteSenderStats>,
+}
+
+impl RTSPStreamStats {
+    // rustdoc-stripper-ignore-next
+    /// Parses the `stats` structure of an `RTPSession`.
+    pub fn from_session_stats(index: u32, stats: &gst::StructureRef) -> Self {
+        skip_assert_initialized!();
+        let mut ret = RTSPStreamStats {
+            index,
+            ..Default::default()
+        };
+
+        let Ok(sources) = stats.get::<glib::ValueArray>("source-stats") else {
+            return ret;
+        };
+
+        for source in sources
+            .iter()
+            .filter_map(|v| v.get::<gst::Structure>().ok())
+        {
+            let ssrc = field::<u32>(&source, "ssrc");
+            let internal = field::<bool>(&source, "internal");
+            let is_sender = field::<bool>(&source, "is-sender");
+
+            if internal {
+                if is_sender {
+                    ret.senders.push(RTSPSenderStats {
+                        ssrc,
+                        packets_sent: field(&source, "packets-sent"),
+                        octets_sent: field(&source, "octets-sent"),
+                        bitrate: field(&source, "bitrate"),
+                    });
+                }
+                continue;
+            }
+
+            if field::<bool>(&source, "have-rb") {
+                ret.receivers.push(RTSPReceiverStats {
+                    ssrc,
+                    fraction_lost: field::<u32>(&source, "rb-fractionlost") as u8,
+                    packets_lost: field(&source, "rb-packetslost"),
+                    ext_highest_seq: field(&source, "rb-exthighestseq"),
+                    jitter: field(&source, "rb-jitter"),
+                    round_trip: round_trip(field(&source, "rb-round-trip")),
+                });
+            }
+
+            if is_sender {
+                ret.remote_senders.push(RTSPRemoteSenderStats {
+                    ssrc,
+                    packets_received: field(&source, "packets-received"),
+                    octets_received: field(&source, "octets-received"),
+                    packets_lost: field(&source, "packets-lost"),
+                    jitter: field(&source, "jitter"),
+                    bitrate: field(&source, "bitrate"),
+                });
+            }
+        }
+
+        ret
+    }
+
+    pub fn packets_sent(&self) -> u64 {
+        self.senders.iter().map(|s| s.packets_sent).sum()
+    }
+
+    pub fn octets_sent(&self) -> u64 {
+        self.senders.iter().map(|s| s.octets_sent).sum()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The sum of the bitrates of the streams sent by the server, in bits
+    /// per second.
+    pub fn bitrate(&self) -> u64 {
+        self.senders.iter().map(|s| s.bitrate).sum()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The highest interarrival jitter reported by a client, in RTP
+    /// timestamp units.
+    pub fn max_jitter(&self) -> Option<u32> {
+        self.receivers.iter().map(|r| r.jitter).max()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Statistics of a stream sent by the server.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RTSPSenderStats {
+    pub ssrc: u32,
+    pub packets_sent: u64,
+    pub octets_sent: u64,
+    // rustdoc-stripper-ignore-next
+    /// The estimated bitrate in bits per second.
+    pub bitrate: u64,
+}
+
+// rustdoc-stripper-ignore-next
+/// The last RTCP reception report of a client.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RTSPReceiverStats {
+    // rustdoc-stripper-ignore-next
+    /// The SSRC of the client.
+    pub ssrc: u32,
+    // rustdoc-stripper-ignore-next
+    /// The fraction of packets lost since the previous report, in units
+    /// of 1/256.
+    pub fraction_lost: u8,
+    // rustdoc-stripper-ignore-next
+    /// The cumulative number of packets lost.
+    pub packets_lost: i32,
+    pub ext_highest_seq: u32,
+    // rustdoc-stripper-ignore-next
+    /// The interarrival jitter in RTP timestamp units.
+    pub jitter: u32,
+    pub round_trip: Option<gst::ClockTime>,
+}
+
+impl RTSPReceiverStats {
+    // rustdoc-stripper-ignore-next
+    /// Returns the fraction of packets lost since the previous report as
+    /// a value between `0.0` and `1.0`.
+    pub fn loss_ratio(&self) -> f64 {
+        self.fraction_lost as f64 / 256.0
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Statistics of a stream received from a client.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RTSPRemoteSenderStats {
+    pub ssrc: u32,
+    pub packets_received: u64,
+    pub octets_received: u64,
+    pub packets_lost: i32,
+    // rustdoc-stripper-ignore-next
+    /// The interarrival jitter in RTP timestamp units.
+    pub jitter: u32,
+    // rustdoc-stripper-ignore-next
+    /// The estimated bitrate in bits per second.
+    pub bitrate: u64,
+}
+
+fn field<T: for<'a> glib::value::FromValue<'a> + Default + 'static>(
+    s: &gst::StructureRef,
+    name: &str,
+) -> T {
+    skip_assert_initialized!();
+    s.get::<T>(name).unwrap_or_default()
+}
+
+// The round trip time is in units of 1/65536 seconds, 0 if unknown
+fn round_trip(round_trip: u32) -> Option<gst::ClockTime> {
+    skip_assert_initialized!();
+    (round_trip > 0).then(|| {
+        gst::ClockTime::from_nseconds((round_trip as u64 * *gst::ClockTime::SECOND).div_ceil(65536))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use glib::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_from_session_stats() {
+        gst::init().unwrap();
+
+        let sender = gst::Structure::builder("application/x-rtp-source-stats")
+            .field("ssrc", 0x1234u32)
+            .field("internal", true)
+            .field("is-sender", true)
+            .field("packets-sent", 100u64)
+            .field("octets-sent", 12000u64)
+            .field("bitrate", 96000u64)
+            .build();
+        let receiver = gst::Structure::builder("application/x-rtp-source-stats")
+            .field("ssrc", 0x5678u32)
+            .field("internal", false)
+            .field("is-sender", false)
+            .field("have-rb", true)
+            .field("rb-fractionlost", 64u32)
+            .field("rb-packetslost", 3i32)
+            .field("rb-exthighestseq", 99u32)
+            .field("rb-jitter", 40u32)
+            .field("rb-round-trip", 32768u32)
+            .build();
+
+        let sources = glib::ValueArray::new([sender.to_value(), receiver.to_value()]);
+        let stats = gst::Structure::builder("application/x-rtp-session-stats")
+            .field("source-stats", sources)
+            .build();
+
+        let stats = RTSPStreamStats::from_session_stats(1, &stats);
+        assert_eq!(stats.index, 1);
+        assert_eq!(
+            stats.senders,
+            [RTSPSenderStats {
+                ssrc: 0x1234,
+                packets_sent: 100,
+                octets_sent: 12000,
+                bitrate: 96000,
+            }]
+        );
+        assert_eq!(stats.receivers.len(), 1);
+        assert_eq!(stats.receivers[0].ssrc, 0x5678);
+        assert_eq!(stats.receivers[0].loss_ratio(), 0.25);
+        assert_eq!(
+            stats.receivers[0].round_trip,
+            Some(gst::ClockTime::from_mseconds(500))
+        );
+        assert!(stats.remote_senders.is_empty());
+        assert_eq!(stats.packets_sent(), 100);
+        assert_eq!(stats.max_jitter(), Some(40));
+
+        let empty = gst::Structure::new_empty("application/x-rtp-session-stats");
+        assert_eq!(
+            RTSPStreamStats::from_session_stats(0, &empty),
+            RTSPStreamStats::default()
+        );
+    }
+}
@@ -23,6 +23,16 @@ impl RTSPToken {
         Builder::new()
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Creates a token with the media factory role `role`, which selects
+    /// the permissions of media factories for requests with this token.
+    pub fn with_role(role: &str) -> Self {
+        skip_assert_initialized!();
+        Self::builder()
+            .field_with_static(crate::RTSP_TOKEN_MEDIA_FACTORY_ROLE, role)
+            .build()
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_iter<S: IntoGStr>(iter: impl IntoIterator<Item = (S, SendValue)>) -> RTSPToken {
         skip_assert_initialized!();
@@ -6,6 +6,7 @@ mod rtsp_auth;
 mod rtsp_client;
 mod rtsp_media;
 mod rtsp_media_factory;
+mod rtsp_media_factory_uri;
 mod rtsp_mount_points;
 mod rtsp_server;
 
@@ -14,7 +15,7 @@ mod rtsp_onvif_media;
 mod rtsp_onvif_media_factory;
 mod rtsp_onvif_server;
 
-pub use self::rtsp_media::SDPInfo;
+pub use self::rtsp_media::{SDPInfo, SeekRequest};
 
 pub mod prelude {
     #[doc(hidden)]
@@ -25,6 +26,7 @@ pub mod prelude {
         rtsp_client::{RTSPClientImpl, RTSPClientImplExt},
         rtsp_media::{RTSPMediaImpl, RTSPMediaImplExt},
         rtsp_media_factory::{RTSPMediaFactoryImpl, RTSPMediaFactoryImplExt},
+        rtsp_media_factory_uri::RTSPMediaFactoryURIImpl,
         rtsp_mount_points::{RTSPMountPointsImpl, RTSPMountPointsImplExt},
         rtsp_onvif_client::RTSPOnvifClientImpl,
         rtsp_onvif_media::RTSPOnvifMediaImpl,
@@ -1,5 +1,7 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
+use std::{ffi::CStr, ptr};
+
 use glib::{prelude::*, subclass::prelude::*, translate::*};
 
 use crate::{ffi, RTSPClient};
@@ -18,7 +20,17 @@ pub trait RTSPClientImpl: ObjectImpl + ObjectSubclass<Type: IsA<RTSPClient>> + S
         self.parent_configure_client_media(media, stream, ctx)
     }
 
-    // TODO: configure_client_transport
+    // rustdoc-stripper-ignore-next
+    /// Configures the transport that was selected for a SETUP request
+    /// before it is used, e.g. for capping the TTL of multicast transports
+    /// or rejecting the transport with an error.
+    fn configure_client_transport(
+        &self,
+        ctx: &crate::RTSPContext,
+        transport: &mut gst_rtsp::RTSPTransport,
+    ) -> Result<(), gst::LoggableError> {
+        self.parent_configure_client_transport(ctx, transport)
+    }
 
     fn params_set(&self, ctx: &crate::RTSPContext) -> gst_rtsp::RTSPResult {
         self.parent_params_set(ctx)
@@ -153,6 +165,39 @@ pub trait RTSPClientImpl: ObjectImpl + ObjectSubclass<Type: IsA<RTSPClient>> + S
     ) -> gst_rtsp::RTSPStatusCode {
         self.parent_adjust_error_code(ctx, status_code)
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Names of non-standard methods, e.g. vendor extensions, that are
+    /// advertised in the `Public` header of OPTIONS responses in addition to
+    /// the methods implemented by GStreamer.
+    fn custom_methods(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Handles a request that the client doesn't implement itself and that
+    /// would otherwise be answered with the error `status_code`, i.e.
+    /// `400 Bad Request` or `501 Not Implemented`.
+    ///
+    /// The returned response is sent instead of the error response. It
+    /// should be created for the request of `ctx`, e.g. with
+    /// [`RTSPMessage::response_builder()`](gst_rtsp::RTSPMessage::response_builder),
+    /// so that its `CSeq` matches.
+    ///
+    /// Parsed requests only carry methods known to GStreamer, so
+    /// [`RTSPContext::method()`](crate::RTSPContext::method) is empty for
+    /// other methods and requests of different custom methods have to be
+    /// told apart by their URI or headers.
+    #[cfg(feature = "v1_22")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_22")))]
+    fn custom_request(
+        &self,
+        ctx: &crate::RTSPContext,
+        status_code: gst_rtsp::RTSPStatusCode,
+    ) -> Option<gst_rtsp::RTSPMessage> {
+        let _ = (ctx, status_code);
+        None
+    }
 }
 
 pub trait RTSPClientImplExt: RTSPClientImpl {
@@ -197,7 +242,29 @@ pub trait RTSPClientImplExt: RTSPClientImpl {
         }
     }
 
-    // TODO: configure_client_transport
+    fn parent_configure_client_transport(
+        &self,
+        ctx: &crate::RTSPContext,
+        transport: &mut gst_rtsp::RTSPTransport,
+    ) -> Result<(), gst::LoggableError> {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GstRTSPClientClass;
+            let f = (*parent_class).configure_client_transport.expect(
+                "No `configure_client_transport` virtual method implementation in parent class",
+            );
+
+            gst::result_from_gboolean!(
+                f(
+                    self.obj().unsafe_cast_ref::<RTSPClient>().to_glib_none().0,
+                    ctx.to_glib_none().0,
+                    transport.as_ptr()
+                ),
+                gst::CAT_RUST,
+                "Parent function `configure_client_transport` failed"
+            )
+        }
+    }
 
     fn parent_params_set(&self, ctx: &crate::RTSPContext) -> gst_rtsp::RTSPResult {
         unsafe {
@@ -841,6 +908,7 @@ unsafe impl<T: RTSPClientImpl> IsSubclassable<T> for RTSPClient {
 
                 klass.create_sdp = Some(client_create_sdp::<T>);
                 klass.configure_client_media = Some(client_configure_client_media::<T>);
+                klass.configure_client_transport = Some(client_configure_client_transport::<T>);
                 klass.params_set = Some(client_params_set::<T>);
                 klass.params_get = Some(client_params_get::<T>);
                 klass.make_path_from_uri = Some(client_make_path_from_uri::<T>);
@@ -876,6 +944,7 @@ unsafe impl<T: RTSPClientImpl> IsSubclassable<T> for RTSPClient {
 
         klass.create_sdp = Some(client_create_sdp::<T>);
         klass.configure_client_media = Some(client_configure_client_media::<T>);
+        klass.configure_client_transport = Some(client_configure_client_transport::<T>);
         klass.params_set = Some(client_params_set::<T>);
         klass.params_get = Some(client_params_get::<T>);
         klass.make_path_from_uri = Some(client_make_path_from_uri::<T>);
@@ -908,6 +977,7 @@ unsafe impl<T: RTSPClientImpl> IsSubclassable<T> for RTSPClient {
         #[cfg_attr(docsrs, doc(cfg(feature = "v1_22")))]
         {
             klass.adjust_error_code = Some(client_adjust_error_code::<T>);
+            klass.send_message = Some(client_send_message::<T>);
         }
     }
 }
@@ -944,6 +1014,26 @@ unsafe extern "C" fn client_configure_client_media<T: RTSPClientImpl>(
     }
 }
 
+unsafe extern "C" fn client_configure_client_transport<T: RTSPClientImpl>(
+    ptr: *mut ffi::GstRTSPClient,
+    ctx: *mut ffi::GstRTSPContext,
+    transport: *mut gst_rtsp::ffi::GstRTSPTransport,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    // The transport is owned by the caller
+    let mut transport =
+        std::mem::ManuallyDrop::new(gst_rtsp::RTSPTransport::from_glib_full(transport));
+    match imp.configure_client_transport(&from_glib_borrow(ctx), &mut transport) {
+        Ok(()) => glib::ffi::GTRUE,
+        Err(err) => {
+            err.log_with_imp(imp);
+            glib::ffi::GFALSE
+        }
+    }
+}
+
 unsafe extern "C" fn client_params_set<T: RTSPClientImpl>(
     ptr: *mut ffi::GstRTSPClient,
     ctx: *mut ffi::GstRTSPContext,
@@ -999,9 +1089,47 @@ unsafe extern "C" fn client_options_request<T: RTSPClientImpl>(
     let instance = &*(ptr as *mut T::Instance);
     let imp = instance.imp();
 
+    let methods = imp.custom_methods();
+    if !methods.is_empty() {
+        append_public_methods((*ctx).response, &methods);
+    }
+
     imp.options_request(&from_glib_borrow(ctx));
 }
 
+unsafe fn append_public_methods(response: *mut gst_rtsp::ffi::GstRTSPMessage, methods: &[String]) {
+    if response.is_null() {
+        return;
+    }
+
+    let mut value = ptr::null_mut();
+    let mut public = if gst_rtsp::ffi::gst_rtsp_message_get_header(
+        response,
+        gst_rtsp::ffi::GST_RTSP_HDR_PUBLIC,
+        &mut value,
+        0,
+    ) == gst_rtsp::ffi::GST_RTSP_OK
+    {
+        CStr::from_ptr(value).to_string_lossy().into_owned()
+    } else {
+        String::new()
+    };
+
+    for method in methods {
+        if !public.is_empty() {
+            public.push_str(", ");
+        }
+        public.push_str(method);
+    }
+
+    gst_rtsp::ffi::gst_rtsp_message_remove_header(response, gst_rtsp::ffi::GST_RTSP_HDR_PUBLIC, -1);
+    gst_rtsp::ffi::gst_rtsp_message_add_header(
+        response,
+        gst_rtsp::ffi::GST_RTSP_HDR_PUBLIC,
+        public.to_glib_none().0,
+    );
+}
+
 unsafe extern "C" fn client_describe_request<T: RTSPClientImpl>(
     ptr: *mut ffi::GstRTSPClient,
     ctx: *mut ffi::GstRTSPContext,
@@ -1251,3 +1379,82 @@ unsafe extern "C" fn client_adjust_error_code<T: RTSPClientImpl>(
     imp.adjust_error_code(&from_glib_borrow(ctx), from_glib(status_code))
         .into_glib()
 }
+
+#[cfg(feature = "v1_22")]
+unsafe extern "C" fn client_send_message<T: RTSPClientImpl>(
+    ptr: *mut ffi::GstRTSPClient,
+    ctx: *mut ffi::GstRTSPContext,
+    message: *mut gst_rtsp::ffi::GstRTSPMessage,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    // Generic error responses are built in the response of the context right
+    // before sending them, so they can only be replaced here
+    if !ctx.is_null() && (*ctx).response == message && !(*ctx).request.is_null() {
+        let ctx = crate::RTSPContext::from_glib_borrow(ctx);
+        if let Some(generic) = ctx.response() {
+            if let Some(
+                status_code @ (gst_rtsp::RTSPStatusCode::BadRequest
+                | gst_rtsp::RTSPStatusCode::NotImplemented),
+            ) = generic.status_code()
+            {
+                if let Some(mut response) = imp.custom_request(&ctx, status_code) {
+                    // Keep the headers that were already added for sending
+                    for field in [
+                        gst_rtsp::RTSPHeaderField::Server,
+                        gst_rtsp::RTSPHeaderField::Session,
+                        gst_rtsp::RTSPHeaderField::Connection,
+                    ] {
+                        if response.header(field, 0).is_none() {
+                            if let Some(value) = generic.header(field, 0) {
+                                response.add_header(field, value);
+                            }
+                        }
+                    }
+
+                    ptr::swap(message, response.to_glib_none_mut().0);
+                }
+            }
+        }
+    }
+
+    let data = T::type_data();
+    let parent_class = data.as_ref().parent_class() as *mut ffi::GstRTSPClientClass;
+    if let Some(f) = (*parent_class).send_message {
+        f(ptr, ctx, message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_append_public_methods() {
+        gst::init().unwrap();
+
+        let mut response = gst_rtsp::RTSPMessage::response_builder(gst_rtsp::RTSPStatusCode::Ok)
+            .public(gst_rtsp::RTSPMethod::OPTIONS | gst_rtsp::RTSPMethod::DESCRIBE)
+            .build();
+        unsafe {
+            append_public_methods(
+                response.to_glib_none_mut().0,
+                &["X-PTZ".to_owned(), "X-REBOOT".to_owned()],
+            );
+        }
+        assert_eq!(
+            response.header(gst_rtsp::RTSPHeaderField::Public, 0),
+            Some("DESCRIBE, OPTIONS, X-PTZ, X-REBOOT")
+        );
+
+        let mut response = gst_rtsp::RTSPMessage::new_response(gst_rtsp::RTSPStatusCode::Ok, None);
+        unsafe {
+            append_public_methods(response.to_glib_none_mut().0, &["X-PTZ".to_owned()]);
+        }
+        assert_eq!(
+            response.header(gst_rtsp::RTSPHeaderField::Public, 0),
+            Some("X-PTZ")
+        );
+    }
+}
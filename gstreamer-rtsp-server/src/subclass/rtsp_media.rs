@@ -22,6 +22,218 @@ impl SDPInfo {
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// The seek requested by the `Range`, `Scale` and `Speed` headers of a PLAY
+/// request, e.g. for implementing [`RTSPMediaImpl`] of replay servers.
+///
+/// Positions are media times. For the `clock` format, they are relative to
+/// the absolute time of the beginning of the media.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeekRequest {
+    unit: gst_rtsp::RTSPRangeUnit,
+    clock_base: Option<gst::ClockTime>,
+    start: Option<gst::ClockTime>,
+    stop: Option<gst::ClockTime>,
+    scale: f64,
+    speed: Option<f64>,
+}
+
+impl SeekRequest {
+    // rustdoc-stripper-ignore-next
+    /// Parses the seek of a PLAY `request`. `clock_base` is the absolute
+    /// time of the beginning of the media and is required for ranges in the
+    /// `clock` format.
+    ///
+    /// Without `Range` header, playback continues from the current position.
+    pub fn from_request(
+        request: &gst_rtsp::RTSPMessage,
+        clock_base: Option<&glib::DateTime>,
+    ) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+        if let Some(scale) = request.header(gst_rtsp::RTSPHeaderField::Scale, 0) {
+            if request.scale().is_none() {
+                return Err(glib::bool_error!("Invalid Scale header {}", scale));
+            }
+        }
+        if let Some(speed) = request.header(gst_rtsp::RTSPHeaderField::Speed, 0) {
+            if request.speed().is_none() {
+                return Err(glib::bool_error!("Invalid Speed header {}", speed));
+            }
+        }
+
+        let clock_base = clock_base.map(unix_time).transpose()?;
+        let mut seek = SeekRequest {
+            unit: gst_rtsp::RTSPRangeUnit::Npt,
+            clock_base,
+            start: None,
+            stop: None,
+            scale: request.scale().unwrap_or(1.0),
+            speed: request.speed(),
+        };
+
+        let Some(range) = request.header(gst_rtsp::RTSPHeaderField::Range, 0) else {
+            return Ok(seek);
+        };
+        let range = gst_rtsp::RTSPTimeRange::parse(range)
+            .map_err(|err| glib::bool_error!("Invalid Range header {}: {}", range, err))?;
+        let (start, stop) = range
+            .times()
+            .ok_or_else(|| glib::bool_error!("Unsupported range {}", range))?;
+
+        seek.unit = range.unit();
+        if seek.unit == gst_rtsp::RTSPRangeUnit::Clock {
+            let base =
+                clock_base.ok_or_else(|| glib::bool_error!("No clock base for range {}", range))?;
+            let relative = |time: Option<gst::ClockTime>| {
+                time.map(|time| {
+                    time.checked_sub(base)
+                        .ok_or_else(|| glib::bool_error!("Range {} before clock base", range))
+                })
+                .transpose()
+            };
+            seek.start = relative(start)?;
+            seek.stop = relative(stop)?;
+        } else {
+            seek.start = start;
+            seek.stop = stop;
+        }
+
+        Ok(seek)
+    }
+
+    pub fn unit(&self) -> gst_rtsp::RTSPRangeUnit {
+        self.unit
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The position to start playing from, or `None` for the current
+    /// position.
+    pub fn start(&self) -> Option<gst::ClockTime> {
+        self.start
+    }
+
+    pub fn stop(&self) -> Option<gst::ClockTime> {
+        self.stop
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    pub fn speed(&self) -> Option<f64> {
+        self.speed
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The playback rate of the seek, i.e. the scale multiplied by the
+    /// speed.
+    pub fn rate(&self) -> f64 {
+        self.scale * self.speed.unwrap_or(1.0)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates the seek event for the requested range and rate.
+    ///
+    /// For reverse playback, the range is played from its start backwards
+    /// to its stop, which are swapped for the event accordingly.
+    pub fn seek_event(&self, flags: gst::SeekFlags) -> gst::Event {
+        let (start, stop) = if self.rate() < 0.0 {
+            (self.stop, self.start)
+        } else {
+            (self.start, self.stop)
+        };
+        let seek_type = |time: Option<gst::ClockTime>| {
+            if time.is_some() {
+                gst::SeekType::Set
+            } else {
+                gst::SeekType::None
+            }
+        };
+
+        gst::event::Seek::new(
+            self.rate(),
+            flags,
+            seek_type(start),
+            start,
+            seek_type(stop),
+            stop,
+        )
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the range that is played after the seek in the format of the
+    /// request, starting at `position` if known.
+    pub fn played_range(
+        &self,
+        position: Option<gst::ClockTime>,
+    ) -> Result<gst_rtsp::RTSPTimeRange, glib::BoolError> {
+        let start = position.or(self.start);
+
+        match (self.unit, self.clock_base) {
+            (gst_rtsp::RTSPRangeUnit::Clock, Some(base)) => {
+                let start = match start {
+                    Some(start) => gst_rtsp::RTSPTime::utc(&date_time(base + start)?)?,
+                    None => gst_rtsp::RTSPTime::Now,
+                };
+                let stop = match self.stop {
+                    Some(stop) => gst_rtsp::RTSPTime::utc(&date_time(base + stop)?)?,
+                    None => gst_rtsp::RTSPTime::End,
+                };
+                Ok(gst_rtsp::RTSPTimeRange::new(
+                    gst_rtsp::RTSPRangeUnit::Clock,
+                    start,
+                    stop,
+                ))
+            }
+            (gst_rtsp::RTSPRangeUnit::Clock, None) => {
+                Err(glib::bool_error!("No clock base for clock range"))
+            }
+            (unit, _) => {
+                let mut range = gst_rtsp::RTSPTimeRange::npt(start, self.stop);
+                if unit != gst_rtsp::RTSPRangeUnit::Npt {
+                    range.convert_units(unit)?;
+                }
+                Ok(range)
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the `Range`, `Scale` and `Speed` headers of the `response` to a
+    /// PLAY request to the played range starting at `position` and the
+    /// granted scale and speed.
+    pub fn apply_to_response(
+        &self,
+        response: &mut gst_rtsp::RTSPMessage,
+        position: Option<gst::ClockTime>,
+    ) -> Result<(), glib::BoolError> {
+        let range = self.played_range(position)?;
+        response.remove_header(gst_rtsp::RTSPHeaderField::Range, None);
+        response.add_header(gst_rtsp::RTSPHeaderField::Range, &range.to_string());
+        response.set_scale(self.scale)?;
+        if let Some(speed) = self.speed {
+            response.set_speed(speed)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn unix_time(date_time: &glib::DateTime) -> Result<gst::ClockTime, glib::BoolError> {
+    skip_assert_initialized!();
+    let seconds = u64::try_from(date_time.to_unix())
+        .map_err(|_| glib::bool_error!("Time before the Unix epoch"))?;
+    Ok(gst::ClockTime::from_seconds(seconds)
+        + gst::ClockTime::from_useconds(date_time.microsecond() as u64))
+}
+
+fn date_time(time: gst::ClockTime) -> Result<glib::DateTime, glib::BoolError> {
+    skip_assert_initialized!();
+    glib::DateTime::from_unix_utc(time.seconds() as i64)?.add(glib::TimeSpan::from_microseconds(
+        (time.useconds() % 1_000_000) as i64,
+    ))
+}
+
 pub trait RTSPMediaImpl: ObjectImpl + ObjectSubclass<Type: IsA<RTSPMedia>> + Send + Sync {
     fn handle_message(&self, message: &gst::MessageRef) -> bool {
         self.parent_handle_message(message)
@@ -654,3 +866,81 @@ unsafe extern "C" fn media_handle_sdp<T: RTSPMediaImpl>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seek_request() {
+        gst::init().unwrap();
+
+        let request =
+            gst_rtsp::RTSPMessage::request_builder(gst_rtsp::RTSPMethod::PLAY, "rtsp://host/")
+                .header(gst_rtsp::RTSPHeaderField::Range, "npt=10-20")
+                .header(gst_rtsp::RTSPHeaderField::Scale, "-2")
+                .build();
+        let seek = SeekRequest::from_request(&request, None).unwrap();
+        assert_eq!(seek.start(), Some(gst::ClockTime::from_seconds(10)));
+        assert_eq!(seek.stop(), Some(gst::ClockTime::from_seconds(20)));
+        assert_eq!(seek.rate(), -2.0);
+
+        let event = seek.seek_event(gst::SeekFlags::FLUSH);
+        let gst::EventView::Seek(event) = event.view() else {
+            unreachable!();
+        };
+        let (rate, _, _, start, _, stop) = event.get();
+        assert_eq!(rate, -2.0);
+        assert_eq!(
+            start,
+            gst::GenericFormattedValue::from(gst::ClockTime::from_seconds(20))
+        );
+        assert_eq!(
+            stop,
+            gst::GenericFormattedValue::from(gst::ClockTime::from_seconds(10))
+        );
+
+        let mut response =
+            gst_rtsp::RTSPMessage::new_response(gst_rtsp::RTSPStatusCode::Ok, Some(&request));
+        seek.apply_to_response(&mut response, Some(gst::ClockTime::from_seconds(12)))
+            .unwrap();
+        assert_eq!(
+            response
+                .header(gst_rtsp::RTSPHeaderField::Range, 0)
+                .unwrap()
+                .parse::<gst_rtsp::RTSPTimeRange>()
+                .unwrap()
+                .times(),
+            Some((
+                Some(gst::ClockTime::from_seconds(12)),
+                Some(gst::ClockTime::from_seconds(20))
+            ))
+        );
+        assert_eq!(response.scale(), Some(-2.0));
+    }
+
+    #[test]
+    fn test_seek_request_clock() {
+        gst::init().unwrap();
+
+        let base = glib::DateTime::from_utc(2024, 1, 2, 1, 0, 0.0).unwrap();
+        let request =
+            gst_rtsp::RTSPMessage::request_builder(gst_rtsp::RTSPMethod::PLAY, "rtsp://host/")
+                .header(gst_rtsp::RTSPHeaderField::Range, "clock=20240102T010005Z-")
+                .header(gst_rtsp::RTSPHeaderField::Speed, "2")
+                .build();
+        assert!(SeekRequest::from_request(&request, None).is_err());
+
+        let seek = SeekRequest::from_request(&request, Some(&base)).unwrap();
+        assert_eq!(seek.unit(), gst_rtsp::RTSPRangeUnit::Clock);
+        assert_eq!(seek.start(), Some(gst::ClockTime::from_seconds(5)));
+        assert_eq!(seek.stop(), None);
+        assert_eq!(seek.rate(), 2.0);
+
+        let range = seek
+            .played_range(Some(gst::ClockTime::from_seconds(7)))
+            .unwrap();
+        let start = glib::DateTime::from_utc(2024, 1, 2, 1, 0, 7.0).unwrap();
+        assert_eq!(range, gst_rtsp::RTSPTimeRange::clock(&start, None).unwrap());
+    }
+}
@@ -0,0 +1,13 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::{prelude::*, subclass::prelude::*};
+
+use super::prelude::*;
+use crate::RTSPMediaFactoryURI;
+
+pub trait RTSPMediaFactoryURIImpl:
+    RTSPMediaFactoryImpl + ObjectSubclass<Type: IsA<RTSPMediaFactoryURI>> + Send + Sync
+{
+}
+
+unsafe impl<T: RTSPMediaFactoryURIImpl> IsSubclassable<T> for RTSPMediaFactoryURI {}
@@ -3,7 +3,7 @@
 use glib::{prelude::*, subclass::prelude::*, translate::*};
 
 use super::prelude::*;
-use crate::{ffi, RTSPOnvifMediaFactory};
+use crate::{ffi, RTSPContext, RTSPOnvifMedia, RTSPOnvifMediaFactory};
 
 pub trait RTSPOnvifMediaFactoryImpl:
     RTSPMediaFactoryImpl + ObjectSubclass<Type: IsA<RTSPOnvifMediaFactory>> + Send + Sync
@@ -11,6 +11,17 @@ pub trait RTSPOnvifMediaFactoryImpl:
     fn has_backchannel_support(&self) -> bool {
         self.parent_has_backchannel_support()
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates the backchannel stream of `media` for the client request in
+    /// `ctx`.
+    ///
+    /// The default implementation adds the `onvif-backchannel` bin created
+    /// from the backchannel launch line to the media and collects its
+    /// `depay_backchannel` depayloader as a stream.
+    fn create_backchannel_stream(&self, media: &RTSPOnvifMedia, ctx: &RTSPContext) -> bool {
+        self.parent_create_backchannel_stream(media, ctx)
+    }
 }
 
 pub trait RTSPOnvifMediaFactoryImplExt: RTSPOnvifMediaFactoryImpl {
@@ -31,6 +42,27 @@ pub trait RTSPOnvifMediaFactoryImplExt: RTSPOnvifMediaFactoryImpl {
                 .unwrap_or(false)
         }
     }
+
+    fn parent_create_backchannel_stream(&self, media: &RTSPOnvifMedia, ctx: &RTSPContext) -> bool {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class =
+                data.as_ref().parent_class() as *mut ffi::GstRTSPOnvifMediaFactoryClass;
+            (*parent_class)
+                .create_backchannel_stream
+                .map(|f| {
+                    from_glib(f(
+                        self.obj()
+                            .unsafe_cast_ref::<RTSPOnvifMediaFactory>()
+                            .to_glib_none()
+                            .0,
+                        media.to_glib_none().0,
+                        ctx.to_glib_none().0,
+                    ))
+                })
+                .unwrap_or(false)
+        }
+    }
 }
 
 impl<T: RTSPOnvifMediaFactoryImpl> RTSPOnvifMediaFactoryImplExt for T {}
@@ -40,6 +72,7 @@ unsafe impl<T: RTSPOnvifMediaFactoryImpl> IsSubclassable<T> for RTSPOnvifMediaFa
         Self::parent_class_init::<T>(klass);
         let klass = klass.as_mut();
         klass.has_backchannel_support = Some(factory_has_backchannel_support::<T>);
+        klass.create_backchannel_stream = Some(factory_create_backchannel_stream::<T>);
     }
 }
 
@@ -51,3 +84,15 @@ unsafe extern "C" fn factory_has_backchannel_support<T: RTSPOnvifMediaFactoryImp
 
     imp.has_backchannel_support().into_glib()
 }
+
+unsafe extern "C" fn factory_create_backchannel_stream<T: RTSPOnvifMediaFactoryImpl>(
+    ptr: *mut ffi::GstRTSPOnvifMediaFactory,
+    media: *mut ffi::GstRTSPOnvifMedia,
+    ctx: *mut ffi::GstRTSPContext,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.create_backchannel_stream(&from_glib_borrow(media), &from_glib_borrow(ctx))
+        .into_glib()
+}
@@ -6,6 +6,7 @@
 use crate::ffi;
 use glib::{prelude::*, translate::*};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
 #[non_exhaustive]
 #[doc(alias = "GstRTSPAuthMethod")]
@@ -206,6 +207,7 @@ impl From<RTSPFamily> for glib::Value {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
 #[non_exhaustive]
 #[doc(alias = "GstRTSPHeaderField")]
@@ -872,6 +874,7 @@ impl From<RTSPRangeUnit> for glib::Value {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
 #[non_exhaustive]
 #[doc(alias = "GstRTSPResult")]
@@ -1150,6 +1153,7 @@ impl From<RTSPState> for glib::Value {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
 #[non_exhaustive]
 #[doc(alias = "GstRTSPStatusCode")]
@@ -1551,3 +1555,107 @@ impl From<RTSPTimeType> for glib::Value {
         ToValue::to_value(&v)
     }
 }
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GstRTSPVersion")]
+pub enum RTSPVersion {
+    #[doc(alias = "GST_RTSP_VERSION_INVALID")]
+    Invalid,
+    #[doc(alias = "GST_RTSP_VERSION_1_0")]
+    _10,
+    #[doc(alias = "GST_RTSP_VERSION_1_1")]
+    _11,
+    #[doc(alias = "GST_RTSP_VERSION_2_0")]
+    _20,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for RTSPVersion {
+    type GlibType = ffi::GstRTSPVersion;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GstRTSPVersion {
+        match self {
+            Self::Invalid => ffi::GST_RTSP_VERSION_INVALID,
+            Self::_10 => ffi::GST_RTSP_VERSION_1_0,
+            Self::_11 => ffi::GST_RTSP_VERSION_1_1,
+            Self::_20 => ffi::GST_RTSP_VERSION_2_0,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstRTSPVersion> for RTSPVersion {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GstRTSPVersion) -> Self {
+        skip_assert_initialized!();
+
+        match value {
+            ffi::GST_RTSP_VERSION_INVALID => Self::Invalid,
+            ffi::GST_RTSP_VERSION_1_0 => Self::_10,
+            ffi::GST_RTSP_VERSION_1_1 => Self::_11,
+            ffi::GST_RTSP_VERSION_2_0 => Self::_20,
+            value => Self::__Unknown(value),
+        }
+    }
+}
+
+impl StaticType for RTSPVersion {
+    #[inline]
+    #[doc(alias = "gst_rtsp_version_get_type")]
+    fn static_type() -> glib::Type {
+        unsafe { from_glib(ffi::gst_rtsp_version_get_type()) }
+    }
+}
+
+impl glib::HasParamSpec for RTSPVersion {
+    type ParamSpec = glib::ParamSpecEnum;
+    type SetValue = Self;
+    type BuilderFn = fn(&str, Self) -> glib::ParamSpecEnumBuilder<Self>;
+
+    fn param_spec_builder() -> Self::BuilderFn {
+        Self::ParamSpec::builder_with_default
+    }
+}
+
+impl glib::value::ValueType for RTSPVersion {
+    type Type = Self;
+}
+
+unsafe impl<'a> glib::value::FromValue<'a> for RTSPVersion {
+    type Checker = glib::value::GenericValueTypeChecker<Self>;
+
+    #[inline]
+    unsafe fn from_value(value: &'a glib::Value) -> Self {
+        skip_assert_initialized!();
+        from_glib(glib::gobject_ffi::g_value_get_enum(value.to_glib_none().0))
+    }
+}
+
+impl ToValue for RTSPVersion {
+    #[inline]
+    fn to_value(&self) -> glib::Value {
+        let mut value = glib::Value::for_value_type::<Self>();
+        unsafe {
+            glib::gobject_ffi::g_value_set_enum(value.to_glib_none_mut().0, self.into_glib());
+        }
+        value
+    }
+
+    #[inline]
+    fn value_type(&self) -> glib::Type {
+        Self::static_type()
+    }
+}
+
+impl From<RTSPVersion> for glib::Value {
+    #[inline]
+    fn from(v: RTSPVersion) -> Self {
+        skip_assert_initialized!();
+        ToValue::to_value(&v)
+    }
+}
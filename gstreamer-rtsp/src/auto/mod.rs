@@ -22,6 +22,7 @@ pub use self::enums::RTSPResult;
 pub use self::enums::RTSPState;
 pub use self::enums::RTSPStatusCode;
 pub use self::enums::RTSPTimeType;
+pub use self::enums::RTSPVersion;
 
 mod flags;
 pub use self::flags::RTSPEvent;
@@ -96,5 +96,33 @@ mod tests {
         check_roundtrip!(crate::RTSPMethod, crate::RTSPMethod::all());
         check_roundtrip!(crate::RTSPProfile, crate::RTSPProfile::all());
         check_roundtrip!(crate::RTSPTransMode, crate::RTSPTransMode::all());
+        check_roundtrip!(
+            crate::RTSPMethod,
+            crate::RTSPMethod::DESCRIBE | crate::RTSPMethod::SETUP
+        );
+    }
+
+    #[test]
+    fn test_enums() {
+        gst::init().unwrap();
+
+        check_serialize!(crate::RTSPStatusCode::NotFound, "\"NotFound\"");
+        check_serialize!(crate::RTSPAuthMethod::Digest, "\"Digest\"");
+        check_serialize!(
+            crate::RTSPHeaderField::WwwAuthenticate,
+            "\"WwwAuthenticate\""
+        );
+        check_serialize!(crate::RTSPResult::Etimeout, "\"Etimeout\"");
+
+        check_deserialize!(
+            crate::RTSPStatusCode,
+            crate::RTSPStatusCode::Unauthorized,
+            "\"Unauthorized\""
+        );
+
+        check_roundtrip!(crate::RTSPStatusCode, crate::RTSPStatusCode::Ok);
+        check_roundtrip!(crate::RTSPAuthMethod, crate::RTSPAuthMethod::Basic);
+        check_roundtrip!(crate::RTSPHeaderField, crate::RTSPHeaderField::Transport);
+        check_roundtrip!(crate::RTSPResult, crate::RTSPResult::Einval);
     }
 }
@@ -0,0 +1,83 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! Helpers for URL driven configuration.
+
+// rustdoc-stripper-ignore-next
+/// Returns the percent-decoded `key=value` pairs of the query of `uri` in
+/// order of occurrence.
+///
+/// Keys without value, e.g. `multicast` in `rtsp://host/stream?multicast`,
+/// get an empty value.
+pub fn options_from_uri(uri: &str) -> Vec<(String, String)> {
+    skip_assert_initialized!();
+    let Some((_, query)) = uri.split_once('?') else {
+        return Vec::new();
+    };
+    let query = query.split_once('#').map_or(query, |(query, _)| query);
+
+    query
+        .split('&')
+        .filter(|option| !option.is_empty())
+        .map(|option| {
+            let (key, value) = option.split_once('=').unwrap_or((option, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_from_uri() {
+        let options = options_from_uri(
+            "rtsp://example.com/stream?transport=tcp&multicast&dest=224.0.0.1%3A5000#frag",
+        );
+        assert_eq!(
+            options,
+            [
+                ("transport".to_owned(), "tcp".to_owned()),
+                ("multicast".to_owned(), String::new()),
+                ("dest".to_owned(), "224.0.0.1:5000".to_owned()),
+            ]
+        );
+
+        assert!(options_from_uri("rtsp://example.com/stream").is_empty());
+        assert_eq!(
+            options_from_uri("rtsp://example.com/?a=100%&b=%zz"),
+            [
+                ("a".to_owned(), "100%".to_owned()),
+                ("b".to_owned(), "%zz".to_owned()),
+            ]
+        );
+    }
+}
@@ -5,6 +5,7 @@
 #![allow(clippy::manual_c_str_literals)]
 #![doc = include_str!("../README.md")]
 
+pub use gio;
 pub use glib;
 pub use gst;
 pub use gst_sdp;
@@ -29,8 +30,57 @@ pub use crate::auto::*;
 #[cfg(feature = "serde")]
 mod flag_serde;
 
+pub mod helpers;
+pub mod negotiation;
 pub mod rtsp_auth_credential;
+mod rtsp_auth_param;
+mod rtsp_channel_allocator;
+pub use crate::rtsp_channel_allocator::ChannelAllocator;
+mod rtsp_client;
+pub use crate::rtsp_client::{
+    RTSPClientSession, RTSPServerOptions, RedirectCredentials, RedirectPolicy,
+};
+mod rtsp_connection;
+pub use crate::rtsp_connection::{RTSPConnection, RTSPConnectionBuilder};
+mod rtsp_digest_auth;
+pub use crate::rtsp_digest_auth::{generate_nonce, DigestAlgorithm, DigestAuth, NonceEncoding};
+mod rtsp_error;
+pub use crate::rtsp_error::RTSPError;
+mod rtsp_header_field;
+mod rtsp_key_mgmt;
+pub use crate::rtsp_key_mgmt::RTSPKeyMgmt;
 pub mod rtsp_message;
+pub use crate::rtsp_message::{
+    RTSPBodyWriter, RTSPMessageViolation, RTSPRequestBuilder, RTSPResponseBuilder,
+};
+mod rtsp_method;
+mod rtsp_onvif;
+pub use crate::rtsp_onvif::{OnvifFrames, OnvifReplayOptions, ONVIF_REPLAY};
+mod rtsp_rtp_info;
+pub use crate::rtsp_rtp_info::RTSPRtpInfo;
+mod rtsp_time_range;
+pub use crate::rtsp_time_range::{RTSPTime, RTSPTimeRange};
+mod rtsp_transport;
+pub use crate::rtsp_transport::{
+    RTSPRange, RTSPTransport, RTSPTransportBuilder, TransportBuildError, TransportMode,
+    TransportParseReport,
+};
+mod rtsp_transport_offer;
+pub use crate::rtsp_transport_offer::RTSPTransportOffer;
+#[cfg(feature = "serde")]
+mod rtsp_transport_serde;
+mod rtsp_udp_ports;
+pub use crate::rtsp_udp_ports::UdpPortPair;
+mod rtsp_url;
+pub use crate::rtsp_url::{RTSPUrlBuilder, ScopedIpAddr};
+mod rtsp_version;
+
+pub mod builders {
+    pub use crate::{
+        RTSPConnectionBuilder, RTSPRequestBuilder, RTSPResponseBuilder, RTSPTransportBuilder,
+        RTSPUrlBuilder,
+    };
+}
 
 // Re-export all the traits in a prelude module, so that applications
 // can always "use gst_rtsp::prelude::*" without getting conflicts
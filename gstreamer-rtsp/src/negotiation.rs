@@ -0,0 +1,312 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! Negotiation of the transport of a stream between the transport the client
+//! requested in its SETUP request and the transport the server replied with.
+
+use std::{error, fmt};
+
+use crate::{
+    RTSPHeaderField, RTSPLowerTrans, RTSPMessage, RTSPProfile, RTSPRange, RTSPTransMode,
+    RTSPTransport, RTSPTransportOffer,
+};
+
+// rustdoc-stripper-ignore-next
+/// Reasons why the transport of a SETUP response can't be used with the
+/// requested transport.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NegotiationError {
+    TransMismatch {
+        requested: RTSPTransMode,
+        replied: RTSPTransMode,
+    },
+    ProfileMismatch {
+        requested: RTSPProfile,
+        replied: RTSPProfile,
+    },
+    LowerTransportMismatch {
+        requested: RTSPLowerTrans,
+        replied: RTSPLowerTrans,
+    },
+    // rustdoc-stripper-ignore-next
+    /// The server changed the client ports of a unicast UDP transport.
+    ClientPortMismatch {
+        requested: Option<RTSPRange>,
+        replied: Option<RTSPRange>,
+    },
+    // rustdoc-stripper-ignore-next
+    /// A unicast UDP reply without server ports.
+    MissingServerPort,
+    // rustdoc-stripper-ignore-next
+    /// A TCP reply without interleaved channels.
+    MissingInterleaved,
+    // rustdoc-stripper-ignore-next
+    /// A multicast reply without destination address or ports.
+    MissingMulticastGroup,
+    // rustdoc-stripper-ignore-next
+    /// The response has no `Transport` header.
+    MissingTransport,
+    // rustdoc-stripper-ignore-next
+    /// None of the offered alternatives matches the replied transport.
+    NoMatchingAlternative,
+}
+
+impl fmt::Display for NegotiationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NegotiationError::TransMismatch { requested, replied } => write!(
+                f,
+                "Transport mismatch: requested {requested:?}, replied {replied:?}"
+            ),
+            NegotiationError::ProfileMismatch { requested, replied } => {
+                write!(
+                    f,
+                    "Profile mismatch: requested {requested:?}, replied {replied:?}"
+                )
+            }
+            NegotiationError::LowerTransportMismatch { requested, replied } => write!(
+                f,
+                "Lower transport mismatch: requested {requested:?}, replied {replied:?}"
+            ),
+            NegotiationError::ClientPortMismatch { requested, replied } => write!(
+                f,
+                "Client port mismatch: requested {requested:?}, replied {replied:?}"
+            ),
+            NegotiationError::MissingServerPort => f.write_str("Reply has no server ports"),
+            NegotiationError::MissingInterleaved => {
+                f.write_str("Reply has no interleaved channels")
+            }
+            NegotiationError::MissingMulticastGroup => {
+                f.write_str("Reply has no multicast destination and ports")
+            }
+            NegotiationError::MissingTransport => f.write_str("Reply has no Transport header"),
+            NegotiationError::NoMatchingAlternative => {
+                f.write_str("Reply matches none of the offered transports")
+            }
+        }
+    }
+}
+
+impl error::Error for NegotiationError {}
+
+// rustdoc-stripper-ignore-next
+/// Merges the transport the server `replied` with into the `requested` one.
+///
+/// The returned transport contains the client side configuration of
+/// `requested` together with the server ports, synchronization source,
+/// interleaved channels and multicast group selected by the server.
+pub fn negotiate(
+    requested: &RTSPTransport,
+    replied: &RTSPTransport,
+) -> Result<RTSPTransport, NegotiationError> {
+    skip_assert_initialized!();
+
+    if requested.trans() != replied.trans() {
+        return Err(NegotiationError::TransMismatch {
+            requested: requested.trans(),
+            replied: replied.trans(),
+        });
+    }
+    if requested.profile() != replied.profile() {
+        return Err(NegotiationError::ProfileMismatch {
+            requested: requested.profile(),
+            replied: replied.profile(),
+        });
+    }
+    if requested.lower_transport() != replied.lower_transport() {
+        return Err(NegotiationError::LowerTransportMismatch {
+            requested: requested.lower_transport(),
+            replied: replied.lower_transport(),
+        });
+    }
+
+    let mut transport = requested.clone();
+    let lower_transport = replied.lower_transport();
+
+    if lower_transport.contains(RTSPLowerTrans::TCP) {
+        // The server is free to pick other channels than the requested ones
+        let interleaved = replied
+            .interleaved()
+            .ok_or(NegotiationError::MissingInterleaved)?;
+        transport.set_interleaved(Some(interleaved));
+    } else if lower_transport.contains(RTSPLowerTrans::UDP_MCAST) {
+        let destination = replied.destination().or(requested.destination());
+        let port = replied.port().or(requested.port());
+        let (Some(destination), Some(port)) = (destination, port) else {
+            return Err(NegotiationError::MissingMulticastGroup);
+        };
+        transport.set_destination(Some(destination));
+        transport.set_port(Some(port));
+        if replied.ttl() != 0 {
+            transport.set_ttl(replied.ttl());
+        }
+    } else {
+        if replied.client_port().is_some() && replied.client_port() != requested.client_port() {
+            return Err(NegotiationError::ClientPortMismatch {
+                requested: requested.client_port(),
+                replied: replied.client_port(),
+            });
+        }
+        let server_port = replied
+            .server_port()
+            .ok_or(NegotiationError::MissingServerPort)?;
+        transport.set_server_port(Some(server_port));
+        if replied.destination().is_some() {
+            transport.set_destination(replied.destination());
+        }
+    }
+
+    if replied.source().is_some() {
+        transport.set_source(replied.source());
+    }
+    if replied.ssrc() != 0 {
+        transport.set_ssrc(replied.ssrc());
+    }
+
+    Ok(transport)
+}
+
+// rustdoc-stripper-ignore-next
+/// Negotiates the transport of a SETUP `response` against all alternatives of
+/// `offer`.
+///
+/// Returns the index of the accepted alternative together with the merged
+/// transport.
+pub fn negotiate_response(
+    offer: &RTSPTransportOffer,
+    response: &RTSPMessage,
+) -> Result<(usize, RTSPTransport), NegotiationError> {
+    skip_assert_initialized!();
+
+    if response.header(RTSPHeaderField::Transport, 0).is_none() {
+        return Err(NegotiationError::MissingTransport);
+    }
+
+    let (idx, replied) = offer
+        .match_response(response)
+        .ok_or(NegotiationError::NoMatchingAlternative)?;
+
+    negotiate(&offer.alternatives()[idx], &replied).map(|transport| (idx, transport))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RTSPStatusCode;
+
+    fn transport(s: &str) -> RTSPTransport {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_negotiate_udp() {
+        gst::init().unwrap();
+
+        let requested = transport("RTP/AVP;unicast;client_port=5000-5001");
+        let merged = negotiate(
+            &requested,
+            &transport(
+                "RTP/AVP;unicast;client_port=5000-5001;server_port=6000-6001;source=10.0.0.1;ssrc=0000002A",
+            ),
+        )
+        .unwrap();
+        assert_eq!(merged.client_port(), Some(RTSPRange::new(5000, 5001)));
+        assert_eq!(merged.server_port(), Some(RTSPRange::new(6000, 6001)));
+        assert_eq!(merged.source(), Some("10.0.0.1"));
+        assert_eq!(merged.ssrc(), 42);
+
+        assert_eq!(
+            negotiate(
+                &requested,
+                &transport("RTP/AVP;unicast;client_port=5000-5001")
+            )
+            .unwrap_err(),
+            NegotiationError::MissingServerPort
+        );
+        assert_eq!(
+            negotiate(
+                &requested,
+                &transport("RTP/AVP;unicast;client_port=7000-7001;server_port=6000-6001")
+            )
+            .unwrap_err(),
+            NegotiationError::ClientPortMismatch {
+                requested: Some(RTSPRange::new(5000, 5001)),
+                replied: Some(RTSPRange::new(7000, 7001)),
+            }
+        );
+        assert_eq!(
+            negotiate(&requested, &transport("RTP/AVP/TCP;interleaved=0-1")).unwrap_err(),
+            NegotiationError::LowerTransportMismatch {
+                requested: RTSPLowerTrans::UDP,
+                replied: RTSPLowerTrans::TCP,
+            }
+        );
+    }
+
+    #[test]
+    fn test_negotiate_tcp_multicast() {
+        gst::init().unwrap();
+
+        let merged = negotiate(
+            &transport("RTP/AVP/TCP;unicast;interleaved=0-1"),
+            &transport("RTP/AVP/TCP;unicast;interleaved=2-3"),
+        )
+        .unwrap();
+        assert_eq!(merged.interleaved(), Some(RTSPRange::new(2, 3)));
+
+        let merged = negotiate(
+            &transport("RTP/AVP;multicast"),
+            &transport("RTP/AVP;multicast;destination=224.2.0.1;port=5000-5001;ttl=16"),
+        )
+        .unwrap();
+        assert_eq!(merged.destination(), Some("224.2.0.1"));
+        assert_eq!(merged.port(), Some(RTSPRange::new(5000, 5001)));
+        assert_eq!(merged.ttl(), 16);
+
+        assert_eq!(
+            negotiate(
+                &transport("RTP/AVP;multicast"),
+                &transport("RTP/AVP;multicast;port=5000-5001")
+            )
+            .unwrap_err(),
+            NegotiationError::MissingMulticastGroup
+        );
+    }
+
+    #[test]
+    fn test_negotiate_response() {
+        gst::init().unwrap();
+
+        let offer: RTSPTransportOffer = [
+            transport("RTP/AVP;unicast;client_port=5000-5001"),
+            transport("RTP/AVP/TCP;unicast;interleaved=0-1"),
+        ]
+        .into_iter()
+        .collect();
+
+        let response = RTSPMessage::response_builder(RTSPStatusCode::Ok)
+            .header(
+                RTSPHeaderField::Transport,
+                "RTP/AVP/TCP;unicast;interleaved=0-1",
+            )
+            .build();
+        let (idx, merged) = negotiate_response(&offer, &response).unwrap();
+        assert_eq!(idx, 1);
+        assert_eq!(merged.interleaved(), Some(RTSPRange::new(0, 1)));
+
+        let response = RTSPMessage::response_builder(RTSPStatusCode::Ok).build();
+        assert_eq!(
+            negotiate_response(&offer, &response).unwrap_err(),
+            NegotiationError::MissingTransport
+        );
+
+        let response = RTSPMessage::response_builder(RTSPStatusCode::Ok)
+            .header(RTSPHeaderField::Transport, "RTP/SAVP;unicast")
+            .build();
+        assert_eq!(
+            negotiate_response(&offer, &response).unwrap_err(),
+            NegotiationError::NoMatchingAlternative
+        );
+    }
+}
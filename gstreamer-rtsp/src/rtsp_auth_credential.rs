@@ -1,19 +1,55 @@
-use crate::{ffi, RTSPAuthCredential, RTSPAuthMethod, RTSPAuthParam};
+use std::{ffi::CStr, marker::PhantomData};
+
+use crate::{
+    ffi, rtsp_message::RTSPMessage, RTSPAuthCredential, RTSPAuthMethod, RTSPAuthParam,
+    RTSPHeaderField,
+};
 use glib::translate::*;
 
 impl RTSPAuthCredential {
+    // rustdoc-stripper-ignore-next
+    /// Parses the credentials or challenges of all `field` headers of
+    /// `msg`, e.g. [`RTSPHeaderField::WwwAuthenticate`] in responses or
+    /// [`RTSPHeaderField::Authorization`] in requests.
+    #[doc(alias = "gst_rtsp_message_parse_auth_credentials")]
+    pub fn parse(msg: &RTSPMessage, field: RTSPHeaderField) -> Vec<RTSPAuthCredential> {
+        skip_assert_initialized!();
+        unsafe {
+            let credentials = ffi::gst_rtsp_message_parse_auth_credentials(
+                mut_override(msg.to_glib_none().0),
+                field.into_glib(),
+            );
+            if credentials.is_null() {
+                return Vec::new();
+            }
+
+            let mut res = Vec::new();
+            let mut ptr = credentials;
+            while !(*ptr).is_null() {
+                res.push(from_glib_none(*ptr));
+                ptr = ptr.add(1);
+            }
+            ffi::gst_rtsp_auth_credentials_free(credentials);
+
+            res
+        }
+    }
+
     pub fn scheme(&self) -> RTSPAuthMethod {
         let ptr: *mut ffi::GstRTSPAuthCredential = self.to_glib_none().0;
         unsafe { from_glib((*ptr).scheme) }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// The unparsed credentials of schemes without parameters, e.g. the
+    /// base64 encoded user and password of Basic authorization.
     pub fn authorization(&self) -> Option<&str> {
         let ptr: *mut ffi::GstRTSPAuthCredential = self.to_glib_none().0;
         unsafe {
             if (*ptr).authorization.is_null() {
                 None
             } else {
-                std::ffi::CStr::from_ptr((*ptr).authorization).to_str().ok()
+                CStr::from_ptr((*ptr).authorization).to_str().ok()
             }
         }
     }
@@ -22,4 +58,169 @@ impl RTSPAuthCredential {
         let ptr: *mut ffi::GstRTSPAuthCredential = self.to_glib_none().0;
         unsafe { FromGlibPtrContainer::from_glib_none((*ptr).params) }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Iterates over the names and values of all parameters without copying
+    /// them.
+    pub fn iter_params(&self) -> Params<'_> {
+        let ptr: *mut ffi::GstRTSPAuthCredential = self.to_glib_none().0;
+        Params {
+            ptr: unsafe { (*ptr).params },
+            phantom: PhantomData,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value of the parameter `name`, compared case-insensitively.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.iter_params()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .and_then(|(_, value)| value)
+    }
+
+    pub fn realm(&self) -> Option<&str> {
+        self.param("realm")
+    }
+
+    pub fn nonce(&self) -> Option<&str> {
+        self.param("nonce")
+    }
+
+    pub fn opaque(&self) -> Option<&str> {
+        self.param("opaque")
+    }
+
+    pub fn algorithm(&self) -> Option<&str> {
+        self.param("algorithm")
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the quality of protection values offered by a Digest
+    /// challenge, e.g. `auth` or `auth-int`.
+    pub fn qop(&self) -> Vec<&str> {
+        self.param("qop")
+            .map(|qop| {
+                qop.split(',')
+                    .map(str::trim)
+                    .filter(|qop| !qop.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns `true` if a Digest challenge signals that the previous nonce
+    /// was stale and the request can be retried with the same credentials.
+    pub fn is_stale(&self) -> bool {
+        self.param("stale")
+            .is_some_and(|stale| stale.eq_ignore_ascii_case("true"))
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Iterator over the parameters of an [`RTSPAuthCredential`], as returned by
+/// [`RTSPAuthCredential::iter_params()`].
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Params<'a> {
+    ptr: *mut *mut ffi::GstRTSPAuthParam,
+    phantom: PhantomData<&'a RTSPAuthCredential>,
+}
+
+impl<'a> Iterator for Params<'a> {
+    type Item = (&'a str, Option<&'a str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            loop {
+                if self.ptr.is_null() || (*self.ptr).is_null() {
+                    return None;
+                }
+
+                let param = *self.ptr;
+                self.ptr = self.ptr.add(1);
+
+                // Parameters without (valid) name can't be looked up anyway
+                if (*param).name.is_null() {
+                    continue;
+                }
+                let Ok(name) = CStr::from_ptr((*param).name).to_str() else {
+                    continue;
+                };
+                let value = if (*param).value.is_null() {
+                    None
+                } else {
+                    CStr::from_ptr((*param).value).to_str().ok()
+                };
+
+                return Some((name, value));
+            }
+        }
+    }
+}
+
+impl std::iter::FusedIterator for Params<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{rtsp_message::RTSPMessage, RTSPAuthMethod, RTSPHeaderField, RTSPStatusCode};
+
+    #[test]
+    fn test_parse_www_authenticate() {
+        gst::init().unwrap();
+
+        let msg = RTSPMessage::response_builder(RTSPStatusCode::Unauthorized)
+            .header(
+                RTSPHeaderField::WwwAuthenticate,
+                "Digest realm=\"cam\", nonce=\"abc\", qop=\"auth,auth-int\", stale=TRUE, algorithm=MD5",
+            )
+            .header(RTSPHeaderField::WwwAuthenticate, "Basic realm=\"cam\"")
+            .build();
+
+        let challenges = msg.parse_www_authenticate();
+        assert_eq!(challenges.len(), 2);
+
+        let digest = &challenges[0];
+        assert_eq!(digest.scheme(), RTSPAuthMethod::Digest);
+        assert_eq!(digest.realm(), Some("cam"));
+        assert_eq!(digest.nonce(), Some("abc"));
+        assert_eq!(digest.qop(), ["auth", "auth-int"]);
+        assert!(digest.is_stale());
+        assert_eq!(digest.param("ALGORITHM"), Some("MD5"));
+        assert_eq!(digest.opaque(), None);
+
+        let basic = &challenges[1];
+        assert_eq!(basic.scheme(), RTSPAuthMethod::Basic);
+        assert_eq!(basic.realm(), Some("cam"));
+        assert!(!basic.is_stale());
+    }
+
+    #[test]
+    fn test_iter_params() {
+        gst::init().unwrap();
+
+        let msg = RTSPMessage::request_builder(crate::RTSPMethod::DESCRIBE, "rtsp://cam/")
+            .header(RTSPHeaderField::Authorization, "Basic dXNlcjpwYXNz")
+            .header(
+                RTSPHeaderField::Authorization,
+                "Digest username=\"user\", realm=\"cam\", uri=\"rtsp://cam/\"",
+            )
+            .build();
+
+        let credentials = crate::RTSPAuthCredential::parse(&msg, RTSPHeaderField::Authorization);
+        assert_eq!(credentials.len(), 2);
+        assert_eq!(credentials[0].authorization(), Some("dXNlcjpwYXNz"));
+        assert_eq!(credentials[0].iter_params().count(), 0);
+        assert_eq!(
+            credentials[1].iter_params().collect::<Vec<_>>(),
+            [
+                ("username", Some("user")),
+                ("realm", Some("cam")),
+                ("uri", Some("rtsp://cam/")),
+            ]
+        );
+
+        let msg = RTSPMessage::request_builder(crate::RTSPMethod::DESCRIBE, "rtsp://cam/").build();
+        assert!(crate::RTSPAuthCredential::parse(&msg, RTSPHeaderField::Authorization).is_empty());
+    }
 }
@@ -1,25 +1,83 @@
+use std::{ffi::CStr, fmt, mem};
+
+use crate::{ffi, RTSPAuthParam};
 use glib::translate::*;
 
 impl RTSPAuthParam {
+    // rustdoc-stripper-ignore-next
+    /// Creates a parameter as used in `WWW-Authenticate` and `Authorization`
+    /// headers, e.g. `realm="cam"`.
+    pub fn new(name: &str, value: Option<&str>) -> Self {
+        assert_initialized_main_thread!();
+        unsafe {
+            let param = glib::ffi::g_malloc0(mem::size_of::<ffi::GstRTSPAuthParam>())
+                as *mut ffi::GstRTSPAuthParam;
+            (*param).name = name.to_glib_full();
+            (*param).value = value.to_glib_full();
+            from_glib_full(param)
+        }
+    }
+
     pub fn name(&self) -> Option<&str> {
-        let ptr: *mut GstRTSPAuthParam = self.to_glib_none().0;
+        let ptr: *mut ffi::GstRTSPAuthParam = self.to_glib_none().0;
         unsafe {
             if (*ptr).name.is_null() {
                 None
             } else {
-                std::ffi::CStr::from_ptr((*ptr).name).to_str().ok()
+                CStr::from_ptr((*ptr).name).to_str().ok()
             }
         }
     }
 
     pub fn value(&self) -> Option<&str> {
-        let ptr: *mut GstRTSPAuthParam = self.to_glib_none().0;
+        let ptr: *mut ffi::GstRTSPAuthParam = self.to_glib_none().0;
         unsafe {
             if (*ptr).value.is_null() {
                 None
             } else {
-                std::ffi::CStr::from_ptr((*ptr).value).to_str().ok()
+                CStr::from_ptr((*ptr).value).to_str().ok()
+            }
+        }
+    }
+}
+
+impl fmt::Display for RTSPAuthParam {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name().unwrap_or_default())?;
+        if let Some(value) = self.value() {
+            f.write_str("=\"")?;
+            for c in value.chars() {
+                if c == '"' || c == '\\' {
+                    f.write_str("\\")?;
+                }
+                write!(f, "{c}")?;
             }
+            f.write_str("\"")?;
         }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        gst::init().unwrap();
+
+        let param = RTSPAuthParam::new("realm", Some("my \"cam\""));
+        assert_eq!(param.name(), Some("realm"));
+        assert_eq!(param.value(), Some("my \"cam\""));
+        assert_eq!(param.to_string(), "realm=\"my \\\"cam\\\"\"");
+
+        let copy = param.clone();
+        drop(param);
+        assert_eq!(copy.name(), Some("realm"));
+
+        let param = RTSPAuthParam::new("stale", None);
+        assert_eq!(param.value(), None);
+        assert_eq!(param.to_string(), "stale");
     }
 }
@@ -0,0 +1,148 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::{RTSPRange, RTSPTransportBuilder};
+
+// rustdoc-stripper-ignore-next
+/// Hands out interleaved channel pairs (`0-1`, `2-3`, ...) for the streams
+/// set up on a single TCP connection.
+///
+/// Channels are identified by a single byte in the interleaved framing, so
+/// there are at most 128 pairs per connection.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChannelAllocator {
+    used: [u64; 4],
+}
+
+impl ChannelAllocator {
+    pub fn new() -> Self {
+        skip_assert_initialized!();
+        Self::default()
+    }
+
+    fn is_used(&self, channel: u8) -> bool {
+        self.used[usize::from(channel / 64)] & (1 << (channel % 64)) != 0
+    }
+
+    fn set_used(&mut self, channel: u8, used: bool) {
+        let word = &mut self.used[usize::from(channel / 64)];
+        if used {
+            *word |= 1 << (channel % 64);
+        } else {
+            *word &= !(1 << (channel % 64));
+        }
+    }
+
+    // All channels of `range`, or `None` if it doesn't fit into a byte
+    fn channels(range: RTSPRange) -> Option<std::ops::RangeInclusive<u8>> {
+        if !range.is_valid() {
+            return None;
+        }
+        let min = u8::try_from(range.min()).ok()?;
+        let max = u8::try_from(range.min() + range.len() as i32 - 1).ok()?;
+        Some(min..=max)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Allocates the lowest free channel pair.
+    ///
+    /// Returns `None` if all channels are in use.
+    pub fn allocate(&mut self) -> Option<RTSPRange> {
+        let rtp = (0..=u8::MAX)
+            .step_by(2)
+            .find(|&channel| !self.is_used(channel) && !self.is_used(channel + 1))?;
+
+        self.set_used(rtp, true);
+        self.set_used(rtp + 1, true);
+
+        Some(RTSPRange::new(i32::from(rtp), i32::from(rtp) + 1))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Marks the channels of `range` as used, e.g. when the server selected
+    /// other channels than the allocated ones.
+    ///
+    /// Fails if any of the channels is already in use or not a valid channel.
+    pub fn reserve(&mut self, range: RTSPRange) -> Result<(), glib::BoolError> {
+        let channels = Self::channels(range)
+            .ok_or_else(|| glib::bool_error!("Invalid interleaved channels {}", range))?;
+
+        if channels.clone().any(|channel| self.is_used(channel)) {
+            return Err(glib::bool_error!(
+                "Interleaved channels {} already in use",
+                range
+            ));
+        }
+
+        for channel in channels {
+            self.set_used(channel, true);
+        }
+
+        Ok(())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Releases the channels of `range`, e.g. after the stream was torn down.
+    pub fn release(&mut self, range: RTSPRange) {
+        for channel in Self::channels(range).into_iter().flatten() {
+            self.set_used(channel, false);
+        }
+    }
+
+    pub fn is_allocated(&self, range: RTSPRange) -> bool {
+        Self::channels(range).is_some_and(|mut channels| channels.any(|c| self.is_used(c)))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Allocates a channel pair and sets it as interleaved channels of
+    /// `builder`.
+    ///
+    /// Returns `None` if all channels are in use.
+    pub fn configure(&mut self, builder: RTSPTransportBuilder) -> Option<RTSPTransportBuilder> {
+        let range = self.allocate()?;
+        Some(builder.interleaved(range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocate() {
+        let mut allocator = ChannelAllocator::new();
+
+        assert_eq!(allocator.allocate(), Some(RTSPRange::new(0, 1)));
+        assert_eq!(allocator.allocate(), Some(RTSPRange::new(2, 3)));
+
+        allocator.reserve(RTSPRange::new(4, 5)).unwrap();
+        assert!(allocator.reserve(RTSPRange::new(5, 6)).is_err());
+        assert!(allocator.reserve(RTSPRange::new(255, 256)).is_err());
+        assert_eq!(allocator.allocate(), Some(RTSPRange::new(6, 7)));
+
+        allocator.release(RTSPRange::new(2, 3));
+        assert!(!allocator.is_allocated(RTSPRange::new(2, 3)));
+        assert!(allocator.is_allocated(RTSPRange::new(0, 1)));
+        assert_eq!(allocator.allocate(), Some(RTSPRange::new(2, 3)));
+
+        let mut allocator = ChannelAllocator::new();
+        for _ in 0..128 {
+            assert!(allocator.allocate().is_some());
+        }
+        assert_eq!(allocator.allocate(), None);
+    }
+
+    #[test]
+    fn test_configure() {
+        gst::init().unwrap();
+
+        let mut allocator = ChannelAllocator::new();
+        allocator.allocate().unwrap();
+
+        let transport = allocator
+            .configure(crate::RTSPTransport::builder().lower_transport(crate::RTSPLowerTrans::TCP))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(transport.interleaved(), Some(RTSPRange::new(2, 3)));
+    }
+}
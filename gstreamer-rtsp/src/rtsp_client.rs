@@ -0,0 +1,699 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use crate::{
+    rtsp_connection::request_uri, rtsp_message::RTSPMessage, OnvifReplayOptions, RTSPConnection,
+    RTSPConnectionBuilder, RTSPError, RTSPHeaderField, RTSPMethod, RTSPMsgType, RTSPResult,
+    RTSPStatusCode, RTSPTimeRange, RTSPTransportOffer, RTSPUrl, RTSPVersion,
+};
+
+// Request headers that were only introduced with RTSP 2.0
+const V2_0_HEADERS: [RTSPHeaderField; 3] = [
+    RTSPHeaderField::PipelinedRequests,
+    RTSPHeaderField::SeekStyle,
+    RTSPHeaderField::AcceptRanges,
+];
+
+// rustdoc-stripper-ignore-next
+/// Whether credentials are reused when following a redirect.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RedirectCredentials {
+    Never,
+    // rustdoc-stripper-ignore-next
+    /// Only reuse credentials if the redirect points to the same host.
+    #[default]
+    SameHost,
+    Always,
+}
+
+// rustdoc-stripper-ignore-next
+/// How redirect (3xx) responses to DESCRIBE and SETUP requests are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RedirectPolicy {
+    max_hops: u32,
+    credentials: RedirectCredentials,
+}
+
+impl Default for RedirectPolicy {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl RedirectPolicy {
+    // rustdoc-stripper-ignore-next
+    /// Don't follow redirects but return the redirect response.
+    pub const NONE: Self = Self {
+        max_hops: 0,
+        credentials: RedirectCredentials::Never,
+    };
+
+    pub fn new(max_hops: u32) -> Self {
+        skip_assert_initialized!();
+        Self {
+            max_hops,
+            credentials: RedirectCredentials::default(),
+        }
+    }
+
+    pub fn credentials(self, credentials: RedirectCredentials) -> Self {
+        Self {
+            credentials,
+            ..self
+        }
+    }
+
+    pub fn max_hops(&self) -> u32 {
+        self.max_hops
+    }
+
+    pub fn redirect_credentials(&self) -> RedirectCredentials {
+        self.credentials
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// What the server announced in its response to an OPTIONS request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RTSPServerOptions {
+    methods: RTSPMethod,
+    supported: Vec<String>,
+    unsupported: Vec<String>,
+}
+
+impl RTSPServerOptions {
+    // rustdoc-stripper-ignore-next
+    /// Methods listed in the `Public` header.
+    pub fn methods(&self) -> RTSPMethod {
+        self.methods
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Option tags listed in the `Supported` header.
+    pub fn supported(&self) -> &[String] {
+        &self.supported
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Required option tags the server listed in the `Unsupported` header.
+    pub fn unsupported(&self) -> &[String] {
+        &self.unsupported
+    }
+
+    pub fn supports_method(&self, method: RTSPMethod) -> bool {
+        self.methods.contains(method)
+    }
+
+    pub fn supports(&self, feature: &str) -> bool {
+        self.supported
+            .iter()
+            .any(|supported| supported.eq_ignore_ascii_case(feature))
+    }
+
+    fn from_response(response: &RTSPMessage) -> Self {
+        skip_assert_initialized!();
+        let mut methods = RTSPMethod::empty();
+        let mut idx = 0;
+        while let Some(public) = response.header(RTSPHeaderField::Public, idx) {
+            methods |= RTSPMethod::options_from_text(public);
+            idx += 1;
+        }
+
+        Self {
+            methods,
+            supported: header_tokens(response, RTSPHeaderField::Supported),
+            unsupported: header_tokens(response, RTSPHeaderField::Unsupported),
+        }
+    }
+}
+
+fn header_tokens(message: &RTSPMessage, field: RTSPHeaderField) -> Vec<String> {
+    skip_assert_initialized!();
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+    while let Some(value) = message.header(field, idx) {
+        tokens.extend(
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|token| !token.is_empty())
+                .map(String::from),
+        );
+        idx += 1;
+    }
+
+    tokens
+}
+
+// rustdoc-stripper-ignore-next
+/// A client-side RTSP session on top of a [`RTSPConnection`].
+///
+/// This takes care of `CSeq` numbering, remembering the session identifier
+/// and following redirects according to the configured [`RedirectPolicy`].
+#[derive(Debug)]
+pub struct RTSPClientSession {
+    conn: RTSPConnection,
+    builder: RTSPConnectionBuilder,
+    cseq: u32,
+    session_id: Option<String>,
+    redirect_policy: RedirectPolicy,
+    required: Vec<String>,
+    proxy_required: Vec<String>,
+    server_options: Option<RTSPServerOptions>,
+    version: RTSPVersion,
+    unsupported: Vec<String>,
+}
+
+impl RTSPClientSession {
+    pub fn connect(builder: RTSPConnectionBuilder) -> Result<Self, RTSPError> {
+        skip_assert_initialized!();
+        let conn = builder.clone().connect()?;
+
+        Ok(Self {
+            conn,
+            builder,
+            cseq: 0,
+            session_id: None,
+            redirect_policy: RedirectPolicy::default(),
+            required: Vec::new(),
+            proxy_required: Vec::new(),
+            server_options: None,
+            version: RTSPVersion::_10,
+            unsupported: Vec::new(),
+        })
+    }
+
+    pub fn connection(&self) -> &RTSPConnection {
+        &self.conn
+    }
+
+    pub fn connection_mut(&mut self) -> &mut RTSPConnection {
+        &mut self.conn
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The URL of the session, which changes when a redirect was followed.
+    pub fn url(&self) -> &str {
+        self.builder.url()
+    }
+
+    pub fn request_uri(&self) -> String {
+        request_uri(self.builder.url())
+    }
+
+    pub fn session_id(&self) -> Option<&str> {
+        self.session_id.as_deref()
+    }
+
+    pub fn redirect_policy(&self) -> RedirectPolicy {
+        self.redirect_policy
+    }
+
+    pub fn set_redirect_policy(&mut self, policy: RedirectPolicy) {
+        self.redirect_policy = policy;
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Declares an option tag, e.g. `onvif-replay`, that the server has to
+    /// support. This is checked by [`RTSPClientSession::options()`].
+    pub fn require(&mut self, feature: &str) {
+        if !self.required.iter().any(|f| f == feature) {
+            self.required.push(feature.to_owned());
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Declares an option tag that proxies between client and server have to
+    /// support.
+    pub fn proxy_require(&mut self, feature: &str) {
+        if !self.proxy_required.iter().any(|f| f == feature) {
+            self.proxy_required.push(feature.to_owned());
+        }
+    }
+
+    pub fn required_features(&self) -> &[String] {
+        &self.required
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The protocol version used for requests.
+    ///
+    /// This starts with the version configured through
+    /// [`RTSPClientSession::set_version()`] and is lowered to the version the
+    /// server responds with, or to RTSP 1.0 if the server rejects the version.
+    pub fn version(&self) -> RTSPVersion {
+        self.version
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the preferred protocol version for requests, `RTSP/1.0` by
+    /// default.
+    pub fn set_version(&mut self, version: RTSPVersion) {
+        self.version = version;
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Option tags any response of the server listed in its `Unsupported`
+    /// header so far.
+    pub fn unsupported_features(&self) -> &[String] {
+        &self.unsupported
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The result of the last successful [`RTSPClientSession::options()`] call.
+    pub fn server_options(&self) -> Option<&RTSPServerOptions> {
+        self.server_options.as_ref()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sends an OPTIONS request and checks that the server supports all
+    /// required option tags.
+    ///
+    /// Fails with [`RTSPError::Unsupported`] listing the options the server or
+    /// a proxy rejected.
+    #[doc(alias = "GST_RTSP_OPTIONS")]
+    pub fn options(&mut self) -> Result<RTSPServerOptions, RTSPError> {
+        let request = RTSPMessage::new_request(RTSPMethod::OPTIONS, &self.request_uri());
+        if !self.required.is_empty() {
+            request.add_header(RTSPHeaderField::Require, &self.required.join(", "));
+        }
+        if !self.proxy_required.is_empty() {
+            request.add_header(
+                RTSPHeaderField::ProxyRequire,
+                &self.proxy_required.join(", "),
+            );
+        }
+
+        let response = self.send_request(request)?;
+        let options = RTSPServerOptions::from_response(&response);
+
+        if !options.unsupported.is_empty() {
+            return Err(RTSPError::Unsupported {
+                operation: "negotiate options",
+                options: options.unsupported,
+            });
+        }
+
+        if response.status_code() == Some(RTSPStatusCode::OptionNotSupported) {
+            return Err(RTSPError::Unsupported {
+                operation: "negotiate options",
+                options: self
+                    .required
+                    .iter()
+                    .chain(self.proxy_required.iter())
+                    .cloned()
+                    .collect(),
+            });
+        }
+
+        check_response("negotiate options", response)?;
+        self.server_options = Some(options.clone());
+
+        Ok(options)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sends `request` and waits for the matching response.
+    ///
+    /// Redirects of DESCRIBE and SETUP requests are followed by reconnecting to
+    /// the new location and replaying the request there, up to the hop limit of
+    /// the [`RedirectPolicy`].
+    pub fn send_request(&mut self, mut request: RTSPMessage) -> Result<RTSPMessage, RTSPError> {
+        let mut hops = 0;
+
+        loop {
+            let response = self.transact(&mut request)?;
+
+            let follows_redirects = request
+                .method()
+                .is_some_and(|method| method.intersects(RTSPMethod::DESCRIBE | RTSPMethod::SETUP));
+            let code = response.status_code();
+            let location = response
+                .header(RTSPHeaderField::Location, 0)
+                .map(String::from);
+
+            let (Some(code), Some(location)) = (code, location) else {
+                self.update_session(&response);
+                return Ok(response);
+            };
+
+            if !follows_redirects || !is_redirect(code) || self.redirect_policy.max_hops == 0 {
+                self.update_session(&response);
+                return Ok(response);
+            }
+
+            if hops >= self.redirect_policy.max_hops {
+                return Err(RTSPError::from_status(
+                    "follow redirect",
+                    code,
+                    Some(response),
+                ));
+            }
+            hops += 1;
+
+            self.redirect(&location)?;
+            request.set_uri(&request_uri(&location));
+        }
+    }
+
+    #[doc(alias = "GST_RTSP_DESCRIBE")]
+    pub fn describe(&mut self) -> Result<RTSPMessage, RTSPError> {
+        let request = RTSPMessage::new_request(RTSPMethod::DESCRIBE, &self.request_uri());
+        request.add_header(RTSPHeaderField::Accept, "application/sdp");
+
+        let response = self.send_request(request)?;
+        check_response("describe", response)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Starts playback of the session, optionally seeking to `range` and
+    /// requesting trick-mode playback through `scale` and `speed`.
+    ///
+    /// The `Scale` and `Speed` granted by the server can be read from the
+    /// response.
+    #[doc(alias = "GST_RTSP_PLAY")]
+    pub fn play(
+        &mut self,
+        range: Option<&RTSPTimeRange>,
+        scale: Option<f64>,
+        speed: Option<f64>,
+    ) -> Result<RTSPMessage, RTSPError> {
+        let mut request = RTSPMessage::new_request(RTSPMethod::PLAY, &self.request_uri());
+        if let Some(range) = range {
+            request.add_header(RTSPHeaderField::Range, &range.to_string());
+        }
+        if let Some(scale) = scale {
+            request
+                .set_scale(scale)
+                .map_err(|_| RTSPError::from_result("play", RTSPResult::Einval))?;
+        }
+        if let Some(speed) = speed {
+            request
+                .set_speed(speed)
+                .map_err(|_| RTSPError::from_result("play", RTSPResult::Einval))?;
+        }
+
+        let response = self.send_request(request)?;
+        check_response("play", response)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Starts ONVIF replay of the recording in the clock `range`, which is
+    /// played backwards if its end is before its start.
+    ///
+    /// A negative `scale` also requests reverse playback.
+    #[doc(alias = "GST_RTSP_PLAY")]
+    pub fn replay(
+        &mut self,
+        range: &RTSPTimeRange,
+        scale: Option<f64>,
+        options: &OnvifReplayOptions,
+    ) -> Result<RTSPMessage, RTSPError> {
+        let mut request = RTSPMessage::request_builder(RTSPMethod::PLAY, &self.request_uri())
+            .onvif_replay()
+            .range(range)
+            .onvif_replay_options(options)
+            .build();
+        if let Some(scale) = scale {
+            request
+                .set_scale(scale)
+                .map_err(|_| RTSPError::from_result("replay", RTSPResult::Einval))?;
+        }
+
+        let response = self.send_request(request)?;
+        check_response("replay", response)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Announces the streams described by `sdp` for publishing them to the
+    /// server with [`RTSPClientSession::record()`].
+    #[doc(alias = "GST_RTSP_ANNOUNCE")]
+    pub fn announce(&mut self, sdp: &gst_sdp::SDPMessageRef) -> Result<RTSPMessage, RTSPError> {
+        let text = sdp
+            .as_text()
+            .map_err(|_| RTSPError::from_result("announce", RTSPResult::Einval))?;
+        let request = RTSPMessage::request_builder(RTSPMethod::ANNOUNCE, &self.request_uri())
+            .header(RTSPHeaderField::ContentType, "application/sdp")
+            .body(text.as_bytes())
+            .build();
+
+        let response = self.send_request(request)?;
+        check_response("announce", response)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets up the stream with the `control` attribute, which is either an
+    /// absolute URL or relative to the URL of the session.
+    ///
+    /// For publishing, the alternatives of `offer` have to use the record
+    /// mode, see [`RTSPTransportOffer::with_mode()`]. The transport the
+    /// server selected can be negotiated from the response with
+    /// [`negotiation::negotiate_response()`](crate::negotiation::negotiate_response).
+    #[doc(alias = "GST_RTSP_SETUP")]
+    pub fn setup(
+        &mut self,
+        control: &str,
+        offer: &RTSPTransportOffer,
+    ) -> Result<RTSPMessage, RTSPError> {
+        let request = RTSPMessage::request_builder(
+            RTSPMethod::SETUP,
+            &control_uri(&self.request_uri(), control),
+        )
+        .transports(offer)
+        .build();
+
+        let response = self.send_request(request)?;
+        check_response("setup", response)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Starts publishing the announced streams, optionally for the given
+    /// `range`.
+    #[doc(alias = "GST_RTSP_RECORD")]
+    pub fn record(&mut self, range: Option<&RTSPTimeRange>) -> Result<RTSPMessage, RTSPError> {
+        let request = RTSPMessage::new_request(RTSPMethod::RECORD, &self.request_uri());
+        if let Some(range) = range {
+            request.add_header(RTSPHeaderField::Range, &range.to_string());
+        }
+
+        let response = self.send_request(request)?;
+        check_response("record", response)
+    }
+
+    fn transact(&mut self, request: &mut RTSPMessage) -> Result<RTSPMessage, RTSPError> {
+        loop {
+            prepare_request(request, self.version);
+            let response = self.transact_once(request)?;
+
+            // Retry once with RTSP 1.0 if the server doesn't speak the
+            // preferred version at all
+            if response.status_code() == Some(RTSPStatusCode::RtspVersionNotSupported)
+                && self.version > RTSPVersion::_10
+            {
+                self.version = RTSPVersion::_10;
+                continue;
+            }
+
+            self.update_protocol(&response);
+            return Ok(response);
+        }
+    }
+
+    fn transact_once(&mut self, request: &mut RTSPMessage) -> Result<RTSPMessage, RTSPError> {
+        self.cseq += 1;
+        request.remove_header(RTSPHeaderField::Cseq, None);
+        request.add_header(RTSPHeaderField::Cseq, &self.cseq.to_string());
+        if let Some(ref session_id) = self.session_id {
+            if request.header(RTSPHeaderField::Session, 0).is_none() {
+                request.add_header(RTSPHeaderField::Session, session_id);
+            }
+        }
+
+        let timeout = self.builder.configured_timeout();
+        self.conn.send(request, timeout)?;
+
+        loop {
+            let message = self.conn.receive(timeout)?;
+            if message.type_() != RTSPMsgType::Response {
+                continue;
+            }
+
+            let cseq = message
+                .header(RTSPHeaderField::Cseq, 0)
+                .and_then(|cseq| cseq.trim().parse::<u32>().ok());
+            if cseq.is_some_and(|cseq| cseq != self.cseq) {
+                continue;
+            }
+
+            return Ok(message);
+        }
+    }
+
+    fn update_protocol(&mut self, response: &RTSPMessage) {
+        self.version = negotiated_version(self.version, response);
+
+        for feature in header_tokens(response, RTSPHeaderField::Unsupported) {
+            if !self
+                .unsupported
+                .iter()
+                .any(|f| f.eq_ignore_ascii_case(&feature))
+            {
+                self.unsupported.push(feature);
+            }
+        }
+    }
+
+    fn update_session(&mut self, response: &RTSPMessage) {
+        if let Some(session) = response.header(RTSPHeaderField::Session, 0) {
+            let id = session.split(';').next().unwrap_or(session).trim();
+            self.session_id = Some(id.to_owned());
+        }
+    }
+
+    fn redirect(&mut self, location: &str) -> Result<(), RTSPError> {
+        let keep_auth = match self.redirect_policy.credentials {
+            RedirectCredentials::Never => false,
+            RedirectCredentials::SameHost => same_host(self.builder.url(), location),
+            RedirectCredentials::Always => true,
+        };
+
+        let builder = self.builder.with_url(location, keep_auth);
+        let conn = builder.clone().connect()?;
+        let _ = self.conn.close();
+
+        self.conn = conn;
+        self.builder = builder;
+        self.session_id = None;
+
+        Ok(())
+    }
+}
+
+// The version to use after receiving `response` to a request with `version`
+fn negotiated_version(version: RTSPVersion, response: &RTSPMessage) -> RTSPVersion {
+    skip_assert_initialized!();
+    match response.version() {
+        Some(replied @ (RTSPVersion::_10 | RTSPVersion::_11 | RTSPVersion::_20))
+            if replied < version =>
+        {
+            replied
+        }
+        _ => version,
+    }
+}
+
+// Sets `version` on `request` and removes headers the version doesn't know
+fn prepare_request(request: &mut RTSPMessage, version: RTSPVersion) {
+    skip_assert_initialized!();
+    request.set_version(version);
+    if version < RTSPVersion::_20 {
+        for field in V2_0_HEADERS {
+            request.remove_header(field, None);
+        }
+    }
+}
+
+fn is_redirect(code: RTSPStatusCode) -> bool {
+    skip_assert_initialized!();
+    matches!(
+        code,
+        RTSPStatusCode::MovedPermanently
+            | RTSPStatusCode::MoveTemporarily
+            | RTSPStatusCode::SeeOther
+            | RTSPStatusCode::RedirectTemporarily
+    )
+}
+
+// The request URI for the stream with the `control` attribute of a session
+// with the request URI `base`
+fn control_uri(base: &str, control: &str) -> String {
+    skip_assert_initialized!();
+    if control.contains("://") {
+        return control.to_owned();
+    }
+    if control.is_empty() || control == "*" {
+        return base.to_owned();
+    }
+
+    format!(
+        "{}/{}",
+        base.trim_end_matches('/'),
+        control.trim_start_matches('/')
+    )
+}
+
+fn same_host(a: &str, b: &str) -> bool {
+    skip_assert_initialized!();
+    match (RTSPUrl::parse(a), RTSPUrl::parse(b)) {
+        ((RTSPResult::Ok, Some(a)), (RTSPResult::Ok, Some(b))) => match (a.host(), b.host()) {
+            (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+pub(crate) fn check_response(
+    operation: &'static str,
+    response: RTSPMessage,
+) -> Result<RTSPMessage, RTSPError> {
+    skip_assert_initialized!();
+    match response.status_code() {
+        Some(RTSPStatusCode::Ok) => Ok(response),
+        Some(code) => Err(RTSPError::from_status(operation, code, Some(response))),
+        None => Err(RTSPError::from_result(operation, RTSPResult::Eparse)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_control_uri() {
+        let base = "rtsp://example.com/live/";
+        assert_eq!(
+            control_uri(base, "stream=0"),
+            "rtsp://example.com/live/stream=0"
+        );
+        assert_eq!(control_uri(base, "*"), base);
+        assert_eq!(
+            control_uri(base, "rtsp://example.com/other/trackID=1"),
+            "rtsp://example.com/other/trackID=1"
+        );
+    }
+
+    #[test]
+    fn test_version_negotiation() {
+        gst::init().unwrap();
+
+        let response = RTSPMessage::response_builder(RTSPStatusCode::Ok)
+            .version(RTSPVersion::_10)
+            .build();
+        assert_eq!(
+            negotiated_version(RTSPVersion::_20, &response),
+            RTSPVersion::_10
+        );
+
+        // Never upgrade beyond the requested version
+        let response = RTSPMessage::response_builder(RTSPStatusCode::Ok)
+            .version(RTSPVersion::_20)
+            .build();
+        assert_eq!(
+            negotiated_version(RTSPVersion::_10, &response),
+            RTSPVersion::_10
+        );
+
+        let mut request = RTSPMessage::request_builder(RTSPMethod::PLAY, "rtsp://example.com/")
+            .header(RTSPHeaderField::SeekStyle, "RAP")
+            .header(RTSPHeaderField::Range, "npt=0-")
+            .build();
+        prepare_request(&mut request, RTSPVersion::_20);
+        assert_eq!(request.version(), Some(RTSPVersion::_20));
+        assert_eq!(request.header(RTSPHeaderField::SeekStyle, 0), Some("RAP"));
+
+        prepare_request(&mut request, RTSPVersion::_10);
+        assert_eq!(request.version(), Some(RTSPVersion::_10));
+        assert_eq!(request.header(RTSPHeaderField::SeekStyle, 0), None);
+        assert_eq!(request.header(RTSPHeaderField::Range, 0), Some("npt=0-"));
+    }
+}
@@ -0,0 +1,835 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    ffi::CStr,
+    future::Future,
+    net::IpAddr,
+    path::PathBuf,
+    ptr,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use glib::{prelude::*, translate::*};
+
+use crate::{
+    ffi,
+    rtsp_error::{result_from_glib, RTSPError},
+    rtsp_message::RTSPMessage,
+    RTSPAuthMethod, RTSPLowerTrans, RTSPResult, RTSPUrl, ScopedIpAddr,
+};
+
+#[derive(Debug)]
+#[doc(alias = "GstRTSPConnection")]
+pub struct RTSPConnection(ptr::NonNull<ffi::GstRTSPConnection>);
+
+impl Drop for RTSPConnection {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let res = ffi::gst_rtsp_connection_free(self.0.as_ptr());
+            debug_assert_eq!(res, ffi::GST_RTSP_OK);
+        }
+    }
+}
+
+unsafe impl Send for RTSPConnection {}
+
+const DEFAULT_PORT: u16 = 554;
+const UNIX_SCHEME: &str = "rtsp+unix://";
+// Connection Attempt Delay recommended by RFC 8305
+const CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+// Maximum number of racing connection attempts at the same time
+const MAX_CONNECTION_ATTEMPTS: usize = 4;
+
+fn timeval(timeout: Option<Duration>) -> Option<glib::ffi::GTimeVal> {
+    skip_assert_initialized!();
+    timeout.map(|timeout| glib::ffi::GTimeVal {
+        tv_sec: timeout.as_secs() as _,
+        tv_usec: timeout.subsec_micros() as _,
+    })
+}
+
+fn timeval_ptr(timeval: &mut Option<glib::ffi::GTimeVal>) -> *mut glib::ffi::GTimeVal {
+    skip_assert_initialized!();
+    timeval.as_mut().map_or(ptr::null_mut(), |tv| tv as *mut _)
+}
+
+impl RTSPConnection {
+    #[doc(alias = "gst_rtsp_connection_create")]
+    pub fn create(url: &RTSPUrl) -> Result<Self, RTSPError> {
+        assert_initialized_main_thread!();
+        unsafe {
+            let mut conn = ptr::null_mut();
+            result_from_glib(
+                "create connection",
+                ffi::gst_rtsp_connection_create(url.to_glib_none().0, &mut conn),
+            )?;
+            Ok(Self::from_glib_full(conn))
+        }
+    }
+
+    #[inline]
+    pub unsafe fn from_glib_full(ptr: *mut ffi::GstRTSPConnection) -> Self {
+        debug_assert!(!ptr.is_null());
+        Self(ptr::NonNull::new_unchecked(ptr))
+    }
+
+    #[inline]
+    pub fn as_ptr(&self) -> *mut ffi::GstRTSPConnection {
+        self.0.as_ptr()
+    }
+
+    #[inline]
+    pub fn into_glib_ptr(self) -> *mut ffi::GstRTSPConnection {
+        let s = std::mem::ManuallyDrop::new(self);
+        s.0.as_ptr()
+    }
+
+    #[doc(alias = "gst_rtsp_connection_create_from_socket")]
+    pub fn create_from_socket(
+        socket: &impl IsA<gio::Socket>,
+        ip: &str,
+        port: u16,
+        initial_buffer: Option<&str>,
+    ) -> Result<Self, RTSPError> {
+        assert_initialized_main_thread!();
+        unsafe {
+            let mut conn = ptr::null_mut();
+            result_from_glib(
+                "create connection from socket",
+                ffi::gst_rtsp_connection_create_from_socket(
+                    socket.as_ref().to_glib_none().0,
+                    ip.to_glib_none().0,
+                    port,
+                    initial_buffer.to_glib_none().0,
+                    &mut conn,
+                ),
+            )?;
+            Ok(Self::from_glib_full(conn))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Connects to the Unix domain socket at `path` and creates a connection
+    /// on top of it.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    pub fn create_unix(path: impl AsRef<std::path::Path>) -> Result<Self, RTSPError> {
+        assert_initialized_main_thread!();
+        let address = gio::UnixSocketAddress::new(path.as_ref());
+        let socket = gio::Socket::new(
+            gio::SocketFamily::Unix,
+            gio::SocketType::Stream,
+            gio::SocketProtocol::Default,
+        )
+        .map_err(|err| RTSPError::from_glib_error("create socket", err))?;
+        socket
+            .connect(&address, gio::Cancellable::NONE)
+            .map_err(|err| RTSPError::from_glib_error("connect socket", err))?;
+
+        Self::create_from_socket(&socket, "localhost", DEFAULT_PORT, None)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a [`RTSPConnectionBuilder`] for connecting to `url`.
+    ///
+    /// Next to `rtsp://`, `rtsps://` and `rtsph://` URLs this also accepts
+    /// `rtsp+unix://` URLs, where the host part is the percent-encoded path of a
+    /// Unix domain socket, e.g. `rtsp+unix://%2Frun%2Frtsp.sock/stream`.
+    pub fn builder(url: &str) -> RTSPConnectionBuilder {
+        assert_initialized_main_thread!();
+        RTSPConnectionBuilder::new(url)
+    }
+
+    #[doc(alias = "gst_rtsp_connection_connect")]
+    pub fn connect(&mut self, timeout: Option<Duration>) -> Result<(), RTSPError> {
+        let mut timeout = timeval(timeout);
+        unsafe {
+            result_from_glib(
+                "connect",
+                ffi::gst_rtsp_connection_connect(self.as_ptr(), timeval_ptr(&mut timeout)),
+            )
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_connection_connect_with_response")]
+    pub fn connect_with_response(
+        &mut self,
+        timeout: Option<Duration>,
+    ) -> Result<RTSPMessage, RTSPError> {
+        let mut timeout = timeval(timeout);
+        let mut response = RTSPMessage::new();
+        unsafe {
+            result_from_glib(
+                "connect",
+                ffi::gst_rtsp_connection_connect_with_response(
+                    self.as_ptr(),
+                    timeval_ptr(&mut timeout),
+                    response.to_glib_none_mut().0,
+                ),
+            )?;
+        }
+        Ok(response)
+    }
+
+    #[doc(alias = "gst_rtsp_connection_close")]
+    pub fn close(&mut self) -> Result<(), RTSPError> {
+        unsafe {
+            result_from_glib(
+                "close connection",
+                ffi::gst_rtsp_connection_close(self.as_ptr()),
+            )
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_connection_send")]
+    pub fn send(
+        &mut self,
+        message: &RTSPMessage,
+        timeout: Option<Duration>,
+    ) -> Result<(), RTSPError> {
+        let mut timeout = timeval(timeout);
+        unsafe {
+            result_from_glib(
+                "send message",
+                ffi::gst_rtsp_connection_send(
+                    self.as_ptr(),
+                    mut_override(message.to_glib_none().0),
+                    timeval_ptr(&mut timeout),
+                ),
+            )
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_connection_receive")]
+    pub fn receive(&mut self, timeout: Option<Duration>) -> Result<RTSPMessage, RTSPError> {
+        let mut timeout = timeval(timeout);
+        let mut message = RTSPMessage::new();
+        unsafe {
+            result_from_glib(
+                "receive message",
+                ffi::gst_rtsp_connection_receive(
+                    self.as_ptr(),
+                    message.to_glib_none_mut().0,
+                    timeval_ptr(&mut timeout),
+                ),
+            )?;
+        }
+        Ok(message)
+    }
+
+    #[doc(alias = "gst_rtsp_connection_flush")]
+    pub fn flush(&mut self, flush: bool) -> Result<(), RTSPError> {
+        unsafe {
+            result_from_glib(
+                "flush connection",
+                ffi::gst_rtsp_connection_flush(self.as_ptr(), flush.into_glib()),
+            )
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_connection_reset_timeout")]
+    pub fn reset_timeout(&mut self) -> Result<(), RTSPError> {
+        unsafe {
+            result_from_glib(
+                "reset timeout",
+                ffi::gst_rtsp_connection_reset_timeout(self.as_ptr()),
+            )
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the time until the session timeout of the connection expires
+    /// and a keep-alive request should be sent.
+    ///
+    /// This is zero if the timeout already expired, or `None` if the timeout
+    /// can't be retrieved.
+    #[doc(alias = "gst_rtsp_connection_next_timeout")]
+    #[doc(alias = "gst_rtsp_connection_next_timeout_usec")]
+    pub fn next_timeout(&mut self) -> Option<Duration> {
+        #[cfg(feature = "v1_18")]
+        let timeout = unsafe { ffi::gst_rtsp_connection_next_timeout_usec(self.as_ptr()) };
+        #[cfg(not(feature = "v1_18"))]
+        let timeout = unsafe {
+            let mut timeout = glib::ffi::GTimeVal {
+                tv_sec: 0,
+                tv_usec: 0,
+            };
+            if ffi::gst_rtsp_connection_next_timeout(self.as_ptr(), &mut timeout)
+                != ffi::GST_RTSP_OK
+            {
+                return None;
+            }
+            i64::from(timeout.tv_sec) * 1_000_000 + i64::from(timeout.tv_usec)
+        };
+
+        Some(Duration::from_micros(timeout.max(0) as u64))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Like [`RTSPConnection::next_timeout()`] but returns the point in time at
+    /// which the session timeout expires.
+    ///
+    /// This is not in the future if the timeout already expired.
+    pub fn deadline(&mut self) -> Option<Instant> {
+        self.next_timeout().map(|timeout| Instant::now() + timeout)
+    }
+
+    #[doc(alias = "get_url")]
+    #[doc(alias = "gst_rtsp_connection_get_url")]
+    pub fn url(&self) -> RTSPUrl {
+        unsafe { from_glib_none(ffi::gst_rtsp_connection_get_url(self.as_ptr())) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the IP address of the remote end, including the zone identifier
+    /// of link-local IPv6 addresses.
+    #[doc(alias = "get_ip")]
+    #[doc(alias = "gst_rtsp_connection_get_ip")]
+    pub fn ip(&self) -> Option<ScopedIpAddr> {
+        unsafe {
+            let ptr = ffi::gst_rtsp_connection_get_ip(self.as_ptr());
+            if ptr.is_null() {
+                None
+            } else {
+                CStr::from_ptr(ptr).to_str().ok()?.parse().ok()
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the IP address of the server.
+    ///
+    /// This accepts plain [`IpAddr`](std::net::IpAddr)s as well as
+    /// [`SocketAddr`](std::net::SocketAddr)s, in which case the port is ignored
+    /// but the scope of link-local IPv6 addresses is preserved.
+    #[doc(alias = "gst_rtsp_connection_set_ip")]
+    pub fn set_ip(&mut self, ip: impl Into<ScopedIpAddr>) {
+        let ip = ip.into();
+        unsafe {
+            ffi::gst_rtsp_connection_set_ip(self.as_ptr(), ip.to_string().to_glib_none().0);
+        }
+    }
+
+    #[doc(alias = "get_tunnelid")]
+    #[doc(alias = "gst_rtsp_connection_get_tunnelid")]
+    pub fn tunnelid(&self) -> Option<&str> {
+        unsafe {
+            let ptr = ffi::gst_rtsp_connection_get_tunnelid(self.as_ptr());
+            if ptr.is_null() {
+                None
+            } else {
+                CStr::from_ptr(ptr).to_str().ok()
+            }
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_connection_is_tunneled")]
+    pub fn is_tunneled(&self) -> bool {
+        unsafe { from_glib(ffi::gst_rtsp_connection_is_tunneled(self.as_ptr())) }
+    }
+
+    #[doc(alias = "gst_rtsp_connection_set_tunneled")]
+    pub fn set_tunneled(&mut self, tunneled: bool) {
+        unsafe {
+            ffi::gst_rtsp_connection_set_tunneled(self.as_ptr(), tunneled.into_glib());
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_connection_set_http_mode")]
+    pub fn set_http_mode(&mut self, enable: bool) {
+        unsafe {
+            ffi::gst_rtsp_connection_set_http_mode(self.as_ptr(), enable.into_glib());
+        }
+    }
+
+    #[doc(alias = "get_remember_session_id")]
+    #[doc(alias = "gst_rtsp_connection_get_remember_session_id")]
+    pub fn remember_session_id(&self) -> bool {
+        unsafe {
+            from_glib(ffi::gst_rtsp_connection_get_remember_session_id(
+                self.as_ptr(),
+            ))
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_connection_set_remember_session_id")]
+    pub fn set_remember_session_id(&mut self, remember: bool) {
+        unsafe {
+            ffi::gst_rtsp_connection_set_remember_session_id(self.as_ptr(), remember.into_glib());
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_connection_set_qos_dscp")]
+    pub fn set_qos_dscp(&mut self, qos_dscp: u32) -> Result<(), RTSPError> {
+        unsafe {
+            result_from_glib(
+                "set QoS DSCP",
+                ffi::gst_rtsp_connection_set_qos_dscp(self.as_ptr(), qos_dscp),
+            )
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_connection_set_proxy")]
+    pub fn set_proxy(&mut self, host: &str, port: u16) -> Result<(), RTSPError> {
+        unsafe {
+            result_from_glib(
+                "set proxy",
+                ffi::gst_rtsp_connection_set_proxy(
+                    self.as_ptr(),
+                    host.to_glib_none().0,
+                    port as u32,
+                ),
+            )
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_connection_set_auth")]
+    pub fn set_auth(
+        &mut self,
+        method: RTSPAuthMethod,
+        user: &str,
+        pass: &str,
+    ) -> Result<(), RTSPError> {
+        unsafe {
+            result_from_glib(
+                "set authentication",
+                ffi::gst_rtsp_connection_set_auth(
+                    self.as_ptr(),
+                    method.into_glib(),
+                    user.to_glib_none().0,
+                    pass.to_glib_none().0,
+                ),
+            )
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_connection_set_auth_param")]
+    pub fn set_auth_param(&mut self, param: &str, value: Option<&str>) {
+        unsafe {
+            ffi::gst_rtsp_connection_set_auth_param(
+                self.as_ptr(),
+                param.to_glib_none().0,
+                value.to_glib_none().0,
+            );
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_connection_clear_auth_params")]
+    pub fn clear_auth_params(&mut self) {
+        unsafe {
+            ffi::gst_rtsp_connection_clear_auth_params(self.as_ptr());
+        }
+    }
+
+    #[doc(alias = "get_read_socket")]
+    #[doc(alias = "gst_rtsp_connection_get_read_socket")]
+    pub fn read_socket(&self) -> Option<gio::Socket> {
+        unsafe { from_glib_none(ffi::gst_rtsp_connection_get_read_socket(self.as_ptr())) }
+    }
+
+    #[doc(alias = "get_write_socket")]
+    #[doc(alias = "gst_rtsp_connection_get_write_socket")]
+    pub fn write_socket(&self) -> Option<gio::Socket> {
+        unsafe { from_glib_none(ffi::gst_rtsp_connection_get_write_socket(self.as_ptr())) }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[must_use = "The builder must be built to be used"]
+pub struct RTSPConnectionBuilder {
+    url: String,
+    ip: Option<ScopedIpAddr>,
+    auth: Option<(RTSPAuthMethod, String, String)>,
+    proxy: Option<(String, u16)>,
+    tunneled: bool,
+    timeout: Option<Duration>,
+}
+
+impl RTSPConnectionBuilder {
+    fn new(url: &str) -> Self {
+        skip_assert_initialized!();
+        Self {
+            url: url.to_owned(),
+            ip: None,
+            auth: None,
+            proxy: None,
+            tunneled: false,
+            timeout: None,
+        }
+    }
+
+    pub(crate) fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub(crate) fn configured_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub(crate) fn with_url(&self, url: &str, keep_auth: bool) -> Self {
+        Self {
+            url: url.to_owned(),
+            ip: None,
+            auth: if keep_auth { self.auth.clone() } else { None },
+            ..self.clone()
+        }
+    }
+
+    pub fn ip(self, ip: impl Into<ScopedIpAddr>) -> Self {
+        Self {
+            ip: Some(ip.into()),
+            ..self
+        }
+    }
+
+    pub fn auth(self, method: RTSPAuthMethod, user: &str, pass: &str) -> Self {
+        Self {
+            auth: Some((method, user.to_owned(), pass.to_owned())),
+            ..self
+        }
+    }
+
+    pub fn proxy(self, host: &str, port: u16) -> Self {
+        Self {
+            proxy: Some((host.to_owned(), port)),
+            ..self
+        }
+    }
+
+    pub fn tunneled(self, tunneled: bool) -> Self {
+        Self { tunneled, ..self }
+    }
+
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates and configures the connection without connecting it.
+    ///
+    /// For `rtsp+unix://` URLs the socket is already connected at this point.
+    pub fn build(self) -> Result<RTSPConnection, RTSPError> {
+        let mut conn = match unix_socket_path(&self.url) {
+            #[cfg(unix)]
+            Some(path) => RTSPConnection::create_unix(path)?,
+            #[cfg(not(unix))]
+            Some(_) => {
+                return Err(RTSPError::from_result("parse URL", RTSPResult::Enotimpl));
+            }
+            None => RTSPConnection::create(&self.parse_url()?)?,
+        };
+        self.configure(&mut conn)?;
+
+        Ok(conn)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates the connection and connects it to the server.
+    ///
+    /// If the host name resolves to both IPv6 and IPv4 addresses, connection
+    /// attempts to both address families are raced as described in RFC 8305
+    /// and the first established connection is used. Attempts are started
+    /// with a delay, with at most four of them in flight at the same time,
+    /// and the remaining attempts are cancelled once one succeeded.
+    pub fn connect(self) -> Result<RTSPConnection, RTSPError> {
+        if unix_socket_path(&self.url).is_some() {
+            return self.build();
+        }
+
+        let url = self.parse_url()?;
+        if let Some((v6, v4)) = self.dual_stack_addresses(&url) {
+            return self.race(v6, v4);
+        }
+
+        let mut conn = RTSPConnection::create(&url)?;
+        self.configure(&mut conn)?;
+        conn.connect(self.timeout)?;
+
+        Ok(conn)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates the connection and connects it to the server without blocking
+    /// the calling thread.
+    ///
+    /// This offloads the blocking [`connect()`](Self::connect) to a thread of
+    /// the GIO blocking thread pool, where name resolution, connecting and the
+    /// tunnel or TLS handshake happen, while the returned future is driven by
+    /// `ctx`. The thread is occupied until the connection is established or
+    /// the configured [`timeout()`](Self::timeout) expires. Dropping the
+    /// future doesn't cancel the connection attempt, the connection is then
+    /// closed once established.
+    pub fn connect_async(
+        self,
+        ctx: &glib::MainContext,
+    ) -> impl Future<Output = Result<RTSPConnection, RTSPError>> {
+        let handle = ctx.spawn(async move { gio::spawn_blocking(move || self.connect()).await });
+
+        async move {
+            match handle.await {
+                Ok(Ok(res)) => res,
+                Ok(Err(panic)) => std::panic::resume_unwind(panic),
+                Err(_) => Err(RTSPError::from_glib_error(
+                    "connect",
+                    glib::Error::new(
+                        gio::IOErrorEnum::Cancelled,
+                        "Main context stopped before connecting",
+                    ),
+                )),
+            }
+        }
+    }
+}
+
+impl RTSPConnectionBuilder {
+    fn parse_url(&self) -> Result<RTSPUrl, RTSPError> {
+        match RTSPUrl::parse(&self.url) {
+            (RTSPResult::Ok, Some(url)) => Ok(url),
+            (RTSPResult::Ok, None) => Err(RTSPError::from_result("parse URL", RTSPResult::Einval)),
+            (res, _) => Err(RTSPError::from_result("parse URL", res)),
+        }
+    }
+
+    fn configure(&self, conn: &mut RTSPConnection) -> Result<(), RTSPError> {
+        if let Some(ip) = self.ip {
+            conn.set_ip(ip);
+        }
+        if let Some((method, ref user, ref pass)) = self.auth {
+            conn.set_auth(method, user, pass)?;
+        }
+        if let Some((ref host, port)) = self.proxy {
+            conn.set_proxy(host, port)?;
+        }
+        conn.set_tunneled(self.tunneled);
+
+        Ok(())
+    }
+
+    // Resolves the host of `url` if racing IPv6 and IPv4 connection attempts
+    // is possible and useful
+    fn dual_stack_addresses(&self, url: &RTSPUrl) -> Option<(Vec<IpAddr>, Vec<IpAddr>)> {
+        // Proxies, HTTP tunnels and the TLS handshake need the actual host name
+        if self.proxy.is_some() || self.tunneled || url.transports().contains(RTSPLowerTrans::TLS) {
+            return None;
+        }
+        if url.host_addr().is_some() {
+            return None;
+        }
+
+        let host = url.host()?;
+        let addresses = gio::Resolver::default()
+            .lookup_by_name(host, gio::Cancellable::NONE)
+            .ok()?;
+
+        let (v6, v4): (Vec<IpAddr>, Vec<IpAddr>) = addresses
+            .into_iter()
+            .map(IpAddr::from)
+            .partition(IpAddr::is_ipv6);
+
+        if v6.is_empty() || v4.is_empty() {
+            None
+        } else {
+            Some((v6, v4))
+        }
+    }
+
+    fn connect_to(&self, addr: IpAddr, attempt: &Attempt) -> Result<RTSPConnection, RTSPError> {
+        let mut url = self.parse_url()?;
+        url.set_host_addr(addr);
+
+        let mut conn = RTSPConnection::create(&url)?;
+        self.configure(&mut conn)?;
+        if !attempt.start(&conn) {
+            return Err(RTSPError::from_result("connect", RTSPResult::Eintr));
+        }
+        let res = conn.connect(self.timeout);
+        attempt.finish();
+        res?;
+
+        Ok(conn)
+    }
+
+    fn race(&self, v6: Vec<IpAddr>, v4: Vec<IpAddr>) -> Result<RTSPConnection, RTSPError> {
+        let (sender, receiver) = mpsc::channel();
+
+        // Alternate between address families, starting with IPv6
+        let mut v6 = v6.into_iter();
+        let mut v4 = v4.into_iter();
+        let mut addrs = Vec::new();
+        loop {
+            match (v6.next(), v4.next()) {
+                (None, None) => break,
+                (a, b) => addrs.extend(a.into_iter().chain(b)),
+            }
+        }
+        let mut addrs = addrs.into_iter().peekable();
+
+        let mut attempts = Vec::new();
+        let mut pending = 0;
+        let mut last_err = None;
+        let res = loop {
+            if pending < MAX_CONNECTION_ATTEMPTS {
+                if let Some(addr) = addrs.next() {
+                    let builder = self.clone();
+                    let sender = sender.clone();
+                    let attempt = Attempt::default();
+                    let thread_attempt = attempt.clone();
+                    let spawned =
+                        thread::Builder::new()
+                            .name("rtsp-connect".into())
+                            .spawn(move || {
+                                // Connections that lost the race are dropped here
+                                let _ = sender.send(builder.connect_to(addr, &thread_attempt));
+                            });
+                    match spawned {
+                        Ok(_) => {
+                            attempts.push(attempt);
+                            pending += 1;
+                        }
+                        Err(_) => {
+                            last_err = Some(RTSPError::from_result("connect", RTSPResult::Esys));
+                            continue;
+                        }
+                    }
+                }
+            }
+
+            if pending == 0 {
+                break Err(
+                    last_err.unwrap_or_else(|| RTSPError::from_result("connect", RTSPResult::Enet))
+                );
+            }
+
+            // Start the next attempt after the connection attempt delay or
+            // directly if one of the current ones failed already
+            let res = if pending < MAX_CONNECTION_ATTEMPTS && addrs.peek().is_some() {
+                match receiver.recv_timeout(CONNECTION_ATTEMPT_DELAY) {
+                    Ok(res) => res,
+                    Err(_) => continue,
+                }
+            } else {
+                // The sender is kept alive, so this can't fail
+                receiver.recv().unwrap()
+            };
+
+            pending -= 1;
+            match res {
+                Ok(conn) => break Ok(conn),
+                Err(err) => last_err = Some(err),
+            }
+        };
+
+        // Cancel the attempts that are still in flight
+        for attempt in &attempts {
+            attempt.cancel();
+        }
+
+        res
+    }
+}
+
+struct ConnectionPtr(ptr::NonNull<ffi::GstRTSPConnection>);
+
+unsafe impl Send for ConnectionPtr {}
+
+#[derive(Default)]
+struct AttemptState {
+    cancelled: bool,
+    conn: Option<ConnectionPtr>,
+}
+
+// A racing connection attempt that can be cancelled from another thread
+#[derive(Default, Clone)]
+struct Attempt(Arc<Mutex<AttemptState>>);
+
+impl Attempt {
+    // Registers `conn` for cancellation while connecting, or returns `false`
+    // if the attempt was cancelled already
+    fn start(&self, conn: &RTSPConnection) -> bool {
+        let mut state = self.0.lock().unwrap();
+        if state.cancelled {
+            return false;
+        }
+        state.conn = Some(ConnectionPtr(conn.0));
+        true
+    }
+
+    fn finish(&self) {
+        self.0.lock().unwrap().conn = None;
+    }
+
+    fn cancel(&self) {
+        let mut state = self.0.lock().unwrap();
+        state.cancelled = true;
+        // The connection stays alive until `finish()` was called, which
+        // can't happen while the lock is held
+        if let Some(ref conn) = state.conn {
+            unsafe {
+                ffi::gst_rtsp_connection_flush(conn.0.as_ptr(), glib::ffi::GTRUE);
+            }
+        }
+    }
+}
+
+// Request URI to use for `url`, which for `rtsp+unix://` URLs is the path part
+// relative to the local host
+pub(crate) fn request_uri(url: &str) -> String {
+    skip_assert_initialized!();
+    match url.strip_prefix(UNIX_SCHEME) {
+        Some(rest) => {
+            let path = rest.find('/').map_or("/", |idx| &rest[idx..]);
+            format!("rtsp://localhost{path}")
+        }
+        None => url.to_owned(),
+    }
+}
+
+// Extracts the socket path from a `rtsp+unix://` URL
+fn unix_socket_path(url: &str) -> Option<PathBuf> {
+    skip_assert_initialized!();
+    let rest = url.strip_prefix(UNIX_SCHEME)?;
+    let host = rest.split_once('/').map_or(rest, |(host, _)| host);
+
+    let mut path = Vec::with_capacity(host.len());
+    let mut bytes = host.bytes();
+    while let Some(b) = bytes.next() {
+        if b == b'%' {
+            let hex = [bytes.next()?, bytes.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            path.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            path.push(b);
+        }
+    }
+
+    String::from_utf8(path).ok().map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unix_socket_path() {
+        assert_eq!(
+            unix_socket_path("rtsp+unix://%2Frun%2Frtsp.sock/stream"),
+            Some(PathBuf::from("/run/rtsp.sock"))
+        );
+        assert_eq!(
+            unix_socket_path("rtsp+unix://%2Ftmp%2Fa%20b.sock"),
+            Some(PathBuf::from("/tmp/a b.sock"))
+        );
+        assert_eq!(unix_socket_path("rtsp+unix://%2"), None);
+        assert_eq!(
+            request_uri("rtsp+unix://%2Frun%2Frtsp.sock/stream"),
+            "rtsp://localhost/stream"
+        );
+        assert_eq!(unix_socket_path("rtsp://localhost/stream"), None);
+    }
+}
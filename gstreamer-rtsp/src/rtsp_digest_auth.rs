@@ -0,0 +1,466 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::fmt::{self, Write};
+
+use crate::{
+    rtsp_message::RTSPMessage, RTSPAuthCredential, RTSPAuthMethod, RTSPHeaderField, RTSPMethod,
+};
+
+// Random bytes of generated client nonces
+const CNONCE_LEN: usize = 16;
+
+// rustdoc-stripper-ignore-next
+/// Text encoding of values generated by [`generate_nonce()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum NonceEncoding {
+    // rustdoc-stripper-ignore-next
+    /// Lowercase hexadecimal digits.
+    #[default]
+    Hex,
+    // rustdoc-stripper-ignore-next
+    /// Standard base64 with padding.
+    Base64,
+}
+
+// rustdoc-stripper-ignore-next
+/// Generates `len` cryptographically secure random bytes from the operating
+/// system and encodes them as text.
+///
+/// This is suitable for server nonces and opaque values of Digest challenges
+/// as well as for client nonces.
+pub fn generate_nonce(len: usize, encoding: NonceEncoding) -> Result<String, glib::BoolError> {
+    skip_assert_initialized!();
+    let mut bytes = vec![0u8; len];
+    getrandom::fill(&mut bytes)
+        .map_err(|err| glib::bool_error!("Failed to generate random bytes: {}", err))?;
+
+    Ok(match encoding {
+        NonceEncoding::Hex => bytes
+            .iter()
+            .fold(String::with_capacity(2 * len), |mut s, b| {
+                write!(s, "{b:02x}").unwrap();
+                s
+            }),
+        NonceEncoding::Base64 => glib::base64_encode(&bytes).into(),
+    })
+}
+
+// rustdoc-stripper-ignore-next
+/// Hash algorithm of a Digest challenge (RFC 7616).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum DigestAlgorithm {
+    #[default]
+    Md5,
+    Sha256,
+}
+
+impl DigestAlgorithm {
+    fn from_name(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("MD5") {
+            Some(DigestAlgorithm::Md5)
+        } else if name.eq_ignore_ascii_case("SHA-256") {
+            Some(DigestAlgorithm::Sha256)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DigestAlgorithm::Md5 => "MD5",
+            DigestAlgorithm::Sha256 => "SHA-256",
+        }
+    }
+
+    fn hash(self, data: &str) -> String {
+        let checksum_type = match self {
+            DigestAlgorithm::Md5 => glib::ChecksumType::Md5,
+            DigestAlgorithm::Sha256 => glib::ChecksumType::Sha256,
+        };
+
+        glib::compute_checksum_for_string(checksum_type, data)
+            .expect("Failed to compute checksum")
+            .into()
+    }
+}
+
+impl fmt::Display for DigestAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Computes `Authorization` header values for a Digest challenge of a server.
+///
+/// The state is kept between requests so that the nonce count increases for
+/// each request authorized with the same server nonce, as required for
+/// `qop=auth`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestAuth {
+    username: String,
+    password: String,
+    realm: String,
+    nonce: String,
+    opaque: Option<String>,
+    algorithm: DigestAlgorithm,
+    qop_auth: bool,
+    nonce_count: u32,
+}
+
+impl DigestAuth {
+    // rustdoc-stripper-ignore-next
+    /// Creates the state for answering the Digest `challenge` of a
+    /// `WWW-Authenticate` header.
+    ///
+    /// Fails if the challenge is no Digest challenge, lacks the realm or
+    /// nonce, or only supports algorithms or qop values other than `MD5`,
+    /// `SHA-256` and `auth`.
+    pub fn from_challenge(
+        challenge: &RTSPAuthCredential,
+        username: &str,
+        password: &str,
+    ) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let mut auth = Self {
+            username: username.to_owned(),
+            password: password.to_owned(),
+            realm: String::new(),
+            nonce: String::new(),
+            opaque: None,
+            algorithm: DigestAlgorithm::Md5,
+            qop_auth: false,
+            nonce_count: 0,
+        };
+        auth.update_challenge(challenge)?;
+
+        Ok(auth)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Selects the first supported Digest challenge of `response`, e.g. one
+    /// with status `401 Unauthorized`.
+    pub fn from_response(
+        response: &RTSPMessage,
+        username: &str,
+        password: &str,
+    ) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let mut err = glib::bool_error!("Response contains no Digest challenge");
+        for challenge in response.parse_www_authenticate() {
+            if challenge.scheme() != RTSPAuthMethod::Digest {
+                continue;
+            }
+            match Self::from_challenge(&challenge, username, password) {
+                Ok(auth) => return Ok(auth),
+                Err(e) => err = e,
+            }
+        }
+
+        Err(err)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Takes over a new challenge of the server, e.g. after it reported the
+    /// nonce as `stale`.
+    ///
+    /// The nonce count restarts if the nonce changed.
+    pub fn update_challenge(
+        &mut self,
+        challenge: &RTSPAuthCredential,
+    ) -> Result<(), glib::BoolError> {
+        if challenge.scheme() != RTSPAuthMethod::Digest {
+            return Err(glib::bool_error!("Not a Digest challenge"));
+        }
+
+        let realm = challenge
+            .realm()
+            .ok_or_else(|| glib::bool_error!("Digest challenge without realm"))?;
+        let nonce = challenge
+            .nonce()
+            .ok_or_else(|| glib::bool_error!("Digest challenge without nonce"))?;
+        let algorithm = match challenge.algorithm() {
+            None => DigestAlgorithm::Md5,
+            Some(name) => DigestAlgorithm::from_name(name)
+                .ok_or_else(|| glib::bool_error!("Unsupported Digest algorithm {}", name))?,
+        };
+
+        let qop = challenge.qop();
+        let qop_auth = qop.iter().any(|qop| qop.eq_ignore_ascii_case("auth"));
+        if !qop.is_empty() && !qop_auth {
+            return Err(glib::bool_error!(
+                "Unsupported Digest qop {}",
+                qop.join(",")
+            ));
+        }
+
+        if nonce != self.nonce {
+            self.nonce = nonce.to_owned();
+            self.nonce_count = 0;
+        }
+        self.realm = realm.to_owned();
+        self.opaque = challenge.opaque().map(String::from);
+        self.algorithm = algorithm;
+        self.qop_auth = qop_auth;
+
+        Ok(())
+    }
+
+    pub fn realm(&self) -> &str {
+        &self.realm
+    }
+
+    pub fn nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    pub fn algorithm(&self) -> DigestAlgorithm {
+        self.algorithm
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Number of requests authorized with the current nonce so far.
+    pub fn nonce_count(&self) -> u32 {
+        self.nonce_count
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Computes the `Authorization` header value for a request with `method`
+    /// and `uri`, generating a new client nonce.
+    pub fn authorization(
+        &mut self,
+        method: RTSPMethod,
+        uri: &str,
+    ) -> Result<String, glib::BoolError> {
+        let cnonce = generate_nonce(CNONCE_LEN, NonceEncoding::Hex)?;
+        self.authorization_with_cnonce(method, uri, &cnonce)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Like [`authorization()`](Self::authorization) but with the given
+    /// client nonce, which is only used for `qop=auth`.
+    ///
+    /// Fails if `method` is not a single method.
+    pub fn authorization_with_cnonce(
+        &mut self,
+        method: RTSPMethod,
+        uri: &str,
+        cnonce: &str,
+    ) -> Result<String, glib::BoolError> {
+        let method = Some(method)
+            .filter(|method| method.bits().count_ones() == 1)
+            .and_then(RTSPMethod::as_text)
+            .ok_or_else(|| glib::bool_error!("Invalid method {:?}", method))?;
+        let algorithm = self.algorithm;
+
+        let ha1 = algorithm.hash(&format!(
+            "{}:{}:{}",
+            self.username, self.realm, self.password
+        ));
+        let ha2 = algorithm.hash(&format!("{method}:{uri}"));
+
+        let mut header = format!(
+            "Digest username=\"{}\", realm=\"{}\", nonce=\"{}\", uri=\"{}\"",
+            escape(&self.username),
+            escape(&self.realm),
+            escape(&self.nonce),
+            escape(uri),
+        );
+
+        if self.qop_auth {
+            self.nonce_count = self.nonce_count.wrapping_add(1);
+            let nc = format!("{:08x}", self.nonce_count);
+            let response =
+                algorithm.hash(&format!("{ha1}:{}:{nc}:{cnonce}:auth:{ha2}", self.nonce));
+            write!(
+                header,
+                ", response=\"{response}\", qop=auth, nc={nc}, cnonce=\"{}\"",
+                escape(cnonce)
+            )
+            .unwrap();
+        } else {
+            let response = algorithm.hash(&format!("{ha1}:{}:{ha2}", self.nonce));
+            write!(header, ", response=\"{response}\"").unwrap();
+        }
+
+        write!(header, ", algorithm={algorithm}").unwrap();
+        if let Some(ref opaque) = self.opaque {
+            write!(header, ", opaque=\"{}\"", escape(opaque)).unwrap();
+        }
+
+        Ok(header)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds the `Authorization` header for the method and URI of the request
+    /// `msg`.
+    pub fn authorize(&mut self, msg: &mut RTSPMessage) -> Result<(), glib::BoolError> {
+        let (Some(method), Some(uri)) = (msg.method(), msg.uri()) else {
+            return Err(glib::bool_error!("Not a request"));
+        };
+
+        let authorization = self.authorization(method, uri)?;
+        msg.remove_header(RTSPHeaderField::Authorization, None);
+        msg.add_header(RTSPHeaderField::Authorization, &authorization);
+
+        Ok(())
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RTSPStatusCode;
+
+    fn challenge(value: &str) -> RTSPAuthCredential {
+        let response = RTSPMessage::response_builder(RTSPStatusCode::Unauthorized)
+            .header(RTSPHeaderField::WwwAuthenticate, value)
+            .build();
+        response.parse_www_authenticate().remove(0)
+    }
+
+    #[test]
+    fn test_generate_nonce() {
+        gst::init().unwrap();
+
+        let nonce = generate_nonce(16, NonceEncoding::Hex).unwrap();
+        assert_eq!(nonce.len(), 32);
+        assert!(nonce.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')));
+        assert_ne!(nonce, generate_nonce(16, NonceEncoding::Hex).unwrap());
+
+        let opaque = generate_nonce(18, NonceEncoding::Base64).unwrap();
+        assert_eq!(opaque.len(), 24);
+        assert_eq!(glib::base64_decode(&opaque).len(), 18);
+
+        assert_eq!(generate_nonce(0, NonceEncoding::Hex).unwrap(), "");
+    }
+
+    #[test]
+    fn test_rfc2617() {
+        gst::init().unwrap();
+
+        let mut auth = DigestAuth::from_challenge(
+            &challenge(
+                "Digest realm=\"testrealm@host.com\", qop=\"auth,auth-int\", \
+                 nonce=\"dcd98b7102dd2f0e8b11d0f600bfb0c093\", \
+                 opaque=\"5ccc069c403ebaf9f0171e9517f40e41\"",
+            ),
+            "Mufasa",
+            "Circle Of Life",
+        )
+        .unwrap();
+        assert_eq!(auth.algorithm(), DigestAlgorithm::Md5);
+
+        assert_eq!(
+            auth.authorization_with_cnonce(RTSPMethod::GET, "/dir/index.html", "0a4f113b")
+                .unwrap(),
+            "Digest username=\"Mufasa\", realm=\"testrealm@host.com\", \
+             nonce=\"dcd98b7102dd2f0e8b11d0f600bfb0c093\", uri=\"/dir/index.html\", \
+             response=\"6629fae49393a05397450978507c4ef1\", qop=auth, nc=00000001, \
+             cnonce=\"0a4f113b\", algorithm=MD5, opaque=\"5ccc069c403ebaf9f0171e9517f40e41\""
+        );
+        assert_eq!(auth.nonce_count(), 1);
+
+        assert!(auth
+            .authorization_with_cnonce(RTSPMethod::empty(), "/dir/index.html", "0a4f113b")
+            .is_err());
+        assert!(auth
+            .authorization_with_cnonce(
+                RTSPMethod::GET | RTSPMethod::PLAY,
+                "/dir/index.html",
+                "0a4f113b"
+            )
+            .is_err());
+        assert_eq!(auth.nonce_count(), 1);
+    }
+
+    #[test]
+    fn test_rfc7616_sha256() {
+        gst::init().unwrap();
+
+        let mut auth = DigestAuth::from_challenge(
+            &challenge(
+                "Digest realm=\"http-auth@example.org\", qop=\"auth, auth-int\", \
+                 algorithm=SHA-256, nonce=\"7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v\", \
+                 opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\"",
+            ),
+            "Mufasa",
+            "Circle of Life",
+        )
+        .unwrap();
+        assert_eq!(auth.algorithm(), DigestAlgorithm::Sha256);
+
+        let header = auth
+            .authorization_with_cnonce(
+                RTSPMethod::GET,
+                "/dir/index.html",
+                "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ",
+            )
+            .unwrap();
+        assert!(header.contains(
+            "response=\"753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1\""
+        ));
+        assert!(header.contains("nc=00000001"));
+
+        // The nonce count increases per request and restarts for new nonces
+        let header = auth
+            .authorization(RTSPMethod::GET, "/dir/index.html")
+            .unwrap();
+        assert!(header.contains("nc=00000002"));
+        auth.update_challenge(&challenge(
+            "Digest realm=\"http-auth@example.org\", qop=\"auth\", \
+             algorithm=SHA-256, nonce=\"other\", stale=TRUE",
+        ))
+        .unwrap();
+        assert_eq!(auth.nonce_count(), 0);
+    }
+
+    #[test]
+    fn test_authorize() {
+        gst::init().unwrap();
+
+        let response = RTSPMessage::response_builder(RTSPStatusCode::Unauthorized)
+            .header(RTSPHeaderField::WwwAuthenticate, "Basic realm=\"cam\"")
+            .header(
+                RTSPHeaderField::WwwAuthenticate,
+                "Digest realm=\"cam\", nonce=\"abc\"",
+            )
+            .build();
+        let mut auth = DigestAuth::from_response(&response, "user", "pass").unwrap();
+
+        let mut request =
+            RTSPMessage::request_builder(RTSPMethod::DESCRIBE, "rtsp://cam/stream").build();
+        auth.authorize(&mut request).unwrap();
+        let header = request.header(RTSPHeaderField::Authorization, 0).unwrap();
+        assert!(header.starts_with("Digest username=\"user\", realm=\"cam\", nonce=\"abc\""));
+        assert!(!header.contains("qop="));
+
+        assert!(DigestAuth::from_challenge(
+            &challenge("Digest realm=\"cam\", nonce=\"abc\", algorithm=SHA-512-256"),
+            "user",
+            "pass",
+        )
+        .is_err());
+        assert!(DigestAuth::from_challenge(
+            &challenge("Digest realm=\"cam\", nonce=\"abc\", qop=\"auth-int\""),
+            "user",
+            "pass",
+        )
+        .is_err());
+    }
+}
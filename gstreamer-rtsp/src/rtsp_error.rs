@@ -0,0 +1,208 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{error, fmt, io};
+
+use glib::translate::*;
+
+use crate::{ffi, rtsp_message::RTSPMessage, RTSPResult, RTSPStatusCode};
+
+// rustdoc-stripper-ignore-next
+/// Error returned by the fallible RTSP APIs.
+///
+/// It keeps track of the operation that failed, so that propagating it with `?`
+/// results in an informative message.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum RTSPError {
+    // rustdoc-stripper-ignore-next
+    /// The RTSP library returned an error code.
+    Result {
+        operation: &'static str,
+        result: RTSPResult,
+        errno: Option<i32>,
+    },
+    // rustdoc-stripper-ignore-next
+    /// The peer answered with an unsuccessful status code.
+    Status {
+        operation: &'static str,
+        code: RTSPStatusCode,
+        response: Option<RTSPMessage>,
+    },
+    // rustdoc-stripper-ignore-next
+    /// The peer does not support some of the required options.
+    Unsupported {
+        operation: &'static str,
+        options: Vec<String>,
+    },
+    // rustdoc-stripper-ignore-next
+    /// A GLib operation, e.g. on the underlying socket, failed.
+    Glib {
+        operation: &'static str,
+        error: glib::Error,
+    },
+}
+
+impl RTSPError {
+    pub fn from_result(operation: &'static str, result: RTSPResult) -> Self {
+        skip_assert_initialized!();
+        RTSPError::Result {
+            operation,
+            result,
+            errno: None,
+        }
+    }
+
+    pub fn from_status(
+        operation: &'static str,
+        code: RTSPStatusCode,
+        response: Option<RTSPMessage>,
+    ) -> Self {
+        skip_assert_initialized!();
+        RTSPError::Status {
+            operation,
+            code,
+            response,
+        }
+    }
+
+    pub fn from_glib_error(operation: &'static str, error: glib::Error) -> Self {
+        skip_assert_initialized!();
+        RTSPError::Glib { operation, error }
+    }
+
+    pub fn operation(&self) -> &'static str {
+        match self {
+            RTSPError::Result { operation, .. }
+            | RTSPError::Status { operation, .. }
+            | RTSPError::Unsupported { operation, .. }
+            | RTSPError::Glib { operation, .. } => operation,
+        }
+    }
+
+    pub fn result(&self) -> RTSPResult {
+        match self {
+            RTSPError::Result { result, .. } => *result,
+            RTSPError::Status { .. } | RTSPError::Unsupported { .. } | RTSPError::Glib { .. } => {
+                RTSPResult::Error
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The OS error of a failed system call, only available for
+    /// [`RTSPResult::Esys`] errors returned by the RTSP library.
+    pub fn errno(&self) -> Option<i32> {
+        match self {
+            RTSPError::Result { errno, .. } => *errno,
+            RTSPError::Status { .. } | RTSPError::Unsupported { .. } | RTSPError::Glib { .. } => {
+                None
+            }
+        }
+    }
+
+    pub fn status_code(&self) -> Option<RTSPStatusCode> {
+        match self {
+            RTSPError::Status { code, .. } => Some(*code),
+            RTSPError::Unsupported { .. } => Some(RTSPStatusCode::OptionNotSupported),
+            RTSPError::Result { .. } | RTSPError::Glib { .. } => None,
+        }
+    }
+
+    pub fn unsupported_options(&self) -> &[String] {
+        match self {
+            RTSPError::Unsupported { options, .. } => options,
+            _ => &[],
+        }
+    }
+
+    pub fn response(&self) -> Option<&RTSPMessage> {
+        match self {
+            RTSPError::Status { response, .. } => response.as_ref(),
+            RTSPError::Result { .. } | RTSPError::Unsupported { .. } | RTSPError::Glib { .. } => {
+                None
+            }
+        }
+    }
+}
+
+impl fmt::Display for RTSPError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RTSPError::Result {
+                operation,
+                result,
+                errno,
+            } => {
+                let text: glib::GString =
+                    unsafe { from_glib_full(ffi::gst_rtsp_strresult(result.into_glib())) };
+                write!(f, "Failed to {operation}: {text}")?;
+                if let Some(errno) = errno {
+                    write!(f, " ({})", io::Error::from_raw_os_error(*errno))?;
+                }
+                Ok(())
+            }
+            RTSPError::Status {
+                operation, code, ..
+            } => {
+                write!(f, "Failed to {operation}: {}", code.into_glib())?;
+                unsafe {
+                    let text = ffi::gst_rtsp_status_as_text(code.into_glib());
+                    if !text.is_null() {
+                        write!(f, " {}", std::ffi::CStr::from_ptr(text).to_string_lossy())?;
+                    }
+                }
+                Ok(())
+            }
+            RTSPError::Unsupported { operation, options } => {
+                write!(
+                    f,
+                    "Failed to {operation}: unsupported options {}",
+                    options.join(", ")
+                )
+            }
+            RTSPError::Glib { operation, error } => write!(f, "Failed to {operation}: {error}"),
+        }
+    }
+}
+
+impl error::Error for RTSPError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            RTSPError::Glib { error, .. } => Some(error),
+            RTSPError::Result { .. } | RTSPError::Status { .. } | RTSPError::Unsupported { .. } => {
+                None
+            }
+        }
+    }
+}
+
+impl From<RTSPError> for glib::BoolError {
+    fn from(err: RTSPError) -> Self {
+        skip_assert_initialized!();
+        glib::bool_error!("{}", err)
+    }
+}
+
+pub(crate) fn result_from_glib(
+    operation: &'static str,
+    res: ffi::GstRTSPResult,
+) -> Result<(), RTSPError> {
+    skip_assert_initialized!();
+    match res {
+        ffi::GST_RTSP_OK => Ok(()),
+        err => {
+            // Read errno right after the failed call, before anything else can
+            // overwrite it
+            let errno = match err {
+                ffi::GST_RTSP_ESYS => io::Error::last_os_error().raw_os_error(),
+                _ => None,
+            };
+
+            Err(RTSPError::Result {
+                operation,
+                result: unsafe { from_glib(err) },
+                errno,
+            })
+        }
+    }
+}
@@ -0,0 +1,31 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::translate::*;
+
+use crate::{ffi, RTSPHeaderField};
+
+impl RTSPHeaderField {
+    // rustdoc-stripper-ignore-next
+    /// Whether a message may contain the header more than once, e.g. when
+    /// copying headers between messages.
+    #[doc(alias = "gst_rtsp_header_allow_multiple")]
+    pub fn allows_multiple(self) -> bool {
+        skip_assert_initialized!();
+        unsafe { from_glib(ffi::gst_rtsp_header_allow_multiple(self.into_glib())) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allows_multiple() {
+        gst::init().unwrap();
+
+        assert!(RTSPHeaderField::WwwAuthenticate.allows_multiple());
+        assert!(RTSPHeaderField::Public.allows_multiple());
+        assert!(!RTSPHeaderField::Cseq.allows_multiple());
+        assert!(!RTSPHeaderField::Session.allows_multiple());
+    }
+}
@@ -0,0 +1,199 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{fmt, str};
+
+use gst_sdp::MIKEYMessage;
+
+// rustdoc-stripper-ignore-next
+/// The MIKEY key management of a secure (SAVP/SAVPF) stream, as transported
+/// in the `KeyMgmt` header of SETUP requests (RFC 4567).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RTSPKeyMgmt {
+    uri: Option<String>,
+    data: Vec<u8>,
+}
+
+impl RTSPKeyMgmt {
+    // rustdoc-stripper-ignore-next
+    /// Creates the key management for the stream at `uri` from the SRTP
+    /// parameters of `caps`, as used by `srtpenc`.
+    ///
+    /// A crypto session is added for each of the sending `ssrcs`.
+    #[doc(alias = "gst_mikey_message_new_from_caps")]
+    pub fn from_srtp_caps(
+        uri: Option<&str>,
+        caps: &gst::CapsRef,
+        ssrcs: &[u32],
+    ) -> Result<Self, glib::BoolError> {
+        assert_initialized_main_thread!();
+        let mut msg = MIKEYMessage::from_caps(caps)
+            .map_err(|_| glib::bool_error!("Caps contain no usable SRTP parameters"))?;
+
+        for &ssrc in ssrcs {
+            msg.make_mut().add_cs_srtp(0, ssrc, 0)?;
+        }
+
+        let bytes = msg
+            .to_bytes()
+            .map_err(|err| glib::bool_error!("Failed to serialize MIKEY: {}", err))?;
+
+        Ok(Self {
+            uri: uri.map(String::from),
+            data: bytes.to_vec(),
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses the first MIKEY entry of a `KeyMgmt` header value.
+    pub fn parse(header: &str) -> Result<Self, glib::BoolError> {
+        assert_initialized_main_thread!();
+        let mut entries = Vec::<String>::new();
+
+        // URIs may contain commas themselves, so only split before `prot=`
+        for part in header.split(',') {
+            match entries.last_mut() {
+                Some(last) if !part.trim_start().starts_with("prot=") => {
+                    last.push(',');
+                    last.push_str(part);
+                }
+                _ => entries.push(part.to_owned()),
+            }
+        }
+
+        for entry in &entries {
+            let mut prot = None;
+            let mut uri = None;
+            let mut data = None;
+
+            for param in entry.split(';') {
+                let (key, value) = param.split_once('=').unwrap_or((param, ""));
+                let value = value.trim().trim_matches('"');
+                match key.trim() {
+                    "prot" => prot = Some(value),
+                    "uri" => uri = Some(value),
+                    "data" => data = Some(value),
+                    _ => (),
+                }
+            }
+
+            if prot != Some("mikey") {
+                continue;
+            }
+
+            let data = data.ok_or_else(|| glib::bool_error!("KeyMgmt entry without data"))?;
+            let data = glib::base64_decode(data);
+
+            // Make sure the data can actually be used later
+            Self::decode(&data)?;
+
+            return Ok(Self {
+                uri: uri.map(String::from),
+                data,
+            });
+        }
+
+        Err(glib::bool_error!("No MIKEY entry in KeyMgmt header"))
+    }
+
+    fn decode(data: &[u8]) -> Result<MIKEYMessage, glib::BoolError> {
+        MIKEYMessage::from_data(data)
+            .map_err(|err| glib::bool_error!("Invalid MIKEY message: {}", err))
+    }
+
+    pub fn uri(&self) -> Option<&str> {
+        self.uri.as_deref()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The binary MIKEY message.
+    pub fn mikey_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns `application/x-srtp` caps with the key material and SRTP
+    /// parameters of the MIKEY message, as needed by `srtpdec`.
+    #[doc(alias = "gst_mikey_message_to_caps")]
+    pub fn srtp_caps(&self) -> Result<gst::Caps, glib::BoolError> {
+        let msg = Self::decode(&self.data)?;
+
+        let mut caps = gst::Caps::new_empty_simple("application/x-srtp");
+        msg.to_caps(caps.get_mut().unwrap())
+            .map_err(|_| glib::bool_error!("MIKEY message contains no SRTP parameters"))?;
+
+        Ok(caps)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates `application/x-srtp` caps for the master key and salt `key`,
+    /// using AES-128-ICM encryption and HMAC-SHA1-80 authentication for SRTP
+    /// and SRTCP.
+    pub fn srtp_caps_for_key(key: &[u8]) -> gst::Caps {
+        assert_initialized_main_thread!();
+        gst::Caps::builder("application/x-srtp")
+            .field("srtp-key", gst::Buffer::from_slice(key.to_vec()))
+            .field("srtp-cipher", "aes-128-icm")
+            .field("srtp-auth", "hmac-sha1-80")
+            .field("srtcp-cipher", "aes-128-icm")
+            .field("srtcp-auth", "hmac-sha1-80")
+            .build()
+    }
+}
+
+impl fmt::Display for RTSPKeyMgmt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("prot=mikey")?;
+        if let Some(ref uri) = self.uri {
+            write!(f, ";uri=\"{uri}\"")?;
+        }
+        write!(f, ";data=\"{}\"", glib::base64_encode(&self.data))
+    }
+}
+
+impl str::FromStr for RTSPKeyMgmt {
+    type Err = glib::BoolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        assert_initialized_main_thread!();
+        Self::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        gst::init().unwrap();
+
+        let key = (0..30).collect::<Vec<u8>>();
+        let caps = RTSPKeyMgmt::srtp_caps_for_key(&key);
+
+        let key_mgmt =
+            RTSPKeyMgmt::from_srtp_caps(Some("rtsp://example.com/stream=0"), &caps, &[42]).unwrap();
+        let header = key_mgmt.to_string();
+        assert!(header.starts_with("prot=mikey;uri=\"rtsp://example.com/stream=0\";data=\""));
+
+        let parsed = header.parse::<RTSPKeyMgmt>().unwrap();
+        assert_eq!(parsed, key_mgmt);
+
+        let caps = parsed.srtp_caps().unwrap();
+        let s = caps.structure(0).unwrap();
+        assert_eq!(s.name(), "application/x-srtp");
+        let buffer = s.get::<gst::Buffer>("srtp-key").unwrap();
+        assert_eq!(&*buffer.map_readable().unwrap(), key.as_slice());
+        assert_eq!(s.get::<&str>("srtp-auth").unwrap(), "hmac-sha1-80");
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        gst::init().unwrap();
+
+        assert!("prot=foo;data=\"AAAA\"".parse::<RTSPKeyMgmt>().is_err());
+        assert!("prot=mikey;uri=\"rtsp://a\""
+            .parse::<RTSPKeyMgmt>()
+            .is_err());
+        assert!("prot=mikey;data=\"AAAA\"".parse::<RTSPKeyMgmt>().is_err());
+    }
+}
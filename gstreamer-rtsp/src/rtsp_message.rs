@@ -1,7 +1,19 @@
-use crate::{ffi, RTSPAuthCredential, RTSPHeaderField, RTSPStatusCode};
+use std::{ffi::CStr, fmt, io};
+
+use crate::{
+    ffi,
+    rtsp_onvif::{OnvifFrames, OnvifReplayOptions, ONVIF_REPLAY},
+    RTSPAuthCredential, RTSPHeaderField, RTSPKeyMgmt, RTSPMethod, RTSPMsgType, RTSPRtpInfo,
+    RTSPStatusCode, RTSPTimeRange, RTSPTransportOffer, RTSPVersion,
+};
 use glib::translate::*;
 
 glib::wrapper! {
+    // rustdoc-stripper-ignore-next
+    /// An RTSP request, response or interleaved data message.
+    ///
+    /// Cloning a message creates a deep copy of its headers and body, so the
+    /// copy can be modified or resent independently of the original.
     #[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
     #[doc(alias = "GstRTSPMessage")]
     pub struct RTSPMessage(Boxed<ffi::GstRTSPMessage>);
@@ -21,9 +33,400 @@ glib::wrapper! {
     }
 }
 
+impl Default for RTSPMessage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RTSPMessage {
     pub const NONE: Option<&'static RTSPMessage> = None;
 
+    #[doc(alias = "gst_rtsp_message_new")]
+    pub fn new() -> RTSPMessage {
+        assert_initialized_main_thread!();
+        unsafe {
+            let mut msg = std::ptr::null_mut();
+            let res = ffi::gst_rtsp_message_new(&mut msg);
+            debug_assert_eq!(res, ffi::GST_RTSP_OK);
+            from_glib_full(msg)
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_message_new_request")]
+    pub fn new_request(method: RTSPMethod, uri: &str) -> RTSPMessage {
+        assert_initialized_main_thread!();
+        unsafe {
+            let mut msg = std::ptr::null_mut();
+            let res = ffi::gst_rtsp_message_new_request(
+                &mut msg,
+                method.into_glib(),
+                uri.to_glib_none().0,
+            );
+            debug_assert_eq!(res, ffi::GST_RTSP_OK);
+            from_glib_full(msg)
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_message_new_response")]
+    pub fn new_response(code: RTSPStatusCode, request: Option<&RTSPMessage>) -> RTSPMessage {
+        assert_initialized_main_thread!();
+        unsafe {
+            let mut msg = std::ptr::null_mut();
+            let res = ffi::gst_rtsp_message_new_response(
+                &mut msg,
+                code.into_glib(),
+                ffi::gst_rtsp_status_as_text(code.into_glib()),
+                request.to_glib_none().0,
+            );
+            debug_assert_eq!(res, ffi::GST_RTSP_OK);
+            from_glib_full(msg)
+        }
+    }
+
+    pub fn request_builder(method: RTSPMethod, uri: &str) -> RTSPRequestBuilder {
+        assert_initialized_main_thread!();
+        RTSPRequestBuilder::new(method, uri)
+    }
+
+    pub fn response_builder(code: RTSPStatusCode) -> RTSPResponseBuilder {
+        assert_initialized_main_thread!();
+        RTSPResponseBuilder::new(code)
+    }
+
+    #[doc(alias = "get_type")]
+    #[doc(alias = "gst_rtsp_message_get_type")]
+    pub fn type_(&self) -> RTSPMsgType {
+        unsafe {
+            from_glib(ffi::gst_rtsp_message_get_type(mut_override(
+                self.to_glib_none().0,
+            )))
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_message_parse_request")]
+    pub fn method(&self) -> Option<RTSPMethod> {
+        unsafe {
+            let mut method = std::mem::MaybeUninit::uninit();
+            let res = ffi::gst_rtsp_message_parse_request(
+                mut_override(self.to_glib_none().0),
+                method.as_mut_ptr(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            );
+            if res == ffi::GST_RTSP_OK {
+                Some(from_glib(method.assume_init()))
+            } else {
+                None
+            }
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_message_parse_request")]
+    pub fn uri(&self) -> Option<&str> {
+        unsafe {
+            let mut uri = std::ptr::null();
+            let res = ffi::gst_rtsp_message_parse_request(
+                mut_override(self.to_glib_none().0),
+                std::ptr::null_mut(),
+                &mut uri,
+                std::ptr::null_mut(),
+            );
+            if res != ffi::GST_RTSP_OK || uri.is_null() {
+                None
+            } else {
+                CStr::from_ptr(uri).to_str().ok()
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Replaces the request URI of a request message, keeping all headers.
+    pub fn set_uri(&mut self, uri: &str) -> bool {
+        if self.type_() != RTSPMsgType::Request {
+            return false;
+        }
+
+        unsafe {
+            let ptr = self.to_glib_none_mut().0;
+            glib::ffi::g_free((*ptr).type_data.request.uri as glib::ffi::gpointer);
+            (*ptr).type_data.request.uri = uri.to_glib_full();
+        }
+
+        true
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the protocol version of a request or response message.
+    pub fn version(&self) -> Option<RTSPVersion> {
+        unsafe {
+            let ptr = mut_override(self.to_glib_none().0);
+            let mut version = std::mem::MaybeUninit::uninit();
+            let res = match self.type_() {
+                RTSPMsgType::Request | RTSPMsgType::HttpRequest => {
+                    ffi::gst_rtsp_message_parse_request(
+                        ptr,
+                        std::ptr::null_mut(),
+                        std::ptr::null_mut(),
+                        version.as_mut_ptr(),
+                    )
+                }
+                RTSPMsgType::Response | RTSPMsgType::HttpResponse => {
+                    ffi::gst_rtsp_message_parse_response(
+                        ptr,
+                        std::ptr::null_mut(),
+                        std::ptr::null_mut(),
+                        version.as_mut_ptr(),
+                    )
+                }
+                _ => return None,
+            };
+            if res == ffi::GST_RTSP_OK {
+                Some(from_glib(version.assume_init()))
+            } else {
+                None
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the protocol version of a request or response message, e.g. to
+    /// send `RTSP/2.0` messages.
+    ///
+    /// Returns `false` for other message types. Note that the methods added by
+    /// RTSP 2.0, e.g. `PLAY_NOTIFY`, have no [`RTSPMethod`] representation.
+    pub fn set_version(&mut self, version: RTSPVersion) -> bool {
+        let type_ = self.type_();
+        unsafe {
+            let ptr = self.to_glib_none_mut().0;
+            match type_ {
+                RTSPMsgType::Request | RTSPMsgType::HttpRequest => {
+                    (*ptr).type_data.request.version = version.into_glib();
+                }
+                RTSPMsgType::Response | RTSPMsgType::HttpResponse => {
+                    (*ptr).type_data.response.version = version.into_glib();
+                }
+                _ => return false,
+            }
+        }
+
+        true
+    }
+
+    #[doc(alias = "gst_rtsp_message_parse_response")]
+    pub fn status_code(&self) -> Option<RTSPStatusCode> {
+        unsafe {
+            let mut code = std::mem::MaybeUninit::uninit();
+            let res = ffi::gst_rtsp_message_parse_response(
+                mut_override(self.to_glib_none().0),
+                code.as_mut_ptr(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            );
+            if res == ffi::GST_RTSP_OK {
+                Some(from_glib(code.assume_init()))
+            } else {
+                None
+            }
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_message_parse_response")]
+    pub fn reason(&self) -> Option<&str> {
+        unsafe {
+            let mut reason = std::ptr::null();
+            let res = ffi::gst_rtsp_message_parse_response(
+                mut_override(self.to_glib_none().0),
+                std::ptr::null_mut(),
+                &mut reason,
+                std::ptr::null_mut(),
+            );
+            if res != ffi::GST_RTSP_OK || reason.is_null() {
+                None
+            } else {
+                CStr::from_ptr(reason).to_str().ok()
+            }
+        }
+    }
+
+    #[doc(alias = "get_header")]
+    #[doc(alias = "gst_rtsp_message_get_header")]
+    pub fn header(&self, field: RTSPHeaderField, index: u32) -> Option<&str> {
+        unsafe {
+            let mut value = std::ptr::null_mut();
+            let res = ffi::gst_rtsp_message_get_header(
+                self.to_glib_none().0,
+                field.into_glib(),
+                &mut value,
+                index as i32,
+            );
+            if res != ffi::GST_RTSP_OK || value.is_null() {
+                None
+            } else {
+                CStr::from_ptr(value).to_str().ok()
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes the header at `index`, or all instances of the header if `index`
+    /// is `None`.
+    #[doc(alias = "gst_rtsp_message_remove_header")]
+    pub fn remove_header(&mut self, field: RTSPHeaderField, index: Option<u32>) {
+        unsafe {
+            ffi::gst_rtsp_message_remove_header(
+                self.to_glib_none_mut().0,
+                field.into_glib(),
+                index.map_or(-1, |index| index as i32),
+            );
+        }
+    }
+
+    #[doc(alias = "get_header_by_name")]
+    #[doc(alias = "gst_rtsp_message_get_header_by_name")]
+    pub fn header_by_name(&self, name: &str, index: u32) -> Option<&str> {
+        unsafe {
+            let mut value = std::ptr::null_mut();
+            let res = ffi::gst_rtsp_message_get_header_by_name(
+                mut_override(self.to_glib_none().0),
+                name.to_glib_none().0,
+                &mut value,
+                index as i32,
+            );
+            if res != ffi::GST_RTSP_OK || value.is_null() {
+                None
+            } else {
+                CStr::from_ptr(value).to_str().ok()
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds a header by name, e.g. a vendor extension header that has no
+    /// corresponding [`RTSPHeaderField`].
+    #[doc(alias = "gst_rtsp_message_add_header_by_name")]
+    pub fn add_header_by_name(&mut self, name: &str, value: &str) {
+        unsafe {
+            ffi::gst_rtsp_message_add_header_by_name(
+                self.to_glib_none_mut().0,
+                name.to_glib_none().0,
+                value.to_glib_none().0,
+            );
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes the header named `name` at `index`, or all instances of it if
+    /// `index` is `None`.
+    #[doc(alias = "gst_rtsp_message_remove_header_by_name")]
+    pub fn remove_header_by_name(&mut self, name: &str, index: Option<u32>) {
+        unsafe {
+            ffi::gst_rtsp_message_remove_header_by_name(
+                self.to_glib_none_mut().0,
+                name.to_glib_none().0,
+                index.map_or(-1, |index| index as i32),
+            );
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses the `RTP-Info` header of a PLAY response. Returns an empty list
+    /// if the header is missing.
+    pub fn rtp_info(&self) -> Result<Vec<RTSPRtpInfo>, glib::BoolError> {
+        match self.header(RTSPHeaderField::RtpInfo, 0) {
+            Some(header) => RTSPRtpInfo::parse_list(header),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the playback rate requested or granted through the `Scale`
+    /// header. Negative values mean reverse playback.
+    pub fn scale(&self) -> Option<f64> {
+        self.header(RTSPHeaderField::Scale, 0)
+            .and_then(|scale| scale.trim().parse::<f64>().ok())
+            .filter(|scale| scale.is_finite() && *scale != 0.0)
+    }
+
+    pub fn set_scale(&mut self, scale: f64) -> Result<(), glib::BoolError> {
+        if !scale.is_finite() || scale == 0.0 {
+            return Err(glib::bool_error!("Invalid scale {}", scale));
+        }
+
+        self.remove_header(RTSPHeaderField::Scale, None);
+        self.add_header(RTSPHeaderField::Scale, &scale.to_string());
+        Ok(())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the delivery speed requested or granted through the `Speed`
+    /// header.
+    pub fn speed(&self) -> Option<f64> {
+        self.header(RTSPHeaderField::Speed, 0)
+            .and_then(|speed| speed.trim().parse::<f64>().ok())
+            .filter(|speed| speed.is_finite() && *speed > 0.0)
+    }
+
+    pub fn set_speed(&mut self, speed: f64) -> Result<(), glib::BoolError> {
+        if !speed.is_finite() || speed <= 0.0 {
+            return Err(glib::bool_error!("Invalid speed {}", speed));
+        }
+
+        self.remove_header(RTSPHeaderField::Speed, None);
+        self.add_header(RTSPHeaderField::Speed, &speed.to_string());
+        Ok(())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Checks the structural requirements of RFC 2326 on requests and
+    /// responses and returns all violations found.
+    ///
+    /// An empty list means that the message is well-formed.
+    pub fn validate(&self) -> Vec<RTSPMessageViolation> {
+        let mut violations = Vec::new();
+
+        let type_ = self.type_();
+        if type_ != RTSPMsgType::Request && type_ != RTSPMsgType::Response {
+            return violations;
+        }
+
+        if self.header(RTSPHeaderField::Cseq, 0).is_none() {
+            violations.push(RTSPMessageViolation::MissingCSeq);
+        }
+
+        let body_size = self.body().len();
+        if let Some(content_length) = self.header(RTSPHeaderField::ContentLength, 0) {
+            let content_length = content_length.trim().parse::<usize>().ok();
+            if content_length != Some(body_size) {
+                violations.push(RTSPMessageViolation::ContentLengthMismatch {
+                    content_length,
+                    body_size,
+                });
+            }
+        }
+
+        if let Some(method) = self.method() {
+            let stateful =
+                RTSPMethod::PLAY | RTSPMethod::PAUSE | RTSPMethod::RECORD | RTSPMethod::TEARDOWN;
+            if stateful.contains(method) && self.header(RTSPHeaderField::Session, 0).is_none() {
+                violations.push(RTSPMessageViolation::MissingSession(method));
+            }
+        }
+
+        let mut duplicates = Vec::new();
+        for (field, _) in self.known_headers() {
+            if duplicates.contains(&field) {
+                continue;
+            }
+            if !field.allows_multiple() && self.header(field, 1).is_some() {
+                duplicates.push(field);
+                violations.push(RTSPMessageViolation::DuplicateHeader(field));
+            }
+        }
+
+        violations
+    }
+
     #[doc(alias = "gst_rtsp_message_add_header")]
     pub fn add_header(&self, header: RTSPHeaderField, value: &str) {
         let ptr = self.to_glib_none().0;
@@ -32,6 +435,154 @@ impl RTSPMessage {
         }
     }
 
+    #[doc(alias = "get_body")]
+    #[doc(alias = "gst_rtsp_message_get_body")]
+    pub fn body(&self) -> &[u8] {
+        unsafe {
+            let mut data = std::ptr::null_mut();
+            let mut size = 0;
+            let res = ffi::gst_rtsp_message_get_body(self.to_glib_none().0, &mut data, &mut size);
+            if res != ffi::GST_RTSP_OK || data.is_null() || size == 0 {
+                &[]
+            } else {
+                std::slice::from_raw_parts(data, size as usize)
+            }
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_message_set_body")]
+    pub fn set_body(&mut self, body: &[u8]) {
+        unsafe {
+            let res = ffi::gst_rtsp_message_set_body(
+                self.to_glib_none_mut().0,
+                body.as_ptr(),
+                body.len() as u32,
+            );
+            debug_assert_eq!(res, ffi::GST_RTSP_OK);
+        }
+    }
+
+    #[cfg(feature = "v1_16")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_16")))]
+    #[doc(alias = "gst_rtsp_message_has_body_buffer")]
+    pub fn has_body_buffer(&self) -> bool {
+        unsafe { from_glib(ffi::gst_rtsp_message_has_body_buffer(self.to_glib_none().0)) }
+    }
+
+    #[cfg(feature = "v1_16")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_16")))]
+    #[doc(alias = "get_body_buffer")]
+    #[doc(alias = "gst_rtsp_message_get_body_buffer")]
+    pub fn body_buffer(&self) -> Option<gst::Buffer> {
+        unsafe {
+            let mut buffer = std::ptr::null_mut();
+            let res = ffi::gst_rtsp_message_get_body_buffer(self.to_glib_none().0, &mut buffer);
+            if res != ffi::GST_RTSP_OK {
+                None
+            } else {
+                from_glib_none(buffer)
+            }
+        }
+    }
+
+    #[cfg(feature = "v1_16")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_16")))]
+    #[doc(alias = "gst_rtsp_message_set_body_buffer")]
+    pub fn set_body_buffer(&mut self, buffer: &gst::Buffer) {
+        unsafe {
+            let res = ffi::gst_rtsp_message_set_body_buffer(
+                self.to_glib_none_mut().0,
+                buffer.to_glib_none().0,
+            );
+            debug_assert_eq!(res, ffi::GST_RTSP_OK);
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the body of the message to `buffer` without copying its memory.
+    #[cfg(feature = "v1_16")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_16")))]
+    #[doc(alias = "gst_rtsp_message_take_body_buffer")]
+    pub fn take_body_buffer(&mut self, buffer: gst::Buffer) {
+        unsafe {
+            let res = ffi::gst_rtsp_message_take_body_buffer(
+                self.to_glib_none_mut().0,
+                buffer.into_glib_ptr(),
+            );
+            debug_assert_eq!(res, ffi::GST_RTSP_OK);
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes the body from the message and returns it without copying.
+    #[cfg(feature = "v1_16")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_16")))]
+    #[doc(alias = "gst_rtsp_message_steal_body_buffer")]
+    pub fn steal_body_buffer(&mut self) -> Option<gst::Buffer> {
+        unsafe {
+            let mut buffer = std::ptr::null_mut();
+            let res =
+                ffi::gst_rtsp_message_steal_body_buffer(self.to_glib_none_mut().0, &mut buffer);
+            if res != ffi::GST_RTSP_OK {
+                None
+            } else {
+                from_glib_full(buffer)
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Appends `data` to the body of the message.
+    ///
+    /// With GStreamer 1.16 or newer each chunk is stored as separate memory
+    /// of the body buffer, so that no contiguous copy of the whole body is
+    /// needed when sending it.
+    pub fn append_body(&mut self, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+
+        #[cfg(feature = "v1_16")]
+        {
+            let buffer = if self.has_body_buffer() {
+                self.steal_body_buffer()
+            } else {
+                let body = self.body();
+                if body.is_empty() {
+                    None
+                } else {
+                    Some(gst::Buffer::from_slice(body.to_vec()))
+                }
+            };
+
+            let mut buffer = buffer.unwrap_or_default();
+            buffer
+                .make_mut()
+                .append_memory(gst::Memory::from_slice(data.to_vec()));
+            self.take_body_buffer(buffer);
+        }
+
+        #[cfg(not(feature = "v1_16"))]
+        unsafe {
+            let ptr = self.to_glib_none_mut().0;
+            let mut body = std::ptr::null_mut();
+            let mut size = 0;
+            ffi::gst_rtsp_message_steal_body(ptr, &mut body, &mut size);
+
+            let new_size = size as usize + data.len();
+            let body = glib::ffi::g_realloc(body as glib::ffi::gpointer, new_size) as *mut u8;
+            std::ptr::copy_nonoverlapping(data.as_ptr(), body.add(size as usize), data.len());
+            ffi::gst_rtsp_message_take_body(ptr, body, new_size as u32);
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a [`Write`](std::io::Write) sink that appends to the body of
+    /// the message.
+    pub fn body_writer(&mut self) -> RTSPBodyWriter<'_> {
+        RTSPBodyWriter(self)
+    }
+
     #[doc(alias = "gst_rtsp_message_init_response")]
     pub fn init_response(&self, code: RTSPStatusCode, request: Option<&RTSPMessage>) {
         let ptr = self.to_glib_none().0;
@@ -55,4 +606,724 @@ impl RTSPMessage {
             FromGlibPtrContainer::from_glib_full(credentials)
         }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses the challenges of all `WWW-Authenticate` headers of the message.
+    #[doc(alias = "gst_rtsp_message_parse_auth_credentials")]
+    pub fn parse_www_authenticate(&self) -> Vec<RTSPAuthCredential> {
+        RTSPAuthCredential::parse(self, RTSPHeaderField::WwwAuthenticate)
+    }
+}
+
+impl RTSPMessage {
+    // Headers with a known header field, in the order of the field values.
+    // Custom headers are not included as the C API provides no way to
+    // enumerate them.
+    fn known_headers(&self) -> Vec<(RTSPHeaderField, &str)> {
+        let mut headers = Vec::new();
+        for field in ffi::GST_RTSP_HDR_ACCEPT..ffi::GST_RTSP_HDR_LAST {
+            let field = unsafe { from_glib(field) };
+            let mut index = 0;
+            while let Some(value) = self.header(field, index) {
+                headers.push((field, value));
+                index += 1;
+            }
+        }
+        headers
+    }
+
+    fn start_line(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ptr = mut_override(self.to_glib_none().0);
+        let type_ = self.type_();
+
+        unsafe {
+            match type_ {
+                RTSPMsgType::Request | RTSPMsgType::HttpRequest => {
+                    let mut method = std::mem::MaybeUninit::uninit();
+                    let mut uri = std::ptr::null();
+                    let mut version = std::mem::MaybeUninit::uninit();
+                    ffi::gst_rtsp_message_parse_request(
+                        ptr,
+                        method.as_mut_ptr(),
+                        &mut uri,
+                        version.as_mut_ptr(),
+                    );
+                    let method = ffi::gst_rtsp_method_as_text(method.assume_init());
+                    write!(
+                        f,
+                        "{} {} {}/{}",
+                        if method.is_null() {
+                            "UNKNOWN".into()
+                        } else {
+                            CStr::from_ptr(method).to_string_lossy()
+                        },
+                        if uri.is_null() {
+                            "".into()
+                        } else {
+                            CStr::from_ptr(uri).to_string_lossy()
+                        },
+                        if type_ == RTSPMsgType::Request {
+                            "RTSP"
+                        } else {
+                            "HTTP"
+                        },
+                        from_glib::<_, RTSPVersion>(version.assume_init()),
+                    )
+                }
+                RTSPMsgType::Response | RTSPMsgType::HttpResponse => {
+                    let mut code = std::mem::MaybeUninit::uninit();
+                    let mut reason = std::ptr::null();
+                    let mut version = std::mem::MaybeUninit::uninit();
+                    ffi::gst_rtsp_message_parse_response(
+                        ptr,
+                        code.as_mut_ptr(),
+                        &mut reason,
+                        version.as_mut_ptr(),
+                    );
+                    write!(
+                        f,
+                        "{}/{} {} {}",
+                        if type_ == RTSPMsgType::Response {
+                            "RTSP"
+                        } else {
+                            "HTTP"
+                        },
+                        from_glib::<_, RTSPVersion>(version.assume_init()),
+                        code.assume_init(),
+                        if reason.is_null() {
+                            "".into()
+                        } else {
+                            CStr::from_ptr(reason).to_string_lossy()
+                        },
+                    )
+                }
+                RTSPMsgType::Data => {
+                    let mut channel = 0;
+                    ffi::gst_rtsp_message_parse_data(ptr, &mut channel);
+                    write!(f, "${channel}")
+                }
+                _ => f.write_str("INVALID"),
+            }
+        }
+    }
+}
+
+fn header_name(field: RTSPHeaderField) -> &'static str {
+    unsafe {
+        let name = ffi::gst_rtsp_header_as_text(field.into_glib());
+        if name.is_null() {
+            "?"
+        } else {
+            CStr::from_ptr(name).to_str().unwrap_or("?")
+        }
+    }
+}
+
+fn header_value(field: RTSPHeaderField, value: &str) -> &str {
+    if field == RTSPHeaderField::Authorization {
+        REDACTED
+    } else {
+        value
+    }
+}
+
+const REDACTED: &str = "<redacted>";
+
+// There is no header field for `Proxy-Authorization`, so it is stored as a
+// custom header and has to be looked up by name.
+const PROXY_AUTHORIZATION: &str = "Proxy-Authorization";
+
+impl RTSPMessage {
+    // Headers as shown by `Debug` and `Display`, with credentials redacted.
+    fn printed_headers(&self) -> Vec<(&'static str, &str)> {
+        let mut headers = self
+            .known_headers()
+            .into_iter()
+            .map(|(field, value)| (header_name(field), header_value(field, value)))
+            .collect::<Vec<_>>();
+
+        let mut index = 0;
+        while self.header_by_name(PROXY_AUTHORIZATION, index).is_some() {
+            headers.push((PROXY_AUTHORIZATION, REDACTED));
+            index += 1;
+        }
+
+        headers
+    }
+}
+
+impl fmt::Debug for RTSPMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        struct Headers<'a>(&'a RTSPMessage);
+
+        impl fmt::Debug for Headers<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_list().entries(self.0.printed_headers()).finish()
+            }
+        }
+
+        struct StartLine<'a>(&'a RTSPMessage);
+
+        impl fmt::Debug for StartLine<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("\"")?;
+                self.0.start_line(f)?;
+                f.write_str("\"")
+            }
+        }
+
+        f.debug_struct("RTSPMessage")
+            .field("type", &self.type_())
+            .field("start-line", &StartLine(self))
+            .field("headers", &Headers(self))
+            .field("body-size", &self.body().len())
+            .finish()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Formats the message similar to its wire format, with the values of the
+/// `Authorization` and `Proxy-Authorization` headers redacted and the body
+/// replaced by its size.
+impl fmt::Display for RTSPMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.start_line(f)?;
+        writeln!(f)?;
+
+        for (name, value) in self.printed_headers() {
+            writeln!(f, "{name}: {value}")?;
+        }
+
+        let body_size = self.body().len();
+        if body_size > 0 {
+            write!(f, "\n[{body_size} bytes body]")?;
+        }
+
+        Ok(())
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Appends everything written to the body of an [`RTSPMessage`].
+#[derive(Debug)]
+pub struct RTSPBodyWriter<'a>(&'a mut RTSPMessage);
+
+impl io::Write for RTSPBodyWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.append_body(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A structural problem of an [`RTSPMessage`] found by
+/// [`RTSPMessage::validate()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RTSPMessageViolation {
+    // rustdoc-stripper-ignore-next
+    /// The `CSeq` header is missing.
+    MissingCSeq,
+    // rustdoc-stripper-ignore-next
+    /// The `Content-Length` header is invalid or does not match the body.
+    ContentLengthMismatch {
+        content_length: Option<usize>,
+        body_size: usize,
+    },
+    // rustdoc-stripper-ignore-next
+    /// The `Session` header is missing on a request for a method that
+    /// operates on a session.
+    MissingSession(RTSPMethod),
+    // rustdoc-stripper-ignore-next
+    /// A header that must appear at most once appears multiple times.
+    DuplicateHeader(RTSPHeaderField),
+}
+
+impl fmt::Display for RTSPMessageViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RTSPMessageViolation::MissingCSeq => f.write_str("Missing CSeq header"),
+            RTSPMessageViolation::ContentLengthMismatch {
+                content_length: Some(content_length),
+                body_size,
+            } => write!(
+                f,
+                "Content-Length {content_length} does not match body size {body_size}"
+            ),
+            RTSPMessageViolation::ContentLengthMismatch {
+                content_length: None,
+                ..
+            } => f.write_str("Invalid Content-Length header"),
+            RTSPMessageViolation::MissingSession(method) => {
+                write!(f, "Missing Session header for {method:?} request")
+            }
+            RTSPMessageViolation::DuplicateHeader(field) => {
+                write!(f, "Duplicate {} header", header_name(*field))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Header {
+    Field(RTSPHeaderField),
+    Name(String),
+}
+
+#[derive(Debug, Clone, Default)]
+struct MessageContent {
+    version: Option<RTSPVersion>,
+    headers: Vec<(Header, String)>,
+    body: Option<Vec<u8>>,
+}
+
+impl MessageContent {
+    fn apply(self, msg: &mut RTSPMessage) {
+        if let Some(version) = self.version {
+            msg.set_version(version);
+        }
+
+        for (header, value) in self.headers {
+            match header {
+                Header::Field(field) => msg.add_header(field, &value),
+                Header::Name(name) => msg.add_header_by_name(&name, &value),
+            }
+        }
+
+        if let Some(body) = self.body {
+            msg.set_body(&body);
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for RTSP request messages.
+#[derive(Debug, Clone)]
+#[must_use = "The builder must be built to be used"]
+pub struct RTSPRequestBuilder {
+    method: RTSPMethod,
+    uri: String,
+    content: MessageContent,
+}
+
+impl RTSPRequestBuilder {
+    fn new(method: RTSPMethod, uri: &str) -> Self {
+        skip_assert_initialized!();
+        Self {
+            method,
+            uri: uri.to_owned(),
+            content: MessageContent::default(),
+        }
+    }
+
+    pub fn header(mut self, field: RTSPHeaderField, value: &str) -> Self {
+        self.content
+            .headers
+            .push((Header::Field(field), value.to_owned()));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds a header that has no corresponding [`RTSPHeaderField`], e.g. a
+    /// vendor extension header.
+    pub fn custom_header(mut self, name: &str, value: &str) -> Self {
+        self.content
+            .headers
+            .push((Header::Name(name.to_owned()), value.to_owned()));
+        self
+    }
+
+    pub fn cseq(self, cseq: u32) -> Self {
+        self.header(RTSPHeaderField::Cseq, &cseq.to_string())
+    }
+
+    pub fn session(self, session_id: &str) -> Self {
+        self.header(RTSPHeaderField::Session, session_id)
+    }
+
+    pub fn range(self, range: &RTSPTimeRange) -> Self {
+        self.header(RTSPHeaderField::Range, &range.to_string())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the `Transport` header of a SETUP request to all alternatives of
+    /// `offer`.
+    pub fn transports(self, offer: &RTSPTransportOffer) -> Self {
+        self.header(RTSPHeaderField::Transport, &offer.to_string())
+    }
+
+    pub fn key_mgmt(self, key_mgmt: &RTSPKeyMgmt) -> Self {
+        self.header(RTSPHeaderField::Keymgmt, &key_mgmt.to_string())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Requires the ONVIF replay extensions (`Require: onvif-replay`).
+    pub fn onvif_replay(self) -> Self {
+        self.header(RTSPHeaderField::Require, ONVIF_REPLAY)
+    }
+
+    pub fn rate_control(self, rate_control: bool) -> Self {
+        self.header(
+            RTSPHeaderField::RateControl,
+            if rate_control { "yes" } else { "no" },
+        )
+    }
+
+    pub fn frames(self, frames: OnvifFrames) -> Self {
+        self.header(RTSPHeaderField::Frames, &frames.to_string())
+    }
+
+    pub fn immediate(self, immediate: bool) -> Self {
+        self.custom_header("Immediate", if immediate { "yes" } else { "no" })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds the ONVIF headers of `options` that differ from their default.
+    pub fn onvif_replay_options(mut self, options: &OnvifReplayOptions) -> Self {
+        if !options.has_rate_control() {
+            self = self.rate_control(false);
+        }
+        if let Some(frames) = options.frames_filter() {
+            self = self.frames(frames);
+        }
+        if options.is_immediate() {
+            self = self.immediate(true);
+        }
+        self
+    }
+
+    pub fn version(mut self, version: RTSPVersion) -> Self {
+        self.content.version = Some(version);
+        self
+    }
+
+    pub fn body(mut self, body: &[u8]) -> Self {
+        self.content.body = Some(body.to_vec());
+        self
+    }
+
+    pub fn build(self) -> RTSPMessage {
+        let mut msg = RTSPMessage::new_request(self.method, &self.uri);
+        self.content.apply(&mut msg);
+        msg
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for RTSP response messages.
+///
+/// If a request is given, the `CSeq` and `Session` headers are copied from it.
+#[derive(Debug, Clone)]
+#[must_use = "The builder must be built to be used"]
+pub struct RTSPResponseBuilder {
+    code: RTSPStatusCode,
+    reason: Option<String>,
+    request: Option<RTSPMessage>,
+    content: MessageContent,
+}
+
+impl RTSPResponseBuilder {
+    fn new(code: RTSPStatusCode) -> Self {
+        skip_assert_initialized!();
+        Self {
+            code,
+            reason: None,
+            request: None,
+            content: MessageContent::default(),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Overrides the default reason phrase of the status code.
+    pub fn reason(mut self, reason: &str) -> Self {
+        self.reason = Some(reason.to_owned());
+        self
+    }
+
+    pub fn request(mut self, request: &RTSPMessage) -> Self {
+        self.request = Some(request.clone());
+        self
+    }
+
+    pub fn header(mut self, field: RTSPHeaderField, value: &str) -> Self {
+        self.content
+            .headers
+            .push((Header::Field(field), value.to_owned()));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds a header that has no corresponding [`RTSPHeaderField`], e.g. a
+    /// vendor extension header.
+    pub fn custom_header(mut self, name: &str, value: &str) -> Self {
+        self.content
+            .headers
+            .push((Header::Name(name.to_owned()), value.to_owned()));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the `Public` header of an OPTIONS response to `methods`.
+    pub fn public(self, methods: RTSPMethod) -> Self {
+        self.header(RTSPHeaderField::Public, &methods.options_as_text())
+    }
+
+    pub fn rtp_info(self, infos: &[RTSPRtpInfo]) -> Self {
+        self.header(RTSPHeaderField::RtpInfo, &RTSPRtpInfo::format_list(infos))
+    }
+
+    pub fn version(mut self, version: RTSPVersion) -> Self {
+        self.content.version = Some(version);
+        self
+    }
+
+    pub fn body(mut self, body: &[u8]) -> Self {
+        self.content.body = Some(body.to_vec());
+        self
+    }
+
+    pub fn build(self) -> RTSPMessage {
+        let reason = self.reason.to_glib_none();
+        let mut msg = unsafe {
+            let mut msg = std::ptr::null_mut();
+            let res = ffi::gst_rtsp_message_new_response(
+                &mut msg,
+                self.code.into_glib(),
+                if reason.0.is_null() {
+                    ffi::gst_rtsp_status_as_text(self.code.into_glib())
+                } else {
+                    reason.0
+                },
+                self.request.to_glib_none().0,
+            );
+            debug_assert_eq!(res, ffi::GST_RTSP_OK);
+            from_glib_full::<_, RTSPMessage>(msg)
+        };
+        self.content.apply(&mut msg);
+        msg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_builder() {
+        gst::init().unwrap();
+
+        let msg = RTSPMessage::request_builder(RTSPMethod::SET_PARAMETER, "rtsp://example.com/")
+            .cseq(3)
+            .header(RTSPHeaderField::ContentType, "text/parameters")
+            .custom_header("X-Vendor", "1")
+            .body(b"volume: 1\r\n")
+            .build();
+
+        assert_eq!(msg.type_(), RTSPMsgType::Request);
+        assert_eq!(msg.method(), Some(RTSPMethod::SET_PARAMETER));
+        assert_eq!(msg.uri(), Some("rtsp://example.com/"));
+        assert_eq!(msg.header(RTSPHeaderField::Cseq, 0), Some("3"));
+        assert_eq!(
+            msg.header(RTSPHeaderField::ContentType, 0),
+            Some("text/parameters")
+        );
+        assert_eq!(msg.header_by_name("X-Vendor", 0), Some("1"));
+        assert_eq!(msg.header_by_name("x-vendor", 0), Some("1"));
+        assert_eq!(msg.header_by_name("CSeq", 0), Some("3"));
+        assert_eq!(msg.body(), b"volume: 1\r\n");
+    }
+
+    #[test]
+    fn test_onvif_replay() {
+        gst::init().unwrap();
+
+        let options = OnvifReplayOptions::new()
+            .rate_control(false)
+            .frames(OnvifFrames::Intra { interval: None })
+            .immediate(true);
+        let msg = RTSPMessage::request_builder(RTSPMethod::PLAY, "rtsp://example.com/")
+            .onvif_replay()
+            .onvif_replay_options(&options)
+            .build();
+
+        assert_eq!(
+            msg.header(RTSPHeaderField::Require, 0),
+            Some("onvif-replay")
+        );
+        assert_eq!(msg.header(RTSPHeaderField::RateControl, 0), Some("no"));
+        assert_eq!(msg.header(RTSPHeaderField::Frames, 0), Some("intra"));
+        assert_eq!(msg.header_by_name("Immediate", 0), Some("yes"));
+
+        let msg = RTSPMessage::request_builder(RTSPMethod::PLAY, "rtsp://example.com/")
+            .onvif_replay_options(&OnvifReplayOptions::default())
+            .build();
+        assert_eq!(msg.header(RTSPHeaderField::RateControl, 0), None);
+        assert_eq!(msg.header_by_name("Immediate", 0), None);
+    }
+
+    #[test]
+    fn test_response_builder() {
+        gst::init().unwrap();
+
+        let request = RTSPMessage::request_builder(RTSPMethod::OPTIONS, "*")
+            .cseq(7)
+            .build();
+        let msg = RTSPMessage::response_builder(RTSPStatusCode::Ok)
+            .request(&request)
+            .reason("Fine")
+            .public(RTSPMethod::OPTIONS | RTSPMethod::DESCRIBE)
+            .build();
+
+        assert_eq!(msg.type_(), RTSPMsgType::Response);
+        assert_eq!(msg.status_code(), Some(RTSPStatusCode::Ok));
+        assert_eq!(msg.reason(), Some("Fine"));
+        assert_eq!(msg.header(RTSPHeaderField::Cseq, 0), Some("7"));
+        assert_eq!(
+            msg.header(RTSPHeaderField::Public, 0),
+            Some("DESCRIBE, OPTIONS")
+        );
+    }
+
+    #[test]
+    fn test_scale_speed() {
+        gst::init().unwrap();
+
+        let mut msg = RTSPMessage::new_request(RTSPMethod::PLAY, "rtsp://example.com/");
+        assert_eq!(msg.scale(), None);
+        assert_eq!(msg.speed(), None);
+
+        msg.set_scale(-2.0).unwrap();
+        msg.set_scale(-4.0).unwrap();
+        msg.set_speed(1.5).unwrap();
+        assert_eq!(msg.header(RTSPHeaderField::Scale, 0), Some("-4"));
+        assert_eq!(msg.header(RTSPHeaderField::Scale, 1), None);
+        assert_eq!(msg.scale(), Some(-4.0));
+        assert_eq!(msg.speed(), Some(1.5));
+
+        assert!(msg.set_scale(0.0).is_err());
+        assert!(msg.set_scale(f64::NAN).is_err());
+        assert!(msg.set_speed(-1.0).is_err());
+        assert!(msg.set_speed(f64::INFINITY).is_err());
+        assert_eq!(msg.scale(), Some(-4.0));
+    }
+
+    #[test]
+    fn test_validate() {
+        gst::init().unwrap();
+
+        let msg = RTSPMessage::request_builder(RTSPMethod::DESCRIBE, "rtsp://example.com/")
+            .cseq(1)
+            .build();
+        assert_eq!(msg.validate(), []);
+
+        let msg = RTSPMessage::request_builder(RTSPMethod::PLAY, "rtsp://example.com/")
+            .header(RTSPHeaderField::ContentLength, "4")
+            .header(RTSPHeaderField::Range, "npt=0-")
+            .header(RTSPHeaderField::Range, "npt=10-")
+            .body(b"foo")
+            .build();
+        assert_eq!(
+            msg.validate(),
+            [
+                RTSPMessageViolation::MissingCSeq,
+                RTSPMessageViolation::ContentLengthMismatch {
+                    content_length: Some(4),
+                    body_size: 3,
+                },
+                RTSPMessageViolation::MissingSession(RTSPMethod::PLAY),
+                RTSPMessageViolation::DuplicateHeader(RTSPHeaderField::Range),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_append_body() {
+        use std::io::Write;
+
+        gst::init().unwrap();
+
+        let mut msg =
+            RTSPMessage::request_builder(RTSPMethod::SET_PARAMETER, "rtsp://example.com/")
+                .body(b"a: 1\r\n")
+                .build();
+        msg.append_body(b"b: 2\r\n");
+        msg.append_body(b"");
+        write!(msg.body_writer(), "c: {}\r\n", 3).unwrap();
+
+        assert_eq!(msg.body(), b"a: 1\r\nb: 2\r\nc: 3\r\n");
+    }
+
+    #[test]
+    fn test_version() {
+        gst::init().unwrap();
+
+        let msg = RTSPMessage::request_builder(RTSPMethod::OPTIONS, "rtsp://example.com/")
+            .version(RTSPVersion::_20)
+            .build();
+        assert_eq!(msg.version(), Some(RTSPVersion::_20));
+        assert!(msg
+            .to_string()
+            .starts_with("OPTIONS rtsp://example.com/ RTSP/2.0\n"));
+
+        let mut msg = RTSPMessage::response_builder(RTSPStatusCode::Ok)
+            .request(&msg)
+            .build();
+        assert!(msg.set_version(RTSPVersion::_20));
+        assert_eq!(msg.version(), Some(RTSPVersion::_20));
+        assert!(msg.to_string().starts_with("RTSP/2.0 200 OK\n"));
+
+        assert_eq!(RTSPMessage::new().version(), None);
+    }
+
+    #[test]
+    fn test_clone() {
+        gst::init().unwrap();
+
+        let mut msg =
+            RTSPMessage::request_builder(RTSPMethod::SET_PARAMETER, "rtsp://example.com/")
+                .cseq(1)
+                .body(b"foo")
+                .build();
+        let copy = msg.clone();
+
+        msg.remove_header(RTSPHeaderField::Cseq, None);
+        msg.add_header(RTSPHeaderField::Cseq, "2");
+        msg.set_body(b"bar");
+        assert!(msg.set_uri("rtsp://example.com/other"));
+
+        assert_eq!(copy.header(RTSPHeaderField::Cseq, 0), Some("1"));
+        assert_eq!(copy.body(), b"foo");
+        assert_eq!(copy.uri(), Some("rtsp://example.com/"));
+        assert_eq!(msg.header(RTSPHeaderField::Cseq, 0), Some("2"));
+        assert_eq!(msg.body(), b"bar");
+    }
+
+    #[test]
+    fn test_display() {
+        gst::init().unwrap();
+
+        let msg = RTSPMessage::request_builder(RTSPMethod::DESCRIBE, "rtsp://example.com/")
+            .cseq(2)
+            .header(RTSPHeaderField::Authorization, "Basic dXNlcjpwYXNz")
+            .custom_header("Proxy-Authorization", "Basic cHJveHk6cGFzcw==")
+            .body(b"v=0")
+            .build();
+
+        let text = msg.to_string();
+        assert!(text.starts_with("DESCRIBE rtsp://example.com/ RTSP/1.0\n"));
+        assert!(text.contains("CSeq: 2\n"));
+        assert!(text.contains("Authorization: <redacted>\n"));
+        assert!(text.contains("Proxy-Authorization: <redacted>\n"));
+        assert!(!text.contains("dXNlcjpwYXNz"));
+        assert!(!text.contains("cHJveHk6cGFzcw=="));
+        assert!(text.ends_with("[3 bytes body]"));
+
+        let text = format!("{msg:?}");
+        assert!(!text.contains("dXNlcjpwYXNz"));
+        assert!(!text.contains("cHJveHk6cGFzcw=="));
+    }
 }
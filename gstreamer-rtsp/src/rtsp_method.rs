@@ -0,0 +1,109 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{fmt, str};
+
+use glib::translate::*;
+
+use crate::{ffi, RTSPMethod};
+
+impl RTSPMethod {
+    // rustdoc-stripper-ignore-next
+    /// Formats the methods as comma-separated list, as used in `Public` and
+    /// `Allow` headers.
+    #[doc(alias = "gst_rtsp_options_as_text")]
+    pub fn options_as_text(self) -> glib::GString {
+        assert_initialized_main_thread!();
+        unsafe { from_glib_full(ffi::gst_rtsp_options_as_text(self.into_glib())) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses a comma-separated list of methods, as used in `Public` and
+    /// `Allow` headers. Unknown methods are ignored.
+    #[doc(alias = "gst_rtsp_options_from_text")]
+    pub fn options_from_text(options: &str) -> RTSPMethod {
+        assert_initialized_main_thread!();
+        unsafe { from_glib(ffi::gst_rtsp_options_from_text(options.to_glib_none().0)) }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Formats the methods like [`RTSPMethod::options_as_text()`], i.e. a single
+/// method as its name, e.g. `DESCRIBE`.
+impl fmt::Display for RTSPMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s: glib::GString =
+            unsafe { from_glib_full(ffi::gst_rtsp_options_as_text(self.into_glib())) };
+        f.write_str(&s)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Parses a single method name, e.g. `DESCRIBE`, compared case-insensitively.
+///
+/// Use [`RTSPMethod::options_from_text()`] for comma-separated lists.
+impl str::FromStr for RTSPMethod {
+    type Err = glib::BoolError;
+
+    #[doc(alias = "gst_rtsp_find_method")]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        skip_assert_initialized!();
+        let method: RTSPMethod =
+            unsafe { from_glib(ffi::gst_rtsp_find_method(s.trim().to_glib_none().0)) };
+        if method.is_empty() {
+            Err(glib::bool_error!("Unknown RTSP method {}", s))
+        } else {
+            Ok(method)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_text() {
+        gst::init().unwrap();
+
+        let methods = RTSPMethod::OPTIONS | RTSPMethod::DESCRIBE | RTSPMethod::PLAY;
+        assert_eq!(methods.options_as_text(), "DESCRIBE, OPTIONS, PLAY");
+        assert_eq!(
+            RTSPMethod::options_from_text(&methods.options_as_text()),
+            methods
+        );
+
+        assert_eq!(
+            RTSPMethod::options_from_text("SETUP,TEARDOWN, FOO"),
+            RTSPMethod::SETUP | RTSPMethod::TEARDOWN
+        );
+        assert_eq!(RTSPMethod::options_from_text(""), RTSPMethod::empty());
+    }
+
+    #[test]
+    fn test_from_str_display() {
+        gst::init().unwrap();
+
+        assert_eq!(
+            "DESCRIBE".parse::<RTSPMethod>().unwrap(),
+            RTSPMethod::DESCRIBE
+        );
+        assert_eq!(
+            " teardown ".parse::<RTSPMethod>().unwrap(),
+            RTSPMethod::TEARDOWN
+        );
+        assert!("FOO".parse::<RTSPMethod>().is_err());
+        assert!("DESCRIBE, PLAY".parse::<RTSPMethod>().is_err());
+
+        assert_eq!(RTSPMethod::SET_PARAMETER.to_string(), "SET_PARAMETER");
+        assert_eq!(
+            (RTSPMethod::PLAY | RTSPMethod::PAUSE).to_string(),
+            "PAUSE, PLAY"
+        );
+
+        // The bitflags iterator yields all known methods one by one
+        for method in RTSPMethod::all().iter() {
+            assert_eq!(method.to_string().parse::<RTSPMethod>().unwrap(), method);
+        }
+        assert_eq!(RTSPMethod::all().iter().count(), 13);
+    }
+}
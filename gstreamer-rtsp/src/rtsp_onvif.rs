@@ -0,0 +1,154 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{fmt, str};
+
+// rustdoc-stripper-ignore-next
+/// Option tag of the ONVIF replay extensions, to be used in `Require`
+/// headers.
+pub const ONVIF_REPLAY: &str = "onvif-replay";
+
+// rustdoc-stripper-ignore-next
+/// The value of the ONVIF `Frames` header, which restricts replay to a
+/// subset of the frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OnvifFrames {
+    // rustdoc-stripper-ignore-next
+    /// Only key frames, optionally at most one per `interval`.
+    Intra { interval: Option<gst::ClockTime> },
+    // rustdoc-stripper-ignore-next
+    /// Key frames and predicted frames, but no bidirectionally predicted
+    /// frames.
+    Predicted,
+}
+
+impl fmt::Display for OnvifFrames {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OnvifFrames::Intra { interval: None } => f.write_str("intra"),
+            OnvifFrames::Intra {
+                interval: Some(interval),
+            } => write!(f, "intra/{}", interval.mseconds()),
+            OnvifFrames::Predicted => f.write_str("predicted"),
+        }
+    }
+}
+
+impl str::FromStr for OnvifFrames {
+    type Err = glib::BoolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        skip_assert_initialized!();
+        let s = s.trim();
+        let (frames, interval) = match s.split_once('/') {
+            Some((frames, interval)) => (frames, Some(interval)),
+            None => (s, None),
+        };
+
+        if frames.eq_ignore_ascii_case("predicted") && interval.is_none() {
+            Ok(OnvifFrames::Predicted)
+        } else if frames.eq_ignore_ascii_case("intra") {
+            let interval = interval
+                .map(|interval| {
+                    interval
+                        .trim()
+                        .parse::<u64>()
+                        .map(gst::ClockTime::from_mseconds)
+                        .map_err(|_| glib::bool_error!("Invalid Frames interval {}", interval))
+                })
+                .transpose()?;
+            Ok(OnvifFrames::Intra { interval })
+        } else {
+            Err(glib::bool_error!("Invalid Frames header {}", s))
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// The ONVIF headers of a replay PLAY request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OnvifReplayOptions {
+    rate_control: bool,
+    frames: Option<OnvifFrames>,
+    immediate: bool,
+}
+
+impl Default for OnvifReplayOptions {
+    fn default() -> Self {
+        Self {
+            rate_control: true,
+            frames: None,
+            immediate: false,
+        }
+    }
+}
+
+impl OnvifReplayOptions {
+    pub fn new() -> Self {
+        skip_assert_initialized!();
+        Self::default()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether the server should send the data in real time (the default) or
+    /// as fast as possible (`Rate-Control: no`).
+    pub fn rate_control(self, rate_control: bool) -> Self {
+        Self {
+            rate_control,
+            ..self
+        }
+    }
+
+    pub fn frames(self, frames: OnvifFrames) -> Self {
+        Self {
+            frames: Some(frames),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether the server should stop sending data for a previous PLAY
+    /// request immediately (`Immediate: yes`).
+    pub fn immediate(self, immediate: bool) -> Self {
+        Self { immediate, ..self }
+    }
+
+    pub fn has_rate_control(&self) -> bool {
+        self.rate_control
+    }
+
+    pub fn frames_filter(&self) -> Option<OnvifFrames> {
+        self.frames
+    }
+
+    pub fn is_immediate(&self) -> bool {
+        self.immediate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames() {
+        gst::init().unwrap();
+
+        for (s, frames) in [
+            ("intra", OnvifFrames::Intra { interval: None }),
+            (
+                "intra/4000",
+                OnvifFrames::Intra {
+                    interval: Some(gst::ClockTime::from_seconds(4)),
+                },
+            ),
+            ("predicted", OnvifFrames::Predicted),
+        ] {
+            assert_eq!(s.parse::<OnvifFrames>().unwrap(), frames);
+            assert_eq!(frames.to_string(), s);
+        }
+
+        assert!("all".parse::<OnvifFrames>().is_err());
+        assert!("intra/x".parse::<OnvifFrames>().is_err());
+        assert!("predicted/100".parse::<OnvifFrames>().is_err());
+    }
+}
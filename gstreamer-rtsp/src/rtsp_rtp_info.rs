@@ -0,0 +1,172 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{fmt, str};
+
+// rustdoc-stripper-ignore-next
+/// One stream entry of an `RTP-Info` header as sent in PLAY responses.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RTSPRtpInfo {
+    url: String,
+    seq: Option<u16>,
+    rtptime: Option<u32>,
+}
+
+impl RTSPRtpInfo {
+    pub fn new(url: &str, seq: Option<u16>, rtptime: Option<u32>) -> Self {
+        skip_assert_initialized!();
+        Self {
+            url: url.to_owned(),
+            seq,
+            rtptime,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sequence number of the first packet sent after the PLAY request.
+    pub fn seq(&self) -> Option<u16> {
+        self.seq
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// RTP timestamp corresponding to the start of the `Range` of the PLAY
+    /// response.
+    pub fn rtptime(&self) -> Option<u32> {
+        self.rtptime
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses all stream entries of an `RTP-Info` header value.
+    pub fn parse_list(header: &str) -> Result<Vec<Self>, glib::BoolError> {
+        skip_assert_initialized!();
+        let mut entries = Vec::<String>::new();
+
+        // URLs may contain commas themselves, so only split before `url=`
+        for part in header.split(',') {
+            match entries.last_mut() {
+                Some(last) if !part.trim_start().starts_with("url=") => {
+                    last.push(',');
+                    last.push_str(part);
+                }
+                _ => entries.push(part.to_owned()),
+            }
+        }
+
+        entries
+            .iter()
+            .filter(|entry| !entry.trim().is_empty())
+            .map(|entry| entry.parse())
+            .collect()
+    }
+
+    pub fn format_list(infos: &[Self]) -> String {
+        skip_assert_initialized!();
+        infos
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+impl str::FromStr for RTSPRtpInfo {
+    type Err = glib::BoolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        skip_assert_initialized!();
+        let mut url = None;
+        let mut seq = None;
+        let mut rtptime = None;
+
+        for param in s.split(';') {
+            let (key, value) = param.split_once('=').unwrap_or((param, ""));
+            let value = value.trim();
+            match key.trim() {
+                "url" => url = Some(value.trim_matches('"')),
+                "seq" => {
+                    seq = Some(
+                        value
+                            .parse()
+                            .map_err(|_| glib::bool_error!("Invalid seq '{}'", value))?,
+                    )
+                }
+                "rtptime" => {
+                    rtptime = Some(
+                        value
+                            .parse()
+                            .map_err(|_| glib::bool_error!("Invalid rtptime '{}'", value))?,
+                    )
+                }
+                // Ignore unknown parameters for forwards compatibility
+                _ => (),
+            }
+        }
+
+        let url = url.ok_or_else(|| glib::bool_error!("RTP-Info entry without url"))?;
+
+        Ok(Self::new(url, seq, rtptime))
+    }
+}
+
+impl fmt::Display for RTSPRtpInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "url={}", self.url)?;
+        if let Some(seq) = self.seq {
+            write!(f, ";seq={seq}")?;
+        }
+        if let Some(rtptime) = self.rtptime {
+            write!(f, ";rtptime={rtptime}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list() {
+        let infos = RTSPRtpInfo::parse_list(
+            "url=rtsp://example.com/foo/audio;seq=23243;rtptime=972948234, \
+             url=rtsp://example.com/foo/video?a=1,2;seq=48932;rtptime=1234",
+        )
+        .unwrap();
+
+        assert_eq!(
+            infos,
+            [
+                RTSPRtpInfo::new("rtsp://example.com/foo/audio", Some(23243), Some(972948234)),
+                RTSPRtpInfo::new(
+                    "rtsp://example.com/foo/video?a=1,2",
+                    Some(48932),
+                    Some(1234)
+                ),
+            ]
+        );
+
+        let infos = RTSPRtpInfo::parse_list("url=trackID=1;seq=1").unwrap();
+        assert_eq!(infos, [RTSPRtpInfo::new("trackID=1", Some(1), None)]);
+
+        assert!(RTSPRtpInfo::parse_list("seq=1;rtptime=2").is_err());
+        assert!(RTSPRtpInfo::parse_list("url=foo;seq=abc").is_err());
+    }
+
+    #[test]
+    fn test_format_list() {
+        let infos = [
+            RTSPRtpInfo::new("rtsp://example.com/audio", Some(1), Some(2)),
+            RTSPRtpInfo::new("rtsp://example.com/video", None, Some(3)),
+        ];
+
+        let header = RTSPRtpInfo::format_list(&infos);
+        assert_eq!(
+            header,
+            "url=rtsp://example.com/audio;seq=1;rtptime=2,url=rtsp://example.com/video;rtptime=3"
+        );
+        assert_eq!(RTSPRtpInfo::parse_list(&header).unwrap(), infos);
+    }
+}
@@ -0,0 +1,326 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{fmt, mem, ptr, str};
+
+use glib::translate::*;
+
+use crate::{
+    ffi,
+    rtsp_error::{result_from_glib, RTSPError},
+    RTSPRangeUnit,
+};
+
+// rustdoc-stripper-ignore-next
+/// A position inside an [`RTSPTimeRange`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RTSPTime {
+    // rustdoc-stripper-ignore-next
+    /// The current position of a live stream (`now`).
+    Now,
+    // rustdoc-stripper-ignore-next
+    /// An open end of the range.
+    End,
+    // rustdoc-stripper-ignore-next
+    /// Seconds since the beginning of the presentation.
+    Seconds(f64),
+    // rustdoc-stripper-ignore-next
+    /// A SMPTE timestamp, i.e. seconds plus frames.
+    Frames { seconds: f64, frames: f64 },
+    // rustdoc-stripper-ignore-next
+    /// An absolute UTC time, with `seconds` counting from midnight.
+    Utc {
+        year: u32,
+        month: u32,
+        day: u32,
+        seconds: f64,
+    },
+}
+
+impl RTSPTime {
+    // rustdoc-stripper-ignore-next
+    /// Converts `date_time` to an absolute UTC time.
+    pub fn utc(date_time: &glib::DateTime) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+        let utc = date_time.to_utc()?;
+
+        Ok(RTSPTime::Utc {
+            year: utc.year() as u32,
+            month: utc.month() as u32,
+            day: utc.day_of_month() as u32,
+            seconds: f64::from(utc.hour() * 3600 + utc.minute() * 60) + utc.seconds(),
+        })
+    }
+
+    unsafe fn from_glib(time: &ffi::GstRTSPTime, time2: &ffi::GstRTSPTime2) -> Self {
+        match time.type_ {
+            ffi::GST_RTSP_TIME_NOW => RTSPTime::Now,
+            ffi::GST_RTSP_TIME_FRAMES => RTSPTime::Frames {
+                seconds: time.seconds,
+                frames: time2.frames,
+            },
+            ffi::GST_RTSP_TIME_UTC => RTSPTime::Utc {
+                year: time2.year,
+                month: time2.month,
+                day: time2.day,
+                seconds: time.seconds,
+            },
+            ffi::GST_RTSP_TIME_SECONDS => RTSPTime::Seconds(time.seconds),
+            _ => RTSPTime::End,
+        }
+    }
+
+    fn into_glib(self) -> (ffi::GstRTSPTime, ffi::GstRTSPTime2) {
+        let mut time2 = ffi::GstRTSPTime2 {
+            frames: -1.0,
+            year: 0,
+            month: 0,
+            day: 0,
+        };
+
+        let time = match self {
+            RTSPTime::Now => ffi::GstRTSPTime {
+                type_: ffi::GST_RTSP_TIME_NOW,
+                seconds: -1.0,
+            },
+            RTSPTime::End => ffi::GstRTSPTime {
+                type_: ffi::GST_RTSP_TIME_END,
+                seconds: -1.0,
+            },
+            RTSPTime::Seconds(seconds) => ffi::GstRTSPTime {
+                type_: ffi::GST_RTSP_TIME_SECONDS,
+                seconds,
+            },
+            RTSPTime::Frames { seconds, frames } => {
+                time2.frames = frames;
+                ffi::GstRTSPTime {
+                    type_: ffi::GST_RTSP_TIME_FRAMES,
+                    seconds,
+                }
+            }
+            RTSPTime::Utc {
+                year,
+                month,
+                day,
+                seconds,
+            } => {
+                time2.year = year;
+                time2.month = month;
+                time2.day = day;
+                ffi::GstRTSPTime {
+                    type_: ffi::GST_RTSP_TIME_UTC,
+                    seconds,
+                }
+            }
+        };
+
+        (time, time2)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// The value of a `Range` header, in one of the npt, smpte or clock formats.
+#[doc(alias = "GstRTSPTimeRange")]
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct RTSPTimeRange(ffi::GstRTSPTimeRange);
+
+impl RTSPTimeRange {
+    pub fn new(unit: RTSPRangeUnit, min: RTSPTime, max: RTSPTime) -> Self {
+        assert_initialized_main_thread!();
+        let (min, min2) = min.into_glib();
+        let (max, max2) = max.into_glib();
+
+        Self(ffi::GstRTSPTimeRange {
+            unit: unit.into_glib(),
+            min,
+            max,
+            min2,
+            max2,
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates an npt range from `start` to `end`. A missing `start` means
+    /// `now`, a missing `end` leaves the range open.
+    pub fn npt(start: Option<gst::ClockTime>, end: Option<gst::ClockTime>) -> Self {
+        assert_initialized_main_thread!();
+        Self::new(
+            RTSPRangeUnit::Npt,
+            start.map_or(RTSPTime::Now, |t| RTSPTime::Seconds(t.seconds_f64())),
+            end.map_or(RTSPTime::End, |t| RTSPTime::Seconds(t.seconds_f64())),
+        )
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a clock range from `start` to `end`, leaving the range open if
+    /// `end` is missing.
+    ///
+    /// `end` may be before `start` for reverse playback, e.g. with ONVIF
+    /// replay.
+    pub fn clock(
+        start: &glib::DateTime,
+        end: Option<&glib::DateTime>,
+    ) -> Result<Self, glib::BoolError> {
+        assert_initialized_main_thread!();
+        Ok(Self::new(
+            RTSPRangeUnit::Clock,
+            RTSPTime::utc(start)?,
+            end.map_or(Ok(RTSPTime::End), RTSPTime::utc)?,
+        ))
+    }
+
+    #[doc(alias = "gst_rtsp_range_parse")]
+    pub fn parse(s: &str) -> Result<Self, RTSPError> {
+        assert_initialized_main_thread!();
+        unsafe {
+            let mut range = ptr::null_mut();
+            result_from_glib(
+                "parse range",
+                ffi::gst_rtsp_range_parse(s.to_glib_none().0, &mut range),
+            )?;
+
+            let res = Self(ptr::read(range));
+            ffi::gst_rtsp_range_free(range);
+            Ok(res)
+        }
+    }
+
+    pub fn unit(&self) -> RTSPRangeUnit {
+        unsafe { from_glib(self.0.unit) }
+    }
+
+    pub fn min(&self) -> RTSPTime {
+        unsafe { RTSPTime::from_glib(&self.0.min, &self.0.min2) }
+    }
+
+    pub fn max(&self) -> RTSPTime {
+        unsafe { RTSPTime::from_glib(&self.0.max, &self.0.max2) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Converts the range in place to `unit`.
+    #[doc(alias = "gst_rtsp_range_convert_units")]
+    pub fn convert_units(&mut self, unit: RTSPRangeUnit) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_rtsp_range_convert_units(&mut self.0, unit.into_glib()),
+                "Failed to convert range units"
+            )
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the start and end of the range as clock times, or `None` if
+    /// they can't be represented as such.
+    #[doc(alias = "get_times")]
+    #[doc(alias = "gst_rtsp_range_get_times")]
+    pub fn times(&self) -> Option<(Option<gst::ClockTime>, Option<gst::ClockTime>)> {
+        unsafe {
+            let mut min = mem::MaybeUninit::uninit();
+            let mut max = mem::MaybeUninit::uninit();
+            if from_glib(ffi::gst_rtsp_range_get_times(
+                &self.0,
+                min.as_mut_ptr(),
+                max.as_mut_ptr(),
+            )) {
+                Some((from_glib(min.assume_init()), from_glib(max.assume_init())))
+            } else {
+                None
+            }
+        }
+    }
+
+    pub fn as_ptr(&self) -> *const ffi::GstRTSPTimeRange {
+        &self.0
+    }
+}
+
+impl PartialEq for RTSPTimeRange {
+    fn eq(&self, other: &Self) -> bool {
+        self.unit() == other.unit() && self.min() == other.min() && self.max() == other.max()
+    }
+}
+
+impl fmt::Debug for RTSPTimeRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RTSPTimeRange")
+            .field("unit", &self.unit())
+            .field("min", &self.min())
+            .field("max", &self.max())
+            .finish()
+    }
+}
+
+impl fmt::Display for RTSPTimeRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s: Option<glib::GString> =
+            unsafe { from_glib_full(ffi::gst_rtsp_range_to_string(&self.0)) };
+        match s {
+            Some(s) => f.write_str(&s),
+            None => Ok(()),
+        }
+    }
+}
+
+impl str::FromStr for RTSPTimeRange {
+    type Err = RTSPError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        assert_initialized_main_thread!();
+        Self::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_npt() {
+        gst::init().unwrap();
+
+        let range = "npt=10-20.5".parse::<RTSPTimeRange>().unwrap();
+        assert_eq!(range.unit(), RTSPRangeUnit::Npt);
+        assert_eq!(range.min(), RTSPTime::Seconds(10.0));
+        assert_eq!(range.max(), RTSPTime::Seconds(20.5));
+        assert_eq!(
+            range.times(),
+            Some((
+                Some(gst::ClockTime::from_seconds(10)),
+                Some(gst::ClockTime::from_mseconds(20_500))
+            ))
+        );
+        assert_eq!(range.to_string().parse::<RTSPTimeRange>().unwrap(), range);
+
+        let range = "npt=now-".parse::<RTSPTimeRange>().unwrap();
+        assert_eq!(range.min(), RTSPTime::Now);
+        assert_eq!(range.max(), RTSPTime::End);
+
+        assert!("foo=1-2".parse::<RTSPTimeRange>().is_err());
+    }
+
+    #[test]
+    fn test_new_clock() {
+        gst::init().unwrap();
+
+        let range = RTSPTimeRange::new(
+            RTSPRangeUnit::Clock,
+            RTSPTime::Utc {
+                year: 2024,
+                month: 1,
+                day: 2,
+                seconds: 3600.0,
+            },
+            RTSPTime::End,
+        );
+        let parsed = range.to_string().parse::<RTSPTimeRange>().unwrap();
+        assert_eq!(parsed, range);
+
+        let start = glib::DateTime::from_utc(2024, 1, 2, 1, 0, 0.0).unwrap();
+        assert_eq!(RTSPTimeRange::clock(&start, None).unwrap(), range);
+
+        let range = RTSPTimeRange::npt(Some(gst::ClockTime::from_seconds(5)), None);
+        assert_eq!(range.min(), RTSPTime::Seconds(5.0));
+        assert_eq!(range.max(), RTSPTime::End);
+    }
+}
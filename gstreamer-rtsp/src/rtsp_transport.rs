@@ -0,0 +1,1524 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{error, ffi::CStr, fmt, net::IpAddr, ptr, str};
+
+use glib::{bitflags::bitflags, translate::*};
+
+use crate::{
+    ffi,
+    rtsp_error::{result_from_glib, RTSPError},
+    RTSPLowerTrans, RTSPProfile, RTSPTransMode,
+};
+
+// rustdoc-stripper-ignore-next
+/// A range of ports or interleaved channels, e.g. `5000-5001`.
+///
+/// `max` is `-1` if the range consists of a single value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[doc(alias = "GstRTSPRange")]
+pub struct RTSPRange {
+    min: i32,
+    max: i32,
+}
+
+impl RTSPRange {
+    pub fn new(min: i32, max: i32) -> Self {
+        skip_assert_initialized!();
+        Self { min, max }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// A range containing a single value.
+    pub fn single(value: i32) -> Self {
+        skip_assert_initialized!();
+        Self::new(value, -1)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The range used by the C API for values that are not set.
+    pub const fn unspecified() -> Self {
+        Self { min: -1, max: -1 }
+    }
+
+    pub fn min(&self) -> i32 {
+        self.min
+    }
+
+    pub fn max(&self) -> i32 {
+        self.max
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.min >= 0 && (self.max == -1 || self.max >= self.min)
+    }
+
+    // Largest value inside the range
+    fn last(&self) -> i32 {
+        if self.max == -1 {
+            self.min
+        } else {
+            self.max
+        }
+    }
+
+    pub fn contains(&self, value: i32) -> bool {
+        self.is_valid() && (self.min..=self.last()).contains(&value)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Number of values inside the range, `0` for invalid ranges.
+    pub fn len(&self) -> usize {
+        if self.is_valid() {
+            (self.last() - self.min) as usize + 1
+        } else {
+            0
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the RTP and RTCP ports if the range follows the convention of
+    /// an even RTP port directly followed by the RTCP port.
+    ///
+    /// A single even port implies the RTCP port following it.
+    pub fn rtp_rtcp_pair(&self) -> Option<(u16, u16)> {
+        if !self.is_valid() || self.min % 2 != 0 || self.last() > self.min + 1 {
+            return None;
+        }
+        if self.max != -1 && self.max != self.min + 1 {
+            return None;
+        }
+
+        let rtp = u16::try_from(self.min).ok()?;
+        let rtcp = rtp.checked_add(1)?;
+        Some((rtp, rtcp))
+    }
+
+    fn from_glib(range: ffi::GstRTSPRange) -> Option<Self> {
+        skip_assert_initialized!();
+        if range.min < 0 {
+            None
+        } else {
+            Some(Self::new(range.min, range.max))
+        }
+    }
+
+    fn into_glib(range: Option<Self>) -> ffi::GstRTSPRange {
+        skip_assert_initialized!();
+        match range {
+            Some(range) => ffi::GstRTSPRange {
+                min: range.min,
+                max: range.max,
+            },
+            None => ffi::GstRTSPRange { min: -1, max: -1 },
+        }
+    }
+}
+
+impl Default for RTSPRange {
+    fn default() -> Self {
+        Self::unspecified()
+    }
+}
+
+impl fmt::Display for RTSPRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.max == -1 {
+            write!(f, "{}", self.min)
+        } else {
+            write!(f, "{}-{}", self.min, self.max)
+        }
+    }
+}
+
+impl str::FromStr for RTSPRange {
+    type Err = glib::BoolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        skip_assert_initialized!();
+        let parse = |v: &str| {
+            v.trim()
+                .parse::<u16>()
+                .map(i32::from)
+                .map_err(|_| glib::bool_error!("Invalid range '{}'", s))
+        };
+
+        let range = match s.split_once('-') {
+            Some((min, max)) => Self::new(parse(min)?, parse(max)?),
+            None => Self::single(parse(s)?),
+        };
+
+        if !range.is_valid() {
+            return Err(glib::bool_error!("Invalid range '{}'", s));
+        }
+
+        Ok(range)
+    }
+}
+
+bitflags! {
+    // rustdoc-stripper-ignore-next
+    /// The `mode` parameter of a transport.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct TransportMode: u32 {
+        const PLAY = 1 << 0;
+        const RECORD = 1 << 1;
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Formats the mode like the value of the `mode` transport parameter, e.g.
+/// `PLAY,RECORD`.
+impl fmt::Display for TransportMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut first = true;
+        for (name, mode) in [
+            ("PLAY", TransportMode::PLAY),
+            ("RECORD", TransportMode::RECORD),
+        ] {
+            if self.contains(mode) {
+                if !first {
+                    f.write_str(",")?;
+                }
+                f.write_str(name)?;
+                first = false;
+            }
+        }
+        Ok(())
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Parses the value of the `mode` transport parameter, e.g. `"PLAY"` or
+/// `PLAY,RECORD`, case-insensitively and with or without quotes.
+impl str::FromStr for TransportMode {
+    type Err = glib::BoolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        skip_assert_initialized!();
+        let mut res = TransportMode::empty();
+        for mode in s.split(',') {
+            let mode = mode.trim().trim_matches('"').trim();
+            if mode.eq_ignore_ascii_case("play") {
+                res |= TransportMode::PLAY;
+            } else if mode.eq_ignore_ascii_case("record") {
+                res |= TransportMode::RECORD;
+            } else {
+                return Err(glib::bool_error!("Invalid transport mode '{}'", mode));
+            }
+        }
+        Ok(res)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A single transport specification of a `Transport` header.
+#[doc(alias = "GstRTSPTransport")]
+pub struct RTSPTransport(ptr::NonNull<ffi::GstRTSPTransport>);
+
+impl Drop for RTSPTransport {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let res = ffi::gst_rtsp_transport_free(self.0.as_ptr());
+            debug_assert_eq!(res, ffi::GST_RTSP_OK);
+        }
+    }
+}
+
+unsafe impl Send for RTSPTransport {}
+unsafe impl Sync for RTSPTransport {}
+
+impl RTSPTransport {
+    #[doc(alias = "gst_rtsp_transport_new")]
+    pub fn new() -> Self {
+        assert_initialized_main_thread!();
+        unsafe {
+            let mut transport = ptr::null_mut();
+            let res = ffi::gst_rtsp_transport_new(&mut transport);
+            debug_assert_eq!(res, ffi::GST_RTSP_OK);
+            Self::from_glib_full(transport)
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_transport_parse")]
+    pub fn parse(s: &str) -> Result<Self, RTSPError> {
+        assert_initialized_main_thread!();
+        let transport = Self::new();
+        unsafe {
+            result_from_glib(
+                "parse transport",
+                ffi::gst_rtsp_transport_parse(s.to_glib_none().0, transport.as_ptr()),
+            )?;
+        }
+        Ok(transport)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses all alternatives of a comma-separated `Transport` header, in
+    /// order of preference.
+    ///
+    /// Alternatives that fail to parse are skipped.
+    pub fn parse_list(header: &str) -> Vec<Self> {
+        assert_initialized_main_thread!();
+        header
+            .split(',')
+            .map(str::trim)
+            .filter(|spec| !spec.is_empty())
+            .filter_map(|spec| Self::parse(spec).ok())
+            .collect()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses a single transport specification like [`parse()`](Self::parse)
+    /// and additionally reports which parameters were recognized, which were
+    /// skipped as unknown and where parsing failed.
+    pub fn parse_verbose(s: &str) -> TransportParseReport {
+        assert_initialized_main_thread!();
+        let mut report = TransportParseReport {
+            transport: None,
+            recognized: Vec::new(),
+            unknown: Vec::new(),
+            error_offset: None,
+        };
+
+        let mut offset = 0;
+        for (idx, param) in s.split(';').enumerate() {
+            let param_offset = offset;
+            offset += param.len() + 1;
+
+            let param = param.trim();
+            if param.is_empty() {
+                continue;
+            }
+
+            let (name, value) = match param.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim())),
+                None => (param, None),
+            };
+
+            let valid = if idx == 0 {
+                // transport/profile/lower-transport
+                Self::parse(name).is_ok_and(|transport| !transport.trans().is_empty())
+            } else {
+                match (name, value) {
+                    ("unicast" | "multicast" | "append", None) => true,
+                    ("destination" | "source", Some(value)) => !value.is_empty(),
+                    ("interleaved" | "port" | "client_port" | "server_port", Some(value)) => {
+                        value.parse::<RTSPRange>().is_ok()
+                    }
+                    ("ttl", Some(value)) => value.parse::<u8>().is_ok(),
+                    ("layers", Some(value)) => value.parse::<u32>().is_ok(),
+                    ("ssrc", Some(value)) => u32::from_str_radix(value, 16).is_ok(),
+                    ("mode", Some(value)) => value.parse::<TransportMode>().is_ok(),
+                    (
+                        "unicast" | "multicast" | "append" | "destination" | "source"
+                        | "interleaved" | "port" | "client_port" | "server_port" | "ttl" | "layers"
+                        | "ssrc" | "mode",
+                        _,
+                    ) => false,
+                    _ => {
+                        report.unknown.push(param.to_owned());
+                        continue;
+                    }
+                }
+            };
+
+            if !valid {
+                report
+                    .error_offset
+                    .get_or_insert(param_offset + param_start(s, param_offset));
+                continue;
+            }
+            report.recognized.push(name.to_owned());
+        }
+
+        match Self::parse(s) {
+            Ok(transport) if !transport.trans().is_empty() => report.transport = Some(transport),
+            _ => {
+                report.error_offset.get_or_insert(0);
+            }
+        }
+
+        report
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a transport with all fields zeroed, i.e. without transport,
+    /// profile or lower transport.
+    ///
+    /// Use [`RTSPTransport::default()`] for a usable RTP/AVP/UDP transport.
+    pub fn empty() -> Self {
+        assert_initialized_main_thread!();
+        let mut transport = Self::new();
+        unsafe {
+            let inner = transport.inner_mut();
+            glib::ffi::g_free(inner.destination as glib::ffi::gpointer);
+            glib::ffi::g_free(inner.source as glib::ffi::gpointer);
+            ptr::write_bytes(inner as *mut ffi::GstRTSPTransport, 0, 1);
+        }
+        transport
+    }
+
+    pub fn builder() -> RTSPTransportBuilder {
+        assert_initialized_main_thread!();
+        RTSPTransportBuilder::new()
+    }
+
+    #[inline]
+    pub unsafe fn from_glib_full(ptr: *mut ffi::GstRTSPTransport) -> Self {
+        debug_assert!(!ptr.is_null());
+        Self(ptr::NonNull::new_unchecked(ptr))
+    }
+
+    #[inline]
+    pub fn as_ptr(&self) -> *mut ffi::GstRTSPTransport {
+        self.0.as_ptr()
+    }
+
+    #[inline]
+    pub fn into_glib_ptr(self) -> *mut ffi::GstRTSPTransport {
+        let s = std::mem::ManuallyDrop::new(self);
+        s.0.as_ptr()
+    }
+
+    #[inline]
+    fn inner(&self) -> &ffi::GstRTSPTransport {
+        unsafe { &*self.0.as_ptr() }
+    }
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut ffi::GstRTSPTransport {
+        unsafe { &mut *self.0.as_ptr() }
+    }
+
+    pub fn trans(&self) -> RTSPTransMode {
+        unsafe { from_glib(self.inner().trans) }
+    }
+
+    pub fn set_trans(&mut self, trans: RTSPTransMode) {
+        self.inner_mut().trans = trans.into_glib();
+    }
+
+    pub fn profile(&self) -> RTSPProfile {
+        unsafe { from_glib(self.inner().profile) }
+    }
+
+    pub fn set_profile(&mut self, profile: RTSPProfile) {
+        self.inner_mut().profile = profile.into_glib();
+    }
+
+    pub fn lower_transport(&self) -> RTSPLowerTrans {
+        unsafe { from_glib(self.inner().lower_transport) }
+    }
+
+    pub fn set_lower_transport(&mut self, lower_transport: RTSPLowerTrans) {
+        self.inner_mut().lower_transport = lower_transport.into_glib();
+    }
+
+    pub fn destination(&self) -> Option<&str> {
+        unsafe {
+            let ptr = self.inner().destination;
+            if ptr.is_null() {
+                None
+            } else {
+                CStr::from_ptr(ptr).to_str().ok()
+            }
+        }
+    }
+
+    pub fn set_destination(&mut self, destination: Option<&str>) {
+        unsafe {
+            let inner = self.inner_mut();
+            glib::ffi::g_free(inner.destination as glib::ffi::gpointer);
+            inner.destination = destination.to_glib_full();
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the destination if it is an IP address.
+    pub fn destination_addr(&self) -> Option<IpAddr> {
+        self.destination()?.parse().ok()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the destination to `addr`.
+    ///
+    /// Fails if this is a multicast transport and `addr` is not a multicast
+    /// address.
+    pub fn set_destination_addr(&mut self, addr: IpAddr) -> Result<(), glib::BoolError> {
+        if self.lower_transport().contains(RTSPLowerTrans::UDP_MCAST) && !addr.is_multicast() {
+            return Err(glib::bool_error!(
+                "Multicast transport with non-multicast destination {}",
+                addr
+            ));
+        }
+
+        self.set_destination(Some(&addr.to_string()));
+        Ok(())
+    }
+
+    pub fn source(&self) -> Option<&str> {
+        unsafe {
+            let ptr = self.inner().source;
+            if ptr.is_null() {
+                None
+            } else {
+                CStr::from_ptr(ptr).to_str().ok()
+            }
+        }
+    }
+
+    pub fn set_source(&mut self, source: Option<&str>) {
+        unsafe {
+            let inner = self.inner_mut();
+            glib::ffi::g_free(inner.source as glib::ffi::gpointer);
+            inner.source = source.to_glib_full();
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the source if it is an IP address.
+    pub fn source_addr(&self) -> Option<IpAddr> {
+        self.source()?.parse().ok()
+    }
+
+    pub fn set_source_addr(&mut self, addr: IpAddr) {
+        self.set_source(Some(&addr.to_string()));
+    }
+
+    pub fn layers(&self) -> u32 {
+        self.inner().layers
+    }
+
+    pub fn set_layers(&mut self, layers: u32) {
+        self.inner_mut().layers = layers;
+    }
+
+    pub fn mode(&self) -> TransportMode {
+        let mut mode = TransportMode::empty();
+        unsafe {
+            mode.set(TransportMode::PLAY, from_glib(self.inner().mode_play));
+            mode.set(TransportMode::RECORD, from_glib(self.inner().mode_record));
+        }
+        mode
+    }
+
+    pub fn set_mode(&mut self, mode: TransportMode) {
+        let inner = self.inner_mut();
+        inner.mode_play = mode.contains(TransportMode::PLAY).into_glib();
+        inner.mode_record = mode.contains(TransportMode::RECORD).into_glib();
+    }
+
+    #[deprecated = "use `mode()` instead"]
+    pub fn is_mode_play(&self) -> bool {
+        self.mode().contains(TransportMode::PLAY)
+    }
+
+    #[deprecated = "use `set_mode()` instead"]
+    pub fn set_mode_play(&mut self, mode_play: bool) {
+        let mut mode = self.mode();
+        mode.set(TransportMode::PLAY, mode_play);
+        self.set_mode(mode);
+    }
+
+    #[deprecated = "use `mode()` instead"]
+    pub fn is_mode_record(&self) -> bool {
+        self.mode().contains(TransportMode::RECORD)
+    }
+
+    #[deprecated = "use `set_mode()` instead"]
+    pub fn set_mode_record(&mut self, mode_record: bool) {
+        let mut mode = self.mode();
+        mode.set(TransportMode::RECORD, mode_record);
+        self.set_mode(mode);
+    }
+
+    pub fn is_append(&self) -> bool {
+        unsafe { from_glib(self.inner().append) }
+    }
+
+    pub fn set_append(&mut self, append: bool) {
+        self.inner_mut().append = append.into_glib();
+    }
+
+    pub fn interleaved(&self) -> Option<RTSPRange> {
+        RTSPRange::from_glib(self.inner().interleaved)
+    }
+
+    pub fn set_interleaved(&mut self, interleaved: Option<RTSPRange>) {
+        self.inner_mut().interleaved = RTSPRange::into_glib(interleaved);
+    }
+
+    pub fn ttl(&self) -> u32 {
+        self.inner().ttl
+    }
+
+    pub fn set_ttl(&mut self, ttl: u32) {
+        self.inner_mut().ttl = ttl;
+    }
+
+    pub fn port(&self) -> Option<RTSPRange> {
+        RTSPRange::from_glib(self.inner().port)
+    }
+
+    pub fn set_port(&mut self, port: Option<RTSPRange>) {
+        self.inner_mut().port = RTSPRange::into_glib(port);
+    }
+
+    pub fn client_port(&self) -> Option<RTSPRange> {
+        RTSPRange::from_glib(self.inner().client_port)
+    }
+
+    pub fn set_client_port(&mut self, client_port: Option<RTSPRange>) {
+        self.inner_mut().client_port = RTSPRange::into_glib(client_port);
+    }
+
+    pub fn server_port(&self) -> Option<RTSPRange> {
+        RTSPRange::from_glib(self.inner().server_port)
+    }
+
+    pub fn set_server_port(&mut self, server_port: Option<RTSPRange>) {
+        self.inner_mut().server_port = RTSPRange::into_glib(server_port);
+    }
+
+    pub fn ssrc(&self) -> u32 {
+        self.inner().ssrc
+    }
+
+    pub fn set_ssrc(&mut self, ssrc: u32) {
+        self.inner_mut().ssrc = ssrc;
+    }
+
+    #[doc(alias = "get_media_type")]
+    #[doc(alias = "gst_rtsp_transport_get_media_type")]
+    pub fn media_type(&self) -> Option<&'static str> {
+        unsafe {
+            let mut media_type = ptr::null();
+            let res = ffi::gst_rtsp_transport_get_media_type(self.as_ptr(), &mut media_type);
+            if res != ffi::GST_RTSP_OK || media_type.is_null() {
+                None
+            } else {
+                CStr::from_ptr(media_type).to_str().ok()
+            }
+        }
+    }
+
+    #[doc(alias = "gst_rtsp_transport_as_text")]
+    pub fn as_text(&self) -> Option<glib::GString> {
+        unsafe { from_glib_full(ffi::gst_rtsp_transport_as_text(self.as_ptr())) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a structure named after the [media type](Self::media_type)
+    /// containing the negotiated parameters of the transport.
+    ///
+    /// Secure RTP transports use `application/x-srtp` as expected by
+    /// `srtpdec`.
+    ///
+    /// Ranges are stored as `gst::IntRange`s or, when consisting of a single
+    /// value, as plain integers in the `port`, `client-port`, `server-port`
+    /// and `interleaved` fields. `ssrc`, `destination`, `source` and `ttl`
+    /// are only set if the transport specifies them.
+    pub fn to_structure(&self) -> Result<gst::Structure, glib::BoolError> {
+        let mut media_type = self
+            .media_type()
+            .ok_or_else(|| glib::bool_error!("Transport has no media type"))?;
+        if self.trans() == RTSPTransMode::RTP
+            && self
+                .profile()
+                .intersects(RTSPProfile::SAVP | RTSPProfile::SAVPF)
+        {
+            media_type = "application/x-srtp";
+        }
+
+        let mut s = gst::Structure::builder(media_type)
+            .field("lower-transport", self.lower_transport())
+            .build();
+
+        for (field, range) in [
+            ("port", self.port()),
+            ("client-port", self.client_port()),
+            ("server-port", self.server_port()),
+            ("interleaved", self.interleaved()),
+        ] {
+            match range {
+                Some(range) if range.len() > 1 => {
+                    s.set(field, gst::IntRange::new(range.min(), range.max()))
+                }
+                Some(range) if range.is_valid() => s.set(field, range.min()),
+                _ => (),
+            }
+        }
+
+        if self.ssrc() != 0 {
+            s.set("ssrc", self.ssrc());
+        }
+        if let Some(destination) = self.destination() {
+            s.set("destination", destination);
+        }
+        if let Some(source) = self.source() {
+            s.set("source", source);
+        }
+        if self.ttl() != 0 {
+            s.set("ttl", self.ttl());
+        }
+
+        Ok(s)
+    }
+
+    pub fn to_caps(&self) -> Result<gst::Caps, glib::BoolError> {
+        self.to_structure().map(gst::Caps::from)
+    }
+
+    pub fn matches_profile(
+        &self,
+        trans: RTSPTransMode,
+        profile: RTSPProfile,
+        lower_transport: RTSPLowerTrans,
+    ) -> bool {
+        self.trans() == trans
+            && self.profile() == profile
+            && self.lower_transport() == lower_transport
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Checks if both transports describe the same kind of transport, i.e.
+    /// use the same transport, profile, lower transport and mode.
+    ///
+    /// Unlike a comparison of all fields this ignores values that are
+    /// negotiated per session, like ports, channels, addresses, ttl or ssrc.
+    pub fn is_compatible_with(&self, other: &RTSPTransport) -> bool {
+        self.matches_profile(other.trans(), other.profile(), other.lower_transport())
+            && self.mode() == other.mode()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Creates a unicast RTP/AVP/UDP transport in play mode with all ranges
+/// unspecified.
+impl Default for RTSPTransport {
+    fn default() -> Self {
+        let mut transport = Self::new();
+        transport.set_trans(RTSPTransMode::RTP);
+        transport.set_profile(RTSPProfile::AVP);
+        transport.set_lower_transport(RTSPLowerTrans::UDP);
+        transport.set_mode(TransportMode::PLAY);
+        transport.set_interleaved(None);
+        transport.set_port(None);
+        transport.set_client_port(None);
+        transport.set_server_port(None);
+        transport
+    }
+}
+
+impl Clone for RTSPTransport {
+    fn clone(&self) -> Self {
+        let mut transport = Self::new();
+        unsafe {
+            let inner = transport.inner_mut();
+            *inner = *self.inner();
+            inner.destination = glib::ffi::g_strdup(self.inner().destination);
+            inner.source = glib::ffi::g_strdup(self.inner().source);
+        }
+        transport
+    }
+}
+
+impl fmt::Debug for RTSPTransport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RTSPTransport")
+            .field("trans", &self.trans())
+            .field("profile", &self.profile())
+            .field("lower-transport", &self.lower_transport())
+            .field("destination", &self.destination())
+            .field("source", &self.source())
+            .field("layers", &self.layers())
+            .field("mode", &self.mode())
+            .field("append", &self.is_append())
+            .field("interleaved", &self.interleaved())
+            .field("ttl", &self.ttl())
+            .field("port", &self.port())
+            .field("client-port", &self.client_port())
+            .field("server-port", &self.server_port())
+            .field("ssrc", &self.ssrc())
+            .finish()
+    }
+}
+
+impl fmt::Display for RTSPTransport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.as_text() {
+            Some(text) => f.write_str(&text),
+            None => Ok(()),
+        }
+    }
+}
+
+impl str::FromStr for RTSPTransport {
+    type Err = RTSPError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        assert_initialized_main_thread!();
+        Self::parse(s)
+    }
+}
+
+// Number of whitespace bytes before the parameter starting at `offset`
+fn param_start(s: &str, offset: usize) -> usize {
+    let param = &s[offset..];
+    param.len() - param.trim_start().len()
+}
+
+// rustdoc-stripper-ignore-next
+/// Result of [`RTSPTransport::parse_verbose()`].
+#[derive(Debug)]
+pub struct TransportParseReport {
+    transport: Option<RTSPTransport>,
+    recognized: Vec<String>,
+    unknown: Vec<String>,
+    error_offset: Option<usize>,
+}
+
+impl TransportParseReport {
+    // rustdoc-stripper-ignore-next
+    /// The parsed transport, or `None` if parsing failed.
+    pub fn transport(&self) -> Option<&RTSPTransport> {
+        self.transport.as_ref()
+    }
+
+    pub fn into_transport(self) -> Option<RTSPTransport> {
+        self.transport
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Names of the recognized parameters, starting with the
+    /// transport/profile/lower-transport specification.
+    pub fn recognized(&self) -> &[String] {
+        &self.recognized
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Unknown parameters that were skipped, including their values.
+    pub fn unknown(&self) -> &[String] {
+        &self.unknown
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Byte offset of the first invalid parameter.
+    ///
+    /// Invalid parameters are ignored by the parser, so this can be set even
+    /// if a transport could be parsed.
+    pub fn error_offset(&self) -> Option<usize> {
+        self.error_offset
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Error returned by [`RTSPTransportBuilder::build()`] for inconsistent
+/// transport configurations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TransportBuildError {
+    // rustdoc-stripper-ignore-next
+    /// TCP transports need interleaved channels.
+    MissingInterleaved,
+    // rustdoc-stripper-ignore-next
+    /// Multicast transports need a destination address.
+    MissingDestination,
+    // rustdoc-stripper-ignore-next
+    /// Multicast transports need a TTL.
+    MissingTtl,
+    // rustdoc-stripper-ignore-next
+    /// The destination of a multicast transport is not a multicast address.
+    NotMulticast(IpAddr),
+    // rustdoc-stripper-ignore-next
+    /// The maximum of a range is smaller than its minimum.
+    InvalidRange {
+        field: &'static str,
+        range: RTSPRange,
+    },
+    // rustdoc-stripper-ignore-next
+    /// A port range does not start at an even RTP port followed by the odd
+    /// RTCP port.
+    InvalidPortPair {
+        field: &'static str,
+        range: RTSPRange,
+    },
+    // rustdoc-stripper-ignore-next
+    /// A URI option passed to [`RTSPTransportBuilder::from_uri_options()`]
+    /// has an invalid value.
+    InvalidOption { name: String, value: String },
+}
+
+impl fmt::Display for TransportBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransportBuildError::MissingInterleaved => {
+                f.write_str("TCP transport without interleaved channels")
+            }
+            TransportBuildError::MissingDestination => {
+                f.write_str("Multicast transport without destination")
+            }
+            TransportBuildError::MissingTtl => f.write_str("Multicast transport without TTL"),
+            TransportBuildError::NotMulticast(addr) => {
+                write!(
+                    f,
+                    "Multicast transport with non-multicast destination {addr}"
+                )
+            }
+            TransportBuildError::InvalidRange { field, range } => {
+                write!(f, "Invalid {field} range {range}")
+            }
+            TransportBuildError::InvalidPortPair { field, range } => write!(
+                f,
+                "Invalid {field} range {range}: expected an even RTP port followed by the RTCP port"
+            ),
+            TransportBuildError::InvalidOption { name, value } => {
+                write!(f, "Invalid value '{value}' for option '{name}'")
+            }
+        }
+    }
+}
+
+impl error::Error for TransportBuildError {}
+
+// rustdoc-stripper-ignore-next
+/// Builder for [`RTSPTransport`]s that checks the configuration for
+/// consistency.
+#[derive(Debug, Clone)]
+#[must_use = "The builder must be built to be used"]
+pub struct RTSPTransportBuilder {
+    trans: RTSPTransMode,
+    profile: RTSPProfile,
+    lower_transport: RTSPLowerTrans,
+    destination: Option<String>,
+    source: Option<String>,
+    layers: u32,
+    mode: TransportMode,
+    append: bool,
+    interleaved: Option<RTSPRange>,
+    ttl: u32,
+    port: Option<RTSPRange>,
+    client_port: Option<RTSPRange>,
+    server_port: Option<RTSPRange>,
+    ssrc: u32,
+}
+
+impl RTSPTransportBuilder {
+    fn new() -> Self {
+        skip_assert_initialized!();
+        Self {
+            trans: RTSPTransMode::RTP,
+            profile: RTSPProfile::AVP,
+            lower_transport: RTSPLowerTrans::UDP,
+            destination: None,
+            source: None,
+            layers: 0,
+            mode: TransportMode::PLAY,
+            append: false,
+            interleaved: None,
+            ttl: 0,
+            port: None,
+            client_port: None,
+            server_port: None,
+            ssrc: 0,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder configured from URI query options as returned by
+    /// [`helpers::options_from_uri()`](crate::helpers::options_from_uri).
+    ///
+    /// The following options are understood, all others are ignored:
+    ///
+    /// * `transport`: `udp`, `tcp` or `multicast`
+    /// * `multicast`: same as `transport=multicast`
+    /// * `profile`: `avp`, `savp`, `avpf` or `savpf`
+    /// * `port` or `client_port`: the client port range, e.g. `5000-5001`
+    /// * `server_port`, `interleaved`: the respective range
+    /// * `destination`, `source`: addresses or host names
+    /// * `ttl`: the multicast TTL
+    /// * `mode`: `play`, `record` or `play,record`
+    pub fn from_uri_options(options: &[(String, String)]) -> Result<Self, TransportBuildError> {
+        assert_initialized_main_thread!();
+        let mut builder = Self::new();
+
+        for (name, value) in options {
+            let invalid = || TransportBuildError::InvalidOption {
+                name: name.clone(),
+                value: value.clone(),
+            };
+            let range = || value.parse::<RTSPRange>().map_err(|_| invalid());
+
+            builder = match name.as_str() {
+                "transport" => match value.to_ascii_lowercase().as_str() {
+                    "udp" => builder.lower_transport(RTSPLowerTrans::UDP),
+                    "tcp" => builder.lower_transport(RTSPLowerTrans::TCP),
+                    "multicast" | "udp-mcast" => builder.lower_transport(RTSPLowerTrans::UDP_MCAST),
+                    _ => return Err(invalid()),
+                },
+                "multicast" => builder.lower_transport(RTSPLowerTrans::UDP_MCAST),
+                "profile" => match value.to_ascii_lowercase().as_str() {
+                    "avp" => builder.profile(RTSPProfile::AVP),
+                    "savp" => builder.profile(RTSPProfile::SAVP),
+                    "avpf" => builder.profile(RTSPProfile::AVPF),
+                    "savpf" => builder.profile(RTSPProfile::SAVPF),
+                    _ => return Err(invalid()),
+                },
+                "port" | "client_port" => builder.client_port(range()?),
+                "server_port" => builder.server_port(range()?),
+                "interleaved" => builder.interleaved(range()?),
+                "destination" => builder.destination(value),
+                "source" => builder.source(value),
+                "ttl" => builder.ttl(value.parse().map_err(|_| invalid())?),
+                "mode" => builder.mode(value.parse().map_err(|_| invalid())?),
+                _ => builder,
+            };
+        }
+
+        Ok(builder)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a unicast RTP/AVP/UDP transport in play mode.
+    ///
+    /// This is the same configuration [`RTSPTransport::builder()`] starts
+    /// with.
+    pub fn rtp_avp_udp() -> Self {
+        assert_initialized_main_thread!();
+        Self::new()
+    }
+
+    pub fn trans(self, trans: RTSPTransMode) -> Self {
+        Self { trans, ..self }
+    }
+
+    pub fn profile(self, profile: RTSPProfile) -> Self {
+        Self { profile, ..self }
+    }
+
+    pub fn lower_transport(self, lower_transport: RTSPLowerTrans) -> Self {
+        Self {
+            lower_transport,
+            ..self
+        }
+    }
+
+    pub fn destination(self, destination: &str) -> Self {
+        Self {
+            destination: Some(destination.to_owned()),
+            ..self
+        }
+    }
+
+    pub fn destination_addr(self, destination: IpAddr) -> Self {
+        self.destination(&destination.to_string())
+    }
+
+    pub fn source(self, source: &str) -> Self {
+        Self {
+            source: Some(source.to_owned()),
+            ..self
+        }
+    }
+
+    pub fn source_addr(self, source: IpAddr) -> Self {
+        self.source(&source.to_string())
+    }
+
+    pub fn layers(self, layers: u32) -> Self {
+        Self { layers, ..self }
+    }
+
+    pub fn mode(self, mode: TransportMode) -> Self {
+        Self { mode, ..self }
+    }
+
+    #[deprecated = "use `mode()` instead"]
+    pub fn mode_play(mut self, mode_play: bool) -> Self {
+        self.mode.set(TransportMode::PLAY, mode_play);
+        self
+    }
+
+    #[deprecated = "use `mode()` instead"]
+    pub fn mode_record(mut self, mode_record: bool) -> Self {
+        self.mode.set(TransportMode::RECORD, mode_record);
+        self
+    }
+
+    pub fn append(self, append: bool) -> Self {
+        Self { append, ..self }
+    }
+
+    pub fn interleaved(self, interleaved: RTSPRange) -> Self {
+        Self {
+            interleaved: Some(interleaved),
+            ..self
+        }
+    }
+
+    pub fn ttl(self, ttl: u32) -> Self {
+        Self { ttl, ..self }
+    }
+
+    pub fn port(self, port: RTSPRange) -> Self {
+        Self {
+            port: Some(port),
+            ..self
+        }
+    }
+
+    pub fn client_port(self, client_port: RTSPRange) -> Self {
+        Self {
+            client_port: Some(client_port),
+            ..self
+        }
+    }
+
+    pub fn server_port(self, server_port: RTSPRange) -> Self {
+        Self {
+            server_port: Some(server_port),
+            ..self
+        }
+    }
+
+    pub fn ssrc(self, ssrc: u32) -> Self {
+        Self { ssrc, ..self }
+    }
+
+    fn validate(&self) -> Result<(), TransportBuildError> {
+        if self.lower_transport.contains(RTSPLowerTrans::TCP) && self.interleaved.is_none() {
+            return Err(TransportBuildError::MissingInterleaved);
+        }
+
+        if self.lower_transport.contains(RTSPLowerTrans::UDP_MCAST) {
+            let Some(ref destination) = self.destination else {
+                return Err(TransportBuildError::MissingDestination);
+            };
+            // Host names are resolved later and can't be checked here
+            if let Ok(addr) = destination.parse::<IpAddr>() {
+                if !addr.is_multicast() {
+                    return Err(TransportBuildError::NotMulticast(addr));
+                }
+            }
+            if self.ttl == 0 {
+                return Err(TransportBuildError::MissingTtl);
+            }
+        }
+
+        for (field, range) in [
+            ("interleaved", self.interleaved),
+            ("port", self.port),
+            ("client_port", self.client_port),
+            ("server_port", self.server_port),
+        ] {
+            let Some(range) = range else {
+                continue;
+            };
+
+            if !range.is_valid() {
+                return Err(TransportBuildError::InvalidRange { field, range });
+            }
+
+            // RTP uses the even port and RTCP the next higher one
+            if field != "interleaved"
+                && self.trans == RTSPTransMode::RTP
+                && range.max() != -1
+                && range.rtp_rtcp_pair().is_none()
+            {
+                return Err(TransportBuildError::InvalidPortPair { field, range });
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn build(self) -> Result<RTSPTransport, TransportBuildError> {
+        self.validate()?;
+
+        let mut transport = RTSPTransport::new();
+        transport.set_trans(self.trans);
+        transport.set_profile(self.profile);
+        transport.set_lower_transport(self.lower_transport);
+        transport.set_destination(self.destination.as_deref());
+        transport.set_source(self.source.as_deref());
+        transport.set_layers(self.layers);
+        transport.set_mode(self.mode);
+        transport.set_append(self.append);
+        transport.set_interleaved(self.interleaved);
+        transport.set_ttl(self.ttl);
+        transport.set_port(self.port);
+        transport.set_client_port(self.client_port);
+        transport.set_server_port(self.server_port);
+        transport.set_ssrc(self.ssrc);
+
+        Ok(transport)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        gst::init().unwrap();
+
+        let transport: RTSPTransport = "RTP/AVP;unicast;client_port=5000-5001;ssrc=1234ABCD"
+            .parse()
+            .unwrap();
+        assert_eq!(transport.trans(), RTSPTransMode::RTP);
+        assert_eq!(transport.profile(), RTSPProfile::AVP);
+        assert_eq!(transport.lower_transport(), RTSPLowerTrans::UDP);
+        assert_eq!(transport.client_port(), Some(RTSPRange::new(5000, 5001)));
+        assert_eq!(transport.server_port(), None);
+        assert_eq!(transport.ssrc(), 0x1234abcd);
+
+        let copy = transport.clone();
+        assert_eq!(copy.to_string(), transport.to_string());
+    }
+
+    #[test]
+    fn test_parse_list() {
+        gst::init().unwrap();
+
+        let transports = RTSPTransport::parse_list(
+            "RTP/AVP;unicast;client_port=5000-5001, RTP/AVP/TCP;unicast;interleaved=0-1,,\
+             RTP/AVP;multicast;destination=224.2.0.1;ttl=16",
+        );
+        assert_eq!(transports.len(), 3);
+
+        assert_eq!(transports[0].lower_transport(), RTSPLowerTrans::UDP);
+        assert_eq!(transports[1].lower_transport(), RTSPLowerTrans::TCP);
+        assert_eq!(transports[1].interleaved(), Some(RTSPRange::new(0, 1)));
+        assert_eq!(transports[2].lower_transport(), RTSPLowerTrans::UDP_MCAST);
+        assert_eq!(transports[2].destination(), Some("224.2.0.1"));
+        assert_eq!(transports[2].ttl(), 16);
+
+        assert!(RTSPTransport::parse_list("").is_empty());
+    }
+
+    #[test]
+    fn test_addr() {
+        gst::init().unwrap();
+
+        let mut transport: RTSPTransport =
+            "RTP/AVP;unicast;destination=192.168.1.2;source=example.com"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            transport.destination_addr(),
+            Some(IpAddr::from([192, 168, 1, 2]))
+        );
+        assert_eq!(transport.source_addr(), None);
+
+        transport.set_source_addr("::1".parse().unwrap());
+        assert_eq!(transport.source(), Some("::1"));
+
+        transport.set_lower_transport(RTSPLowerTrans::UDP_MCAST);
+        assert!(transport
+            .set_destination_addr(IpAddr::from([192, 168, 1, 3]))
+            .is_err());
+        assert_eq!(transport.destination(), Some("192.168.1.2"));
+        transport
+            .set_destination_addr(IpAddr::from([224, 2, 0, 1]))
+            .unwrap();
+        assert_eq!(transport.destination(), Some("224.2.0.1"));
+
+        let err = RTSPTransport::builder()
+            .lower_transport(RTSPLowerTrans::UDP_MCAST)
+            .destination_addr(IpAddr::from([10, 0, 0, 1]))
+            .ttl(1)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            TransportBuildError::NotMulticast(IpAddr::from([10, 0, 0, 1]))
+        );
+    }
+
+    #[test]
+    fn test_from_uri_options() {
+        gst::init().unwrap();
+
+        let options = crate::helpers::options_from_uri(
+            "rtsp://example.com/stream?transport=tcp&interleaved=2-3&foo=bar",
+        );
+        let transport = RTSPTransportBuilder::from_uri_options(&options)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(transport.lower_transport(), RTSPLowerTrans::TCP);
+        assert_eq!(transport.interleaved(), Some(RTSPRange::new(2, 3)));
+
+        let options = crate::helpers::options_from_uri(
+            "rtsp://example.com/stream?multicast&destination=224.2.0.1&port=5000-5001&ttl=8&mode=record",
+        );
+        let transport = RTSPTransportBuilder::from_uri_options(&options)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(transport.lower_transport(), RTSPLowerTrans::UDP_MCAST);
+        assert_eq!(transport.destination(), Some("224.2.0.1"));
+        assert_eq!(transport.client_port(), Some(RTSPRange::new(5000, 5001)));
+        assert_eq!(transport.ttl(), 8);
+        assert_eq!(transport.mode(), TransportMode::RECORD);
+
+        let options = crate::helpers::options_from_uri("rtsp://example.com/?transport=sctp");
+        assert_eq!(
+            RTSPTransportBuilder::from_uri_options(&options).unwrap_err(),
+            TransportBuildError::InvalidOption {
+                name: "transport".to_owned(),
+                value: "sctp".to_owned(),
+            }
+        );
+
+        assert!("5000-4000".parse::<RTSPRange>().is_err());
+        assert_eq!(
+            "5000".parse::<RTSPRange>().unwrap(),
+            RTSPRange::single(5000)
+        );
+    }
+
+    #[test]
+    fn test_to_structure() {
+        gst::init().unwrap();
+
+        let transport: RTSPTransport =
+            "RTP/AVP;unicast;client_port=5000-5001;server_port=6000;ssrc=0000002A"
+                .parse()
+                .unwrap();
+        let s = transport.to_structure().unwrap();
+        assert_eq!(s.name(), "application/x-rtp");
+        assert_eq!(
+            s.get::<gst::IntRange<i32>>("client-port").unwrap(),
+            gst::IntRange::new(5000, 5001)
+        );
+        assert_eq!(s.get::<i32>("server-port").unwrap(), 6000);
+        assert_eq!(s.get::<u32>("ssrc").unwrap(), 42);
+        assert_eq!(
+            s.get::<RTSPLowerTrans>("lower-transport").unwrap(),
+            RTSPLowerTrans::UDP
+        );
+        assert!(!s.has_field("destination"));
+
+        let transport: RTSPTransport =
+            "RTP/SAVP;multicast;destination=224.2.0.1;port=5000-5001;ttl=4"
+                .parse()
+                .unwrap();
+        let caps = transport.to_caps().unwrap();
+        let s = caps.structure(0).unwrap();
+        assert_eq!(s.name(), "application/x-srtp");
+        assert_eq!(s.get::<&str>("destination").unwrap(), "224.2.0.1");
+        assert_eq!(s.get::<u32>("ttl").unwrap(), 4);
+    }
+
+    #[test]
+    fn test_default() {
+        gst::init().unwrap();
+
+        let transport = RTSPTransport::default();
+        assert!(transport.matches_profile(
+            RTSPTransMode::RTP,
+            RTSPProfile::AVP,
+            RTSPLowerTrans::UDP
+        ));
+        assert_eq!(transport.mode(), TransportMode::PLAY);
+        assert_eq!(transport.client_port(), None);
+        assert_eq!(transport.interleaved(), None);
+        assert_eq!(transport.media_type(), Some("application/x-rtp"));
+        assert!(transport.to_string().starts_with("RTP/AVP"));
+
+        let transport = RTSPTransportBuilder::rtp_avp_udp()
+            .client_port(RTSPRange::new(5000, 5001))
+            .build()
+            .unwrap();
+        assert!(transport.is_compatible_with(&RTSPTransport::default()));
+
+        let transport = RTSPTransport::empty();
+        assert!(transport.mode().is_empty());
+        assert_eq!(transport.destination(), None);
+    }
+
+    #[test]
+    fn test_parse_verbose() {
+        gst::init().unwrap();
+
+        let report = RTSPTransport::parse_verbose(
+            "RTP/AVP;unicast;client_port=5000-5001;x-dynamic-rate=1; ttl=foo;ssrc=0000002A",
+        );
+        assert_eq!(
+            report.recognized(),
+            ["RTP/AVP", "unicast", "client_port", "ssrc"]
+        );
+        assert_eq!(report.unknown(), ["x-dynamic-rate=1"]);
+        assert_eq!(report.error_offset(), Some(56));
+        let transport = report.into_transport().unwrap();
+        assert_eq!(transport.client_port(), Some(RTSPRange::new(5000, 5001)));
+        assert_eq!(transport.ssrc(), 42);
+
+        let report = RTSPTransport::parse_verbose("FOO/BAR;unicast");
+        assert!(report.transport().is_none());
+        assert_eq!(report.recognized(), ["unicast"]);
+        assert_eq!(report.error_offset(), Some(0));
+    }
+
+    #[test]
+    fn test_mode() {
+        gst::init().unwrap();
+
+        let mode = "\"PLAY\",\"RECORD\"".parse::<TransportMode>().unwrap();
+        assert_eq!(mode, TransportMode::PLAY | TransportMode::RECORD);
+        assert_eq!(mode.to_string(), "PLAY,RECORD");
+        assert_eq!(
+            "record".parse::<TransportMode>().unwrap(),
+            TransportMode::RECORD
+        );
+        assert!("pause".parse::<TransportMode>().is_err());
+
+        let mut transport: RTSPTransport = "RTP/AVP;unicast;mode=\"RECORD\"".parse().unwrap();
+        assert_eq!(transport.mode(), TransportMode::RECORD);
+        transport.set_mode(mode);
+        assert_eq!(transport.mode(), mode);
+
+        #[allow(deprecated)]
+        {
+            assert!(transport.is_mode_play());
+            transport.set_mode_play(false);
+            assert!(!transport.is_mode_play());
+            assert!(transport.is_mode_record());
+        }
+    }
+
+    #[test]
+    fn test_compatible() {
+        gst::init().unwrap();
+
+        let transport: RTSPTransport = "RTP/AVP;unicast;client_port=5000-5001;ssrc=0000002A"
+            .parse()
+            .unwrap();
+        assert!(transport.matches_profile(
+            RTSPTransMode::RTP,
+            RTSPProfile::AVP,
+            RTSPLowerTrans::UDP
+        ));
+        assert!(!transport.matches_profile(
+            RTSPTransMode::RTP,
+            RTSPProfile::SAVP,
+            RTSPLowerTrans::UDP
+        ));
+
+        let other: RTSPTransport = "RTP/AVP;unicast;client_port=6000-6001;ttl=4"
+            .parse()
+            .unwrap();
+        assert!(transport.is_compatible_with(&other));
+
+        let other: RTSPTransport = "RTP/AVP;unicast;client_port=5000-5001;mode=\"RECORD\""
+            .parse()
+            .unwrap();
+        assert!(!transport.is_compatible_with(&other));
+
+        let other: RTSPTransport = "RTP/AVP/TCP;unicast;interleaved=0-1".parse().unwrap();
+        assert!(!transport.is_compatible_with(&other));
+    }
+
+    #[test]
+    fn test_range() {
+        let range = RTSPRange::new(5000, 5001);
+        assert!(range.is_valid());
+        assert!(range.contains(5001));
+        assert!(!range.contains(5002));
+        assert_eq!(range.len(), 2);
+        assert_eq!(range.rtp_rtcp_pair(), Some((5000, 5001)));
+        assert_eq!(range.to_string(), "5000-5001");
+
+        let range = RTSPRange::single(5000);
+        assert_eq!(range.len(), 1);
+        assert_eq!(range.rtp_rtcp_pair(), Some((5000, 5001)));
+        assert_eq!(range.to_string(), "5000");
+
+        assert_eq!(RTSPRange::new(5001, 5002).rtp_rtcp_pair(), None);
+        assert_eq!(RTSPRange::new(5000, 5003).rtp_rtcp_pair(), None);
+        assert_eq!(
+            RTSPRange::new(65534, 65535).rtp_rtcp_pair(),
+            Some((65534, 65535))
+        );
+        assert_eq!(RTSPRange::new(70000, 70001).rtp_rtcp_pair(), None);
+
+        let range = RTSPRange::unspecified();
+        assert!(!range.is_valid());
+        assert!(range.is_empty());
+        assert!(!range.contains(-1));
+        assert_eq!(RTSPRange::default(), range);
+        assert!(!RTSPRange::new(10, 5).is_valid());
+    }
+
+    #[test]
+    fn test_builder() {
+        gst::init().unwrap();
+
+        let transport = RTSPTransport::builder()
+            .client_port(RTSPRange::new(5000, 5001))
+            .build()
+            .unwrap();
+        assert_eq!(transport.lower_transport(), RTSPLowerTrans::UDP);
+        assert_eq!(transport.client_port(), Some(RTSPRange::new(5000, 5001)));
+        assert_eq!(transport.interleaved(), None);
+
+        let transport = RTSPTransport::builder()
+            .lower_transport(RTSPLowerTrans::TCP)
+            .interleaved(RTSPRange::new(0, 1))
+            .build()
+            .unwrap();
+        assert_eq!(transport.interleaved(), Some(RTSPRange::new(0, 1)));
+
+        assert_eq!(
+            RTSPTransport::builder()
+                .lower_transport(RTSPLowerTrans::TCP)
+                .build()
+                .unwrap_err(),
+            TransportBuildError::MissingInterleaved
+        );
+        assert_eq!(
+            RTSPTransport::builder()
+                .lower_transport(RTSPLowerTrans::UDP_MCAST)
+                .ttl(16)
+                .build()
+                .unwrap_err(),
+            TransportBuildError::MissingDestination
+        );
+        assert_eq!(
+            RTSPTransport::builder()
+                .lower_transport(RTSPLowerTrans::UDP_MCAST)
+                .destination("224.2.0.1")
+                .build()
+                .unwrap_err(),
+            TransportBuildError::MissingTtl
+        );
+        assert_eq!(
+            RTSPTransport::builder()
+                .client_port(RTSPRange::new(5001, 5000))
+                .build()
+                .unwrap_err(),
+            TransportBuildError::InvalidRange {
+                field: "client_port",
+                range: RTSPRange::new(5001, 5000)
+            }
+        );
+        assert_eq!(
+            RTSPTransport::builder()
+                .server_port(RTSPRange::new(5001, 5002))
+                .build()
+                .unwrap_err(),
+            TransportBuildError::InvalidPortPair {
+                field: "server_port",
+                range: RTSPRange::new(5001, 5002)
+            }
+        );
+    }
+}
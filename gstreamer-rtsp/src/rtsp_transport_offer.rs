@@ -0,0 +1,208 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::fmt;
+
+use crate::{RTSPHeaderField, RTSPMessage, RTSPTransport, TransportMode};
+
+// rustdoc-stripper-ignore-next
+/// Preference-ordered transport alternatives offered in a SETUP request.
+///
+/// The offer is formatted as a single comma-separated `Transport` header and
+/// can afterwards be used to find out which of the alternatives the server
+/// selected in its response.
+#[derive(Debug, Clone, Default)]
+pub struct RTSPTransportOffer {
+    alternatives: Vec<RTSPTransport>,
+}
+
+impl RTSPTransportOffer {
+    pub fn new() -> Self {
+        skip_assert_initialized!();
+        Self::default()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Appends `transport` with a lower preference than all previously added
+    /// alternatives.
+    pub fn push(&mut self, transport: RTSPTransport) {
+        self.alternatives.push(transport);
+    }
+
+    pub fn alternative(mut self, transport: RTSPTransport) -> Self {
+        self.push(transport);
+        self
+    }
+
+    pub fn alternatives(&self) -> &[RTSPTransport] {
+        &self.alternatives
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.alternatives.is_empty()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a copy of the offer with the mode of all alternatives set to
+    /// `mode`, e.g. [`TransportMode::RECORD`] for publishing streams.
+    pub fn with_mode(&self, mode: TransportMode) -> Self {
+        self.alternatives
+            .iter()
+            .cloned()
+            .map(|mut transport| {
+                transport.set_mode(mode);
+                transport
+            })
+            .collect()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the index of the alternative the server accepted with
+    /// `transport`.
+    ///
+    /// Alternatives have to use the same transport, profile and lower
+    /// transport as the reply. If several of them do, the one with the same
+    /// client ports or interleaved channels is preferred, otherwise the most
+    /// preferred one is selected.
+    pub fn match_transport(&self, transport: &RTSPTransport) -> Option<usize> {
+        let mut candidates = self.alternatives.iter().enumerate().filter(|(_, alt)| {
+            alt.matches_profile(
+                transport.trans(),
+                transport.profile(),
+                transport.lower_transport(),
+            )
+        });
+
+        let first = candidates.clone().next()?.0;
+        let exact = candidates.find(|(_, alt)| {
+            (transport.client_port().is_some() && alt.client_port() == transport.client_port())
+                || (transport.interleaved().is_some()
+                    && alt.interleaved() == transport.interleaved())
+        });
+
+        Some(exact.map_or(first, |(idx, _)| idx))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the index of the accepted alternative together with the
+    /// transport from the `Transport` header of a SETUP `response`.
+    pub fn match_response(&self, response: &RTSPMessage) -> Option<(usize, RTSPTransport)> {
+        let header = response.header(RTSPHeaderField::Transport, 0)?;
+
+        RTSPTransport::parse_list(header)
+            .into_iter()
+            .find_map(|transport| Some((self.match_transport(&transport)?, transport)))
+    }
+}
+
+impl FromIterator<RTSPTransport> for RTSPTransportOffer {
+    fn from_iter<T: IntoIterator<Item = RTSPTransport>>(iter: T) -> Self {
+        skip_assert_initialized!();
+        Self {
+            alternatives: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl fmt::Display for RTSPTransportOffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut first = true;
+        for text in self.alternatives.iter().filter_map(RTSPTransport::as_text) {
+            if !first {
+                f.write_str(",")?;
+            }
+            f.write_str(&text)?;
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RTSPLowerTrans, RTSPRange, RTSPStatusCode};
+
+    fn offer() -> RTSPTransportOffer {
+        RTSPTransportOffer::new()
+            .alternative(
+                RTSPTransport::builder()
+                    .client_port(RTSPRange::new(5000, 5001))
+                    .build()
+                    .unwrap(),
+            )
+            .alternative(
+                RTSPTransport::builder()
+                    .lower_transport(RTSPLowerTrans::TCP)
+                    .interleaved(RTSPRange::new(0, 1))
+                    .build()
+                    .unwrap(),
+            )
+            .alternative(
+                RTSPTransport::builder()
+                    .lower_transport(RTSPLowerTrans::UDP_MCAST)
+                    .destination("224.2.0.1")
+                    .ttl(16)
+                    .build()
+                    .unwrap(),
+            )
+    }
+
+    #[test]
+    fn test_header() {
+        gst::init().unwrap();
+
+        let header = offer().to_string();
+        let transports = RTSPTransport::parse_list(&header);
+        assert_eq!(transports.len(), 3);
+        assert_eq!(
+            transports[0].client_port(),
+            Some(RTSPRange::new(5000, 5001))
+        );
+        assert_eq!(transports[1].interleaved(), Some(RTSPRange::new(0, 1)));
+        assert_eq!(transports[2].destination(), Some("224.2.0.1"));
+
+        assert_eq!(RTSPTransportOffer::new().to_string(), "");
+    }
+
+    #[test]
+    fn test_with_mode() {
+        gst::init().unwrap();
+
+        let offer = offer().with_mode(TransportMode::RECORD);
+        assert_eq!(offer.alternatives().len(), 3);
+        assert!(offer
+            .alternatives()
+            .iter()
+            .all(|transport| transport.mode() == TransportMode::RECORD));
+        assert!(offer.to_string().contains("RECORD"));
+    }
+
+    #[test]
+    fn test_match() {
+        gst::init().unwrap();
+
+        let offer = offer();
+        let matches = |s: &str| offer.match_transport(&s.parse().unwrap());
+
+        assert_eq!(
+            matches("RTP/AVP;unicast;client_port=5000-5001;server_port=6000-6001"),
+            Some(0)
+        );
+        assert_eq!(matches("RTP/AVP/TCP;unicast;interleaved=0-1"), Some(1));
+        assert_eq!(
+            matches("RTP/AVP;multicast;destination=224.2.0.1;port=5000-5001"),
+            Some(2)
+        );
+        assert_eq!(matches("RTP/SAVP;unicast;client_port=5000-5001"), None);
+
+        let response = RTSPMessage::response_builder(RTSPStatusCode::Ok)
+            .header(
+                RTSPHeaderField::Transport,
+                "RTP/AVP/TCP;unicast;interleaved=0-1;ssrc=0000002A",
+            )
+            .build();
+        let (idx, transport) = offer.match_response(&response).unwrap();
+        assert_eq!(idx, 1);
+        assert_eq!(transport.ssrc(), 42);
+    }
+}
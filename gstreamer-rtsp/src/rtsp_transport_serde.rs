@@ -0,0 +1,170 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use serde::{
+    de::{Deserialize, Deserializer},
+    ser::{Serialize, Serializer},
+};
+
+use crate::{RTSPLowerTrans, RTSPProfile, RTSPRange, RTSPTransMode, RTSPTransport, TransportMode};
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct RTSPTransportSerde {
+    trans: RTSPTransMode,
+    profile: RTSPProfile,
+    lower_transport: RTSPLowerTrans,
+    destination: Option<String>,
+    source: Option<String>,
+    layers: u32,
+    mode: TransportMode,
+    append: bool,
+    interleaved: Option<RTSPRange>,
+    ttl: u32,
+    port: Option<RTSPRange>,
+    client_port: Option<RTSPRange>,
+    server_port: Option<RTSPRange>,
+    ssrc: u32,
+}
+
+impl Serialize for RTSPTransport {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let transport = RTSPTransportSerde {
+            trans: self.trans(),
+            profile: self.profile(),
+            lower_transport: self.lower_transport(),
+            destination: self.destination().map(String::from),
+            source: self.source().map(String::from),
+            layers: self.layers(),
+            mode: self.mode(),
+            append: self.is_append(),
+            interleaved: self.interleaved(),
+            ttl: self.ttl(),
+            port: self.port(),
+            client_port: self.client_port(),
+            server_port: self.server_port(),
+            ssrc: self.ssrc(),
+        };
+        transport.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RTSPTransport {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        RTSPTransportSerde::deserialize(deserializer).map(|de| {
+            let mut transport = RTSPTransport::new();
+            transport.set_trans(de.trans);
+            transport.set_profile(de.profile);
+            transport.set_lower_transport(de.lower_transport);
+            transport.set_destination(de.destination.as_deref());
+            transport.set_source(de.source.as_deref());
+            transport.set_layers(de.layers);
+            transport.set_mode(de.mode);
+            transport.set_append(de.append);
+            transport.set_interleaved(de.interleaved);
+            transport.set_ttl(de.ttl);
+            transport.set_port(de.port);
+            transport.set_client_port(de.client_port);
+            transport.set_server_port(de.server_port);
+            transport.set_ssrc(de.ssrc);
+            transport
+        })
+    }
+}
+
+// Serialized like the GLib flags types, e.g. `play+record`
+impl Serialize for TransportMode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = String::new();
+        for (nick, mode) in [
+            ("play", TransportMode::PLAY),
+            ("record", TransportMode::RECORD),
+        ] {
+            if self.contains(mode) {
+                if !s.is_empty() {
+                    s.push('+');
+                }
+                s.push_str(nick);
+            }
+        }
+        serializer.serialize_str(&s)
+    }
+}
+
+impl<'de> Deserialize<'de> for TransportMode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        let s = String::deserialize(deserializer)?;
+        let mut mode = TransportMode::empty();
+        for nick in s.split('+').filter(|nick| !nick.is_empty()) {
+            mode |= match nick {
+                "play" => TransportMode::PLAY,
+                "record" => TransportMode::RECORD,
+                _ => {
+                    return Err(serde::de::Error::custom(format!(
+                        "Invalid transport mode '{nick}'"
+                    )))
+                }
+            };
+        }
+        Ok(mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RTSPLowerTrans, RTSPRange, RTSPTransport};
+
+    #[test]
+    fn test_serialize() {
+        gst::init().unwrap();
+
+        let transport = RTSPTransport::parse(
+            "RTP/AVP;multicast;destination=224.2.0.1;port=5000-5001;ttl=16;ssrc=0000002A",
+        )
+        .unwrap();
+
+        let res = serde_json::to_string(&transport).unwrap();
+        assert_eq!(
+            res,
+            concat!(
+                r#"{"trans":"rtp","profile":"avp","lower_transport":"udp-mcast","#,
+                r#""destination":"224.2.0.1","source":null,"layers":0,"#,
+                r#""mode":"play","append":false,"#,
+                r#""interleaved":null,"ttl":16,"port":{"min":5000,"max":5001},"#,
+                r#""client_port":null,"server_port":null,"ssrc":42}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_mode() {
+        let mode = crate::TransportMode::PLAY | crate::TransportMode::RECORD;
+        let json = serde_json::to_string(&mode).unwrap();
+        assert_eq!(json, r#""play+record""#);
+        assert_eq!(
+            serde_json::from_str::<crate::TransportMode>(&json).unwrap(),
+            mode
+        );
+        assert!(serde_json::from_str::<crate::TransportMode>(r#""pause""#).is_err());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        gst::init().unwrap();
+
+        let transport = RTSPTransport::builder()
+            .lower_transport(RTSPLowerTrans::TCP)
+            .interleaved(RTSPRange::new(2, 3))
+            .source("192.168.1.1")
+            .ssrc(1234)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_string(&transport).unwrap();
+        let deserialized: RTSPTransport = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.to_string(), transport.to_string());
+        assert_eq!(deserialized.interleaved(), Some(RTSPRange::new(2, 3)));
+        assert_eq!(deserialized.source(), Some("192.168.1.1"));
+        assert_eq!(deserialized.ssrc(), 1234);
+    }
+}
@@ -0,0 +1,170 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    net::{IpAddr, SocketAddr},
+    ops::RangeInclusive,
+};
+
+use gio::prelude::*;
+
+use crate::{RTSPRange, RTSPTransportBuilder};
+
+// Number of ephemeral ports tried before giving up
+const EPHEMERAL_ATTEMPTS: usize = 16;
+
+// rustdoc-stripper-ignore-next
+/// Bound UDP sockets for an even RTP port and the following RTCP port.
+///
+/// The ports stay reserved as long as the value is alive, so they can be
+/// offered as client ports in a SETUP request without another process taking
+/// them in between. Dropping the value after a failed SETUP releases them.
+#[derive(Debug)]
+pub struct UdpPortPair {
+    rtp: gio::Socket,
+    rtcp: gio::Socket,
+    range: RTSPRange,
+}
+
+impl UdpPortPair {
+    // rustdoc-stripper-ignore-next
+    /// Binds a port pair on `addr`, either inside `ports` or on ephemeral
+    /// ports chosen by the operating system.
+    pub fn bind(addr: IpAddr, ports: Option<RangeInclusive<u16>>) -> Result<Self, glib::Error> {
+        assert_initialized_main_thread!();
+
+        match ports {
+            Some(ports) => {
+                // Port 0 would make the OS choose a random port
+                let start = (*ports.start()).max(2);
+                let start = start.saturating_add(start % 2);
+                let end = ports.end().saturating_sub(1);
+                for port in (start..=end).step_by(2) {
+                    if let Ok(pair) = Self::bind_at(addr, port) {
+                        return Ok(pair);
+                    }
+                }
+
+                Err(glib::Error::new(
+                    gio::IOErrorEnum::AddressInUse,
+                    &format!(
+                        "No free UDP port pair in range {}-{}",
+                        ports.start(),
+                        ports.end()
+                    ),
+                ))
+            }
+            None => {
+                let mut last_err = None;
+                for _ in 0..EPHEMERAL_ATTEMPTS {
+                    // Let the OS pick a port and retry if it's odd or its
+                    // successor is taken
+                    let rtp = new_socket(addr, 0)?;
+                    let port = local_port(&rtp)?;
+                    if port % 2 != 0 || port == u16::MAX {
+                        continue;
+                    }
+
+                    match new_socket(addr, port + 1) {
+                        Ok(rtcp) => return Ok(Self::new(rtp, rtcp, port)),
+                        Err(err) => last_err = Some(err),
+                    }
+                }
+
+                Err(last_err.unwrap_or_else(|| {
+                    glib::Error::new(
+                        gio::IOErrorEnum::AddressInUse,
+                        "No free ephemeral UDP port pair",
+                    )
+                }))
+            }
+        }
+    }
+
+    fn bind_at(addr: IpAddr, port: u16) -> Result<Self, glib::Error> {
+        let rtp = new_socket(addr, port)?;
+        let rtcp = new_socket(addr, port + 1)?;
+        Ok(Self::new(rtp, rtcp, port))
+    }
+
+    fn new(rtp: gio::Socket, rtcp: gio::Socket, port: u16) -> Self {
+        Self {
+            rtp,
+            rtcp,
+            range: RTSPRange::new(i32::from(port), i32::from(port) + 1),
+        }
+    }
+
+    pub fn rtp_socket(&self) -> &gio::Socket {
+        &self.rtp
+    }
+
+    pub fn rtcp_socket(&self) -> &gio::Socket {
+        &self.rtcp
+    }
+
+    pub fn range(&self) -> RTSPRange {
+        self.range
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the bound ports as client ports of `builder`.
+    pub fn configure(&self, builder: RTSPTransportBuilder) -> RTSPTransportBuilder {
+        builder.client_port(self.range)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the RTP and RTCP sockets, e.g. for `udpsrc` after the SETUP
+    /// request succeeded.
+    pub fn into_sockets(self) -> (gio::Socket, gio::Socket) {
+        (self.rtp, self.rtcp)
+    }
+}
+
+fn new_socket(addr: IpAddr, port: u16) -> Result<gio::Socket, glib::Error> {
+    let family = match addr {
+        IpAddr::V4(_) => gio::SocketFamily::Ipv4,
+        IpAddr::V6(_) => gio::SocketFamily::Ipv6,
+    };
+
+    let socket = gio::Socket::new(family, gio::SocketType::Datagram, gio::SocketProtocol::Udp)?;
+    socket.bind(
+        &gio::InetSocketAddress::from(SocketAddr::new(addr, port)),
+        false,
+    )?;
+
+    Ok(socket)
+}
+
+fn local_port(socket: &gio::Socket) -> Result<u16, glib::Error> {
+    let addr = socket
+        .local_address()?
+        .downcast::<gio::InetSocketAddress>()
+        .map_err(|_| glib::Error::new(gio::IOErrorEnum::Failed, "Not an inet socket"))?;
+
+    Ok(addr.port())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bind() {
+        gst::init().unwrap();
+
+        let localhost = IpAddr::from([127, 0, 0, 1]);
+        let pair = UdpPortPair::bind(localhost, None).unwrap();
+        let (rtp, rtcp) = pair.range().rtp_rtcp_pair().unwrap();
+        assert_eq!(local_port(pair.rtp_socket()).unwrap(), rtp);
+        assert_eq!(local_port(pair.rtcp_socket()).unwrap(), rtcp);
+
+        // The ports stay reserved while the pair is alive
+        assert!(UdpPortPair::bind(localhost, Some(rtp..=rtcp)).is_err());
+
+        let transport = pair
+            .configure(crate::RTSPTransport::builder())
+            .build()
+            .unwrap();
+        assert_eq!(transport.client_port(), Some(pair.range()));
+    }
+}
@@ -0,0 +1,27 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{ffi::CStr, fmt};
+
+use glib::translate::*;
+
+use crate::{ffi, RTSPVersion};
+
+impl RTSPVersion {
+    #[doc(alias = "gst_rtsp_version_as_text")]
+    pub fn as_str(&self) -> Option<&'static str> {
+        unsafe {
+            let text = ffi::gst_rtsp_version_as_text(self.into_glib());
+            if text.is_null() {
+                None
+            } else {
+                CStr::from_ptr(text).to_str().ok()
+            }
+        }
+    }
+}
+
+impl fmt::Display for RTSPVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str().unwrap_or("?"))
+    }
+}
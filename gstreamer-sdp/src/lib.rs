@@ -24,25 +24,39 @@ macro_rules! skip_assert_initialized {
 #[allow(unused_imports)]
 mod auto;
 
+mod mikey_message;
+pub use crate::mikey_message::*;
 mod sdp_attribute;
 pub use crate::sdp_attribute::*;
 mod sdp_bandwidth;
 pub use crate::sdp_bandwidth::*;
 mod sdp_connection;
 pub use crate::sdp_connection::*;
+mod sdp_group;
+pub use crate::sdp_group::*;
+mod sdp_ice;
+pub use crate::sdp_ice::*;
 mod sdp_key;
 pub use crate::sdp_key::*;
 pub mod sdp_media;
-pub use crate::sdp_media::{SDPMedia, SDPMediaRef};
+pub use crate::sdp_media::{SDPMedia, SDPMediaBuilder, SDPMediaRef};
 pub mod sdp_message;
-pub use crate::sdp_message::{SDPMessage, SDPMessageRef};
+pub use crate::sdp_message::{SDPMessage, SDPMessageBuilder, SDPMessageRef};
 mod sdp_origin;
 pub use crate::sdp_origin::*;
+mod sdp_rtp;
+pub use crate::sdp_rtp::*;
+#[cfg(feature = "serde")]
+mod sdp_serde;
 mod sdp_time;
 pub use crate::sdp_time::*;
 mod sdp_zone;
 pub use crate::sdp_zone::*;
 
+pub mod builders {
+    pub use crate::{MIKEYMessageBuilder, SDPMediaBuilder, SDPMessageBuilder};
+}
+
 // Re-export all the traits in a prelude module, so that applications
 // can always "use gst_sdp::prelude::*" without getting conflicts
 pub mod prelude {
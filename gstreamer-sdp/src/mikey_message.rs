@@ -0,0 +1,514 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{fmt, ptr};
+
+use glib::translate::*;
+
+use crate::ffi;
+
+gst::mini_object_wrapper!(MIKEYMessage, MIKEYMessageRef, ffi::GstMIKEYMessage, || {
+    ffi::gst_mikey_message_get_type()
+});
+
+impl MIKEYMessage {
+    #[doc(alias = "gst_mikey_message_new")]
+    pub fn new() -> Self {
+        assert_initialized_main_thread!();
+        unsafe { from_glib_full(ffi::gst_mikey_message_new()) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for an unencrypted pre-shared key message that
+    /// transports an SRTP master key, as used with `a=key-mgmt` in SDP.
+    pub fn builder() -> MIKEYMessageBuilder {
+        assert_initialized_main_thread!();
+        MIKEYMessageBuilder::new()
+    }
+
+    #[doc(alias = "gst_mikey_message_new_from_data")]
+    pub fn from_data(data: &[u8]) -> Result<Self, glib::Error> {
+        assert_initialized_main_thread!();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let msg = ffi::gst_mikey_message_new_from_data(
+                data.as_ptr() as glib::ffi::gconstpointer,
+                data.len(),
+                ptr::null_mut(),
+                &mut error,
+            );
+            if error.is_null() {
+                Ok(from_glib_full(msg))
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a message from the `srtp-key`, `srtp-cipher` and `srtp-auth`
+    /// fields of `application/x-srtp` caps.
+    #[doc(alias = "gst_mikey_message_new_from_caps")]
+    pub fn from_caps(caps: &gst::CapsRef) -> Result<Self, glib::BoolError> {
+        assert_initialized_main_thread!();
+        unsafe {
+            Option::<_>::from_glib_full(ffi::gst_mikey_message_new_from_caps(caps.as_mut_ptr()))
+                .ok_or_else(|| glib::bool_error!("Failed to create MIKEY message from caps"))
+        }
+    }
+}
+
+impl Default for MIKEYMessage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MIKEYMessageRef {
+    #[doc(alias = "CSB_id")]
+    pub fn csb_id(&self) -> u32 {
+        unsafe { (*self.as_ptr()).CSB_id }
+    }
+
+    #[doc(alias = "gst_mikey_message_get_n_cs")]
+    pub fn n_cs(&self) -> u32 {
+        unsafe { ffi::gst_mikey_message_get_n_cs(self.as_ptr()) }
+    }
+
+    #[doc(alias = "gst_mikey_message_get_cs_srtp")]
+    pub fn cs_srtp(&self, idx: u32) -> Option<MIKEYCryptoSession> {
+        unsafe {
+            let map = ffi::gst_mikey_message_get_cs_srtp(self.as_ptr(), idx);
+            if map.is_null() {
+                None
+            } else {
+                Some(MIKEYCryptoSession {
+                    policy: (*map).policy,
+                    ssrc: (*map).ssrc,
+                    roc: (*map).roc,
+                })
+            }
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_add_cs_srtp")]
+    pub fn add_cs_srtp(&mut self, policy: u8, ssrc: u32, roc: u32) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_add_cs_srtp(self.as_mut_ptr(), policy, ssrc, roc),
+                "Failed to add crypto session"
+            )
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_get_n_payloads")]
+    pub fn n_payloads(&self) -> u32 {
+        unsafe { ffi::gst_mikey_message_get_n_payloads(self.as_ptr()) }
+    }
+
+    #[doc(alias = "gst_mikey_message_to_bytes")]
+    pub fn to_bytes(&self) -> Result<glib::Bytes, glib::Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let bytes =
+                ffi::gst_mikey_message_to_bytes(self.as_mut_ptr(), ptr::null_mut(), &mut error);
+            if error.is_null() {
+                Ok(from_glib_full(bytes))
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    #[doc(alias = "gst_mikey_message_base64_encode")]
+    pub fn base64_encode(&self) -> Result<glib::GString, glib::BoolError> {
+        unsafe {
+            Option::<_>::from_glib_full(ffi::gst_mikey_message_base64_encode(self.as_mut_ptr()))
+                .ok_or_else(|| glib::bool_error!("Failed to encode MIKEY message"))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value for an SDP `key-mgmt` attribute, i.e. `mikey`
+    /// followed by the base64 encoded message.
+    pub fn key_mgmt(&self) -> Result<String, glib::BoolError> {
+        Ok(format!("mikey {}", self.base64_encode()?))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the `srtp-key`, `srtp-cipher`, `srtp-auth`, `srtcp-cipher` and
+    /// `srtcp-auth` fields of `caps` from the message.
+    #[doc(alias = "gst_mikey_message_to_caps")]
+    pub fn to_caps(&self, caps: &mut gst::CapsRef) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_to_caps(self.as_ptr(), caps.as_mut_ptr()),
+                "Failed to set caps from MIKEY message"
+            )
+        }
+    }
+}
+
+impl fmt::Debug for MIKEYMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        MIKEYMessageRef::fmt(self, f)
+    }
+}
+
+impl fmt::Debug for MIKEYMessageRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MIKEYMessage")
+            .field("csb_id", &self.csb_id())
+            .field(
+                "crypto_sessions",
+                &(0..self.n_cs())
+                    .filter_map(|idx| self.cs_srtp(idx))
+                    .collect::<Vec<_>>(),
+            )
+            .field("n_payloads", &self.n_payloads())
+            .finish()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// An SRTP crypto session of a MIKEY message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[doc(alias = "GstMIKEYMapSRTP")]
+pub struct MIKEYCryptoSession {
+    // rustdoc-stripper-ignore-next
+    /// The security policy applied to the stream.
+    pub policy: u8,
+    pub ssrc: u32,
+    // rustdoc-stripper-ignore-next
+    /// The current rollover counter of the stream.
+    pub roc: u32,
+}
+
+// rustdoc-stripper-ignore-next
+/// The SRTP encryption transform of a MIKEY security policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum SrtpCipher {
+    Null,
+    #[default]
+    AesCm128,
+    AesCm256,
+    AesGcm128,
+    AesGcm256,
+}
+
+impl SrtpCipher {
+    // rustdoc-stripper-ignore-next
+    /// Length of the master key in bytes.
+    pub fn key_len(self) -> usize {
+        match self {
+            SrtpCipher::Null => 0,
+            SrtpCipher::AesCm128 | SrtpCipher::AesGcm128 => 16,
+            SrtpCipher::AesCm256 | SrtpCipher::AesGcm256 => 32,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Length of the master salt in bytes.
+    pub fn salt_len(self) -> usize {
+        match self {
+            SrtpCipher::Null | SrtpCipher::AesCm128 | SrtpCipher::AesCm256 => 14,
+            SrtpCipher::AesGcm128 | SrtpCipher::AesGcm256 => 12,
+        }
+    }
+
+    fn is_aead(self) -> bool {
+        matches!(self, SrtpCipher::AesGcm128 | SrtpCipher::AesGcm256)
+    }
+
+    fn enc_alg(self) -> ffi::GstMIKEYEncAlg {
+        match self {
+            SrtpCipher::Null => ffi::GST_MIKEY_ENC_NULL,
+            SrtpCipher::AesCm128 | SrtpCipher::AesCm256 => ffi::GST_MIKEY_ENC_AES_CM_128,
+            SrtpCipher::AesGcm128 | SrtpCipher::AesGcm256 => ffi::GST_MIKEY_ENC_AES_GCM_128,
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// The SRTP message authentication of a MIKEY security policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum SrtpAuth {
+    Null,
+    HmacSha1_32,
+    #[default]
+    HmacSha1_80,
+}
+
+impl SrtpAuth {
+    fn tag_len(self) -> u8 {
+        match self {
+            SrtpAuth::Null => 0,
+            SrtpAuth::HmacSha1_32 => 4,
+            SrtpAuth::HmacSha1_80 => 10,
+        }
+    }
+}
+
+// Length of the RAND payload, as recommended by RFC 3830
+const RAND_LEN: u8 = 16;
+// Length of the HMAC-SHA1 authentication key
+const AUTH_KEY_LEN: u8 = 20;
+// Length of the AES-GCM authentication tag
+const AEAD_AUTH_TAG_LEN: u8 = 16;
+
+// rustdoc-stripper-ignore-next
+/// Builder for [`MIKEYMessage`]s.
+///
+/// Without further configuration this creates a message for SRTP with
+/// AES-CM-128 encryption and HMAC-SHA1-80 authentication with a single
+/// crypto session for any SSRC, a timestamp and random data. Only the key
+/// has to be provided.
+#[derive(Debug, Clone)]
+#[must_use = "The builder must be built to be used"]
+pub struct MIKEYMessageBuilder {
+    csb_id: Option<u32>,
+    crypto_sessions: Vec<MIKEYCryptoSession>,
+    cipher: SrtpCipher,
+    auth: SrtpAuth,
+    key: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+impl MIKEYMessageBuilder {
+    fn new() -> Self {
+        skip_assert_initialized!();
+        Self {
+            csb_id: None,
+            crypto_sessions: Vec::new(),
+            cipher: SrtpCipher::default(),
+            auth: SrtpAuth::default(),
+            key: None,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the crypto session bundle ID. Defaults to a random value.
+    pub fn csb_id(self, csb_id: u32) -> Self {
+        Self {
+            csb_id: Some(csb_id),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds a crypto session for the stream with `ssrc` and the rollover
+    /// counter `roc`.
+    pub fn crypto_session(mut self, ssrc: u32, roc: u32) -> Self {
+        self.crypto_sessions.push(MIKEYCryptoSession {
+            policy: 0,
+            ssrc,
+            roc,
+        });
+        self
+    }
+
+    pub fn cipher(self, cipher: SrtpCipher) -> Self {
+        Self { cipher, ..self }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the message authentication. This is ignored for AES-GCM, which
+    /// provides authentication itself.
+    pub fn auth(self, auth: SrtpAuth) -> Self {
+        Self { auth, ..self }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the SRTP master key and salt, whose lengths have to match the
+    /// cipher.
+    ///
+    /// Both are transported together as traffic encryption key like
+    /// GStreamer's SRTP elements expect.
+    pub fn key(self, key: &[u8], salt: &[u8]) -> Self {
+        Self {
+            key: Some((key.to_vec(), salt.to_vec())),
+            ..self
+        }
+    }
+
+    pub fn build(self) -> Result<MIKEYMessage, glib::BoolError> {
+        let (key, salt) = self
+            .key
+            .ok_or_else(|| glib::bool_error!("MIKEY message without key"))?;
+        if key.len() != self.cipher.key_len() || salt.len() != self.cipher.salt_len() {
+            return Err(glib::bool_error!(
+                "Expected {} bytes key and {} bytes salt for {:?}",
+                self.cipher.key_len(),
+                self.cipher.salt_len(),
+                self.cipher
+            ));
+        }
+
+        let auth = if self.cipher.is_aead() {
+            SrtpAuth::Null
+        } else {
+            self.auth
+        };
+        let encrypt = u8::from(self.cipher != SrtpCipher::Null);
+        let mut params = vec![
+            (ffi::GST_MIKEY_SP_SRTP_ENC_ALG, self.cipher.enc_alg() as u8),
+            (ffi::GST_MIKEY_SP_SRTP_ENC_KEY_LEN, key.len() as u8),
+            (ffi::GST_MIKEY_SP_SRTP_SALT_KEY_LEN, salt.len() as u8),
+            (ffi::GST_MIKEY_SP_SRTP_SRTP_ENC, encrypt),
+            (ffi::GST_MIKEY_SP_SRTP_SRTCP_ENC, encrypt),
+        ];
+        if self.cipher.is_aead() {
+            params.push((ffi::GST_MIKEY_SP_SRTP_AEAD_AUTH_TAG_LEN, AEAD_AUTH_TAG_LEN));
+        } else if auth == SrtpAuth::Null {
+            params.push((
+                ffi::GST_MIKEY_SP_SRTP_AUTH_ALG,
+                ffi::GST_MIKEY_MAC_NULL as u8,
+            ));
+            params.push((ffi::GST_MIKEY_SP_SRTP_SRTP_AUTH, 0));
+        } else {
+            params.extend([
+                (
+                    ffi::GST_MIKEY_SP_SRTP_AUTH_ALG,
+                    ffi::GST_MIKEY_MAC_HMAC_SHA_1_160 as u8,
+                ),
+                (ffi::GST_MIKEY_SP_SRTP_AUTH_KEY_LEN, AUTH_KEY_LEN),
+                (ffi::GST_MIKEY_SP_SRTP_AUTH_TAG_LEN, auth.tag_len()),
+                (ffi::GST_MIKEY_SP_SRTP_SRTP_AUTH, 1),
+            ]);
+        }
+
+        let mut crypto_sessions = self.crypto_sessions;
+        if crypto_sessions.is_empty() {
+            crypto_sessions.push(MIKEYCryptoSession {
+                policy: 0,
+                ssrc: 0,
+                roc: 0,
+            });
+        }
+
+        let msg = MIKEYMessage::new();
+        unsafe {
+            let ptr = msg.as_mut_ptr();
+
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_set_info(
+                    ptr,
+                    ffi::GST_MIKEY_VERSION as u8,
+                    ffi::GST_MIKEY_TYPE_PSK_INIT,
+                    glib::ffi::GFALSE,
+                    ffi::GST_MIKEY_PRF_MIKEY_1,
+                    self.csb_id.unwrap_or_else(glib::random_int),
+                    ffi::GST_MIKEY_MAP_TYPE_SRTP,
+                ),
+                "Failed to set MIKEY message info"
+            )?;
+
+            for cs in &crypto_sessions {
+                glib::result_from_gboolean!(
+                    ffi::gst_mikey_message_add_cs_srtp(ptr, cs.policy, cs.ssrc, cs.roc),
+                    "Failed to add crypto session"
+                )?;
+            }
+
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_add_t_now_ntp_utc(ptr),
+                "Failed to add timestamp"
+            )?;
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_add_rand_len(ptr, RAND_LEN),
+                "Failed to add random data"
+            )?;
+
+            let sp = ffi::gst_mikey_payload_new(ffi::GST_MIKEY_PT_SP);
+            ffi::gst_mikey_payload_sp_set(sp, 0, ffi::GST_MIKEY_SEC_PROTO_SRTP);
+            for (type_, value) in &params {
+                ffi::gst_mikey_payload_sp_add_param(sp, *type_ as u8, 1, value);
+            }
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_add_payload(ptr, sp),
+                "Failed to add security policy"
+            )?;
+
+            let key_data = [key, salt].concat();
+            let kemac = ffi::gst_mikey_payload_new(ffi::GST_MIKEY_PT_KEMAC);
+            ffi::gst_mikey_payload_kemac_set(
+                kemac,
+                ffi::GST_MIKEY_ENC_NULL,
+                ffi::GST_MIKEY_MAC_NULL,
+            );
+            let pkd = ffi::gst_mikey_payload_new(ffi::GST_MIKEY_PT_KEY_DATA);
+            ffi::gst_mikey_payload_key_data_set_key(
+                pkd,
+                ffi::GST_MIKEY_KD_TEK,
+                key_data.len() as u16,
+                key_data.as_ptr(),
+            );
+            ffi::gst_mikey_payload_kemac_add_sub(kemac, pkd);
+            glib::result_from_gboolean!(
+                ffi::gst_mikey_message_add_payload(ptr, kemac),
+                "Failed to add key data"
+            )?;
+        }
+
+        Ok(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        gst::init().unwrap();
+
+        let key = [0x01; 16];
+        let salt = [0x02; 14];
+        let msg = MIKEYMessage::builder()
+            .csb_id(42)
+            .crypto_session(0x1234_5678, 0)
+            .key(&key, &salt)
+            .build()
+            .unwrap();
+
+        assert_eq!(msg.csb_id(), 42);
+        assert_eq!(msg.n_cs(), 1);
+        assert_eq!(
+            msg.cs_srtp(0),
+            Some(MIKEYCryptoSession {
+                policy: 0,
+                ssrc: 0x1234_5678,
+                roc: 0
+            })
+        );
+        // Timestamp, random data, security policy and key data
+        assert_eq!(msg.n_payloads(), 4);
+        assert!(msg.key_mgmt().unwrap().starts_with("mikey "));
+
+        let mut caps = gst::Caps::new_empty_simple("application/x-srtp");
+        msg.to_caps(caps.make_mut()).unwrap();
+        let s = caps.structure(0).unwrap();
+        assert_eq!(s.get::<&str>("srtp-cipher").unwrap(), "aes-128-icm");
+        assert_eq!(s.get::<&str>("srtp-auth").unwrap(), "hmac-sha1-80");
+        let srtp_key = s.get::<gst::Buffer>("srtp-key").unwrap();
+        let map = srtp_key.map_readable().unwrap();
+        assert_eq!(&map[..16], &key);
+        assert_eq!(&map[16..], &salt);
+
+        let parsed = MIKEYMessage::from_data(&msg.to_bytes().unwrap()).unwrap();
+        assert_eq!(parsed.csb_id(), 42);
+        assert_eq!(parsed.cs_srtp(0), msg.cs_srtp(0));
+
+        let mut msg = msg;
+        msg.make_mut().add_cs_srtp(0, 0x8765_4321, 1).unwrap();
+        assert_eq!(msg.n_cs(), 2);
+        assert_eq!(msg.cs_srtp(1).map(|cs| cs.ssrc), Some(0x8765_4321));
+
+        assert!(MIKEYMessage::builder().build().is_err());
+        assert!(MIKEYMessage::builder()
+            .cipher(SrtpCipher::AesCm256)
+            .key(&key, &salt)
+            .build()
+            .is_err());
+    }
+}
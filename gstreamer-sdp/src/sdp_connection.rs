@@ -1,10 +1,18 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::{ffi::CStr, fmt, mem};
+use std::{ffi::CStr, fmt, mem, net::IpAddr};
 
 use crate::ffi;
 use glib::translate::*;
 
+// The SDP address type of `addr`
+pub(crate) fn addrtype(addr: &IpAddr) -> &'static str {
+    match addr {
+        IpAddr::V4(_) => "IP4",
+        IpAddr::V6(_) => "IP6",
+    }
+}
+
 #[repr(transparent)]
 #[doc(alias = "GstSDPConnection")]
 pub struct SDPConnection(pub(crate) ffi::GstSDPConnection);
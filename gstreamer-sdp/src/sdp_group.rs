@@ -0,0 +1,158 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{fmt, str};
+
+use crate::{SDPAttribute, SDPMediaRef, SDPMessageRef};
+
+// rustdoc-stripper-ignore-next
+/// The value of a `group` attribute (RFC 5888), e.g. `BUNDLE 0 1`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MediaGroup {
+    // rustdoc-stripper-ignore-next
+    /// The grouping semantics, e.g. `BUNDLE` or `LS`.
+    pub semantics: String,
+    // rustdoc-stripper-ignore-next
+    /// The media identifications (`mid` attributes) of the grouped media.
+    pub mids: Vec<String>,
+}
+
+impl MediaGroup {
+    pub fn is_bundle(&self) -> bool {
+        self.semantics.eq_ignore_ascii_case("BUNDLE")
+    }
+}
+
+impl fmt::Display for MediaGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.semantics)?;
+        for mid in &self.mids {
+            write!(f, " {mid}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl str::FromStr for MediaGroup {
+    type Err = glib::BoolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        skip_assert_initialized!();
+        let mut fields = s.split_ascii_whitespace();
+        let semantics = fields
+            .next()
+            .ok_or_else(|| glib::bool_error!("Missing semantics in group {}", s))?;
+
+        Ok(MediaGroup {
+            semantics: semantics.to_string(),
+            mids: fields.map(String::from).collect(),
+        })
+    }
+}
+
+impl SDPMediaRef {
+    pub fn mid(&self) -> Option<&str> {
+        self.attribute_val("mid")
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the media identification, replacing an existing one.
+    pub fn set_mid(&mut self, mid: &str) {
+        match self.attributes().position(|attr| attr.key() == "mid") {
+            Some(idx) => self
+                .replace_attribute(idx as u32, SDPAttribute::new("mid", Some(mid)))
+                .unwrap(),
+            None => self.add_attribute("mid", Some(mid)),
+        }
+    }
+}
+
+impl SDPMessageRef {
+    // rustdoc-stripper-ignore-next
+    /// Iterates over the `group` attributes of the session.
+    ///
+    /// Attributes that can't be parsed are skipped.
+    pub fn groups(&self) -> impl Iterator<Item = MediaGroup> + '_ {
+        self.attributes()
+            .filter(|attr| attr.key() == "group")
+            .filter_map(|attr| attr.value()?.parse().ok())
+    }
+
+    pub fn add_group(&mut self, group: &MediaGroup) {
+        self.add_attribute("group", Some(&group.to_string()));
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the first `BUNDLE` group of the session.
+    pub fn bundle(&self) -> Option<MediaGroup> {
+        self.groups().find(MediaGroup::is_bundle)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the index of the media with the media identification `mid`.
+    pub fn media_index_by_mid(&self, mid: &str) -> Option<u32> {
+        self.medias()
+            .position(|media| media.mid() == Some(mid))
+            .map(|idx| idx as u32)
+    }
+
+    pub fn media_by_mid(&self, mid: &str) -> Option<&SDPMediaRef> {
+        self.media(self.media_index_by_mid(mid)?)
+    }
+
+    pub fn media_by_mid_mut(&mut self, mid: &str) -> Option<&mut SDPMediaRef> {
+        let idx = self.media_index_by_mid(mid)?;
+        self.media_mut(idx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group() {
+        let group = "BUNDLE audio video".parse::<MediaGroup>().unwrap();
+        assert!(group.is_bundle());
+        assert_eq!(group.mids, ["audio", "video"]);
+        assert_eq!(group.to_string(), "BUNDLE audio video");
+
+        let group = "LS".parse::<MediaGroup>().unwrap();
+        assert!(!group.is_bundle());
+        assert!(group.mids.is_empty());
+
+        assert!(" ".parse::<MediaGroup>().is_err());
+    }
+
+    #[test]
+    fn test_message() {
+        gst::init().unwrap();
+
+        let mut message = crate::SDPMessage::new();
+        message.add_group(&MediaGroup {
+            semantics: String::from("LS"),
+            mids: vec![String::from("a"), String::from("v")],
+        });
+        message.add_attribute("group", Some("BUNDLE a v"));
+
+        for mid in ["a", "v"] {
+            let mut media = crate::SDPMedia::new();
+            media.set_mid("x");
+            media.set_mid(mid);
+            assert_eq!(media.attributes_len(), 1);
+            message.add_media(media);
+        }
+
+        assert_eq!(message.groups().count(), 2);
+        assert_eq!(message.bundle().unwrap().mids, ["a", "v"]);
+        assert_eq!(message.media_index_by_mid("v"), Some(1));
+        assert_eq!(message.media_by_mid("a").unwrap().mid(), Some("a"));
+        assert!(message.media_by_mid("x").is_none());
+
+        message
+            .media_by_mid_mut("v")
+            .unwrap()
+            .set_port_info(5000, 1);
+        assert_eq!(message.media(1).unwrap().port(), 5000);
+    }
+}
@@ -0,0 +1,302 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{fmt, str};
+
+use crate::{SDPAttribute, SDPMediaRef, SDPMessageRef};
+
+// rustdoc-stripper-ignore-next
+/// The value of an ICE `candidate` attribute (RFC 8839), e.g.
+/// `1 1 UDP 2130706431 192.168.1.10 50000 typ host`.
+///
+/// Parsing also accepts the `candidate:` prefix as used for trickled
+/// candidates, while formatting omits it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IceCandidate {
+    pub foundation: String,
+    pub component: u16,
+    // rustdoc-stripper-ignore-next
+    /// The transport protocol, e.g. `UDP` or `TCP`.
+    pub transport: String,
+    pub priority: u32,
+    // rustdoc-stripper-ignore-next
+    /// The IP address or, e.g. for mDNS candidates, the host name.
+    pub address: String,
+    pub port: u16,
+    // rustdoc-stripper-ignore-next
+    /// The candidate type, e.g. `host`, `srflx`, `prflx` or `relay`.
+    pub candidate_type: String,
+    pub related_address: Option<String>,
+    pub related_port: Option<u16>,
+    // rustdoc-stripper-ignore-next
+    /// Extension attributes, e.g. `tcptype` or `generation`.
+    pub extensions: Vec<(String, String)>,
+}
+
+impl fmt::Display for IceCandidate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {} typ {}",
+            self.foundation,
+            self.component,
+            self.transport,
+            self.priority,
+            self.address,
+            self.port,
+            self.candidate_type
+        )?;
+        if let Some(ref related_address) = self.related_address {
+            write!(f, " raddr {related_address}")?;
+        }
+        if let Some(related_port) = self.related_port {
+            write!(f, " rport {related_port}")?;
+        }
+        for (name, value) in &self.extensions {
+            write!(f, " {name} {value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl str::FromStr for IceCandidate {
+    type Err = glib::BoolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        skip_assert_initialized!();
+        let value = s.trim();
+        let value = value.strip_prefix("candidate:").unwrap_or(value);
+        let mut fields = value.split_ascii_whitespace();
+        let mut next = |name: &str| {
+            fields
+                .next()
+                .ok_or_else(|| glib::bool_error!("Missing {} in candidate {}", name, s))
+        };
+        let invalid = |name: &str| glib::bool_error!("Invalid {} in candidate {}", name, s);
+
+        let foundation = next("foundation")?.to_string();
+        let component = next("component")?
+            .parse::<u16>()
+            .map_err(|_| invalid("component"))?;
+        let transport = next("transport")?.to_string();
+        let priority = next("priority")?
+            .parse::<u32>()
+            .map_err(|_| invalid("priority"))?;
+        let address = next("address")?.to_string();
+        let port = next("port")?.parse::<u16>().map_err(|_| invalid("port"))?;
+        if next("type")? != "typ" {
+            return Err(invalid("type"));
+        }
+        let candidate_type = next("type")?.to_string();
+
+        let mut candidate = IceCandidate {
+            foundation,
+            component,
+            transport,
+            priority,
+            address,
+            port,
+            candidate_type,
+            related_address: None,
+            related_port: None,
+            extensions: Vec::new(),
+        };
+
+        while let Ok(name) = next("extension") {
+            let value = next(name)?;
+            match name {
+                "raddr" => candidate.related_address = Some(value.to_string()),
+                "rport" => {
+                    candidate.related_port =
+                        Some(value.parse::<u16>().map_err(|_| invalid("rport"))?)
+                }
+                _ => candidate
+                    .extensions
+                    .push((name.to_string(), value.to_string())),
+            }
+        }
+
+        Ok(candidate)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// The ICE username fragment and password of the `ice-ufrag` and `ice-pwd`
+/// attributes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IceCredentials {
+    pub ufrag: String,
+    pub pwd: String,
+}
+
+impl SDPMediaRef {
+    // rustdoc-stripper-ignore-next
+    /// Iterates over the `candidate` attributes of the media.
+    ///
+    /// Attributes that can't be parsed are skipped.
+    pub fn candidates(&self) -> impl Iterator<Item = IceCandidate> + '_ {
+        self.attributes()
+            .filter(|attr| attr.key() == "candidate")
+            .filter_map(|attr| attr.value()?.parse().ok())
+    }
+
+    pub fn add_candidate(&mut self, candidate: &IceCandidate) {
+        self.add_attribute("candidate", Some(&candidate.to_string()));
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the ICE credentials of the media.
+    ///
+    /// If the media has none, the ones of the session apply.
+    pub fn ice_credentials(&self) -> Option<IceCredentials> {
+        Some(IceCredentials {
+            ufrag: self.attribute_val("ice-ufrag")?.to_string(),
+            pwd: self.attribute_val("ice-pwd")?.to_string(),
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the ICE credentials of the media, replacing existing ones.
+    pub fn set_ice_credentials(&mut self, credentials: &IceCredentials) {
+        for (key, value) in [
+            ("ice-ufrag", credentials.ufrag.as_str()),
+            ("ice-pwd", credentials.pwd.as_str()),
+        ] {
+            match self.attributes().position(|attr| attr.key() == key) {
+                Some(idx) => self
+                    .replace_attribute(idx as u32, SDPAttribute::new(key, Some(value)))
+                    .unwrap(),
+                None => self.add_attribute(key, Some(value)),
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether the media contains an `end-of-candidates` attribute, i.e.
+    /// all candidates were gathered.
+    pub fn is_end_of_candidates(&self) -> bool {
+        self.attributes()
+            .any(|attr| attr.key() == "end-of-candidates")
+    }
+
+    pub fn set_end_of_candidates(&mut self) {
+        if !self.is_end_of_candidates() {
+            self.add_attribute("end-of-candidates", None);
+        }
+    }
+}
+
+impl SDPMessageRef {
+    // rustdoc-stripper-ignore-next
+    /// Returns the session level ICE credentials.
+    pub fn ice_credentials(&self) -> Option<IceCredentials> {
+        Some(IceCredentials {
+            ufrag: self.attribute_val("ice-ufrag")?.to_string(),
+            pwd: self.attribute_val("ice-pwd")?.to_string(),
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the session level ICE credentials, replacing existing ones.
+    pub fn set_ice_credentials(&mut self, credentials: &IceCredentials) {
+        for (key, value) in [
+            ("ice-ufrag", credentials.ufrag.as_str()),
+            ("ice-pwd", credentials.pwd.as_str()),
+        ] {
+            match self.attributes().position(|attr| attr.key() == key) {
+                Some(idx) => self
+                    .replace_attribute(idx as u32, SDPAttribute::new(key, Some(value)))
+                    .unwrap(),
+                None => self.add_attribute(key, Some(value)),
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether the session contains an `end-of-candidates` attribute, i.e.
+    /// all candidates of all media were gathered.
+    pub fn is_end_of_candidates(&self) -> bool {
+        self.attributes()
+            .any(|attr| attr.key() == "end-of-candidates")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candidate() {
+        let s = "3 1 tcp 1518280447 192.168.1.10 9 typ host tcptype active generation 0";
+        let candidate = s.parse::<IceCandidate>().unwrap();
+        assert_eq!(candidate.foundation, "3");
+        assert_eq!(candidate.component, 1);
+        assert_eq!(candidate.transport, "tcp");
+        assert_eq!(candidate.priority, 1518280447);
+        assert_eq!(candidate.port, 9);
+        assert_eq!(candidate.candidate_type, "host");
+        assert_eq!(
+            candidate.extensions,
+            [
+                (String::from("tcptype"), String::from("active")),
+                (String::from("generation"), String::from("0")),
+            ]
+        );
+        assert_eq!(candidate.to_string(), s);
+
+        let s = "candidate:842163049 1 udp 1677729535 203.0.113.5 61665 typ srflx raddr 10.0.0.2 rport 61665";
+        let candidate = s.parse::<IceCandidate>().unwrap();
+        assert_eq!(candidate.candidate_type, "srflx");
+        assert_eq!(candidate.related_address.as_deref(), Some("10.0.0.2"));
+        assert_eq!(candidate.related_port, Some(61665));
+        assert_eq!(candidate.to_string(), &s["candidate:".len()..]);
+
+        assert!("1 1 udp 1 10.0.0.1 5000 host"
+            .parse::<IceCandidate>()
+            .is_err());
+        assert!("1 1 udp 1 10.0.0.1 70000 typ host"
+            .parse::<IceCandidate>()
+            .is_err());
+        assert!("1 1 udp 1 10.0.0.1 5000 typ host generation"
+            .parse::<IceCandidate>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_media() {
+        gst::init().unwrap();
+
+        let mut media = crate::SDPMedia::new();
+        assert_eq!(media.ice_credentials(), None);
+
+        media.set_ice_credentials(&IceCredentials {
+            ufrag: String::from("a"),
+            pwd: String::from("b"),
+        });
+        media.set_ice_credentials(&IceCredentials {
+            ufrag: String::from("c"),
+            pwd: String::from("d"),
+        });
+        assert_eq!(media.attributes_len(), 2);
+        assert_eq!(
+            media.ice_credentials(),
+            Some(IceCredentials {
+                ufrag: String::from("c"),
+                pwd: String::from("d"),
+            })
+        );
+
+        let candidate = "1 1 UDP 2130706431 192.168.1.10 50000 typ host"
+            .parse::<IceCandidate>()
+            .unwrap();
+        media.add_candidate(&candidate);
+        media.add_attribute("candidate", Some("invalid"));
+        assert!(!media.is_end_of_candidates());
+        media.set_end_of_candidates();
+        media.set_end_of_candidates();
+
+        assert_eq!(media.candidates().collect::<Vec<_>>(), [candidate]);
+        assert!(media.is_end_of_candidates());
+        assert_eq!(media.attributes_len(), 5);
+    }
+}
@@ -2,6 +2,7 @@
 
 use std::{
     borrow::{Borrow, BorrowMut, ToOwned},
+    collections::BTreeMap,
     ffi::CStr,
     fmt, mem, ops, ptr,
 };
@@ -10,7 +11,7 @@ use glib::translate::*;
 
 use crate::{
     ffi, sdp_attribute::SDPAttribute, sdp_bandwidth::SDPBandwidth, sdp_connection::SDPConnection,
-    sdp_key::SDPKey,
+    sdp_key::SDPKey, sdp_message::SDPMessageRef, Fmtp, RtcpFb, RtpMap,
 };
 
 glib::wrapper! {
@@ -42,6 +43,40 @@ impl SDPMedia {
             from_glib_full(media)
         }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a media section on `port` with transport protocol `proto`
+    /// with one payload type per `caps`, including their `rtpmap` and `fmtp`
+    /// attributes.
+    ///
+    /// The media type is taken from the `media` field of the caps.
+    pub fn from_caps<'a>(
+        port: u32,
+        proto: &str,
+        caps: impl IntoIterator<Item = &'a gst::CapsRef>,
+    ) -> Result<Self, glib::BoolError> {
+        assert_initialized_main_thread!();
+        let mut media = SDPMedia::new();
+        media.set_port_info(port, 1);
+        media.set_proto(proto);
+
+        for caps in caps {
+            media.set_media_from_caps(caps)?;
+        }
+        if media.formats_len() == 0 {
+            return Err(glib::bool_error!("No caps given"));
+        }
+
+        Ok(media)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a media section of type `media`, e.g. `video`,
+    /// on `port` with transport protocol `proto`, e.g. `RTP/AVP`.
+    pub fn builder(media: &str, port: u32, proto: &str) -> SDPMediaBuilder {
+        assert_initialized_main_thread!();
+        SDPMediaBuilder::new(media, port, proto)
+    }
 }
 
 impl Default for SDPMedia {
@@ -197,6 +232,30 @@ impl SDPMediaRef {
         unsafe { ffi::gst_sdp_media_attributes_len(&self.0) }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Iterates over the `rtpmap` attributes of the media.
+    ///
+    /// Attributes that can't be parsed are skipped.
+    pub fn rtpmaps(&self) -> impl Iterator<Item = RtpMap> + '_ {
+        self.attribute_values("rtpmap")
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Iterates over the `fmtp` attributes of the media.
+    ///
+    /// Attributes that can't be parsed are skipped.
+    pub fn fmtps(&self) -> impl Iterator<Item = Fmtp> + '_ {
+        self.attribute_values("fmtp")
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Iterates over the `rtcp-fb` attributes of the media.
+    ///
+    /// Attributes that can't be parsed are skipped.
+    pub fn rtcp_fbs(&self) -> impl Iterator<Item = RtcpFb> + '_ {
+        self.attribute_values("rtcp-fb")
+    }
+
     #[doc(alias = "gst_sdp_media_attributes_to_caps")]
     pub fn attributes_to_caps(&self, caps: &mut gst::CapsRef) -> Result<(), glib::BoolError> {
         let result = unsafe { ffi::gst_sdp_media_attributes_to_caps(&self.0, caps.as_mut_ptr()) };
@@ -287,6 +346,44 @@ impl SDPMediaRef {
         unsafe { from_glib_full(ffi::gst_sdp_media_get_caps_from_media(&self.0, pt)) }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Returns the complete `application/x-rtp` caps for the payload type
+    /// `pt`, as needed for configuring a depayloader.
+    ///
+    /// Next to the `rtpmap`, `fmtp` (e.g. `sprop-parameter-sets`) and
+    /// `framesize` attributes of the payload type, this contains all other
+    /// attributes of the media and, if given, of the session `message`.
+    /// Attributes of the media take precedence.
+    pub fn rtp_caps(
+        &self,
+        message: Option<&SDPMessageRef>,
+        pt: u8,
+    ) -> Result<gst::Caps, glib::BoolError> {
+        let mut caps = self
+            .caps_from_media(i32::from(pt))
+            .ok_or_else(|| glib::bool_error!("No caps for payload type {}", pt))?;
+
+        let caps_mut = caps.make_mut();
+        if let Some(message) = message {
+            message.attributes_to_caps(caps_mut)?;
+        }
+        self.attributes_to_caps(caps_mut)?;
+
+        Ok(caps)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the caps of all RTP payload types of the media as returned by
+    /// [`rtp_caps()`](Self::rtp_caps).
+    ///
+    /// Formats that are no payload types or have no usable caps are skipped.
+    pub fn rtp_caps_map(&self, message: Option<&SDPMessageRef>) -> BTreeMap<u8, gst::Caps> {
+        self.formats()
+            .filter_map(|format| format.parse::<u8>().ok())
+            .filter_map(|pt| self.rtp_caps(message, pt).ok().map(|caps| (pt, caps)))
+            .collect()
+    }
+
     #[doc(alias = "get_connection")]
     #[doc(alias = "gst_sdp_media_get_connection")]
     pub fn connection(&self, idx: u32) -> Option<&SDPConnection> {
@@ -634,6 +731,141 @@ impl SDPMediaRef {
             _ => Err(glib::bool_error!("Failed to add media from structure")),
         }
     }
+
+    fn attribute_values<'a, T: std::str::FromStr + 'a>(
+        &'a self,
+        key: &'a str,
+    ) -> impl Iterator<Item = T> + 'a {
+        self.attributes()
+            .filter(move |attr| attr.key() == key)
+            .filter_map(|attr| attr.value()?.parse::<T>().ok())
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for [`SDPMedia`] sections.
+#[derive(Debug, Clone)]
+#[must_use = "The builder must be built to be used"]
+pub struct SDPMediaBuilder {
+    media: SDPMedia,
+    fmtps: Vec<u8>,
+}
+
+impl SDPMediaBuilder {
+    fn new(media: &str, port: u32, proto: &str) -> Self {
+        skip_assert_initialized!();
+        let mut sdp_media = SDPMedia::new();
+        sdp_media.set_media(media);
+        sdp_media.set_port_info(port, 1);
+        sdp_media.set_proto(proto);
+
+        Self {
+            media: sdp_media,
+            fmtps: Vec::new(),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the number of consecutive ports used by the media, e.g. for
+    /// layered encodings.
+    pub fn num_ports(mut self, num_ports: u32) -> Self {
+        let port = self.media.port();
+        self.media.set_port_info(port, num_ports);
+        self
+    }
+
+    pub fn format(mut self, format: &str) -> Self {
+        if !self.media.formats().any(|f| f == format) {
+            self.media.add_format(format);
+        }
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds the RTP payload type `pt` as format together with its `rtpmap`
+    /// attribute, e.g. `96 VP8/90000` or `97 OPUS/48000/2`.
+    pub fn rtpmap(
+        self,
+        pt: u8,
+        encoding: &str,
+        clock_rate: u32,
+        encoding_params: Option<&str>,
+    ) -> Self {
+        let rtpmap = RtpMap {
+            pt,
+            encoding: encoding.to_string(),
+            clock_rate,
+            params: encoding_params.map(String::from),
+        };
+        self.format(&pt.to_string())
+            .attribute("rtpmap", Some(&rtpmap.to_string()))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds the format specific parameters of the payload type `pt`, which
+    /// has to be added as format too.
+    pub fn fmtp(mut self, pt: u8, params: &str) -> Self {
+        self.fmtps.push(pt);
+        self.attribute("fmtp", Some(&format!("{pt} {params}")))
+    }
+
+    pub fn attribute(mut self, key: &str, value: Option<&str>) -> Self {
+        self.media.add_attribute(key, value);
+        self
+    }
+
+    pub fn connection(
+        mut self,
+        nettype: &str,
+        addrtype: &str,
+        address: &str,
+        ttl: u32,
+        addr_number: u32,
+    ) -> Self {
+        self.media
+            .add_connection(nettype, addrtype, address, ttl, addr_number);
+        self
+    }
+
+    pub fn bandwidth(mut self, bwtype: &str, bandwidth: u32) -> Self {
+        self.media.add_bandwidth(bwtype, bandwidth);
+        self
+    }
+
+    pub fn information(mut self, information: &str) -> Self {
+        self.media.set_information(information);
+        self
+    }
+
+    pub fn key(mut self, type_: &str, data: &str) -> Self {
+        self.media.set_key(type_, data);
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Builds the media section.
+    ///
+    /// Fails if no format was added or if `fmtp` parameters were given for
+    /// payload types that are no format of the media.
+    pub fn build(self) -> Result<SDPMedia, glib::BoolError> {
+        if self.media.media().is_none_or(str::is_empty) {
+            return Err(glib::bool_error!("Media without type"));
+        }
+        if self.media.proto().is_none_or(str::is_empty) {
+            return Err(glib::bool_error!("Media without transport protocol"));
+        }
+        if self.media.formats_len() == 0 {
+            return Err(glib::bool_error!("Media without formats"));
+        }
+        for pt in &self.fmtps {
+            let pt = pt.to_string();
+            if !self.media.formats().any(|f| f == pt) {
+                return Err(glib::bool_error!("fmtp for unknown payload type {}", pt));
+            }
+        }
+
+        Ok(self.media)
+    }
 }
 
 impl Borrow<SDPMediaRef> for SDPMedia {
@@ -799,4 +1031,120 @@ mod tests {
         let sdp = SDPMedia::new();
         assert!(!format!("{sdp:?}").is_empty());
     }
+
+    #[test]
+    fn builder() {
+        init();
+
+        let media = SDPMedia::builder("video", 5000, "RTP/AVP")
+            .rtpmap(96, "H264", 90000, None)
+            .fmtp(96, "packetization-mode=1")
+            .rtpmap(97, "VP8", 90000, None)
+            .connection("IN", "IP4", "192.168.1.2", 0, 0)
+            .bandwidth("AS", 2000)
+            .attribute("recvonly", None)
+            .build()
+            .unwrap();
+
+        assert_eq!(media.media(), Some("video"));
+        assert_eq!(media.port(), 5000);
+        assert_eq!(media.proto(), Some("RTP/AVP"));
+        assert_eq!(media.formats().collect::<Vec<_>>(), ["96", "97"]);
+        assert_eq!(media.attribute_val_n("rtpmap", 1), Some("97 VP8/90000"));
+        assert_eq!(media.attribute_val("fmtp"), Some("96 packetization-mode=1"));
+        assert_eq!(media.connections_len(), 1);
+        let caps = media.caps_from_media(96).unwrap();
+        let s = caps.structure(0).unwrap();
+        assert_eq!(s.get::<&str>("encoding-name").unwrap(), "H264");
+
+        assert!(SDPMedia::builder("audio", 5002, "RTP/AVP").build().is_err());
+        assert!(SDPMedia::builder("audio", 5002, "RTP/AVP")
+            .format("0")
+            .fmtp(8, "foo=1")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn rtp_caps() {
+        init();
+
+        let h264 = gst::Caps::builder("application/x-rtp")
+            .field("media", "video")
+            .field("payload", 96)
+            .field("clock-rate", 90000)
+            .field("encoding-name", "H264")
+            .field("sprop-parameter-sets", "Z0IAH5WoFAFuQA==,aM48gA==")
+            .build();
+        let vp8 = gst::Caps::builder("application/x-rtp")
+            .field("media", "video")
+            .field("payload", 97)
+            .field("clock-rate", 90000)
+            .field("encoding-name", "VP8")
+            .build();
+
+        let mut media =
+            SDPMedia::from_caps(5000, "RTP/AVP", [h264.as_ref(), vp8.as_ref()]).unwrap();
+        assert_eq!(media.media(), Some("video"));
+        assert_eq!(media.formats().collect::<Vec<_>>(), ["96", "97"]);
+        media.add_attribute("framesize", Some("96 1280-720"));
+        media.add_attribute("recvonly", None);
+
+        let mut message = crate::SDPMessage::new();
+        message.add_attribute("x-label", Some("main"));
+
+        let caps = media.rtp_caps_map(Some(&message));
+        assert_eq!(caps.keys().copied().collect::<Vec<_>>(), [96, 97]);
+
+        let s = caps[&96].structure(0).unwrap();
+        assert_eq!(s.name(), "application/x-rtp");
+        assert_eq!(s.get::<&str>("encoding-name").unwrap(), "H264");
+        assert_eq!(
+            s.get::<&str>("sprop-parameter-sets").unwrap(),
+            "Z0IAH5WoFAFuQA==,aM48gA=="
+        );
+        assert_eq!(s.get::<&str>("a-framesize").unwrap(), "1280-720");
+        assert_eq!(s.get::<&str>("a-x-label").unwrap(), "main");
+
+        let s = caps[&97].structure(0).unwrap();
+        assert_eq!(s.get::<&str>("encoding-name").unwrap(), "VP8");
+        assert!(!s.has_field("a-framesize"));
+
+        assert!(media.rtp_caps(None, 98).is_err());
+        assert!(SDPMedia::from_caps(5000, "RTP/AVP", []).is_err());
+    }
+
+    #[test]
+    fn rtp_attributes() {
+        init();
+
+        let media = SDPMedia::builder("video", 5000, "RTP/AVPF")
+            .rtpmap(96, "H264", 90000, None)
+            .fmtp(96, "packetization-mode=1;profile-level-id=42e01f")
+            .rtpmap(97, "rtx", 90000, None)
+            .fmtp(97, "apt=96")
+            .attribute("rtpmap", Some("invalid"))
+            .attribute("rtcp-fb", Some("96 nack pli"))
+            .attribute("rtcp-fb", Some("* ccm fir"))
+            .build()
+            .unwrap();
+
+        let rtpmaps = media.rtpmaps().collect::<Vec<_>>();
+        assert_eq!(rtpmaps.len(), 2);
+        assert_eq!(rtpmaps[0].pt, 96);
+        assert_eq!(rtpmaps[0].encoding, "H264");
+        assert_eq!(rtpmaps[1].to_string(), "97 rtx/90000");
+
+        let fmtps = media.fmtps().collect::<Vec<_>>();
+        assert_eq!(fmtps.len(), 2);
+        assert_eq!(fmtps[0].parameter("packetization-mode"), Some("1"));
+        assert_eq!(fmtps[1].pt, 97);
+        assert_eq!(fmtps[1].parameter("apt"), Some("96"));
+
+        let fbs = media.rtcp_fbs().collect::<Vec<_>>();
+        assert_eq!(fbs.len(), 2);
+        assert_eq!(fbs[0].pt, Some(96));
+        assert_eq!(fbs[1].pt, None);
+        assert_eq!(fbs[1].feedback, "ccm");
+    }
 }
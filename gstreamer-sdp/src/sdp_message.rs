@@ -3,7 +3,10 @@
 use std::{
     borrow::{Borrow, BorrowMut, ToOwned},
     ffi::CStr,
-    fmt, mem, ops, ptr,
+    fmt, mem,
+    net::IpAddr,
+    ops, ptr,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 use glib::{prelude::*, translate::*};
@@ -12,11 +15,11 @@ use crate::{
     ffi,
     sdp_attribute::SDPAttribute,
     sdp_bandwidth::SDPBandwidth,
-    sdp_connection::SDPConnection,
+    sdp_connection::{self, SDPConnection},
     sdp_key::SDPKey,
     sdp_media::{SDPMedia, SDPMediaRef},
     sdp_origin::SDPOrigin,
-    sdp_time::SDPTime,
+    sdp_time::{self, SDPTime},
     sdp_zone::SDPZone,
 };
 
@@ -86,6 +89,13 @@ impl SDPMessage {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a session description.
+    pub fn builder() -> SDPMessageBuilder {
+        assert_initialized_main_thread!();
+        SDPMessageBuilder::new()
+    }
+
     #[doc(alias = "gst_sdp_message_parse_buffer")]
     pub fn parse_buffer(data: &[u8]) -> Result<Self, glib::BoolError> {
         assert_initialized_main_thread!();
@@ -860,6 +870,59 @@ impl SDPMessageRef {
         };
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Sets the origin for `addr` with a new session ID and version based
+    /// on the current NTP time, as recommended by RFC 4566.
+    ///
+    /// The username is `-` as user IDs are not supported.
+    pub fn set_origin_from_address(&mut self, addr: IpAddr) {
+        let ntp = ntp_timestamp();
+        self.set_origin(
+            "-",
+            &ntp.to_string(),
+            &(ntp >> 32).to_string(),
+            "IN",
+            sdp_connection::addrtype(&addr),
+            &addr.to_string(),
+        );
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Increments the session version of the origin, which has to happen
+    /// whenever a modified session description is sent.
+    ///
+    /// Fails if there is no origin or the version is no number.
+    pub fn bump_version(&mut self) -> Result<(), glib::BoolError> {
+        let origin = self
+            .origin()
+            .filter(|origin| origin.username().is_some())
+            .ok_or_else(|| glib::bool_error!("Session description without origin"))?;
+        let version = origin
+            .sess_version()
+            .and_then(|version| version.parse::<u64>().ok())
+            .and_then(|version| version.checked_add(1))
+            .ok_or_else(|| glib::bool_error!("Invalid session version"))?;
+
+        let [username, sess_id, nettype, addrtype, addr] = [
+            origin.username(),
+            origin.sess_id(),
+            origin.nettype(),
+            origin.addrtype(),
+            origin.addr(),
+        ]
+        .map(|field| field.unwrap_or_default().to_string());
+        self.set_origin(
+            &username,
+            &sess_id,
+            &version.to_string(),
+            &nettype,
+            &addrtype,
+            &addr,
+        );
+
+        Ok(())
+    }
+
     #[doc(alias = "gst_sdp_message_set_session_name")]
     pub fn set_session_name(&mut self, session_name: &str) {
         unsafe {
@@ -932,6 +995,311 @@ impl SDPMessageRef {
     pub fn zones(&self) -> ZonesIter<'_> {
         ZonesIter::new(self)
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a copy of the message with the address of the session and of
+    /// all media connections replaced by `address`.
+    ///
+    /// TTL and number of addresses are only kept for multicast addresses.
+    pub fn with_connection_address(&self, address: IpAddr) -> SDPMessage {
+        let addrtype = sdp_connection::addrtype(&address);
+        let new_connection = |connection: &SDPConnection| {
+            let (ttl, addr_number) = if address.is_multicast() {
+                (connection.ttl(), connection.addr_number())
+            } else {
+                (0, 0)
+            };
+            SDPConnection::new(
+                connection.nettype().unwrap_or("IN"),
+                addrtype,
+                &address.to_string(),
+                ttl,
+                addr_number,
+            )
+        };
+
+        let mut message = self.to_owned();
+        if let Some(connection) = self
+            .connection()
+            .filter(|connection| connection.address().is_some())
+        {
+            let connection = new_connection(connection);
+            message.set_connection(
+                connection.nettype().unwrap(),
+                addrtype,
+                connection.address().unwrap(),
+                connection.ttl(),
+                connection.addr_number(),
+            );
+        }
+        for media in message.medias_mut() {
+            for idx in 0..media.connections_len() {
+                let connection = new_connection(media.connection(idx).unwrap());
+                media.replace_connection(idx, connection).unwrap();
+            }
+        }
+
+        message
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a copy of the message with the ports of all media replaced by
+    /// the return value of `func`, which is called with the index of the
+    /// media and the media.
+    pub fn with_media_ports<F: FnMut(usize, &SDPMediaRef) -> u32>(
+        &self,
+        mut func: F,
+    ) -> SDPMessage {
+        let mut message = self.to_owned();
+        for (idx, media) in message.medias_mut().enumerate() {
+            let port = func(idx, media);
+            let num_ports = media.num_ports();
+            media.set_port_info(port, num_ports);
+        }
+
+        message
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a copy of the message with only the media for which `func`
+    /// returns `true`.
+    pub fn filter_medias<F: FnMut(&SDPMediaRef) -> bool>(&self, mut func: F) -> SDPMessage {
+        self.with_medias(self.medias().filter(|media| func(media)))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a copy of the message with the media at the indices of
+    /// `order` in that order, e.g. `[1, 0]` to swap the first two media.
+    ///
+    /// Media not in `order` are removed. Fails if an index is out of bounds
+    /// or given more than once.
+    pub fn reorder_medias(&self, order: &[usize]) -> Result<SDPMessage, glib::BoolError> {
+        let medias = order
+            .iter()
+            .enumerate()
+            .map(|(i, &idx)| {
+                if order[..i].contains(&idx) {
+                    return Err(glib::bool_error!("Media {} given more than once", idx));
+                }
+                u32::try_from(idx)
+                    .ok()
+                    .and_then(|idx| self.media(idx))
+                    .ok_or_else(|| glib::bool_error!("Invalid media index {}", idx))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(self.with_medias(medias))
+    }
+
+    fn with_medias<'a>(&self, medias: impl IntoIterator<Item = &'a SDPMediaRef>) -> SDPMessage {
+        let mut message = self.to_owned();
+        unsafe {
+            let array = (*message.as_ptr()).medias;
+            let data = (*array).data as *mut ffi::GstSDPMedia;
+            for idx in 0..(*array).len as usize {
+                ffi::gst_sdp_media_uninit(data.add(idx));
+            }
+            glib::ffi::g_array_set_size(array, 0);
+        }
+        for media in medias {
+            message.add_media(media.to_owned());
+        }
+
+        message
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a copy of the message with all absolute `control` attributes
+    /// below `old_base` rewritten to be below `new_base`, e.g. for proxying
+    /// a stream from `old_base` at `new_base`.
+    ///
+    /// Relative and other `control` attributes are kept as is. Relative ones
+    /// are resolved against the `Content-Base` and don't need rewriting.
+    pub fn rebase_control(&self, old_base: &str, new_base: &str) -> SDPMessage {
+        let old_base = old_base.trim_end_matches('/');
+        let new_base = new_base.trim_end_matches('/');
+        let rebase = |value: &str| {
+            let rest = value.strip_prefix(old_base)?;
+            if rest.is_empty() || rest.starts_with(['/', '?']) {
+                Some(format!("{new_base}{rest}"))
+            } else {
+                None
+            }
+        };
+
+        let mut message = self.to_owned();
+        for idx in 0..message.attributes_len() {
+            let attr = message.attribute(idx).unwrap();
+            if attr.key() != "control" {
+                continue;
+            }
+            if let Some(value) = attr.value().and_then(rebase) {
+                message
+                    .replace_attribute(idx, SDPAttribute::new("control", Some(&value)))
+                    .unwrap();
+            }
+        }
+        for media in message.medias_mut() {
+            for idx in 0..media.attributes_len() {
+                let attr = media.attribute(idx).unwrap();
+                if attr.key() != "control" {
+                    continue;
+                }
+                if let Some(value) = attr.value().and_then(rebase) {
+                    media
+                        .replace_attribute(idx, SDPAttribute::new("control", Some(&value)))
+                        .unwrap();
+                }
+            }
+        }
+
+        message
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for [`SDPMessage`]s.
+///
+/// The version defaults to `0`, the session name to `-` and, without any
+/// explicit time, the session is unbounded (`t=0 0`).
+#[derive(Debug, Clone)]
+#[must_use = "The builder must be built to be used"]
+pub struct SDPMessageBuilder {
+    message: SDPMessage,
+    has_origin: bool,
+    has_session_name: bool,
+    has_connection: bool,
+}
+
+impl SDPMessageBuilder {
+    fn new() -> Self {
+        skip_assert_initialized!();
+        let mut message = SDPMessage::new();
+        message.set_version("0");
+
+        Self {
+            message,
+            has_origin: false,
+            has_session_name: false,
+            has_connection: false,
+        }
+    }
+
+    pub fn origin(
+        mut self,
+        username: &str,
+        sess_id: &str,
+        sess_version: &str,
+        nettype: &str,
+        addrtype: &str,
+        addr: &str,
+    ) -> Self {
+        self.message
+            .set_origin(username, sess_id, sess_version, nettype, addrtype, addr);
+        self.has_origin = true;
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the origin for `addr` with a session ID and version based on the
+    /// current NTP time.
+    ///
+    /// See [`SDPMessageRef::set_origin_from_address()`].
+    pub fn origin_from_address(mut self, addr: IpAddr) -> Self {
+        self.message.set_origin_from_address(addr);
+        self.has_origin = true;
+        self
+    }
+
+    pub fn session_name(mut self, session_name: &str) -> Self {
+        self.message.set_session_name(session_name);
+        self.has_session_name = true;
+        self
+    }
+
+    pub fn information(mut self, information: &str) -> Self {
+        self.message.set_information(information);
+        self
+    }
+
+    pub fn uri(mut self, uri: &str) -> Self {
+        self.message.set_uri(uri);
+        self
+    }
+
+    pub fn email(mut self, email: &str) -> Self {
+        self.message.add_email(email);
+        self
+    }
+
+    pub fn phone(mut self, phone: &str) -> Self {
+        self.message.add_phone(phone);
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the session level connection, which applies to all media without
+    /// their own connection.
+    pub fn connection(
+        mut self,
+        nettype: &str,
+        addrtype: &str,
+        address: &str,
+        ttl: u32,
+        addr_number: u32,
+    ) -> Self {
+        self.message
+            .set_connection(nettype, addrtype, address, ttl, addr_number);
+        self.has_connection = true;
+        self
+    }
+
+    pub fn bandwidth(mut self, bwtype: &str, bandwidth: u32) -> Self {
+        self.message
+            .insert_bandwidth(None, SDPBandwidth::new(bwtype, bandwidth))
+            .unwrap();
+        self
+    }
+
+    pub fn time(mut self, start: &str, stop: &str, repeat: &[&str]) -> Self {
+        self.message.add_time(start, stop, repeat);
+        self
+    }
+
+    pub fn attribute(mut self, key: &str, value: Option<&str>) -> Self {
+        self.message.add_attribute(key, value);
+        self
+    }
+
+    pub fn media(mut self, media: SDPMedia) -> Self {
+        self.message.add_media(media);
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Builds the session description.
+    ///
+    /// Fails if no origin was set or if a media has no connection while
+    /// there is no session level connection either.
+    pub fn build(mut self) -> Result<SDPMessage, glib::BoolError> {
+        if !self.has_origin {
+            return Err(glib::bool_error!("Session description without origin"));
+        }
+        if !self.has_connection {
+            if let Some(idx) = self.message.medias().position(|m| m.connections_len() == 0) {
+                return Err(glib::bool_error!("Media {} without connection", idx));
+            }
+        }
+
+        if !self.has_session_name {
+            self.message.set_session_name("-");
+        }
+        if self.message.times_len() == 0 {
+            self.message.add_time("0", "0", &[]);
+        }
+
+        Ok(self.message)
+    }
 }
 
 impl Borrow<SDPMessageRef> for SDPMessage {
@@ -1270,6 +1638,15 @@ define_iter!(
     |message: &SDPMessageRef| message.zones_len()
 );
 
+// Current time as 64 bit NTP timestamp
+fn ntp_timestamp() -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let frac = (u64::from(now.subsec_nanos()) << 32) / 1_000_000_000;
+    ((now.as_secs() + sdp_time::NTP_UNIX_OFFSET) << 32) | frac
+}
+
 #[cfg(test)]
 mod tests {
     use crate::SDPMessage;
@@ -1288,6 +1665,149 @@ mod tests {
         assert_eq!(media.formats_len(), 1);
     }
 
+    #[test]
+    fn builder() {
+        init();
+
+        let sdp = SDPMessage::builder()
+            .origin("-", "1234", "1", "IN", "IP4", "192.168.1.1")
+            .connection("IN", "IP4", "192.168.1.1", 0, 0)
+            .attribute("tool", Some("gst"))
+            .media(
+                crate::SDPMedia::builder("audio", 5000, "RTP/AVP")
+                    .rtpmap(97, "OPUS", 48000, Some("2"))
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            sdp.as_text().unwrap(),
+            "v=0\r\n\
+             o=- 1234 1 IN IP4 192.168.1.1\r\n\
+             s=-\r\n\
+             c=IN IP4 192.168.1.1\r\n\
+             t=0 0\r\n\
+             a=tool:gst\r\n\
+             m=audio 5000 RTP/AVP 97\r\n\
+             a=rtpmap:97 OPUS/48000/2\r\n"
+        );
+
+        assert!(SDPMessage::builder().build().is_err());
+        assert!(SDPMessage::builder()
+            .origin("-", "1234", "1", "IN", "IP4", "192.168.1.1")
+            .media(
+                crate::SDPMedia::builder("audio", 5000, "RTP/AVP")
+                    .format("0")
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn rewrite() {
+        init();
+
+        let sdp = "v=0\r
+o=- 1234 1 IN IP4 192.168.1.1\r
+s=-\r
+c=IN IP4 192.168.1.1\r
+t=0 0\r
+a=control:rtsp://cam/stream\r
+m=video 5000 RTP/AVP 96\r
+c=IN IP4 224.2.0.1/16\r
+a=rtpmap:96 H264/90000\r
+a=control:rtsp://cam/stream/trackID=0\r
+m=audio 5002 RTP/AVP 0\r
+a=control:trackID=1\r
+m=application 5004 RTP/AVP 107\r
+a=control:rtsp://cam/stream2/trackID=2\r
+";
+        let message = SDPMessage::parse_buffer(sdp.as_bytes()).unwrap();
+
+        let rewritten = message
+            .with_connection_address("10.0.0.1".parse().unwrap())
+            .with_media_ports(|idx, _| 6000 + 2 * idx as u32)
+            .rebase_control("rtsp://cam/stream/", "rtsp://proxy/cam1");
+        assert_eq!(
+            rewritten.as_text().unwrap(),
+            "v=0\r
+o=- 1234 1 IN IP4 192.168.1.1\r
+s=-\r
+c=IN IP4 10.0.0.1\r
+t=0 0\r
+a=control:rtsp://proxy/cam1\r
+m=video 6000 RTP/AVP 96\r
+c=IN IP4 10.0.0.1\r
+a=rtpmap:96 H264/90000\r
+a=control:rtsp://proxy/cam1/trackID=0\r
+m=audio 6002 RTP/AVP 0\r
+a=control:trackID=1\r
+m=application 6004 RTP/AVP 107\r
+a=control:rtsp://cam/stream2/trackID=2\r
+"
+        );
+        // The original message is untouched
+        assert_eq!(message.as_text().unwrap(), sdp);
+
+        let filtered = message.filter_medias(|media| media.media() != Some("application"));
+        assert_eq!(
+            filtered
+                .medias()
+                .map(|media| media.media().unwrap())
+                .collect::<Vec<_>>(),
+            ["video", "audio"]
+        );
+
+        let reordered = message.reorder_medias(&[1, 0]).unwrap();
+        assert_eq!(
+            reordered
+                .medias()
+                .map(|media| media.media().unwrap())
+                .collect::<Vec<_>>(),
+            ["audio", "video"]
+        );
+        assert!(message.reorder_medias(&[0, 0]).is_err());
+        assert!(message.reorder_medias(&[3]).is_err());
+        assert_eq!(message.medias_len(), 3);
+    }
+
+    #[test]
+    fn origin() {
+        init();
+
+        let mut sdp = SDPMessage::builder()
+            .origin_from_address("2001:db8::1".parse().unwrap())
+            .connection("IN", "IP6", "2001:db8::1", 0, 0)
+            .build()
+            .unwrap();
+
+        let origin = sdp.origin().unwrap();
+        assert_eq!(origin.username(), Some("-"));
+        assert_eq!(origin.nettype(), Some("IN"));
+        assert_eq!(origin.addrtype(), Some("IP6"));
+        assert_eq!(origin.addr(), Some("2001:db8::1"));
+        let sess_id = origin.sess_id().unwrap().to_string();
+        let version = origin.sess_version().unwrap().parse::<u64>().unwrap();
+        // Later than 2020-01-01 in NTP time
+        assert!(version > 3_786_825_600);
+
+        sdp.bump_version().unwrap();
+        sdp.bump_version().unwrap();
+        let origin = sdp.origin().unwrap();
+        assert_eq!(origin.sess_id(), Some(sess_id.as_str()));
+        assert_eq!(
+            origin.sess_version(),
+            Some((version + 2).to_string().as_str())
+        );
+        assert_eq!(origin.addr(), Some("2001:db8::1"));
+
+        assert!(SDPMessage::new().bump_version().is_err());
+    }
+
     #[test]
     fn debug_impl() {
         init();
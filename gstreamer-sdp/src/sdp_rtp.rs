@@ -0,0 +1,252 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{fmt, str};
+
+fn parse_pt(pt: &str) -> Result<u8, glib::BoolError> {
+    pt.parse::<u8>()
+        .ok()
+        .filter(|pt| *pt < 128)
+        .ok_or_else(|| glib::bool_error!("Invalid payload type {}", pt))
+}
+
+// Splits `<pt> <rest>` as used by all per-payload-type attributes
+fn split_pt(s: &str) -> Result<(&str, &str), glib::BoolError> {
+    let s = s.trim();
+    match s.split_once(|c: char| c.is_ascii_whitespace()) {
+        Some((pt, rest)) => Ok((pt, rest.trim_start())),
+        None => Err(glib::bool_error!("Missing payload type in {}", s)),
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// The value of an `rtpmap` attribute, e.g. `96 H264/90000` or
+/// `111 opus/48000/2`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RtpMap {
+    pub pt: u8,
+    pub encoding: String,
+    pub clock_rate: u32,
+    // rustdoc-stripper-ignore-next
+    /// Encoding parameters, e.g. the number of audio channels.
+    pub params: Option<String>,
+}
+
+impl fmt::Display for RtpMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}/{}", self.pt, self.encoding, self.clock_rate)?;
+        if let Some(ref params) = self.params {
+            write!(f, "/{params}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl str::FromStr for RtpMap {
+    type Err = glib::BoolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        skip_assert_initialized!();
+        let (pt, rest) = split_pt(s)?;
+        let pt = parse_pt(pt)?;
+
+        let mut parts = rest.trim_end().splitn(3, '/');
+        let encoding = parts
+            .next()
+            .filter(|encoding| !encoding.is_empty())
+            .ok_or_else(|| glib::bool_error!("Missing encoding name in rtpmap {}", s))?;
+        let clock_rate = parts
+            .next()
+            .and_then(|clock_rate| clock_rate.parse::<u32>().ok())
+            .ok_or_else(|| glib::bool_error!("Invalid clock rate in rtpmap {}", s))?;
+        let params = parts.next().map(String::from);
+
+        Ok(RtpMap {
+            pt,
+            encoding: encoding.to_string(),
+            clock_rate,
+            params,
+        })
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// The value of an `fmtp` attribute, e.g.
+/// `96 packetization-mode=1;profile-level-id=42e01f`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fmtp {
+    pub pt: u8,
+    // rustdoc-stripper-ignore-next
+    /// The format specific parameters. These are usually a `;` separated
+    /// list of `name=value` pairs, which can be accessed with
+    /// [`parameters()`](Self::parameters).
+    pub params: String,
+}
+
+impl Fmtp {
+    // rustdoc-stripper-ignore-next
+    /// Iterates over the `;` separated parameters as name and, if present,
+    /// value.
+    pub fn parameters(&self) -> impl Iterator<Item = (&str, Option<&str>)> + '_ {
+        self.params
+            .split(';')
+            .map(str::trim)
+            .filter(|param| !param.is_empty())
+            .map(|param| match param.split_once('=') {
+                Some((name, value)) => (name.trim_end(), Some(value.trim_start())),
+                None => (param, None),
+            })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value of the parameter `name`, which is compared case
+    /// insensitively.
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .and_then(|(_, value)| value)
+    }
+}
+
+impl fmt::Display for Fmtp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.pt, self.params)
+    }
+}
+
+impl str::FromStr for Fmtp {
+    type Err = glib::BoolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        skip_assert_initialized!();
+        let (pt, params) = split_pt(s)?;
+
+        Ok(Fmtp {
+            pt: parse_pt(pt)?,
+            params: params.trim_end().to_string(),
+        })
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// The value of an `rtcp-fb` attribute (RFC 4585), e.g. `96 nack pli` or
+/// `* ccm fir`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RtcpFb {
+    // rustdoc-stripper-ignore-next
+    /// The payload type, or `None` for all payload types (`*`).
+    pub pt: Option<u8>,
+    // rustdoc-stripper-ignore-next
+    /// The feedback type, e.g. `nack`, `ccm` or `transport-cc`.
+    pub feedback: String,
+    // rustdoc-stripper-ignore-next
+    /// The feedback parameters, e.g. `pli` for `nack` or `fir` for `ccm`.
+    pub params: Option<String>,
+}
+
+impl fmt::Display for RtcpFb {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.pt {
+            Some(pt) => write!(f, "{pt} {}", self.feedback)?,
+            None => write!(f, "* {}", self.feedback)?,
+        }
+        if let Some(ref params) = self.params {
+            write!(f, " {params}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl str::FromStr for RtcpFb {
+    type Err = glib::BoolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        skip_assert_initialized!();
+        let (pt, rest) = split_pt(s)?;
+        let pt = match pt {
+            "*" => None,
+            pt => Some(parse_pt(pt)?),
+        };
+
+        let rest = rest.trim_end();
+        let (feedback, params) = match rest.split_once(|c: char| c.is_ascii_whitespace()) {
+            Some((feedback, params)) => (feedback, Some(params.trim_start().to_string())),
+            None => (rest, None),
+        };
+
+        Ok(RtcpFb {
+            pt,
+            feedback: feedback.to_string(),
+            params,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rtpmap() {
+        let rtpmap = "111 opus/48000/2".parse::<RtpMap>().unwrap();
+        assert_eq!(
+            rtpmap,
+            RtpMap {
+                pt: 111,
+                encoding: String::from("opus"),
+                clock_rate: 48000,
+                params: Some(String::from("2")),
+            }
+        );
+        assert_eq!(rtpmap.to_string(), "111 opus/48000/2");
+
+        let rtpmap = " 96  H264/90000 ".parse::<RtpMap>().unwrap();
+        assert_eq!(rtpmap.params, None);
+        assert_eq!(rtpmap.to_string(), "96 H264/90000");
+
+        assert!("96 H264".parse::<RtpMap>().is_err());
+        assert!("H264/90000".parse::<RtpMap>().is_err());
+        assert!("200 H264/90000".parse::<RtpMap>().is_err());
+    }
+
+    #[test]
+    fn test_fmtp() {
+        let fmtp = "96 packetization-mode=1; profile-level-id=42e01f;sprop-parameter-sets=Z0IAH5WoFAFuQA==,aM48gA=="
+            .parse::<Fmtp>()
+            .unwrap();
+        assert_eq!(fmtp.pt, 96);
+        assert_eq!(fmtp.parameter("packetization-mode"), Some("1"));
+        assert_eq!(fmtp.parameter("Profile-Level-Id"), Some("42e01f"));
+        assert_eq!(
+            fmtp.parameter("sprop-parameter-sets"),
+            Some("Z0IAH5WoFAFuQA==,aM48gA==")
+        );
+        assert_eq!(fmtp.parameter("level-asymmetry-allowed"), None);
+
+        let fmtp = "101 0-15".parse::<Fmtp>().unwrap();
+        assert_eq!(fmtp.parameters().collect::<Vec<_>>(), [("0-15", None)]);
+        assert_eq!(fmtp.to_string(), "101 0-15");
+
+        assert!("x 0-15".parse::<Fmtp>().is_err());
+    }
+
+    #[test]
+    fn test_rtcp_fb() {
+        for (s, pt, feedback, params) in [
+            ("96 nack", Some(96), "nack", None),
+            ("96 nack pli", Some(96), "nack", Some("pli")),
+            ("* ccm fir", None, "ccm", Some("fir")),
+            ("* trr-int 100", None, "trr-int", Some("100")),
+        ] {
+            let fb = s.parse::<RtcpFb>().unwrap();
+            assert_eq!(fb.pt, pt);
+            assert_eq!(fb.feedback, feedback);
+            assert_eq!(fb.params.as_deref(), params);
+            assert_eq!(fb.to_string(), s);
+        }
+
+        assert!("nack".parse::<RtcpFb>().is_err());
+        assert!("** nack".parse::<RtcpFb>().is_err());
+    }
+}
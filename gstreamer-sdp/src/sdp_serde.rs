@@ -0,0 +1,373 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use serde::{
+    de::{Deserialize, Deserializer},
+    ser::{Serialize, Serializer},
+};
+
+use crate::{SDPMedia, SDPMediaRef, SDPMessage, SDPMessageRef};
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SDPOriginSerde {
+    username: String,
+    sess_id: String,
+    sess_version: String,
+    nettype: String,
+    addrtype: String,
+    addr: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SDPConnectionSerde {
+    nettype: String,
+    addrtype: String,
+    address: String,
+    ttl: u32,
+    addr_number: u32,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SDPBandwidthSerde {
+    bwtype: String,
+    value: u32,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SDPTimeSerde {
+    start: String,
+    stop: String,
+    repeat: Vec<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SDPZoneSerde {
+    time: String,
+    typed_time: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SDPKeySerde {
+    type_: String,
+    data: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SDPAttributeSerde {
+    key: String,
+    value: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SDPMediaSerde {
+    media: Option<String>,
+    port: u32,
+    num_ports: u32,
+    proto: Option<String>,
+    formats: Vec<String>,
+    information: Option<String>,
+    connections: Vec<SDPConnectionSerde>,
+    bandwidths: Vec<SDPBandwidthSerde>,
+    key: Option<SDPKeySerde>,
+    attributes: Vec<SDPAttributeSerde>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SDPMessageSerde {
+    version: Option<String>,
+    origin: Option<SDPOriginSerde>,
+    session_name: Option<String>,
+    information: Option<String>,
+    uri: Option<String>,
+    emails: Vec<String>,
+    phones: Vec<String>,
+    connection: Option<SDPConnectionSerde>,
+    bandwidths: Vec<SDPBandwidthSerde>,
+    times: Vec<SDPTimeSerde>,
+    zones: Vec<SDPZoneSerde>,
+    key: Option<SDPKeySerde>,
+    attributes: Vec<SDPAttributeSerde>,
+    medias: Vec<SDPMedia>,
+}
+
+// The C structs always contain the connection, key and origin and only
+// have their fields unset if they are not present.
+fn connection(connection: Option<&crate::SDPConnection>) -> Option<SDPConnectionSerde> {
+    let connection = connection?;
+    Some(SDPConnectionSerde {
+        nettype: connection.nettype().map(String::from).unwrap_or_default(),
+        addrtype: connection.addrtype().map(String::from).unwrap_or_default(),
+        address: connection.address()?.to_string(),
+        ttl: connection.ttl(),
+        addr_number: connection.addr_number(),
+    })
+}
+
+fn key(key: Option<&crate::SDPKey>) -> Option<SDPKeySerde> {
+    let key = key?;
+    Some(SDPKeySerde {
+        type_: key.type_()?.to_string(),
+        data: key.data().map(String::from).unwrap_or_default(),
+    })
+}
+
+fn bandwidths<'a>(
+    bandwidths: impl Iterator<Item = &'a crate::SDPBandwidth>,
+) -> Vec<SDPBandwidthSerde> {
+    bandwidths
+        .map(|bw| SDPBandwidthSerde {
+            bwtype: bw.bwtype().map(String::from).unwrap_or_default(),
+            value: bw.value(),
+        })
+        .collect()
+}
+
+fn attributes<'a>(
+    attributes: impl Iterator<Item = &'a crate::SDPAttribute>,
+) -> Vec<SDPAttributeSerde> {
+    attributes
+        .map(|attr| SDPAttributeSerde {
+            key: attr.key().to_string(),
+            value: attr.value().map(String::from),
+        })
+        .collect()
+}
+
+impl Serialize for SDPMediaRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let media = SDPMediaSerde {
+            media: self.media().map(String::from),
+            port: self.port(),
+            num_ports: self.num_ports(),
+            proto: self.proto().map(String::from),
+            formats: self.formats().map(String::from).collect(),
+            information: self.information().map(String::from),
+            connections: self
+                .connections()
+                .filter_map(|c| connection(Some(c)))
+                .collect(),
+            bandwidths: bandwidths(self.bandwidths()),
+            key: key(self.key()),
+            attributes: attributes(self.attributes()),
+        };
+        media.serialize(serializer)
+    }
+}
+
+impl Serialize for SDPMedia {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SDPMedia {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        SDPMediaSerde::deserialize(deserializer).map(|de| {
+            let mut media = SDPMedia::new();
+            if let Some(ref m) = de.media {
+                media.set_media(m);
+            }
+            media.set_port_info(de.port, de.num_ports);
+            if let Some(ref proto) = de.proto {
+                media.set_proto(proto);
+            }
+            for format in &de.formats {
+                media.add_format(format);
+            }
+            if let Some(ref information) = de.information {
+                media.set_information(information);
+            }
+            for c in &de.connections {
+                media.add_connection(&c.nettype, &c.addrtype, &c.address, c.ttl, c.addr_number);
+            }
+            for bw in &de.bandwidths {
+                media.add_bandwidth(&bw.bwtype, bw.value);
+            }
+            if let Some(ref key) = de.key {
+                media.set_key(&key.type_, &key.data);
+            }
+            for attr in &de.attributes {
+                media.add_attribute(&attr.key, attr.value.as_deref());
+            }
+            media
+        })
+    }
+}
+
+impl Serialize for SDPMessageRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let origin = self.origin().and_then(|origin| {
+            let field = |f: Option<&str>| f.map(String::from).unwrap_or_default();
+            Some(SDPOriginSerde {
+                username: origin.username()?.to_string(),
+                sess_id: field(origin.sess_id()),
+                sess_version: field(origin.sess_version()),
+                nettype: field(origin.nettype()),
+                addrtype: field(origin.addrtype()),
+                addr: field(origin.addr()),
+            })
+        });
+
+        let message = SDPMessageSerde {
+            version: self.version().map(String::from),
+            origin,
+            session_name: self.session_name().map(String::from),
+            information: self.information().map(String::from),
+            uri: self.uri().map(String::from),
+            emails: self.emails().map(String::from).collect(),
+            phones: self.phones().map(String::from).collect(),
+            connection: connection(self.connection()),
+            bandwidths: bandwidths(self.bandwidths()),
+            times: self
+                .times()
+                .map(|time| SDPTimeSerde {
+                    start: time.start().map(String::from).unwrap_or_default(),
+                    stop: time.stop().map(String::from).unwrap_or_default(),
+                    repeat: time.repeat().into_iter().map(String::from).collect(),
+                })
+                .collect(),
+            zones: self
+                .zones()
+                .map(|zone| SDPZoneSerde {
+                    time: zone.time().map(String::from).unwrap_or_default(),
+                    typed_time: zone.typed_time().map(String::from).unwrap_or_default(),
+                })
+                .collect(),
+            key: key(self.key()),
+            attributes: attributes(self.attributes()),
+            medias: self.medias().map(ToOwned::to_owned).collect(),
+        };
+        message.serialize(serializer)
+    }
+}
+
+impl Serialize for SDPMessage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SDPMessage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        SDPMessageSerde::deserialize(deserializer).map(|de| {
+            let mut message = SDPMessage::new();
+            if let Some(ref version) = de.version {
+                message.set_version(version);
+            }
+            if let Some(ref o) = de.origin {
+                message.set_origin(
+                    &o.username,
+                    &o.sess_id,
+                    &o.sess_version,
+                    &o.nettype,
+                    &o.addrtype,
+                    &o.addr,
+                );
+            }
+            if let Some(ref session_name) = de.session_name {
+                message.set_session_name(session_name);
+            }
+            if let Some(ref information) = de.information {
+                message.set_information(information);
+            }
+            if let Some(ref uri) = de.uri {
+                message.set_uri(uri);
+            }
+            for email in &de.emails {
+                message.add_email(email);
+            }
+            for phone in &de.phones {
+                message.add_phone(phone);
+            }
+            if let Some(ref c) = de.connection {
+                message.set_connection(&c.nettype, &c.addrtype, &c.address, c.ttl, c.addr_number);
+            }
+            for bw in &de.bandwidths {
+                message.add_bandwidth(&bw.bwtype, bw.value);
+            }
+            for time in &de.times {
+                let repeat = time.repeat.iter().map(String::as_str).collect::<Vec<_>>();
+                message.add_time(&time.start, &time.stop, &repeat);
+            }
+            for zone in &de.zones {
+                message.add_zone(&zone.time, &zone.typed_time);
+            }
+            if let Some(ref key) = de.key {
+                message.set_key(&key.type_, &key.data);
+            }
+            for attr in &de.attributes {
+                message.add_attribute(&attr.key, attr.value.as_deref());
+            }
+            for media in de.medias {
+                message.add_media(media);
+            }
+            message
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SDPMessage;
+
+    const SDP: &str = "v=0\r
+o=- 1188340656180883 1 IN IP4 192.168.1.10\r
+s=Session\r
+c=IN IP4 192.168.1.10\r
+t=0 0\r
+a=tool:gst\r
+m=video 5000 RTP/AVP 96\r
+b=AS:2000\r
+a=rtpmap:96 H264/90000\r
+a=fmtp:96 packetization-mode=1\r
+a=recvonly\r
+";
+
+    #[test]
+    fn test_serialize() {
+        gst::init().unwrap();
+
+        let message = SDPMessage::parse_buffer(SDP.as_bytes()).unwrap();
+        let json = serde_json::to_value(&message).unwrap();
+
+        assert_eq!(json["version"], "0");
+        assert_eq!(json["origin"]["sess_id"], "1188340656180883");
+        assert_eq!(json["connection"]["address"], "192.168.1.10");
+        assert_eq!(json["key"], serde_json::Value::Null);
+        assert_eq!(
+            json["times"],
+            serde_json::json!([{ "start": "0", "stop": "0", "repeat": [] }])
+        );
+
+        let media = &json["medias"][0];
+        assert_eq!(media["media"], "video");
+        assert_eq!(media["port"], 5000);
+        assert_eq!(media["proto"], "RTP/AVP");
+        assert_eq!(media["formats"], serde_json::json!(["96"]));
+        assert_eq!(media["connections"], serde_json::json!([]));
+        assert_eq!(
+            media["bandwidths"],
+            serde_json::json!([{ "bwtype": "AS", "value": 2000 }])
+        );
+        assert_eq!(
+            media["attributes"],
+            serde_json::json!([
+                { "key": "rtpmap", "value": "96 H264/90000" },
+                { "key": "fmtp", "value": "96 packetization-mode=1" },
+                { "key": "recvonly", "value": null },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        gst::init().unwrap();
+
+        let message = SDPMessage::parse_buffer(SDP.as_bytes()).unwrap();
+        let json = serde_json::to_string(&message).unwrap();
+        let message = serde_json::from_str::<SDPMessage>(&json).unwrap();
+
+        assert_eq!(message.as_text().unwrap(), SDP);
+    }
+}
@@ -1,10 +1,142 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::{ffi::CStr, fmt, mem, os::raw::c_char, ptr};
+use std::{
+    ffi::CStr,
+    fmt, mem,
+    os::raw::c_char,
+    ptr, str,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use crate::ffi;
 use glib::translate::*;
 
+// Seconds between the NTP epoch (1900-01-01) and the Unix epoch
+pub(crate) const NTP_UNIX_OFFSET: u64 = 2_208_988_800;
+
+// The time of NTP seconds, as used for SDP times
+pub(crate) fn ntp_to_system_time(ntp: u64) -> Option<SystemTime> {
+    if ntp >= NTP_UNIX_OFFSET {
+        UNIX_EPOCH.checked_add(Duration::from_secs(ntp - NTP_UNIX_OFFSET))
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::from_secs(NTP_UNIX_OFFSET - ntp))
+    }
+}
+
+pub(crate) fn system_time_to_ntp(time: SystemTime) -> Option<u64> {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_secs().checked_add(NTP_UNIX_OFFSET),
+        Err(err) => NTP_UNIX_OFFSET.checked_sub(err.duration().as_secs()),
+    }
+}
+
+// Parses a typed time like `7d`, `-1h` or `3600` into seconds
+pub(crate) fn parse_typed_time(s: &str) -> Result<i64, glib::BoolError> {
+    let (value, unit) = match s.as_bytes().last() {
+        Some(b'd') => (&s[..s.len() - 1], 86400),
+        Some(b'h') => (&s[..s.len() - 1], 3600),
+        Some(b'm') => (&s[..s.len() - 1], 60),
+        Some(b's') => (&s[..s.len() - 1], 1),
+        _ => (s, 1),
+    };
+
+    value
+        .parse::<i64>()
+        .ok()
+        .and_then(|value| value.checked_mul(unit))
+        .ok_or_else(|| glib::bool_error!("Invalid typed time {}", s))
+}
+
+// Formats seconds as typed time with the largest possible unit
+pub(crate) fn format_typed_time(secs: i64) -> String {
+    match secs {
+        0 => String::from("0"),
+        _ if secs % 86400 == 0 => format!("{}d", secs / 86400),
+        _ if secs % 3600 == 0 => format!("{}h", secs / 3600),
+        _ if secs % 60 == 0 => format!("{}m", secs / 60),
+        _ => secs.to_string(),
+    }
+}
+
+fn parse_time(time: Option<&str>) -> Result<Option<SystemTime>, glib::BoolError> {
+    let time = time.ok_or_else(|| glib::bool_error!("Time not set"))?;
+    match time
+        .parse::<u64>()
+        .map_err(|_| glib::bool_error!("Invalid time {}", time))?
+    {
+        0 => Ok(None),
+        ntp => ntp_to_system_time(ntp)
+            .map(Some)
+            .ok_or_else(|| glib::bool_error!("Invalid time {}", time)),
+    }
+}
+
+fn format_time(time: Option<SystemTime>) -> Result<String, glib::BoolError> {
+    match time {
+        None => Ok(String::from("0")),
+        Some(time) => system_time_to_ntp(time)
+            .filter(|ntp| *ntp != 0)
+            .map(|ntp| ntp.to_string())
+            .ok_or_else(|| glib::bool_error!("Time {:?} not representable", time)),
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// The repeat times of an `r=` line, e.g. `7d 1h 0 25h` for a session that
+/// is repeated weekly for one hour at the start time and 25 hours later.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SDPRepeat {
+    pub interval: Duration,
+    pub duration: Duration,
+    // rustdoc-stripper-ignore-next
+    /// Offsets of the repetitions relative to the start time.
+    pub offsets: Vec<Duration>,
+}
+
+impl fmt::Display for SDPRepeat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let typed = |d: &Duration| format_typed_time(d.as_secs() as i64);
+        write!(f, "{} {}", typed(&self.interval), typed(&self.duration))?;
+        for offset in &self.offsets {
+            write!(f, " {}", typed(offset))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl str::FromStr for SDPRepeat {
+    type Err = glib::BoolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        skip_assert_initialized!();
+        let mut fields = s.split_ascii_whitespace().map(|field| {
+            parse_typed_time(field)
+                .ok()
+                .and_then(|secs| u64::try_from(secs).ok())
+                .map(Duration::from_secs)
+                .ok_or_else(|| glib::bool_error!("Invalid repeat time {}", field))
+        });
+
+        let interval = fields
+            .next()
+            .ok_or_else(|| glib::bool_error!("Missing repeat interval in {}", s))??;
+        let duration = fields
+            .next()
+            .ok_or_else(|| glib::bool_error!("Missing active duration in {}", s))??;
+        let offsets = fields.collect::<Result<Vec<_>, _>>()?;
+        if offsets.is_empty() {
+            return Err(glib::bool_error!("Missing offsets in {}", s));
+        }
+
+        Ok(SDPRepeat {
+            interval,
+            duration,
+            offsets,
+        })
+    }
+}
+
 #[repr(transparent)]
 #[doc(alias = "GstSDPTime")]
 pub struct SDPTime(pub(crate) ffi::GstSDPTime);
@@ -27,6 +159,53 @@ impl SDPTime {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Creates a time description from typed start and stop times, where
+    /// `None` means unbounded.
+    ///
+    /// `SystemTime` converts from and to e.g. `chrono::DateTime<Utc>`.
+    pub fn from_times(
+        start: Option<SystemTime>,
+        stop: Option<SystemTime>,
+        repeat: Option<&SDPRepeat>,
+    ) -> Result<Self, glib::BoolError> {
+        assert_initialized_main_thread!();
+        let repeat = repeat.map(ToString::to_string);
+        let repeat = repeat
+            .as_deref()
+            .map(|repeat| repeat.split(' ').collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        Ok(SDPTime::new(
+            &format_time(start)?,
+            &format_time(stop)?,
+            &repeat,
+        ))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the start time, or `None` if the session is unbounded.
+    pub fn start_time(&self) -> Result<Option<SystemTime>, glib::BoolError> {
+        parse_time(self.start())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the stop time, or `None` if the session is unbounded.
+    pub fn stop_time(&self) -> Result<Option<SystemTime>, glib::BoolError> {
+        parse_time(self.stop())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the repeat times, if any.
+    pub fn repeat_times(&self) -> Result<Option<SDPRepeat>, glib::BoolError> {
+        let repeat = self.repeat();
+        if repeat.is_empty() {
+            return Ok(None);
+        }
+
+        repeat.join(" ").parse().map(Some)
+    }
+
     pub fn start(&self) -> Option<&str> {
         unsafe {
             if self.0.start.is_null() {
@@ -103,3 +282,59 @@ impl fmt::Debug for SDPTime {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typed_time() {
+        for (s, secs) in [("7d", 604800), ("-1h", -3600), ("25h", 90000), ("90", 90)] {
+            assert_eq!(parse_typed_time(s).unwrap(), secs);
+        }
+        assert_eq!(parse_typed_time("30s").unwrap(), 30);
+        assert!(parse_typed_time("1w").is_err());
+        assert!(parse_typed_time("h").is_err());
+
+        assert_eq!(format_typed_time(604800), "7d");
+        assert_eq!(format_typed_time(-3600), "-1h");
+        assert_eq!(format_typed_time(90000), "25h");
+        assert_eq!(format_typed_time(90), "90");
+    }
+
+    #[test]
+    fn test_time() {
+        gst::init().unwrap();
+
+        // RFC 4566 example: weekly for one hour at the start time and
+        // 25 hours later
+        let time = SDPTime::new(
+            "3034423619",
+            "3042462419",
+            &["604800", "3600", "0", "90000"],
+        );
+        let start = time.start_time().unwrap().unwrap();
+        assert_eq!(
+            start.duration_since(UNIX_EPOCH).unwrap().as_secs(),
+            3034423619 - NTP_UNIX_OFFSET
+        );
+        let repeat = time.repeat_times().unwrap().unwrap();
+        assert_eq!(repeat.interval, Duration::from_secs(7 * 86400));
+        assert_eq!(repeat.duration, Duration::from_secs(3600));
+        assert_eq!(
+            repeat.offsets,
+            [Duration::ZERO, Duration::from_secs(25 * 3600)]
+        );
+        assert_eq!(repeat.to_string(), "7d 1h 0 25h");
+
+        let time = SDPTime::from_times(Some(start), None, Some(&repeat)).unwrap();
+        assert_eq!(time.start(), Some("3034423619"));
+        assert_eq!(time.stop(), Some("0"));
+        assert_eq!(time.stop_time().unwrap(), None);
+        assert_eq!(time.repeat(), ["7d", "1h", "0", "25h"]);
+        assert_eq!(time.repeat_times().unwrap(), Some(repeat));
+
+        assert!("7d 1h".parse::<SDPRepeat>().is_err());
+        assert!("7d -1h 0".parse::<SDPRepeat>().is_err());
+    }
+}
@@ -1,8 +1,8 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::{ffi::CStr, fmt, mem};
+use std::{ffi::CStr, fmt, mem, time::SystemTime};
 
-use crate::ffi;
+use crate::{ffi, sdp_time};
 use glib::translate::*;
 
 #[repr(transparent)]
@@ -26,6 +26,41 @@ impl SDPZone {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Creates a time zone adjustment of `offset` seconds, e.g. `-3600` at
+    /// the end of daylight saving time, starting at `time`.
+    pub fn from_adjustment(time: SystemTime, offset: i64) -> Result<Self, glib::BoolError> {
+        assert_initialized_main_thread!();
+        let ntp = sdp_time::system_time_to_ntp(time)
+            .ok_or_else(|| glib::bool_error!("Time {:?} not representable", time))?;
+
+        Ok(SDPZone::new(
+            &ntp.to_string(),
+            &sdp_time::format_typed_time(offset),
+        ))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the time at which the adjustment takes effect.
+    pub fn adjustment_time(&self) -> Result<SystemTime, glib::BoolError> {
+        let time = self
+            .time()
+            .ok_or_else(|| glib::bool_error!("Adjustment time not set"))?;
+        time.parse::<u64>()
+            .ok()
+            .and_then(sdp_time::ntp_to_system_time)
+            .ok_or_else(|| glib::bool_error!("Invalid adjustment time {}", time))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the offset in seconds.
+    pub fn offset(&self) -> Result<i64, glib::BoolError> {
+        let typed_time = self
+            .typed_time()
+            .ok_or_else(|| glib::bool_error!("Offset not set"))?;
+        sdp_time::parse_typed_time(typed_time)
+    }
+
     pub fn time(&self) -> Option<&str> {
         unsafe {
             if self.0.time.is_null() {
@@ -74,3 +109,23 @@ impl fmt::Debug for SDPZone {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjustment() {
+        gst::init().unwrap();
+
+        let zone = SDPZone::new("2882844526", "-1h");
+        assert_eq!(zone.offset().unwrap(), -3600);
+        let time = zone.adjustment_time().unwrap();
+
+        let zone = SDPZone::from_adjustment(time, -3600).unwrap();
+        assert_eq!(zone.time(), Some("2882844526"));
+        assert_eq!(zone.typed_time(), Some("-1h"));
+
+        assert!(SDPZone::new("x", "0").adjustment_time().is_err());
+    }
+}
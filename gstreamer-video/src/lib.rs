@@ -62,6 +62,8 @@ mod video_info_dma_drm;
 pub use crate::video_info_dma_drm::*;
 pub mod video_frame;
 pub use crate::video_frame::{VideoFrame, VideoFrameExt, VideoFrameRef};
+pub mod video_frame_plane;
+pub use crate::video_frame_plane::{VideoFramePlane, VideoFramePlaneMut};
 mod video_overlay;
 pub use crate::video_overlay::is_video_overlay_prepare_window_handle_message;
 
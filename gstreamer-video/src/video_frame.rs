@@ -2,7 +2,7 @@
 
 use std::{fmt, marker::PhantomData, mem, ops, ptr, slice};
 
-use crate::ffi;
+use crate::{ffi, VideoFramePlane, VideoFramePlaneMut};
 use glib::translate::{from_glib, from_glib_none, Borrowed, ToGlibPtr};
 
 pub enum Readable {}
@@ -48,6 +48,65 @@ fn plane_buffer_info<T: IsVideoFrame>(
     Ok((plane as usize, (w * h) as usize))
 }
 
+// Returns stride, row size and number of rows of the plane
+fn plane_layout<T: IsVideoFrame>(
+    frame: &T,
+    plane: u32,
+) -> Result<(usize, usize, usize), glib::BoolError> {
+    skip_assert_initialized!();
+
+    if plane >= frame.n_planes() {
+        return Err(glib::bool_error!(
+            "Plane index higher than number of planes"
+        ));
+    }
+
+    let format_info = frame.format_info();
+
+    // The palette is 256 RGBA colors
+    if format_info.has_palette() && plane == 1 {
+        return Ok((4, 4, 256));
+    }
+
+    let stride = frame.plane_stride()[plane as usize];
+    let height = frame.plane_height(plane) as usize;
+
+    if stride < 0 {
+        return Err(glib::bool_error!("Negative strides are not supported"));
+    }
+
+    let stride = stride as usize;
+    if stride == 0 || height == 0 {
+        return Ok((0, 0, 0));
+    }
+
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "v1_18")] {
+            let comp = format_info.component(plane)[0];
+        } else {
+            let comp = plane as i32;
+        }
+    }
+
+    // Formats with complex packing have no pixel stride, in which case the
+    // whole stride is considered part of the row
+    let pstride = if comp < 0 {
+        0
+    } else {
+        frame.comp_pstride(comp as u32)
+    };
+    let row_size = if pstride <= 0 {
+        stride
+    } else {
+        usize::min(
+            stride,
+            frame.comp_width(comp as u32) as usize * pstride as usize,
+        )
+    };
+
+    Ok((stride, row_size, height))
+}
+
 pub struct VideoFrame<T> {
     frame: ffi::GstVideoFrame,
     phantom: PhantomData<T>,
@@ -313,6 +372,14 @@ impl<T> VideoFrame<T> {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Returns a view on the data of `plane` that allows iterating over its
+    /// rows without the padding of each row.
+    pub fn plane(&self, plane: u32) -> Result<VideoFramePlane<'_>, glib::BoolError> {
+        let (stride, row_size, height) = plane_layout(self, plane)?;
+        VideoFramePlane::new(self.plane_data(plane)?, stride, row_size, height)
+    }
+
     pub fn planes_data(&self) -> [&[u8]; 4] {
         let mut planes = [[].as_slice(); 4];
 
@@ -528,6 +595,14 @@ impl VideoFrame<Writable> {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Returns a mutable view on the data of `plane` that allows iterating
+    /// over its rows without the padding of each row.
+    pub fn plane_mut(&mut self, plane: u32) -> Result<VideoFramePlaneMut<'_>, glib::BoolError> {
+        let (stride, row_size, height) = plane_layout(self, plane)?;
+        VideoFramePlaneMut::new(self.plane_data_mut(plane)?, stride, row_size, height)
+    }
+
     pub fn planes_data_mut(&mut self) -> [&mut [u8]; 4] {
         unsafe {
             let mut planes = [
@@ -649,6 +724,14 @@ impl<T> VideoFrameRef<T> {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Returns a view on the data of `plane` that allows iterating over its
+    /// rows without the padding of each row.
+    pub fn plane(&self, plane: u32) -> Result<VideoFramePlane<'_>, glib::BoolError> {
+        let (stride, row_size, height) = plane_layout(self, plane)?;
+        VideoFramePlane::new(self.plane_data(plane)?, stride, row_size, height)
+    }
+
     pub fn planes_data(&self) -> [&[u8]; 4] {
         let mut planes = [[].as_slice(); 4];
 
@@ -860,6 +943,14 @@ impl<'a> VideoFrameRef<&'a mut gst::BufferRef> {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Returns a mutable view on the data of `plane` that allows iterating
+    /// over its rows without the padding of each row.
+    pub fn plane_mut(&mut self, plane: u32) -> Result<VideoFramePlaneMut<'_>, glib::BoolError> {
+        let (stride, row_size, height) = plane_layout(self, plane)?;
+        VideoFramePlaneMut::new(self.plane_data_mut(plane)?, stride, row_size, height)
+    }
+
     pub fn planes_data_mut(&mut self) -> [&mut [u8]; 4] {
         unsafe {
             let mut planes = [
@@ -0,0 +1,476 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{iter::FusedIterator, slice};
+
+// Returns the used length of `len` bytes of data in the given layout
+fn check_layout(
+    len: usize,
+    stride: usize,
+    row_size: usize,
+    height: usize,
+) -> Result<usize, glib::BoolError> {
+    skip_assert_initialized!();
+
+    if height == 0 {
+        return Ok(0);
+    }
+
+    if stride == 0 {
+        return Err(glib::bool_error!("Stride of a plane can't be zero"));
+    }
+
+    if row_size == 0 {
+        return Err(glib::bool_error!("Row size of a plane can't be zero"));
+    }
+
+    if row_size > stride {
+        return Err(glib::bool_error!(
+            "Row size {} larger than stride {}",
+            row_size,
+            stride
+        ));
+    }
+
+    let size = stride
+        .checked_mul(height - 1)
+        .and_then(|size| size.checked_add(row_size))
+        .ok_or_else(|| glib::bool_error!("Plane size overflows"))?;
+    if size > len {
+        return Err(glib::bool_error!(
+            "Plane of {} bytes too small for {} rows of {} bytes with stride {}",
+            len,
+            height,
+            row_size,
+            stride
+        ));
+    }
+
+    Ok(usize::min(len, stride.saturating_mul(height)))
+}
+
+// rustdoc-stripper-ignore-next
+/// A view on the data of a plane of a video frame, e.g. from
+/// [`VideoFrame::plane()`](crate::VideoFrame::plane), that gives access to
+/// the rows of the plane without the padding at the end of each row.
+#[derive(Debug, Clone, Copy)]
+pub struct VideoFramePlane<'a> {
+    data: &'a [u8],
+    stride: usize,
+    row_size: usize,
+    height: usize,
+}
+
+impl<'a> VideoFramePlane<'a> {
+    // rustdoc-stripper-ignore-next
+    /// Creates a view of `height` rows of `row_size` bytes in `data`, each
+    /// starting `stride` bytes after the previous one.
+    ///
+    /// This fails if `data` is too small for the rows.
+    pub fn new(
+        data: &'a [u8],
+        stride: usize,
+        row_size: usize,
+        height: usize,
+    ) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let len = check_layout(data.len(), stride, row_size, height)?;
+
+        Ok(Self {
+            data: &data[..len],
+            stride,
+            row_size,
+            height,
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The data of the plane, including the padding between the rows.
+    #[inline]
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The size of each row in bytes without padding.
+    #[inline]
+    pub fn row_size(&self) -> usize {
+        self.row_size
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The number of rows.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[inline]
+    pub fn row(&self, row: usize) -> Option<&'a [u8]> {
+        if row >= self.height {
+            return None;
+        }
+
+        let start = row * self.stride;
+        Some(&self.data[start..start + self.row_size])
+    }
+
+    #[inline]
+    pub fn rows(&self) -> VideoFramePlaneRows<'a> {
+        VideoFramePlaneRows {
+            chunks: self.data.chunks(self.stride.max(1)),
+            row_size: self.row_size,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Splits the plane into views of `rows` rows each, except for the last
+    /// one which can have fewer rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rows` is 0.
+    #[inline]
+    pub fn row_chunks(&self, rows: usize) -> VideoFramePlaneChunks<'a> {
+        assert_ne!(rows, 0, "Number of rows can't be zero");
+
+        VideoFramePlaneChunks {
+            chunks: self.data.chunks(self.stride.max(1).saturating_mul(rows)),
+            stride: self.stride,
+            row_size: self.row_size,
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A mutable view on the data of a plane of a video frame, e.g. from
+/// [`VideoFrame::plane_mut()`](crate::VideoFrame::plane_mut).
+///
+/// See [`VideoFramePlane`].
+#[derive(Debug)]
+pub struct VideoFramePlaneMut<'a> {
+    data: &'a mut [u8],
+    stride: usize,
+    row_size: usize,
+    height: usize,
+}
+
+impl<'a> VideoFramePlaneMut<'a> {
+    // rustdoc-stripper-ignore-next
+    /// Creates a view of `height` rows of `row_size` bytes in `data`, each
+    /// starting `stride` bytes after the previous one.
+    ///
+    /// This fails if `data` is too small for the rows.
+    pub fn new(
+        data: &'a mut [u8],
+        stride: usize,
+        row_size: usize,
+        height: usize,
+    ) -> Result<Self, glib::BoolError> {
+        skip_assert_initialized!();
+
+        let len = check_layout(data.len(), stride, row_size, height)?;
+
+        Ok(Self {
+            data: &mut data[..len],
+            stride,
+            row_size,
+            height,
+        })
+    }
+
+    #[inline]
+    pub fn as_plane(&self) -> VideoFramePlane<'_> {
+        VideoFramePlane {
+            data: self.data,
+            stride: self.stride,
+            row_size: self.row_size,
+            height: self.height,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The data of the plane, including the padding between the rows.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        self.data
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The data of the plane, including the padding between the rows.
+    #[inline]
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        self.data
+    }
+
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The size of each row in bytes without padding.
+    #[inline]
+    pub fn row_size(&self) -> usize {
+        self.row_size
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The number of rows.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[inline]
+    pub fn row(&self, row: usize) -> Option<&[u8]> {
+        self.as_plane().row(row)
+    }
+
+    #[inline]
+    pub fn row_mut(&mut self, row: usize) -> Option<&mut [u8]> {
+        if row >= self.height {
+            return None;
+        }
+
+        let start = row * self.stride;
+        Some(&mut self.data[start..start + self.row_size])
+    }
+
+    #[inline]
+    pub fn rows(&self) -> VideoFramePlaneRows<'_> {
+        self.as_plane().rows()
+    }
+
+    #[inline]
+    pub fn rows_mut(&mut self) -> VideoFramePlaneRowsMut<'_> {
+        VideoFramePlaneRowsMut {
+            chunks: self.data.chunks_mut(self.stride.max(1)),
+            row_size: self.row_size,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Splits the plane into views of `rows` rows each, except for the last
+    /// one which can have fewer rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rows` is 0.
+    #[inline]
+    pub fn row_chunks(&self, rows: usize) -> VideoFramePlaneChunks<'_> {
+        self.as_plane().row_chunks(rows)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Splits the plane into mutable views of `rows` rows each, except for
+    /// the last one which can have fewer rows, e.g. for processing them in
+    /// parallel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rows` is 0.
+    #[inline]
+    pub fn row_chunks_mut(&mut self, rows: usize) -> VideoFramePlaneChunksMut<'_> {
+        assert_ne!(rows, 0, "Number of rows can't be zero");
+
+        VideoFramePlaneChunksMut {
+            chunks: self
+                .data
+                .chunks_mut(self.stride.max(1).saturating_mul(rows)),
+            stride: self.stride,
+            row_size: self.row_size,
+        }
+    }
+}
+
+macro_rules! define_rows_iter(
+    ($name:ident, $chunks:ident, $item:ty, $split:ident) => {
+        #[must_use = "iterators are lazy and do nothing unless consumed"]
+        #[derive(Debug)]
+        pub struct $name<'a> {
+            chunks: slice::$chunks<'a, u8>,
+            row_size: usize,
+        }
+
+        impl<'a> Iterator for $name<'a> {
+            type Item = $item;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                let row_size = self.row_size;
+                self.chunks.next().map(|row| row.$split(row_size).0)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.chunks.size_hint()
+            }
+
+            #[inline]
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                let row_size = self.row_size;
+                self.chunks.nth(n).map(|row| row.$split(row_size).0)
+            }
+        }
+
+        impl DoubleEndedIterator for $name<'_> {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let row_size = self.row_size;
+                self.chunks.next_back().map(|row| row.$split(row_size).0)
+            }
+        }
+
+        impl ExactSizeIterator for $name<'_> {}
+
+        impl FusedIterator for $name<'_> {}
+    };
+);
+
+define_rows_iter!(VideoFramePlaneRows, Chunks, &'a [u8], split_at);
+define_rows_iter!(
+    VideoFramePlaneRowsMut,
+    ChunksMut,
+    &'a mut [u8],
+    split_at_mut
+);
+
+macro_rules! define_chunks_iter(
+    ($name:ident, $chunks:ident, $data:ty, $item:ident) => {
+        #[must_use = "iterators are lazy and do nothing unless consumed"]
+        #[derive(Debug)]
+        pub struct $name<'a> {
+            chunks: slice::$chunks<'a, u8>,
+            stride: usize,
+            row_size: usize,
+        }
+
+        impl<'a> $name<'a> {
+            #[inline]
+            fn plane(&self, data: $data) -> $item<'a> {
+                $item {
+                    height: data.len().div_ceil(self.stride),
+                    data,
+                    stride: self.stride,
+                    row_size: self.row_size,
+                }
+            }
+        }
+
+        impl<'a> Iterator for $name<'a> {
+            type Item = $item<'a>;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                let data = self.chunks.next()?;
+                Some(self.plane(data))
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.chunks.size_hint()
+            }
+        }
+
+        impl DoubleEndedIterator for $name<'_> {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let data = self.chunks.next_back()?;
+                Some(self.plane(data))
+            }
+        }
+
+        impl ExactSizeIterator for $name<'_> {}
+
+        impl FusedIterator for $name<'_> {}
+    };
+);
+
+define_chunks_iter!(VideoFramePlaneChunks, Chunks, &'a [u8], VideoFramePlane);
+define_chunks_iter!(
+    VideoFramePlaneChunksMut,
+    ChunksMut,
+    &'a mut [u8],
+    VideoFramePlaneMut
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{VideoFormat, VideoFrame, VideoInfo};
+
+    #[test]
+    fn test_plane_rows() {
+        let data = (0..22).collect::<Vec<u8>>();
+
+        let plane = VideoFramePlane::new(&data, 8, 6, 3).unwrap();
+        assert_eq!(plane.data().len(), 22);
+        assert_eq!(plane.row(1), Some(&data[8..14]));
+        assert_eq!(plane.row(2), Some(&data[16..]));
+        assert_eq!(plane.row(3), None);
+
+        let rows = plane.rows().collect::<Vec<_>>();
+        assert_eq!(rows, [&data[0..6], &data[8..14], &data[16..]]);
+        assert_eq!(plane.rows().rev().next(), Some(&data[16..]));
+
+        let chunks = plane.row_chunks(2).collect::<Vec<_>>();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].height(), 2);
+        assert_eq!(chunks[1].height(), 1);
+        assert_eq!(chunks[1].row(0), Some(&data[16..]));
+
+        assert!(VideoFramePlane::new(&data, 8, 6, 4).is_err());
+        assert!(VideoFramePlane::new(&data, 4, 6, 2).is_err());
+        assert!(VideoFramePlane::new(&data, 0, 0, 1).is_err());
+        assert!(VideoFramePlane::new(&data, 8, 0, 1).is_err());
+        assert_eq!(
+            VideoFramePlane::new(&data, 0, 0, 0).unwrap().rows().len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_video_frame_planes() {
+        gst::init().unwrap();
+
+        let info = VideoInfo::builder(VideoFormat::Nv12, 6, 4).build().unwrap();
+        let buffer = gst::Buffer::with_size(info.size()).unwrap();
+        let mut frame = VideoFrame::from_buffer_writable(buffer, &info).unwrap();
+
+        {
+            let mut plane = frame.plane_mut(0).unwrap();
+            assert_eq!(plane.stride(), 8);
+            assert_eq!(plane.row_size(), 6);
+            assert_eq!(plane.height(), 4);
+            for (i, row) in plane.rows_mut().enumerate() {
+                row.fill(i as u8);
+            }
+
+            let mut chunks = plane.row_chunks_mut(3);
+            assert_eq!(chunks.len(), 2);
+            let mut last = chunks.next_back().unwrap();
+            assert_eq!(last.height(), 1);
+            last.row_mut(0).unwrap().fill(42);
+        }
+
+        let plane = frame.plane_mut(1).unwrap();
+        assert_eq!(plane.stride(), 8);
+        assert_eq!(plane.row_size(), 6);
+        assert_eq!(plane.height(), 2);
+        assert!(frame.plane(2).is_err());
+
+        let frame = VideoFrame::from_buffer_readable(frame.into_buffer(), &info).unwrap();
+        let plane = frame.plane(0).unwrap();
+        assert_eq!(plane.row(1), Some([1; 6].as_slice()));
+        assert_eq!(plane.row(3), Some([42; 6].as_slice()));
+
+        let frame = frame.as_video_frame_ref();
+        assert_eq!(frame.plane(0).unwrap().rows().len(), 4);
+    }
+}
@@ -1,15 +1,24 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::{boxed::Box as Box_, mem::transmute, path};
+use std::{
+    boxed::Box as Box_,
+    mem::transmute,
+    path,
+    sync::{Arc, Mutex},
+};
 
 use glib::{
+    object::IsClass,
     prelude::*,
     signal::{connect_raw, SignalHandlerId},
     translate::*,
     GString,
 };
 
-use crate::{ffi, prelude::*, Bin, BinFlags, Element, LoggableError};
+use crate::{
+    ffi, prelude::*, Bin, BinFlags, Element, GhostPad, LoggableError, Pad, PadDirection,
+    PadPresence,
+};
 
 impl Bin {
     // rustdoc-stripper-ignore-next
@@ -185,6 +194,81 @@ pub trait GstBinExtManual: IsA<Bin> + 'static {
         crate::auto::functions::debug_bin_to_dot_file_with_ts(self, details, file_name)
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Creates a [`GhostPad`] targeting `pad` of a child element of the bin and adds it to the
+    /// bin.
+    ///
+    /// The ghost pad is named `src` or `sink` depending on the direction of `pad`, with a
+    /// numeric suffix like `src_0` if the bin already has a pad with that name. If the bin is
+    /// not in the `Null` state, the ghost pad is activated before adding it to the bin.
+    fn add_ghost_pad_for<P: IsA<Pad> + IsA<crate::Object>>(
+        &self,
+        pad: &P,
+    ) -> Result<GhostPad, glib::BoolError> {
+        let bin = self.as_ref();
+        let target = pad.upcast_ref::<Pad>();
+
+        let is_child = target
+            .parent_element()
+            .and_then(|element| element.parent())
+            .is_some_and(|parent| &parent == bin.upcast_ref::<crate::Object>());
+        if !is_child {
+            return Err(glib::bool_error!(
+                "Pad '{}' does not belong to a child element of '{}'",
+                target.name(),
+                bin.name(),
+            ));
+        }
+
+        let prefix = match target.direction() {
+            PadDirection::Src => "src",
+            PadDirection::Sink => "sink",
+            _ => {
+                return Err(glib::bool_error!(
+                    "Pad '{}' has an unknown direction",
+                    target.name()
+                ))
+            }
+        };
+
+        let name = if bin.static_pad(prefix).is_none() {
+            prefix.to_owned()
+        } else {
+            (0..)
+                .map(|idx| format!("{prefix}_{idx}"))
+                .find(|name| bin.static_pad(name).is_none())
+                .unwrap()
+        };
+
+        let ghost_pad = GhostPad::builder_with_target(pad)?.name(name).build();
+        if bin.current_state() > crate::State::Null {
+            ghost_pad.set_active(true)?;
+        }
+        bin.add_pad(&ghost_pad)?;
+
+        Ok(ghost_pad)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a source [`GhostPad`] for the child `element` of the bin and adds it to the bin.
+    ///
+    /// This targets the `src` pad of `element` if it is not linked yet, otherwise any unlinked
+    /// source pad or a newly requested pad. See [`add_ghost_pad_for()`](Self::add_ghost_pad_for)
+    /// for details about the ghost pad.
+    fn ghost_src_pad_of(&self, element: &impl IsA<Element>) -> Result<GhostPad, glib::BoolError> {
+        ghost_pad_of(self.as_ref(), element.as_ref(), PadDirection::Src)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a sink [`GhostPad`] for the child `element` of the bin and adds it to the bin.
+    ///
+    /// This targets the `sink` pad of `element` if it is not linked yet, otherwise any unlinked
+    /// sink pad or a newly requested pad. See [`add_ghost_pad_for()`](Self::add_ghost_pad_for)
+    /// for details about the ghost pad.
+    fn ghost_sink_pad_of(&self, element: &impl IsA<Element>) -> Result<GhostPad, glib::BoolError> {
+        ghost_pad_of(self.as_ref(), element.as_ref(), PadDirection::Sink)
+    }
+
     fn set_bin_flags(&self, flags: BinFlags) {
         unsafe {
             let ptr: *mut ffi::GstObject = self.as_ptr() as *mut _;
@@ -213,6 +297,65 @@ pub trait GstBinExtManual: IsA<Bin> + 'static {
 
 impl<O: IsA<Bin>> GstBinExtManual for O {}
 
+fn ghost_pad_of(
+    bin: &Bin,
+    element: &Element,
+    direction: PadDirection,
+) -> Result<GhostPad, glib::BoolError> {
+    skip_assert_initialized!();
+
+    // Check this before possibly requesting a pad from `element`
+    let is_child = element
+        .parent()
+        .is_some_and(|parent| &parent == bin.upcast_ref::<crate::Object>());
+    if !is_child {
+        return Err(glib::bool_error!(
+            "Element '{}' is not a child of '{}'",
+            element.name(),
+            bin.name(),
+        ));
+    }
+
+    let (pad, requested) = unlinked_pad(element, direction)?;
+    bin.add_ghost_pad_for(&pad).inspect_err(|_| {
+        if requested {
+            element.release_request_pad(&pad);
+        }
+    })
+}
+
+// Finds a pad of `element` in `direction` that can be used as ghost pad target,
+// and whether it was newly requested
+fn unlinked_pad(
+    element: &Element,
+    direction: PadDirection,
+) -> Result<(Pad, bool), glib::BoolError> {
+    skip_assert_initialized!();
+
+    let (name, pads) = match direction {
+        PadDirection::Src => ("src", element.src_pads()),
+        _ => ("sink", element.sink_pads()),
+    };
+
+    if let Some(pad) = element.static_pad(name).filter(|pad| !pad.is_linked()) {
+        return Ok((pad, false));
+    }
+
+    if let Some(pad) = pads.into_iter().find(|pad| !pad.is_linked()) {
+        return Ok((pad, false));
+    }
+
+    element
+        .pad_template_list()
+        .iter()
+        .filter(|templ| templ.direction() == direction && templ.presence() == PadPresence::Request)
+        .find_map(|templ| element.request_pad(templ, None, None))
+        .map(|pad| (pad, true))
+        .ok_or_else(|| {
+            glib::bool_error!("Element '{}' has no unlinked {} pad", element.name(), name)
+        })
+}
+
 impl Default for Bin {
     fn default() -> Self {
         glib::object::Object::new()
@@ -290,6 +433,425 @@ impl<'a> BinBuilder<'a> {
     }
 
     impl_builder_gvalue_extra_setters!(property_and_name);
+
+    // rustdoc-stripper-ignore-next
+    /// Adds an element created from the factory `factory_name` with the
+    /// given `properties` to the [`Bin`].
+    ///
+    /// See [`BinChildrenBuilder::element()`].
+    pub fn element(
+        self,
+        factory_name: &'a str,
+        properties: impl IntoIterator<Item = (&'a str, glib::Value)>,
+    ) -> BinChildrenBuilder<'a, Bin> {
+        BinChildrenBuilder::new(self.builder).element(factory_name, properties)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds `element` to the [`Bin`].
+    ///
+    /// See [`BinChildrenBuilder::add()`].
+    pub fn add(self, element: &impl IsA<Element>) -> BinChildrenBuilder<'a, Bin> {
+        BinChildrenBuilder::new(self.builder).add(element)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Creates an array of property names and values for
+/// [`BinChildrenBuilder::element()`].
+///
+/// ```rust
+/// # use gstreamer as gst;
+/// gst::init().unwrap();
+///
+/// let pipeline = gst::Pipeline::builder()
+///     .element("fakesrc", gst::props! { "name" => "src", "num-buffers" => 10 })
+///     .element("fakesink", gst::props! { "name" => "sink", "sync" => true })
+///     .link_many(["src", "sink"])
+///     .build()
+///     .unwrap();
+/// ```
+#[macro_export]
+macro_rules! props(
+    () => {{
+        let properties: [(&str, $crate::glib::Value); 0] = [];
+        properties
+    }};
+    ($($name:expr => $value:expr),+ $(,)?) => {
+        [$(($name, $crate::glib::value::ToValue::to_value(&$value))),+]
+    };
+);
+
+enum BinChild<'a> {
+    Element {
+        factory_name: &'a str,
+        properties: Vec<(&'a str, glib::Value)>,
+    },
+    Add(Element),
+    LinkMany(Vec<&'a str>),
+    LinkPads {
+        src: &'a str,
+        srcpad: Option<&'a str>,
+        dest: &'a str,
+        sinkpad: Option<&'a str>,
+    },
+}
+
+// rustdoc-stripper-ignore-next
+/// A [builder-pattern] type to construct [`Bin`]s or [`Pipeline`](crate::Pipeline)s
+/// together with their child elements and the links between them.
+///
+/// This is created by [`BinBuilder::element()`] or
+/// [`PipelineBuilder::element()`](crate::PipelineBuilder::element) and is a
+/// type-safe alternative to [`parse::bin_from_description()`](crate::parse::bin_from_description)
+/// and [`parse::launch()`](crate::parse::launch).
+///
+/// Elements are added and linked in the order in which they are specified,
+/// and elements are referred to by their name when linking them.
+///
+/// [builder-pattern]: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
+#[must_use = "The builder must be built to be used"]
+pub struct BinChildrenBuilder<'a, T> {
+    builder: crate::gobject::GObjectBuilder<'a, T>,
+    children: Vec<BinChild<'a>>,
+}
+
+impl<'a, T: IsA<Bin> + IsA<crate::Object> + IsClass> BinChildrenBuilder<'a, T> {
+    pub(crate) fn new(builder: crate::gobject::GObjectBuilder<'a, T>) -> Self {
+        skip_assert_initialized!();
+        Self {
+            builder,
+            children: Vec::new(),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds an element created from the factory `factory_name` with the
+    /// given `properties`, e.g. created with [`props!`](crate::props).
+    ///
+    /// The `name` property can be set for referring to the element in
+    /// [`link_many()`](Self::link_many) and [`link_pads()`](Self::link_pads).
+    pub fn element(
+        mut self,
+        factory_name: &'a str,
+        properties: impl IntoIterator<Item = (&'a str, glib::Value)>,
+    ) -> Self {
+        self.children.push(BinChild::Element {
+            factory_name,
+            properties: properties.into_iter().collect(),
+        });
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds an already created `element`.
+    pub fn add(mut self, element: &impl IsA<Element>) -> Self {
+        self.children.push(BinChild::Add(element.as_ref().clone()));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Links the elements with the given names in order.
+    ///
+    /// If an element has no unlinked source pad yet but has
+    /// [`Sometimes`](crate::PadPresence::Sometimes) source pads, the link is
+    /// made with the first pad added to the element that can be linked. If no
+    /// pad could be linked once the element signalled that no more pads are
+    /// added, an error message is posted.
+    pub fn link_many(mut self, names: impl IntoIterator<Item = &'a str>) -> Self {
+        self.children
+            .push(BinChild::LinkMany(names.into_iter().collect()));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Links the pad `srcpad` of the element `src` to the pad `sinkpad` of the
+    /// element `dest`.
+    ///
+    /// If `srcpad` or `sinkpad` is `None` then any compatible pad is used.
+    /// Like with [`link_many()`](Self::link_many), the link is made once
+    /// the pad is added if `src` doesn't have the pad yet but has
+    /// [`Sometimes`](crate::PadPresence::Sometimes) source pads. An error
+    /// message is posted if `srcpad` is added but can't be linked.
+    pub fn link_pads(
+        mut self,
+        src: &'a str,
+        srcpad: Option<&'a str>,
+        dest: &'a str,
+        sinkpad: Option<&'a str>,
+    ) -> Self {
+        self.children.push(BinChild::LinkPads {
+            src,
+            srcpad,
+            dest,
+            sinkpad,
+        });
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Builds the bin and adds and links all its children.
+    ///
+    /// This fails if an element can't be created, added or linked, an element
+    /// doesn't have all the given properties or property values of the wrong
+    /// type are provided, or no element with a name given for linking was added.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the bin doesn't have all the given properties or
+    /// property values of the wrong type are provided.
+    pub fn build(self) -> Result<T, glib::BoolError> {
+        let bin = self.builder.build().unwrap();
+
+        for child in self.children {
+            child.apply(bin.upcast_ref())?;
+        }
+
+        Ok(bin)
+    }
+}
+
+impl BinChild<'_> {
+    fn apply(self, bin: &Bin) -> Result<(), glib::BoolError> {
+        match self {
+            BinChild::Element {
+                factory_name,
+                properties,
+            } => {
+                let factory = crate::ElementFactory::find(factory_name).ok_or_else(|| {
+                    glib::bool_error!(
+                        "Failed to find element factory with name '{}' for creating element",
+                        factory_name
+                    )
+                })?;
+                let factory = factory.load().map_err(|_| {
+                    glib::bool_error!(
+                        "Failed to load element factory '{}' for creating element",
+                        factory_name
+                    )
+                })?;
+                let class = glib::object::ObjectClass::from_type(factory.element_type())
+                    .ok_or_else(|| {
+                        glib::bool_error!(
+                            "Failed to create element from factory '{}'",
+                            factory_name
+                        )
+                    })?;
+                for (name, value) in &properties {
+                    check_property(&class, factory_name, name, value)?;
+                }
+
+                let element = properties
+                    .into_iter()
+                    .fold(factory.create(), |builder, (name, value)| {
+                        builder.property(name, value)
+                    })
+                    .build()?;
+                add_child(bin, &element)
+            }
+            BinChild::Add(element) => add_child(bin, &element),
+            BinChild::LinkMany(names) => {
+                let elements = names
+                    .into_iter()
+                    .map(|name| child_by_name(bin, name))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                for pair in elements.windows(2) {
+                    link_or_defer(&pair[0], None, &pair[1], None)?;
+                }
+
+                Ok(())
+            }
+            BinChild::LinkPads {
+                src,
+                srcpad,
+                dest,
+                sinkpad,
+            } => link_or_defer(
+                &child_by_name(bin, src)?,
+                srcpad,
+                &child_by_name(bin, dest)?,
+                sinkpad,
+            ),
+        }
+    }
+}
+
+// Checks that elements of `class` have a writable property `name` that can be set to `value`
+fn check_property(
+    class: &glib::object::ObjectClass,
+    factory_name: &str,
+    name: &str,
+    value: &glib::Value,
+) -> Result<(), glib::BoolError> {
+    skip_assert_initialized!();
+    let pspec = class.find_property(name).ok_or_else(|| {
+        glib::bool_error!(
+            "Element factory '{}' has no property '{}'",
+            factory_name,
+            name
+        )
+    })?;
+    if !pspec.flags().contains(glib::ParamFlags::WRITABLE) {
+        return Err(glib::bool_error!(
+            "Property '{}' of element factory '{}' is not writable",
+            name,
+            factory_name
+        ));
+    }
+
+    let value_type = match value.get::<Option<glib::Object>>() {
+        Ok(Some(obj)) => obj.type_(),
+        _ => value.type_(),
+    };
+    if !value_type.is_a(pspec.value_type()) {
+        return Err(glib::bool_error!(
+            "Property '{}' of element factory '{}' has type '{}' but a value of type '{}' was given",
+            name,
+            factory_name,
+            pspec.value_type(),
+            value_type
+        ));
+    }
+
+    Ok(())
+}
+
+fn add_child(bin: &Bin, element: &Element) -> Result<(), glib::BoolError> {
+    skip_assert_initialized!();
+    bin.add(element).map_err(|_| {
+        glib::bool_error!(
+            "Failed to add element '{}' to '{}'",
+            element.name(),
+            bin.name()
+        )
+    })
+}
+
+fn child_by_name(bin: &Bin, name: &str) -> Result<Element, glib::BoolError> {
+    skip_assert_initialized!();
+    bin.by_name(name)
+        .ok_or_else(|| glib::bool_error!("No element named '{}' in '{}'", name, bin.name()))
+}
+
+// Links the pads or, if the source pad does not exist yet and `src` has sometimes
+// pads, does so once the pad is added
+fn link_or_defer(
+    src: &Element,
+    srcpad: Option<&str>,
+    dest: &Element,
+    sinkpad: Option<&str>,
+) -> Result<(), glib::BoolError> {
+    skip_assert_initialized!();
+    let err = match src.link_pads(srcpad, dest, sinkpad) {
+        Ok(()) => return Ok(()),
+        Err(err) => err,
+    };
+
+    // Failing to link an existing pad is a real error, e.g. because of incompatible caps
+    let has_existing_pad = match srcpad {
+        Some(srcpad) => src.static_pad(srcpad).is_some(),
+        None => src.src_pads().iter().any(|pad| !pad.is_linked()),
+    };
+    let has_sometimes_pads = src.pad_template_list().iter().any(|templ| {
+        templ.direction() == crate::PadDirection::Src
+            && templ.presence() == crate::PadPresence::Sometimes
+    });
+    if has_existing_pad || !has_sometimes_pads {
+        return Err(err);
+    }
+
+    // As with the delayed links of `gst_parse_launch()`, only the first pad
+    // that can be linked is linked
+    let state = Arc::new(Mutex::new(DeferredLink::default()));
+
+    let pad_added = {
+        let state = state.clone();
+        let dest = dest.downgrade();
+        let srcpad = srcpad.map(String::from);
+        let sinkpad = sinkpad.map(String::from);
+        src.connect_pad_added(move |src, pad| {
+            if pad.direction() != crate::PadDirection::Src || pad.is_linked() {
+                return;
+            }
+            let name = pad.name();
+            if srcpad
+                .as_deref()
+                .is_some_and(|srcpad| srcpad != name.as_str())
+            {
+                return;
+            }
+            let Some(dest) = dest.upgrade() else {
+                return;
+            };
+
+            let mut state = state.lock().unwrap();
+            if state.done {
+                return;
+            }
+            match src.link_pads(Some(&name), &dest, sinkpad.as_deref()) {
+                Ok(()) => state.finish(src),
+                Err(err) if srcpad.is_some() => {
+                    state.finish(src);
+                    crate::element_error!(
+                        src,
+                        crate::CoreError::Pad,
+                        ("Failed to link pad '{}' to '{}'", name, dest.name()),
+                        ["{}", err]
+                    );
+                }
+                // Another pad might still be linked
+                Err(err) => crate::debug!(crate::CAT_RUST, obj = src, "{}", err),
+            }
+        })
+    };
+
+    let no_more_pads = {
+        let state = state.clone();
+        let dest = dest.downgrade();
+        src.connect_no_more_pads(move |src| {
+            let mut state = state.lock().unwrap();
+            if state.done {
+                return;
+            }
+            state.finish(src);
+
+            let dest = dest.upgrade().map(|dest| dest.name());
+            crate::element_error!(
+                src,
+                crate::CoreError::Pad,
+                (
+                    "No pad of '{}' could be linked to '{}'",
+                    src.name(),
+                    dest.as_deref().unwrap_or("")
+                )
+            );
+        })
+    };
+
+    let mut state = state.lock().unwrap();
+    state.handlers.extend([pad_added, no_more_pads]);
+    if state.done {
+        state.finish(src);
+    }
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct DeferredLink {
+    done: bool,
+    handlers: Vec<SignalHandlerId>,
+}
+
+impl DeferredLink {
+    // Disconnects the signal handlers of the deferred link
+    fn finish(&mut self, src: &Element) {
+        self.done = true;
+        for handler in self.handlers.drain(..) {
+            src.disconnect(handler);
+        }
+    }
 }
 
 unsafe extern "C" fn do_latency_trampoline<
@@ -364,4 +926,122 @@ mod tests {
         assert!(bin.property::<bool>("async-handling"));
         assert!(bin.property::<bool>("message-forward"));
     }
+
+    #[test]
+    fn builder_children() {
+        crate::init().unwrap();
+
+        let identity = crate::ElementFactory::make("identity")
+            .name("identity")
+            .build()
+            .unwrap();
+        let bin = Bin::builder()
+            .name("test-bin")
+            .element(
+                "fakesrc",
+                crate::props! { "name" => "src", "num-buffers" => 1 },
+            )
+            .add(&identity)
+            .element("fakesink", crate::props! { "name" => "sink" })
+            .link_many(["src", "identity"])
+            .link_pads("identity", Some("src"), "sink", Some("sink"))
+            .build()
+            .unwrap();
+
+        assert_eq!(bin.name(), "test-bin");
+        assert_eq!(bin.children().len(), 3);
+        let src = bin.by_name("src").unwrap();
+        assert_eq!(src.property::<i32>("num-buffers"), 1);
+        assert!(src.static_pad("src").unwrap().is_linked());
+        assert!(identity.static_pad("src").unwrap().is_linked());
+
+        let err = Bin::builder()
+            .name("error-bin")
+            .element("fakesrc", crate::props! { "name" => "src" })
+            .link_many(["src", "sink"])
+            .build()
+            .unwrap_err();
+        assert_eq!(err.message, "No element named 'sink' in 'error-bin'");
+
+        let res = Bin::builder()
+            .element("fakesink", crate::props! { "name" => "sink" })
+            .element("fakesrc", crate::props! { "name" => "src" })
+            .link_many(["sink", "src"])
+            .build();
+        assert!(res.unwrap_err().message.starts_with("Failed to link pads"));
+
+        let err = Bin::builder()
+            .element("fakesrc", crate::props! { "num-bufers" => 1 })
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.message,
+            "Element factory 'fakesrc' has no property 'num-bufers'"
+        );
+
+        let err = Bin::builder()
+            .element("fakesrc", crate::props! { "num-buffers" => "1" })
+            .build()
+            .unwrap_err();
+        assert!(err
+            .message
+            .starts_with("Property 'num-buffers' of element factory 'fakesrc' has type 'gint'"));
+    }
+
+    #[test]
+    fn ghost_pads() {
+        crate::init().unwrap();
+
+        let bin = Bin::with_name("test-bin");
+        let identity = crate::ElementFactory::make("identity")
+            .name("identity")
+            .build()
+            .unwrap();
+        let tee = crate::ElementFactory::make("tee")
+            .name("tee")
+            .build()
+            .unwrap();
+        bin.add_many([&identity, &tee]).unwrap();
+        identity.link(&tee).unwrap();
+
+        let sink = bin.ghost_sink_pad_of(&identity).unwrap();
+        assert_eq!(sink.name(), "sink");
+        assert_eq!(sink.target(), identity.static_pad("sink"));
+        assert!(!sink.is_active());
+
+        // The identity src pad is already linked
+        let err = bin.ghost_src_pad_of(&identity).unwrap_err();
+        assert_eq!(err.message, "Element 'identity' has no unlinked src pad");
+
+        bin.set_state(crate::State::Ready).unwrap();
+
+        let src = bin.ghost_src_pad_of(&tee).unwrap();
+        assert_eq!(src.name(), "src");
+        assert!(src.target().unwrap().name().starts_with("src_"));
+        assert!(src.is_active());
+
+        let src_0 = bin.ghost_src_pad_of(&tee).unwrap();
+        assert_eq!(src_0.name(), "src_0");
+        assert_eq!(
+            bin.src_pads(),
+            [src.clone().upcast::<Pad>(), src_0.clone().upcast()]
+        );
+
+        let other = crate::ElementFactory::make("identity").build().unwrap();
+        assert!(bin.ghost_src_pad_of(&other).is_err());
+
+        // No pad is requested from elements that are not children of the bin
+        let other_tee = crate::ElementFactory::make("tee")
+            .name("other-tee")
+            .build()
+            .unwrap();
+        let err = bin.ghost_src_pad_of(&other_tee).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Element 'other-tee' is not a child of 'test-bin'"
+        );
+        assert!(other_tee.src_pads().is_empty());
+
+        bin.set_state(crate::State::Null).unwrap();
+    }
 }
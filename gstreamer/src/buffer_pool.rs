@@ -241,6 +241,127 @@ impl BufferPoolConfigRef {
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// A [builder-pattern] type to configure [`BufferPool`]s.
+///
+/// This is created by [`BufferPoolExtManual::config_builder()`] and starts
+/// from the current configuration of the pool. All parameters that are not
+/// set are kept as they are.
+///
+/// [builder-pattern]: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
+#[must_use = "The builder must be built to be used"]
+#[derive(Debug)]
+pub struct BufferPoolConfigBuilder<'a> {
+    config: BufferPoolConfig,
+    caps: Option<&'a crate::Caps>,
+    size: Option<u32>,
+    min_buffers: Option<u32>,
+    max_buffers: Option<u32>,
+    options: Vec<&'a str>,
+    allocator: Option<&'a Allocator>,
+    allocation_params: Option<&'a AllocationParams>,
+}
+
+impl<'a> BufferPoolConfigBuilder<'a> {
+    fn new(config: BufferPoolConfig) -> Self {
+        skip_assert_initialized!();
+        Self {
+            config,
+            caps: None,
+            size: None,
+            min_buffers: None,
+            max_buffers: None,
+            options: Vec::new(),
+            allocator: None,
+            allocation_params: None,
+        }
+    }
+
+    pub fn caps(self, caps: &'a crate::Caps) -> Self {
+        Self {
+            caps: Some(caps),
+            ..self
+        }
+    }
+
+    pub fn size(self, size: u32) -> Self {
+        Self {
+            size: Some(size),
+            ..self
+        }
+    }
+
+    pub fn min_buffers(self, min_buffers: u32) -> Self {
+        Self {
+            min_buffers: Some(min_buffers),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the maximum number of buffers, or 0 for an unlimited number.
+    pub fn max_buffers(self, max_buffers: u32) -> Self {
+        Self {
+            max_buffers: Some(max_buffers),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds `option`, e.g. `gst_video::BUFFER_POOL_OPTION_VIDEO_META`.
+    pub fn option(mut self, option: &'a str) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    pub fn allocator(self, allocator: &'a impl IsA<Allocator>) -> Self {
+        Self {
+            allocator: Some(allocator.upcast_ref()),
+            ..self
+        }
+    }
+
+    pub fn allocation_params(self, allocation_params: &'a AllocationParams) -> Self {
+        Self {
+            allocation_params: Some(allocation_params),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Builds the configuration, which can be set with
+    /// [`BufferPoolExtManual::set_config()`].
+    #[must_use = "Building the config without using it has no effect"]
+    pub fn build(self) -> BufferPoolConfig {
+        let mut config = self.config;
+
+        let (caps, size, min_buffers, max_buffers) = config.params().unwrap_or((None, 0, 0, 0));
+        config.set_params(
+            self.caps.or(caps.as_ref()),
+            self.size.unwrap_or(size),
+            self.min_buffers.unwrap_or(min_buffers),
+            self.max_buffers.unwrap_or(max_buffers),
+        );
+
+        for option in self.options {
+            config.add_option(option);
+        }
+
+        if self.allocator.is_some() || self.allocation_params.is_some() {
+            let (allocator, params) = config
+                .allocator()
+                .map(|(allocator, params)| (allocator, Some(params)))
+                .unwrap_or((None, None));
+            config.set_allocator(
+                self.allocator.or(allocator.as_ref()),
+                self.allocation_params.or(params.as_ref()),
+            );
+        }
+
+        config
+    }
+}
+
 crate::utils::define_fixed_size_iter!(
     OptionsIter,
     &'a BufferPoolConfigRef,
@@ -382,6 +503,84 @@ impl FromGlibPtrNone<*mut ffi::GstBufferPoolAcquireParams> for BufferPoolAcquire
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// A buffer acquired from a [`BufferPool`] with
+/// [`BufferPoolExtManual::acquire_pooled_buffer()`] that is released back to
+/// the pool when dropped.
+///
+/// The buffer is writable as long as no further references to it are
+/// created. Use [`into_buffer()`](Self::into_buffer) for passing it on, after
+/// which it is released back to the pool once its last reference is dropped.
+#[derive(Debug)]
+pub struct PooledBuffer {
+    buffer: mem::ManuallyDrop<crate::Buffer>,
+    pool: BufferPool,
+}
+
+impl PooledBuffer {
+    // rustdoc-stripper-ignore-next
+    /// The pool the buffer was acquired from.
+    #[inline]
+    pub fn pool(&self) -> &BufferPool {
+        &self.pool
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Converts into the underlying buffer.
+    #[inline]
+    pub fn into_buffer(self) -> crate::Buffer {
+        let mut s = mem::ManuallyDrop::new(self);
+        unsafe {
+            ptr::drop_in_place(&mut s.pool);
+            mem::ManuallyDrop::take(&mut s.buffer)
+        }
+    }
+}
+
+impl Deref for PooledBuffer {
+    type Target = crate::BufferRef;
+
+    #[inline]
+    fn deref(&self) -> &crate::BufferRef {
+        &self.buffer
+    }
+}
+
+impl DerefMut for PooledBuffer {
+    // rustdoc-stripper-ignore-next
+    /// Makes the buffer writable, which copies it if there are further
+    /// references to it.
+    #[inline]
+    fn deref_mut(&mut self) -> &mut crate::BufferRef {
+        self.buffer.make_mut()
+    }
+}
+
+impl Drop for PooledBuffer {
+    fn drop(&mut self) {
+        unsafe {
+            let buffer = mem::ManuallyDrop::take(&mut self.buffer);
+
+            // Copies and buffers with further references are dropped normally, in
+            // which case the pool gets its buffer back with the last reference
+            if buffer.is_writable() && (*buffer.as_ptr()).pool == self.pool.to_glib_none().0 {
+                ffi::gst_buffer_pool_release_buffer(
+                    self.pool.to_glib_none().0,
+                    buffer.into_glib_ptr(),
+                );
+            }
+        }
+    }
+}
+
+impl From<PooledBuffer> for crate::Buffer {
+    #[inline]
+    fn from(buffer: PooledBuffer) -> Self {
+        skip_assert_initialized!();
+        buffer.into_buffer()
+    }
+}
+
 pub trait BufferPoolExtManual: IsA<BufferPool> + 'static {
     #[doc(alias = "get_config")]
     #[doc(alias = "gst_buffer_pool_get_config")]
@@ -431,6 +630,30 @@ pub trait BufferPoolExtManual: IsA<BufferPool> + 'static {
             .map(|_| from_glib_full(buffer))
         }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Acquires a buffer like [`acquire_buffer()`](Self::acquire_buffer) and
+    /// returns a guard that releases it back to the pool when dropped.
+    #[doc(alias = "gst_buffer_pool_acquire_buffer")]
+    #[doc(alias = "gst_buffer_pool_release_buffer")]
+    fn acquire_pooled_buffer(
+        &self,
+        params: Option<&BufferPoolAcquireParams>,
+    ) -> Result<PooledBuffer, crate::FlowError> {
+        let buffer = self.acquire_buffer(params)?;
+
+        Ok(PooledBuffer {
+            buffer: mem::ManuallyDrop::new(buffer),
+            pool: self.as_ref().clone(),
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a new configuration of the pool, starting from its
+    /// current configuration.
+    fn config_builder<'a>(&self) -> BufferPoolConfigBuilder<'a> {
+        BufferPoolConfigBuilder::new(self.config())
+    }
 }
 
 impl<O: IsA<BufferPool>> BufferPoolExtManual for O {}
@@ -478,4 +701,58 @@ mod tests {
         let _buf1 = pool.acquire_buffer(None).unwrap();
         pool.set_active(false).unwrap();
     }
+
+    #[test]
+    fn pooled_buffer() {
+        crate::init().unwrap();
+
+        let pool = crate::BufferPool::new();
+        let config = pool.config_builder().size(1024).max_buffers(1).build();
+        pool.set_config(config).unwrap();
+
+        pool.set_active(true).unwrap();
+
+        let params =
+            crate::BufferPoolAcquireParams::with_flags(crate::BufferPoolAcquireFlags::DONTWAIT);
+
+        let mut buf = pool.acquire_pooled_buffer(Some(&params)).unwrap();
+        assert_eq!(buf.size(), 1024);
+        assert_eq!(buf.pool(), &pool);
+        buf.set_pts(crate::ClockTime::SECOND);
+        assert!(pool.acquire_pooled_buffer(Some(&params)).is_err());
+
+        drop(buf);
+        let buf = pool.acquire_pooled_buffer(Some(&params)).unwrap();
+
+        let buf = buf.into_buffer();
+        assert!(pool.acquire_pooled_buffer(Some(&params)).is_err());
+        drop(buf);
+        let _buf = pool.acquire_pooled_buffer(Some(&params)).unwrap();
+
+        pool.set_active(false).unwrap();
+    }
+
+    #[test]
+    fn config_builder() {
+        crate::init().unwrap();
+
+        let pool = crate::BufferPool::new();
+        let caps = crate::Caps::builder("foo/bar").build();
+        let config = pool
+            .config_builder()
+            .caps(&caps)
+            .size(1024)
+            .min_buffers(1)
+            .max_buffers(4)
+            .option("GstBufferPoolOptionTest")
+            .build();
+
+        assert_eq!(config.params(), Some((Some(caps), 1024, 1, 4)));
+        assert!(config.has_option("GstBufferPoolOptionTest"));
+        pool.set_config(config).unwrap();
+
+        let config = pool.config_builder().max_buffers(2).build();
+        assert_eq!(config.params().unwrap().1, 1024);
+        assert_eq!(config.params().unwrap().3, 2);
+    }
 }
@@ -1,10 +1,18 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::num::NonZeroU32;
+use std::{
+    num::NonZeroU32,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
+use futures_core::Stream;
+use futures_util::{stream::FusedStream, StreamExt};
 use glib::{prelude::*, translate::*};
 
-use crate::{ffi, Caps, DeviceMonitor};
+use crate::{
+    bus::BusStream, ffi, prelude::*, Caps, Device, DeviceMonitor, MessageRef, MessageView,
+};
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct DeviceMonitorFilterId(NonZeroU32);
@@ -74,6 +82,239 @@ pub trait DeviceMonitorExtManual: IsA<DeviceMonitor> + 'static {
             ))
         }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a snapshot of the devices currently known to the monitor.
+    ///
+    /// See [`DeviceSnapshot::diff()`] for comparing two snapshots.
+    fn snapshot(&self) -> DeviceSnapshot {
+        self.devices().into_iter().collect()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a stream of the devices added, removed or changed while the monitor is running.
+    ///
+    /// This consumes all messages of the monitor's bus: other messages are dropped and only one
+    /// stream or bus sync handler can exist at a time.
+    ///
+    /// The monitor has to be started with [`start()`](DeviceMonitorExt::start) for any devices
+    /// to be reported. Devices that were already present before can be retrieved with
+    /// [`snapshot()`](Self::snapshot) and kept up to date with [`DeviceSnapshot::apply()`].
+    fn devices_stream(&self) -> DeviceMonitorStream {
+        DeviceMonitorStream(self.as_ref().bus().stream())
+    }
 }
 
 impl<O: IsA<DeviceMonitor>> DeviceMonitorExtManual for O {}
+
+// rustdoc-stripper-ignore-next
+/// A change of the devices known to a [`DeviceMonitor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceEvent {
+    Added(Device),
+    Removed(Device),
+    #[cfg(feature = "v1_16")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v1_16")))]
+    Changed {
+        device: Device,
+        old_device: Device,
+    },
+}
+
+impl DeviceEvent {
+    // rustdoc-stripper-ignore-next
+    /// Creates the event corresponding to a device added, removed or changed message.
+    pub fn from_message(message: &MessageRef) -> Option<Self> {
+        skip_assert_initialized!();
+        match message.view() {
+            MessageView::DeviceAdded(m) => Some(Self::Added(m.device())),
+            MessageView::DeviceRemoved(m) => Some(Self::Removed(m.device())),
+            #[cfg(feature = "v1_16")]
+            MessageView::DeviceChanged(m) => {
+                let (device, old_device) = m.device_changed();
+                Some(Self::Changed { device, old_device })
+            }
+            _ => None,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The device the event is about, i.e. the new version for changed devices.
+    pub fn device(&self) -> &Device {
+        match self {
+            Self::Added(device) | Self::Removed(device) => device,
+            #[cfg(feature = "v1_16")]
+            Self::Changed { device, .. } => device,
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Stream of [`DeviceEvent`]s, created with
+/// [`DeviceMonitorExtManual::devices_stream()`].
+#[must_use = "streams do nothing unless polled"]
+#[derive(Debug)]
+pub struct DeviceMonitorStream(BusStream);
+
+impl Stream for DeviceMonitorStream {
+    type Item = DeviceEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<Option<Self::Item>> {
+        loop {
+            match self.0.poll_next_unpin(context) {
+                Poll::Ready(Some(message)) => {
+                    if let Some(event) = DeviceEvent::from_message(&message) {
+                        return Poll::Ready(Some(event));
+                    }
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl FusedStream for DeviceMonitorStream {
+    fn is_terminated(&self) -> bool {
+        self.0.is_terminated()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// The devices known to a [`DeviceMonitor`] at some point in time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeviceSnapshot(Vec<Device>);
+
+impl DeviceSnapshot {
+    pub fn devices(&self) -> &[Device] {
+        &self.0
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the events that turn this snapshot into `other`.
+    ///
+    /// Devices are compared by identity. As device providers replace the device object of a
+    /// changed device, a changed device shows up as removed and added again.
+    pub fn diff(&self, other: &DeviceSnapshot) -> Vec<DeviceEvent> {
+        let removed = self
+            .0
+            .iter()
+            .filter(|device| !other.0.contains(device))
+            .map(|device| DeviceEvent::Removed(device.clone()));
+        let added = other
+            .0
+            .iter()
+            .filter(|device| !self.0.contains(device))
+            .map(|device| DeviceEvent::Added(device.clone()));
+
+        removed.chain(added).collect()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Updates the snapshot with `event`, e.g. from a [`DeviceMonitorStream`].
+    pub fn apply(&mut self, event: &DeviceEvent) {
+        match event {
+            DeviceEvent::Added(device) => {
+                if !self.0.contains(device) {
+                    self.0.push(device.clone());
+                }
+            }
+            DeviceEvent::Removed(device) => self.0.retain(|d| d != device),
+            #[cfg(feature = "v1_16")]
+            DeviceEvent::Changed { device, old_device } => {
+                match self.0.iter_mut().find(|d| *d == old_device) {
+                    Some(d) => *d = device.clone(),
+                    None => self.0.push(device.clone()),
+                }
+            }
+        }
+    }
+}
+
+impl FromIterator<Device> for DeviceSnapshot {
+    fn from_iter<I: IntoIterator<Item = Device>>(iter: I) -> Self {
+        skip_assert_initialized!();
+        Self(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subclass::prelude::*;
+
+    pub mod imp {
+        use super::*;
+
+        #[derive(Default)]
+        pub struct TestDevice;
+
+        impl ObjectImpl for TestDevice {}
+        impl GstObjectImpl for TestDevice {}
+        impl DeviceImpl for TestDevice {}
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for TestDevice {
+            const NAME: &'static str = "TestDeviceMonitorDevice";
+            type Type = super::TestDevice;
+            type ParentType = Device;
+        }
+    }
+
+    glib::wrapper! {
+        pub struct TestDevice(ObjectSubclass<imp::TestDevice>) @extends Device, crate::Object;
+    }
+
+    fn test_device(name: &str) -> Device {
+        glib::Object::builder::<TestDevice>()
+            .property("display-name", name)
+            .property("device-class", "Test/Device")
+            .build()
+            .upcast()
+    }
+
+    #[test]
+    fn test_snapshot() {
+        crate::init().unwrap();
+
+        let a = test_device("a");
+        let b = test_device("b");
+        let c = test_device("c");
+
+        let old = DeviceSnapshot::from_iter([a.clone(), b.clone()]);
+        let new = DeviceSnapshot::from_iter([b.clone(), c.clone()]);
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                DeviceEvent::Removed(a.clone()),
+                DeviceEvent::Added(c.clone())
+            ]
+        );
+        assert!(new.diff(&new).is_empty());
+
+        let mut snapshot = old.clone();
+        for event in old.diff(&new) {
+            snapshot.apply(&event);
+        }
+        assert_eq!(snapshot.devices(), &[b, c]);
+    }
+
+    #[test]
+    fn test_devices_stream() {
+        crate::init().unwrap();
+
+        let monitor = DeviceMonitor::new();
+        let bus = monitor.bus();
+        let mut stream = monitor.devices_stream();
+
+        let a = test_device("a");
+        bus.post(crate::message::DeviceAdded::new(&a)).unwrap();
+        bus.post(crate::message::Eos::new()).unwrap();
+        bus.post(crate::message::DeviceRemoved::new(&a)).unwrap();
+
+        futures_executor::block_on(async {
+            assert_eq!(stream.next().await, Some(DeviceEvent::Added(a.clone())));
+            assert_eq!(stream.next().await, Some(DeviceEvent::Removed(a)));
+        });
+    }
+}
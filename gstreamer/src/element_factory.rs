@@ -5,9 +5,12 @@ use std::ffi::CStr;
 use glib::{prelude::*, translate::*};
 
 use crate::{
-    ffi, CapsRef, Element, ElementFactory, Rank, StaticPadTemplate, ELEMENT_METADATA_AUTHOR,
-    ELEMENT_METADATA_DESCRIPTION, ELEMENT_METADATA_DOC_URI, ELEMENT_METADATA_ICON_NAME,
-    ELEMENT_METADATA_KLASS, ELEMENT_METADATA_LONGNAME,
+    ffi,
+    prelude::{GstObjectExt, PluginFeatureExt, PluginFeatureExtManual},
+    Caps, CapsRef, Element, ElementFactory, ElementFactoryType, PadDirection, Rank,
+    StaticPadTemplate, ELEMENT_METADATA_AUTHOR, ELEMENT_METADATA_DESCRIPTION,
+    ELEMENT_METADATA_DOC_URI, ELEMENT_METADATA_ICON_NAME, ELEMENT_METADATA_KLASS,
+    ELEMENT_METADATA_LONGNAME,
 };
 
 impl ElementFactory {
@@ -91,6 +94,59 @@ impl ElementFactory {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Creates a query for finding element factories by type, rank, klass and caps.
+    ///
+    /// ```rust
+    /// # use gstreamer as gst;
+    /// # gst::init().unwrap();
+    /// let caps = gst::Caps::builder("video/x-h264").build();
+    /// let decoders = gst::ElementFactory::query()
+    ///     .type_(gst::ElementFactoryType::DECODER)
+    ///     .min_rank(gst::Rank::MARGINAL)
+    ///     .klass("Video")
+    ///     .sink_caps(&caps)
+    ///     .find();
+    ///
+    /// for decoder in decoders {
+    ///     println!("{} ({:?})", decoder.longname(), decoder.rank());
+    /// }
+    /// ```
+    pub fn query<'a>() -> ElementFactoryQuery<'a> {
+        assert_initialized_main_thread!();
+        ElementFactoryQuery {
+            type_: ElementFactoryType::ANY,
+            min_rank: Rank::NONE,
+            klass: Vec::new(),
+            caps: Vec::new(),
+            subset_only: false,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Finds all decoders with at least marginal rank that can handle `caps`,
+    /// sorted by rank.
+    pub fn find_decoders_for_caps(caps: &CapsRef) -> Vec<ElementFactoryInfo> {
+        assert_initialized_main_thread!();
+        Self::query()
+            .type_(ElementFactoryType::DECODER)
+            .min_rank(Rank::MARGINAL)
+            .sink_caps(caps)
+            .find()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Finds all encoders with at least marginal rank that can produce `caps`,
+    /// sorted by rank.
+    pub fn find_encoders_for_caps(caps: &CapsRef) -> Vec<ElementFactoryInfo> {
+        assert_initialized_main_thread!();
+        Self::query()
+            .type_(ElementFactoryType::ENCODER)
+            .min_rank(Rank::MARGINAL)
+            .src_caps(caps)
+            .find()
+    }
+
     #[doc(alias = "gst_element_factory_get_metadata")]
     #[doc(alias = "get_metadata")]
     pub fn metadata(&self, key: &str) -> Option<&str> {
@@ -183,6 +239,223 @@ impl ElementFactory {
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// Query for [`ElementFactory`]s, created with [`ElementFactory::query()`].
+#[must_use = "The query must be run with `find()` to be used"]
+#[derive(Debug, Clone)]
+pub struct ElementFactoryQuery<'a> {
+    type_: ElementFactoryType,
+    min_rank: Rank,
+    klass: Vec<&'a str>,
+    caps: Vec<(&'a CapsRef, PadDirection)>,
+    subset_only: bool,
+}
+
+impl<'a> ElementFactoryQuery<'a> {
+    // rustdoc-stripper-ignore-next
+    /// Only finds factories of the given type.
+    ///
+    /// By default factories of any type are found.
+    pub fn type_(self, type_: ElementFactoryType) -> Self {
+        Self { type_, ..self }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Only finds factories with at least the given rank.
+    ///
+    /// By default factories of any rank are found.
+    pub fn min_rank(self, min_rank: Rank) -> Self {
+        Self { min_rank, ..self }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Only finds factories whose klass contains all `/` separated parts of `klass`, e.g.
+    /// `Codec/Decoder` or `Video`.
+    ///
+    /// Can be called multiple times, in which case all klasses have to match.
+    pub fn klass(mut self, klass: &'a str) -> Self {
+        self.klass.push(klass);
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Only finds factories that can accept `caps` on a sink pad.
+    pub fn sink_caps(mut self, caps: &'a CapsRef) -> Self {
+        self.caps.push((caps, PadDirection::Sink));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Only finds factories that can produce `caps` on a source pad.
+    pub fn src_caps(mut self, caps: &'a CapsRef) -> Self {
+        self.caps.push((caps, PadDirection::Src));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// If `true`, the caps passed to [`sink_caps()`](Self::sink_caps) and
+    /// [`src_caps()`](Self::src_caps) have to be a subset of the pad template caps instead
+    /// of only intersecting with them.
+    ///
+    /// Defaults to `false`.
+    pub fn subset_only(self, subset_only: bool) -> Self {
+        Self {
+            subset_only,
+            ..self
+        }
+    }
+
+    fn matches(&self, factory: &ElementFactory) -> bool {
+        if !self.klass.is_empty() {
+            let factory_klass = factory.klass().split('/').collect::<Vec<_>>();
+            let klass_matches = self
+                .klass
+                .iter()
+                .flat_map(|klass| klass.split('/'))
+                .filter(|part| !part.is_empty())
+                .all(|part| factory_klass.contains(&part));
+            if !klass_matches {
+                return false;
+            }
+        }
+
+        self.caps
+            .iter()
+            .all(|(caps, direction)| match (direction, self.subset_only) {
+                (PadDirection::Sink, true) => factory.can_sink_all_caps(caps),
+                (PadDirection::Sink, false) => factory.can_sink_any_caps(caps),
+                (_, true) => factory.can_src_all_caps(caps),
+                (_, false) => factory.can_src_any_caps(caps),
+            })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Runs the query and returns all matching factories, sorted by rank in descending order
+    /// and then by name.
+    #[doc(alias = "gst_element_factory_list_get_elements")]
+    #[doc(alias = "gst_element_factory_list_filter")]
+    pub fn find(&self) -> Vec<ElementFactoryInfo> {
+        let mut factories = ElementFactory::factories_with_type(self.type_, self.min_rank)
+            .into_iter()
+            .filter(|factory| self.matches(factory))
+            .map(ElementFactoryInfo::new)
+            .collect::<Vec<_>>();
+
+        factories.sort_by(|a, b| {
+            b.factory
+                .rank()
+                .cmp(&a.factory.rank())
+                .then_with(|| a.factory.name().cmp(&b.factory.name()))
+        });
+
+        factories
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Description of an [`ElementFactory`], as returned by [`ElementFactoryQuery::find()`].
+#[derive(Debug, Clone)]
+pub struct ElementFactoryInfo {
+    factory: ElementFactory,
+    sink_caps: Caps,
+    src_caps: Caps,
+}
+
+impl ElementFactoryInfo {
+    fn new(factory: ElementFactory) -> Self {
+        skip_assert_initialized!();
+
+        let mut sink_caps = Caps::new_empty();
+        let mut src_caps = Caps::new_empty();
+        for templ in factory.static_pad_templates() {
+            match templ.direction() {
+                PadDirection::Sink => sink_caps.merge(templ.caps()),
+                PadDirection::Src => src_caps.merge(templ.caps()),
+                _ => (),
+            }
+        }
+
+        Self {
+            factory,
+            sink_caps,
+            src_caps,
+        }
+    }
+
+    #[inline]
+    pub fn factory(&self) -> &ElementFactory {
+        &self.factory
+    }
+
+    pub fn name(&self) -> glib::GString {
+        self.factory.name()
+    }
+
+    pub fn rank(&self) -> Rank {
+        self.factory.rank()
+    }
+
+    pub fn plugin_name(&self) -> Option<glib::GString> {
+        self.factory.plugin_name()
+    }
+
+    #[inline]
+    pub fn longname(&self) -> &str {
+        self.factory.longname()
+    }
+
+    #[inline]
+    pub fn klass(&self) -> &str {
+        self.factory.klass()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the `/` separated parts of the klass, e.g. `Codec`, `Decoder` and `Video`.
+    pub fn klass_parts(&self) -> impl Iterator<Item = &str> {
+        self.factory.klass().split('/')
+    }
+
+    #[inline]
+    pub fn description(&self) -> &str {
+        self.factory.description()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether the factory creates elements that use hardware acceleration.
+    pub fn is_hardware(&self) -> bool {
+        self.factory.has_type(ElementFactoryType::HARDWARE)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Union of the caps of all sink pad templates of the factory.
+    #[inline]
+    pub fn sink_caps(&self) -> &Caps {
+        &self.sink_caps
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Union of the caps of all source pad templates of the factory.
+    #[inline]
+    pub fn src_caps(&self) -> &Caps {
+        &self.src_caps
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for an element from this factory.
+    #[track_caller]
+    pub fn create(&self) -> ElementBuilder<'_> {
+        self.factory.create()
+    }
+}
+
+impl From<ElementFactoryInfo> for ElementFactory {
+    #[inline]
+    fn from(info: ElementFactoryInfo) -> Self {
+        skip_assert_initialized!();
+        info.factory
+    }
+}
+
 // rustdoc-stripper-ignore-next
 /// Builder for `Element`s.
 #[must_use = "The builder must be built to be used"]
@@ -255,9 +528,7 @@ impl<'a> ElementBuilder<'a> {
         // The below is basically a reimplementation of the C function. We want to call
         // glib::Object::with_type() ourselves here for checking properties and their values
         // correctly and to provide consistent behaviour.
-        use crate::prelude::{
-            ElementExtManual, GstObjectExt, GstObjectExtManual, PluginFeatureExtManual,
-        };
+        use crate::prelude::{ElementExtManual, GstObjectExtManual};
 
         let factory = factory.load().map_err(|_| {
             crate::warning!(
@@ -364,4 +635,47 @@ mod tests {
         let (_klass, e) = glib::EnumValue::from_value(&v).unwrap();
         assert_eq!(e.nick(), "ready-to-paused");
     }
+
+    #[test]
+    fn query() {
+        crate::init().unwrap();
+
+        let factories = ElementFactory::query()
+            .type_(crate::ElementFactoryType::SINK)
+            .klass("Sink")
+            .find();
+        let fakesink = factories
+            .iter()
+            .find(|info| info.name() == "fakesink")
+            .unwrap();
+        assert_eq!(fakesink.klass(), "Sink");
+        assert_eq!(fakesink.plugin_name().as_deref(), Some("coreelements"));
+        assert!(fakesink.sink_caps().is_any());
+        assert!(fakesink.src_caps().is_empty());
+        assert!(!fakesink.is_hardware());
+        assert!(factories
+            .windows(2)
+            .all(|infos| infos[0].rank() >= infos[1].rank()));
+        assert!(factories
+            .iter()
+            .all(|info| info.klass_parts().any(|part| part == "Sink")));
+
+        let caps = crate::Caps::builder("some/x-caps").build();
+        let factories = ElementFactory::query()
+            .klass("Generic")
+            .sink_caps(&caps)
+            .src_caps(&caps)
+            .find();
+        assert!(factories.iter().any(|info| info.name() == "identity"));
+        assert!(factories.iter().all(|info| info.name() != "fakesink"));
+
+        let factories = ElementFactory::query()
+            .type_(crate::ElementFactoryType::SRC)
+            .klass("Sink")
+            .find();
+        assert!(factories.is_empty());
+
+        let elem = ElementFactory::find_decoders_for_caps(&caps);
+        assert!(elem.is_empty());
+    }
 }
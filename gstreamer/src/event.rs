@@ -355,6 +355,22 @@ pub trait StickyEventType: ToOwned {
     unsafe fn from_event(event: Event) -> Self::Owned;
 }
 
+// rustdoc-stripper-ignore-next
+/// Concrete event types that correspond to a single [`EventType`].
+pub trait ConcreteEventType: ToOwned {
+    const TYPE: EventType;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the event as this concrete event type if it is of the
+    /// corresponding [`EventType`].
+    fn from_event_ref(event: &EventRef) -> Option<&Self>;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the event as this concrete event type if it is of the
+    /// corresponding [`EventType`], or the event itself otherwise.
+    fn from_event(event: Event) -> Result<Self::Owned, Event>;
+}
+
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum EventView<'a> {
@@ -451,6 +467,31 @@ macro_rules! declare_concrete_event {
         }
     };
     ($name:ident, $param:ident) => {
+        declare_concrete_event!(@untyped $name, $param);
+
+        impl ConcreteEventType for $name {
+            const TYPE: EventType = EventType::$name;
+
+            #[inline]
+            fn from_event_ref(event: &EventRef) -> Option<&Self> {
+                if event.type_() == EventType::$name {
+                    Some(unsafe { &*(event as *const EventRef as *const Self) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            fn from_event(event: Event) -> Result<Self::Owned, Event> {
+                if event.type_() == EventType::$name {
+                    Ok($name::<Event>(event))
+                } else {
+                    Err(event)
+                }
+            }
+        }
+    };
+    (@untyped $name:ident, $param:ident) => {
         #[repr(transparent)]
         pub struct $name<$param = EventRef>($param);
 
@@ -2151,7 +2192,7 @@ impl std::fmt::Debug for CustomBothOob<Event> {
     }
 }
 
-declare_concrete_event!(Other, T);
+declare_concrete_event!(@untyped Other, T);
 
 impl std::fmt::Debug for Other {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
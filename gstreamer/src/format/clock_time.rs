@@ -29,6 +29,17 @@ impl fmt::Display for TryFromFloatSecsError {
 
 impl std::error::Error for TryFromFloatSecsError {}
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseClockTimeError;
+
+impl fmt::Display for ParseClockTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("can not parse ClockTime: expected `[[H:]MM:]SS[.fraction]`")
+    }
+}
+
+impl std::error::Error for ParseClockTimeError {}
+
 // rustdoc-stripper-ignore-next
 /// A Time quantity
 ///
@@ -243,6 +254,40 @@ impl ClockTime {
         );
         ClockTime(nseconds * Self::NSECOND.0)
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses a time in the format `[[H:]MM:]SS[.fraction]`, e.g. `1:02:03.500`, `02:03`
+    /// or `3.5`.
+    ///
+    /// This is the inverse of the [`Display`](fmt::Display) implementation. The fraction can
+    /// have up to 9 digits.
+    ///
+    /// ```rust
+    /// # use gstreamer::ClockTime;
+    /// let time = ClockTime::parse("1:02:03.500").unwrap();
+    /// assert_eq!(time, ClockTime::from_mseconds(3_723_500));
+    /// assert_eq!(ClockTime::parse(&time.to_string()), Ok(time));
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, ParseClockTimeError> {
+        skip_assert_initialized!();
+        s.parse()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Formats the time as `HH:MM:SS`, truncating any fraction of a second.
+    ///
+    /// The hours have at least two digits but can have more for times longer than 99 hours.
+    ///
+    /// ```rust
+    /// # use gstreamer::ClockTime;
+    /// assert_eq!(ClockTime::from_mseconds(3_723_500).format_hhmmss(), "01:02:03");
+    /// ```
+    pub fn format_hhmmss(self) -> String {
+        let (m, s) = num_integer::div_rem(self.seconds(), 60);
+        let (h, m) = num_integer::div_rem(m, 60);
+
+        format!("{h:02}:{m:02}:{s:02}")
+    }
 }
 
 impl Signed<ClockTime> {
@@ -618,6 +663,119 @@ impl From<ClockTime> for Duration {
     }
 }
 
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl TryFrom<chrono::TimeDelta> for ClockTime {
+    type Error = DurationError;
+
+    #[inline]
+    fn try_from(d: chrono::TimeDelta) -> Result<Self, Self::Error> {
+        skip_assert_initialized!();
+
+        let d = d.to_std().map_err(|_| DurationError)?;
+        ClockTime::try_from(d)
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl From<ClockTime> for chrono::TimeDelta {
+    #[inline]
+    fn from(t: ClockTime) -> Self {
+        skip_assert_initialized!();
+
+        // Can't fail as the range of `TimeDelta` is larger than the one of `ClockTime`
+        chrono::TimeDelta::from_std(Duration::from(t)).unwrap()
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl TryFrom<chrono::TimeDelta> for Signed<ClockTime> {
+    type Error = DurationError;
+
+    #[inline]
+    fn try_from(d: chrono::TimeDelta) -> Result<Self, Self::Error> {
+        skip_assert_initialized!();
+
+        if d < chrono::TimeDelta::zero() {
+            ClockTime::try_from(-d).map(Signed::Negative)
+        } else {
+            ClockTime::try_from(d).map(Signed::Positive)
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl From<Signed<ClockTime>> for chrono::TimeDelta {
+    #[inline]
+    fn from(t: Signed<ClockTime>) -> Self {
+        skip_assert_initialized!();
+
+        match t {
+            Signed::Positive(t) => chrono::TimeDelta::from(t),
+            Signed::Negative(t) => -chrono::TimeDelta::from(t),
+        }
+    }
+}
+
+// Parses a component of a time string, which must only consist of ASCII digits
+fn parse_clock_time_component(s: &str) -> Result<u64, ParseClockTimeError> {
+    skip_assert_initialized!();
+
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ParseClockTimeError);
+    }
+
+    s.parse::<u64>().map_err(|_| ParseClockTimeError)
+}
+
+impl std::str::FromStr for ClockTime {
+    type Err = ParseClockTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        skip_assert_initialized!();
+
+        let (s, fraction) = match s.split_once('.') {
+            Some((s, fraction)) => (s, Some(fraction)),
+            None => (s, None),
+        };
+
+        let mut parts = s.rsplit(':');
+        let seconds = parse_clock_time_component(parts.next().ok_or(ParseClockTimeError)?)?;
+        let minutes = parts.next().map(parse_clock_time_component).transpose()?;
+        let hours = parts.next().map(parse_clock_time_component).transpose()?;
+        if parts.next().is_some() {
+            return Err(ParseClockTimeError);
+        }
+
+        // Only the leading component is allowed to overflow into the next unit
+        if (minutes.is_some() && seconds >= 60) || (hours.is_some() && minutes >= Some(60)) {
+            return Err(ParseClockTimeError);
+        }
+
+        let nseconds = match fraction {
+            Some(fraction) if fraction.len() <= 9 => {
+                parse_clock_time_component(fraction)? * 10u64.pow(9 - fraction.len() as u32)
+            }
+            Some(_) => return Err(ParseClockTimeError),
+            None => 0,
+        };
+
+        hours
+            .unwrap_or(0)
+            .checked_mul(60)
+            .and_then(|m| m.checked_add(minutes.unwrap_or(0)))
+            .and_then(|m| m.checked_mul(60))
+            .and_then(|s| s.checked_add(seconds))
+            .and_then(|s| s.checked_mul(*ClockTime::SECOND))
+            .and_then(|ns| ns.checked_add(nseconds))
+            .and_then(|ns| ClockTime::try_from(ns).ok())
+            .ok_or(ParseClockTimeError)
+    }
+}
+
 impl_common_ops_for_newtype_uint!(ClockTime, u64);
 impl_signed_div_mul!(ClockTime, u64);
 impl_signed_int_into_signed!(ClockTime, u64);
@@ -1848,4 +2006,95 @@ mod tests {
         assert_eq!(t1.absdiff(t2), d);
         assert_eq!(t2.absdiff(t1), d);
     }
+
+    #[test]
+    fn parse() {
+        use std::str::FromStr;
+
+        assert_eq!(
+            ClockTime::parse("1:02:03.500"),
+            Ok(ClockTime::from_mseconds(3_723_500))
+        );
+        assert_eq!(
+            ClockTime::parse("01:02:03"),
+            Ok(ClockTime::from_seconds(3_723))
+        );
+        assert_eq!(
+            ClockTime::parse("62:03.25"),
+            Ok(ClockTime::from_mseconds(3_723_250))
+        );
+        assert_eq!(
+            ClockTime::parse("3.000000001"),
+            Ok(ClockTime::from_nseconds(3_000_000_001))
+        );
+        assert_eq!(ClockTime::parse("90"), Ok(ClockTime::from_seconds(90)));
+        assert_eq!(ClockTime::from_str("0:00:00"), Ok(ClockTime::ZERO));
+
+        let some = 45_834_908_569_837 * ClockTime::NSECOND;
+        assert_eq!(ClockTime::parse(&some.to_string()), Ok(some));
+        assert_eq!(
+            ClockTime::parse(&ClockTime::MAX.to_string()),
+            Ok(ClockTime::MAX)
+        );
+
+        for s in [
+            "",
+            ":",
+            "1:",
+            ":30",
+            "1:60",
+            "1:60:00",
+            "1:2:3:4",
+            "1.",
+            ".5",
+            "1.0000000001",
+            "+1",
+            "-1",
+            " 1",
+            "1:02:03.-5",
+            "--:--:--",
+            "5124095:34:33.709551615",
+        ] {
+            assert_eq!(ClockTime::parse(s), Err(ParseClockTimeError), "{s}");
+        }
+    }
+
+    #[test]
+    fn format_hhmmss() {
+        assert_eq!(ClockTime::ZERO.format_hhmmss(), "00:00:00");
+        assert_eq!(
+            ClockTime::from_mseconds(3_723_999).format_hhmmss(),
+            "01:02:03"
+        );
+        assert_eq!(
+            (45_834_908_569_837 * ClockTime::NSECOND).format_hhmmss(),
+            "12:43:54"
+        );
+        assert_eq!(ClockTime::MAX.format_hhmmss(), "5124095:34:33");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono() {
+        let t = ClockTime::from_mseconds(1_500);
+        let d = chrono::TimeDelta::from(t);
+        assert_eq!(d, chrono::TimeDelta::milliseconds(1_500));
+        assert_eq!(ClockTime::try_from(d).unwrap(), t);
+        assert!(ClockTime::try_from(-d).is_err());
+        assert_eq!(
+            chrono::TimeDelta::from(ClockTime::MAX).num_nanoseconds(),
+            Some(ClockTime::MAX.nseconds() as i64)
+        );
+
+        assert_eq!(
+            Signed::<ClockTime>::try_from(-d).unwrap(),
+            Signed::Negative(t)
+        );
+        assert_eq!(
+            Signed::<ClockTime>::try_from(d).unwrap(),
+            Signed::Positive(t)
+        );
+        assert_eq!(chrono::TimeDelta::from(Signed::Negative(t)), -d);
+        assert!(ClockTime::try_from(chrono::TimeDelta::MAX).is_err());
+    }
 }
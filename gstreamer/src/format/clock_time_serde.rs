@@ -1,5 +1,12 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
+// rustdoc-stripper-ignore-next
+//! Serde implementations for [`ClockTime`].
+//!
+//! By default a [`ClockTime`] is serialized as its number of nanoseconds. The
+//! [`human_readable`] module provides a representation as a string like
+//! `1:02:03.500000000` for human readable formats instead.
+
 use serde::{
     de::{Deserialize, Deserializer},
     ser::{Serialize, Serializer},
@@ -26,6 +33,97 @@ impl<'de> Deserialize<'de> for ClockTime {
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// Human readable serde representation of [`ClockTime`]s, to be used with
+/// `#[serde(with = "gst::format::clock_time_serde::human_readable")]`.
+///
+/// For human readable formats like JSON, times are serialized as strings in the
+/// format of the [`Display`](std::fmt::Display) implementation, e.g.
+/// `1:02:03.500000000`, and deserialized with [`ClockTime::parse()`]. For other
+/// formats, the default representation is used.
+///
+/// The [`option`](human_readable::option) submodule provides the same for
+/// `Option<ClockTime>`.
+///
+/// ```rust
+/// # use gstreamer as gst;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Position {
+///     #[serde(with = "gst::format::clock_time_serde::human_readable")]
+///     position: gst::ClockTime,
+///     #[serde(with = "gst::format::clock_time_serde::human_readable::option")]
+///     duration: Option<gst::ClockTime>,
+/// }
+/// ```
+pub mod human_readable {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::ClockTime;
+
+    struct HumanReadable(ClockTime);
+
+    impl Serialize for HumanReadable {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                serializer.collect_str(&self.0)
+            } else {
+                self.0.serialize(serializer)
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for HumanReadable {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            skip_assert_initialized!();
+            if deserializer.is_human_readable() {
+                let s = String::deserialize(deserializer)?;
+                ClockTime::parse(&s).map(HumanReadable).map_err(|_| {
+                    use serde::de::{Error, Unexpected};
+                    D::Error::invalid_value(Unexpected::Str(&s), &"valid `ClockTime` string")
+                })
+            } else {
+                ClockTime::deserialize(deserializer).map(HumanReadable)
+            }
+        }
+    }
+
+    pub fn serialize<S: Serializer>(
+        clock_time: &ClockTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        HumanReadable(*clock_time).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ClockTime, D::Error> {
+        skip_assert_initialized!();
+        HumanReadable::deserialize(deserializer).map(|clock_time| clock_time.0)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Human readable serde representation of `Option<ClockTime>`s.
+    pub mod option {
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        use super::HumanReadable;
+        use crate::ClockTime;
+
+        pub fn serialize<S: Serializer>(
+            clock_time: &Option<ClockTime>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            clock_time.map(HumanReadable).serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<ClockTime>, D::Error> {
+            skip_assert_initialized!();
+            Option::<HumanReadable>::deserialize(deserializer)
+                .map(|clock_time| clock_time.map(|clock_time| clock_time.0))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::ClockTime;
@@ -116,4 +214,46 @@ mod tests {
         let clocktime: Option<ClockTime> = ron::de::from_str(clocktime_ser.as_str()).unwrap();
         assert!(clocktime.is_none());
     }
+
+    #[test]
+    fn test_human_readable() {
+        crate::init().unwrap();
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Position {
+            #[serde(with = "super::human_readable")]
+            position: ClockTime,
+            #[serde(with = "super::human_readable::option")]
+            duration: Option<ClockTime>,
+        }
+
+        let position = Position {
+            position: ClockTime::from_mseconds(3_723_500),
+            duration: None,
+        };
+        let res = serde_json::to_string(&position).unwrap();
+        assert_eq!(res, r#"{"position":"1:02:03.500000000","duration":null}"#);
+        assert_eq!(serde_json::from_str::<Position>(&res).unwrap(), position);
+
+        let position = Position {
+            position: ClockTime::ZERO,
+            duration: Some(ClockTime::from_seconds(90)),
+        };
+        let res = ron::ser::to_string(&position).unwrap();
+        assert_eq!(
+            res,
+            r#"(position:"0:00:00.000000000",duration:Some("0:01:30.000000000"))"#
+        );
+        assert_eq!(ron::de::from_str::<Position>(&res).unwrap(), position);
+
+        let position: Position =
+            serde_json::from_str(r#"{"position":"1:30","duration":"2:00:00"}"#).unwrap();
+        assert_eq!(position.position, ClockTime::from_seconds(90));
+        assert_eq!(position.duration, Some(ClockTime::from_seconds(7_200)));
+
+        assert!(
+            serde_json::from_str::<Position>(r#"{"position":"1:60","duration":null}"#).is_err()
+        );
+        assert!(serde_json::from_str::<Position>(r#"{"position":90,"duration":null}"#).is_err());
+    }
 }
@@ -490,7 +490,8 @@ mod macros;
 mod clock_time;
 pub use clock_time::*;
 #[cfg(feature = "serde")]
-mod clock_time_serde;
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod clock_time_serde;
 
 mod compatible;
 pub use compatible::*;
@@ -57,6 +57,8 @@ pub mod log;
 pub mod log_context;
 #[cfg(feature = "log")]
 pub use crate::log::DebugCategoryLogger;
+#[cfg(feature = "tracing")]
+pub use crate::log::DebugCategorySubscriber;
 pub use crate::log::{
     DebugCategory, DebugLogFunction, DebugLogger, DebugMessage, LoggedObject, CAT_BUFFER,
     CAT_BUFFER_LIST, CAT_BUS, CAT_CALL_TRACE, CAT_CAPS, CAT_CLOCK, CAT_CONTEXT, CAT_DEFAULT,
@@ -118,6 +120,9 @@ pub use crate::caps_features::{
 };
 #[cfg(feature = "serde")]
 mod caps_features_serde;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod serde_map;
 
 pub mod tags;
 pub use crate::tags::{
@@ -176,14 +181,14 @@ mod static_pad_template;
 pub use crate::static_pad_template::*;
 
 pub mod promise;
-pub use promise::{Promise, PromiseError};
+pub use promise::{AwaitablePromise, Promise, PromiseError};
 
 pub mod bus;
 mod element;
 pub mod element_factory;
 
 mod bin;
-pub use bin::BinBuilder;
+pub use bin::{BinBuilder, BinChildrenBuilder};
 
 mod pipeline;
 pub use pipeline::PipelineBuilder;
@@ -232,7 +237,7 @@ pub use task::{TaskLock, TaskLockGuard};
 mod task_pool;
 pub use self::iterator::{Iterator, IteratorError, IteratorImpl, StdIterator};
 pub use crate::{
-    device_monitor::DeviceMonitorFilterId,
+    device_monitor::{DeviceEvent, DeviceMonitorFilterId, DeviceMonitorStream, DeviceSnapshot},
     element::{
         ElementMessageType, NotifyWatchId, ELEMENT_METADATA_AUTHOR, ELEMENT_METADATA_DESCRIPTION,
         ELEMENT_METADATA_DOC_URI, ELEMENT_METADATA_ICON_NAME, ELEMENT_METADATA_KLASS,
@@ -272,7 +277,10 @@ mod clock;
 pub use crate::clock::{AtomicClockReturn, ClockId, PeriodicClockId, SingleShotClockId};
 
 mod buffer_pool;
-pub use crate::buffer_pool::{BufferPoolAcquireParams, BufferPoolConfig, BufferPoolConfigRef};
+pub use crate::buffer_pool::{
+    BufferPoolAcquireParams, BufferPoolConfig, BufferPoolConfigBuilder, BufferPoolConfigRef,
+    PooledBuffer,
+};
 
 mod pad_template;
 pub use pad_template::PadTemplateBuilder;
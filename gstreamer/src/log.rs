@@ -1182,6 +1182,109 @@ impl log::Log for DebugCategoryLogger {
     fn flush(&self) {}
 }
 
+#[cfg(feature = "tracing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+#[derive(Debug)]
+pub struct DebugCategorySubscriber {
+    cat: DebugCategory,
+    next_span_id: std::sync::atomic::AtomicU64,
+}
+
+#[cfg(feature = "tracing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+impl DebugCategorySubscriber {
+    // rustdoc-stripper-ignore-next
+    /// Creates a `tracing` subscriber that logs all events into `cat`.
+    ///
+    /// Spans are not tracked, only the fields of each event are logged.
+    pub fn new(cat: DebugCategory) -> Self {
+        skip_assert_initialized!();
+        Self {
+            cat,
+            next_span_id: std::sync::atomic::AtomicU64::new(1),
+        }
+    }
+
+    fn to_level(level: tracing::Level) -> crate::DebugLevel {
+        skip_assert_initialized!();
+        match level {
+            tracing::Level::ERROR => DebugLevel::Error,
+            tracing::Level::WARN => DebugLevel::Warning,
+            tracing::Level::INFO => DebugLevel::Info,
+            tracing::Level::DEBUG => DebugLevel::Debug,
+            tracing::Level::TRACE => DebugLevel::Trace,
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+#[derive(Default)]
+struct TracingEventVisitor {
+    message: String,
+    fields: String,
+}
+
+#[cfg(feature = "tracing")]
+impl tracing::field::Visit for TracingEventVisitor {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn fmt::Debug) {
+        use std::fmt::Write;
+
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+impl tracing::Subscriber for DebugCategorySubscriber {
+    fn register_callsite(
+        &self,
+        _metadata: &'static tracing::Metadata<'static>,
+    ) -> tracing::subscriber::Interest {
+        // The threshold of the category can change at any time
+        tracing::subscriber::Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &tracing::Metadata) -> bool {
+        metadata.is_event() && self.cat.above_threshold(Self::to_level(*metadata.level()))
+    }
+
+    fn new_span(&self, _span: &tracing::span::Attributes) -> tracing::span::Id {
+        tracing::span::Id::from_u64(
+            self.next_span_id
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+        )
+    }
+
+    fn record(&self, _span: &tracing::span::Id, _values: &tracing::span::Record) {}
+
+    fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+
+    fn event(&self, event: &tracing::Event) {
+        let metadata = event.metadata();
+        let mut visitor = TracingEventVisitor::default();
+        event.record(&mut visitor);
+
+        metadata.file().unwrap_or("").run_with_gstr(|file| {
+            self.cat.log(
+                None::<&glib::Object>,
+                Self::to_level(*metadata.level()),
+                file,
+                metadata.module_path().unwrap_or(""),
+                metadata.line().unwrap_or(0),
+                format_args!("{}{}", visitor.message, visitor.fields),
+            );
+        });
+    }
+
+    fn enter(&self, _span: &tracing::span::Id) {}
+
+    fn exit(&self, _span: &tracing::span::Id) {}
+}
+
 unsafe extern "C" fn log_handler<T>(
     category: *mut ffi::GstDebugCategory,
     level: ffi::GstDebugLevel,
@@ -1361,6 +1464,109 @@ pub fn remove_log_function(log_fn: DebugLogFunction) {
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// Adds a log function that forwards all GStreamer debug messages to the `log` crate.
+///
+/// The name of the debug category is used as target, and the logged object, if any, is
+/// prepended to the message. Only messages above the threshold of their debug category are
+/// forwarded, see e.g. [`set_threshold_from_string()`].
+///
+/// Usually this is combined with [`remove_default_log_function()`]. It must not be combined
+/// with a [`DebugCategoryLogger`] as the `log` backend as that would forward messages in
+/// circles.
+#[cfg(feature = "log")]
+#[cfg_attr(docsrs, doc(cfg(feature = "log")))]
+pub fn forward_to_log() -> DebugLogFunction {
+    skip_assert_initialized!();
+    add_log_function(|category, level, file, _function, line, object, message| {
+        let level = match level {
+            DebugLevel::Error => log::Level::Error,
+            DebugLevel::Warning | DebugLevel::Fixme => log::Level::Warn,
+            DebugLevel::Info => log::Level::Info,
+            DebugLevel::Debug => log::Level::Debug,
+            DebugLevel::Log | DebugLevel::Trace | DebugLevel::Memdump => log::Level::Trace,
+            _ => return,
+        };
+        if level > log::max_level() {
+            return;
+        }
+
+        let metadata = log::Metadata::builder()
+            .level(level)
+            .target(category.name())
+            .build();
+        let logger = log::logger();
+        if !logger.enabled(&metadata) {
+            return;
+        }
+
+        let Some(message) = message.get() else {
+            return;
+        };
+        let args = match object {
+            Some(object) => format!("{object}: {message}"),
+            None => message.to_string(),
+        };
+        logger.log(
+            &log::Record::builder()
+                .metadata(metadata)
+                .file(Some(file.as_str()))
+                .line(Some(line))
+                .args(format_args!("{args}"))
+                .build(),
+        );
+    })
+}
+
+// rustdoc-stripper-ignore-next
+/// Adds a log function that forwards all GStreamer debug messages as `tracing` events.
+///
+/// As `tracing` requires static targets, all events have the `gstreamer` target. The debug
+/// category, the logged object, if any, and the source location are provided as the
+/// `category`, `object`, `file`, `function` and `line` fields. Only messages above the
+/// threshold of their debug category are forwarded, see e.g. [`set_threshold_from_string()`].
+///
+/// Usually this is combined with [`remove_default_log_function()`]. It must not be combined
+/// with a [`DebugCategorySubscriber`] as that would forward messages in circles.
+#[cfg(feature = "tracing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+pub fn forward_to_tracing() -> DebugLogFunction {
+    skip_assert_initialized!();
+    add_log_function(|category, level, file, function, line, object, message| {
+        let Some(message) = message.get() else {
+            return;
+        };
+        let object = object.map(|object| object.to_string());
+
+        macro_rules! event {
+            ($level:expr) => {
+                tracing::event!(
+                    target: "gstreamer",
+                    $level,
+                    category = category.name(),
+                    object = object.as_deref(),
+                    file = file.as_str(),
+                    function = function.as_str(),
+                    line,
+                    "{}",
+                    message
+                )
+            };
+        }
+
+        match level {
+            DebugLevel::Error => event!(tracing::Level::ERROR),
+            DebugLevel::Warning | DebugLevel::Fixme => event!(tracing::Level::WARN),
+            DebugLevel::Info => event!(tracing::Level::INFO),
+            DebugLevel::Debug => event!(tracing::Level::DEBUG),
+            DebugLevel::Log | DebugLevel::Trace | DebugLevel::Memdump => {
+                event!(tracing::Level::TRACE)
+            }
+            _ => (),
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::{mpsc, Arc, Mutex};
@@ -1426,22 +1632,65 @@ mod tests {
         memdump!(cat, obj = obj, "meh");
     }
 
+    // The `log` logger can only be set once, so this logger is shared by all tests. Messages
+    // logged from this module go to the `Log_trait` category and messages forwarded from the
+    // `Forward_to_log` category are captured.
     #[cfg(feature = "log")]
-    static LOGGER: LazyLock<DebugCategoryLogger> = LazyLock::new(|| {
-        DebugCategoryLogger::new(DebugCategory::new(
+    struct TestLogger {
+        cat_logger: DebugCategoryLogger,
+        forwarded: Mutex<Vec<(log::Level, String)>>,
+    }
+
+    #[cfg(feature = "log")]
+    impl log::Log for TestLogger {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            match metadata.target() {
+                "Forward_to_log" => true,
+                target if target == module_path!() => self.cat_logger.enabled(metadata),
+                _ => false,
+            }
+        }
+
+        fn log(&self, record: &log::Record) {
+            match record.target() {
+                "Forward_to_log" => self
+                    .forwarded
+                    .lock()
+                    .unwrap()
+                    .push((record.level(), record.args().to_string())),
+                target if target == module_path!() => self.cat_logger.log(record),
+                _ => (),
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[cfg(feature = "log")]
+    static LOGGER: LazyLock<TestLogger> = LazyLock::new(|| TestLogger {
+        cat_logger: DebugCategoryLogger::new(DebugCategory::new(
             "Log_trait",
             crate::DebugColorFlags::empty(),
             Some("Using the Log trait"),
-        ))
+        )),
+        forwarded: Mutex::new(Vec::new()),
     });
 
+    #[cfg(feature = "log")]
+    fn set_test_logger() {
+        static SET_LOGGER: std::sync::Once = std::sync::Once::new();
+        SET_LOGGER.call_once(|| {
+            log::set_logger(&(*LOGGER)).expect("Failed to set logger");
+            log::set_max_level(log::LevelFilter::Trace);
+        });
+    }
+
     #[test]
     #[cfg(feature = "log")]
     fn log_trait() {
         crate::init().unwrap();
 
-        log::set_logger(&(*LOGGER)).expect("Failed to set logger");
-        log::set_max_level(log::LevelFilter::Trace);
+        set_test_logger();
         log::error!("meh");
         log::warn!("fish");
 
@@ -1470,7 +1719,7 @@ mod tests {
         remove_default_log_function();
         add_log_function(handler);
 
-        let cat = LOGGER.0;
+        let cat = LOGGER.cat_logger.0;
 
         cat.set_threshold(crate::DebugLevel::Warning);
         log::error!("meh");
@@ -1485,6 +1734,50 @@ mod tests {
         log::warn!("meh");
     }
 
+    #[test]
+    #[cfg(feature = "log")]
+    fn forward_to_log_function() {
+        crate::init().unwrap();
+
+        set_test_logger();
+        let cat = DebugCategory::new(
+            "Forward_to_log",
+            crate::DebugColorFlags::empty(),
+            Some("Forwarding to the log crate"),
+        );
+        cat.set_threshold(DebugLevel::Memdump);
+        let obj = crate::Bin::with_name("forward-bin");
+
+        let log_fn = forward_to_log();
+        error!(cat, obj = &obj, "error");
+        warning!(cat, "warning");
+        fixme!(cat, "fixme");
+        info!(cat, "info");
+        debug!(cat, "debug");
+        log!(cat, "log");
+        trace!(cat, "trace");
+        memdump!(cat, "memdump");
+
+        cat.set_threshold(DebugLevel::Warning);
+        info!(cat, "filtered");
+        remove_log_function(log_fn);
+        error!(cat, "removed");
+
+        assert_eq!(
+            *LOGGER.forwarded.lock().unwrap(),
+            [
+                (log::Level::Error, String::from("forward-bin: error")),
+                (log::Level::Warn, String::from("warning")),
+                (log::Level::Warn, String::from("fixme")),
+                (log::Level::Info, String::from("info")),
+                (log::Level::Debug, String::from("debug")),
+                (log::Level::Trace, String::from("log")),
+                (log::Level::Trace, String::from("trace")),
+                (log::Level::Trace, String::from("memdump")),
+            ]
+        );
+    }
+
     #[test]
     fn log_handler() {
         crate::init().unwrap();
@@ -1552,6 +1845,188 @@ mod tests {
         assert!(!arg_evaluated);
     }
 
+    // A `DebugCategorySubscriber` and `forward_to_tracing()` at the same time would forward
+    // messages in circles
+    #[cfg(feature = "tracing")]
+    static TRACING_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    #[cfg(feature = "tracing")]
+    fn tracing_subscriber() {
+        crate::init().unwrap();
+        let _lock = TRACING_LOCK.lock().unwrap();
+
+        let cat = DebugCategory::new(
+            "Tracing_subscriber",
+            crate::DebugColorFlags::empty(),
+            Some("Using the tracing Subscriber trait"),
+        );
+        cat.set_threshold(DebugLevel::Warning);
+
+        let (sender, receiver) = mpsc::channel();
+        let sender = Arc::new(Mutex::new(sender));
+        let handler = move |category: DebugCategory,
+                            level: DebugLevel,
+                            _file: &glib::GStr,
+                            _function: &glib::GStr,
+                            _line: u32,
+                            _object: Option<&LoggedObject>,
+                            message: &DebugMessage| {
+            if category != cat {
+                // This test can run in parallel with other tests.
+                return;
+            }
+
+            let _ = sender
+                .lock()
+                .unwrap()
+                .send((level, message.get().unwrap().to_string()));
+        };
+
+        let log_fn = add_log_function(handler);
+
+        tracing::subscriber::with_default(DebugCategorySubscriber::new(cat), || {
+            tracing::error!(answer = 42, "meh");
+            tracing::info!("fish");
+            tracing::warn!("meh");
+        });
+
+        assert_eq!(
+            receiver.recv().unwrap(),
+            (DebugLevel::Error, String::from("meh answer=42"))
+        );
+        assert_eq!(
+            receiver.recv().unwrap(),
+            (DebugLevel::Warning, String::from("meh"))
+        );
+
+        remove_log_function(log_fn);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[derive(Debug, Default, PartialEq, Eq)]
+    struct ForwardedEvent {
+        level: Option<tracing::Level>,
+        target: String,
+        category: String,
+        object: Option<String>,
+        message: String,
+    }
+
+    #[cfg(feature = "tracing")]
+    impl tracing::field::Visit for ForwardedEvent {
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            match field.name() {
+                "category" => self.category = value.to_owned(),
+                "object" => self.object = Some(value.to_owned()),
+                _ => self.record_debug(field, &value),
+            }
+        }
+
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn fmt::Debug) {
+            if field.name() == "message" {
+                self.message = format!("{value:?}");
+            }
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[derive(Default)]
+    struct CapturingSubscriber {
+        events: Mutex<Vec<ForwardedEvent>>,
+    }
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for CapturingSubscriber {
+        fn register_callsite(
+            &self,
+            _metadata: &'static tracing::Metadata<'static>,
+        ) -> tracing::subscriber::Interest {
+            tracing::subscriber::Interest::sometimes()
+        }
+
+        fn enabled(&self, metadata: &tracing::Metadata) -> bool {
+            metadata.is_event()
+        }
+
+        fn new_span(&self, _span: &tracing::span::Attributes) -> tracing::span::Id {
+            tracing::span::Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &tracing::span::Id, _values: &tracing::span::Record) {}
+
+        fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+
+        fn event(&self, event: &tracing::Event) {
+            let mut forwarded = ForwardedEvent {
+                level: Some(*event.metadata().level()),
+                target: event.metadata().target().to_owned(),
+                ..Default::default()
+            };
+            event.record(&mut forwarded);
+            if forwarded.category == "Forward_to_tracing" {
+                self.events.lock().unwrap().push(forwarded);
+            }
+        }
+
+        fn enter(&self, _span: &tracing::span::Id) {}
+
+        fn exit(&self, _span: &tracing::span::Id) {}
+    }
+
+    #[test]
+    #[cfg(feature = "tracing")]
+    fn forward_to_tracing_function() {
+        crate::init().unwrap();
+        let _lock = TRACING_LOCK.lock().unwrap();
+
+        let cat = DebugCategory::new(
+            "Forward_to_tracing",
+            crate::DebugColorFlags::empty(),
+            Some("Forwarding to the tracing crate"),
+        );
+        cat.set_threshold(DebugLevel::Memdump);
+        let obj = crate::Bin::with_name("forward-bin");
+
+        let subscriber = Arc::new(CapturingSubscriber::default());
+        let log_fn = forward_to_tracing();
+        tracing::subscriber::with_default(subscriber.clone(), || {
+            error!(cat, obj = &obj, "error");
+            warning!(cat, "warning");
+            fixme!(cat, "fixme");
+            info!(cat, "info");
+            debug!(cat, "debug");
+            log!(cat, "log");
+            trace!(cat, "trace");
+            memdump!(cat, "memdump");
+
+            cat.set_threshold(DebugLevel::Warning);
+            info!(cat, "filtered");
+        });
+        remove_log_function(log_fn);
+
+        let event = |level, object: Option<&str>, message: &str| ForwardedEvent {
+            level: Some(level),
+            target: String::from("gstreamer"),
+            category: String::from("Forward_to_tracing"),
+            object: object.map(String::from),
+            message: String::from(message),
+        };
+        assert_eq!(
+            *subscriber.events.lock().unwrap(),
+            [
+                event(tracing::Level::ERROR, Some("forward-bin"), "error"),
+                event(tracing::Level::WARN, None, "warning"),
+                event(tracing::Level::WARN, None, "fixme"),
+                event(tracing::Level::INFO, None, "info"),
+                event(tracing::Level::DEBUG, None, "debug"),
+                event(tracing::Level::TRACE, None, "log"),
+                event(tracing::Level::TRACE, None, "trace"),
+                event(tracing::Level::TRACE, None, "memdump"),
+            ]
+        );
+    }
+
     #[cfg(feature = "v1_22")]
     #[test]
     fn id_logging() {
@@ -279,6 +279,22 @@ impl fmt::Debug for MessageRef {
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// Concrete message types that correspond to a single [`MessageType`].
+pub trait ConcreteMessageType: ToOwned {
+    const TYPE: MessageType;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the message as this concrete message type if it is of the
+    /// corresponding [`MessageType`].
+    fn from_message_ref(message: &MessageRef) -> Option<&Self>;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the message as this concrete message type if it is of the
+    /// corresponding [`MessageType`], or the message itself otherwise.
+    fn from_message(message: Message) -> Result<Self::Owned, Message>;
+}
+
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum MessageView<'a> {
@@ -379,6 +395,31 @@ pub enum MessageViewMut<'a> {
 
 macro_rules! declare_concrete_message(
     ($name:ident, $param:ident) => {
+        declare_concrete_message!(@untyped $name, $param);
+
+        impl ConcreteMessageType for $name {
+            const TYPE: MessageType = MessageType::$name;
+
+            #[inline]
+            fn from_message_ref(message: &MessageRef) -> Option<&Self> {
+                if message.type_() == MessageType::$name {
+                    Some(unsafe { &*(message as *const MessageRef as *const Self) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            fn from_message(message: Message) -> Result<Self::Owned, Message> {
+                if message.type_() == MessageType::$name {
+                    Ok($name::<Message>(message))
+                } else {
+                    Err(message)
+                }
+            }
+        }
+    };
+    (@untyped $name:ident, $param:ident) => {
         #[repr(transparent)]
         pub struct $name<$param = MessageRef>($param);
 
@@ -2605,7 +2646,7 @@ crate::utils::define_fixed_size_iter!(
 
 #[cfg(feature = "v1_16")]
 #[cfg_attr(docsrs, doc(cfg(feature = "v1_16")))]
-declare_concrete_message!(DeviceChanged, T);
+declare_concrete_message!(@untyped DeviceChanged, T);
 #[cfg(feature = "v1_16")]
 #[cfg_attr(docsrs, doc(cfg(feature = "v1_16")))]
 impl DeviceChanged {
@@ -2662,7 +2703,7 @@ impl std::fmt::Debug for DeviceChanged<Message> {
 
 #[cfg(feature = "v1_18")]
 #[cfg_attr(docsrs, doc(cfg(feature = "v1_18")))]
-declare_concrete_message!(InstantRateRequest, T);
+declare_concrete_message!(@untyped InstantRateRequest, T);
 #[cfg(feature = "v1_18")]
 #[cfg_attr(docsrs, doc(cfg(feature = "v1_18")))]
 impl InstantRateRequest {
@@ -5,7 +5,7 @@ use std::{
     num::NonZeroU64,
     ops::ControlFlow,
     panic::{self, AssertUnwindSafe},
-    ptr,
+    ptr, sync,
 };
 
 use glib::{ffi::gpointer, prelude::*, translate::*};
@@ -14,8 +14,9 @@ use crate::{
     ffi,
     format::{FormattedValue, SpecificFormattedValueFullRange, SpecificFormattedValueIntrinsic},
     prelude::*,
-    Buffer, BufferList, Event, FlowError, FlowReturn, FlowSuccess, Format, GenericFormattedValue,
-    LoggableError, Pad, PadFlags, PadProbeReturn, PadProbeType, Query, QueryRef, StaticPadTemplate,
+    Buffer, BufferList, BufferRef, Event, FlowError, FlowReturn, FlowSuccess, Format,
+    GenericFormattedValue, LoggableError, Pad, PadFlags, PadProbeReturn, PadProbeType, Query,
+    QueryRef, StaticPadTemplate,
 };
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -226,6 +227,151 @@ pub trait PadExtManual: IsA<Pad> + 'static {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Blocks the downstream dataflow on the pad and calls `func` once the pad
+    /// is blocked.
+    ///
+    /// The pad is unblocked again and the probe removed once `func` returns.
+    #[doc(alias = "gst_pad_add_probe")]
+    fn block_async<F>(&self, func: F) -> Option<PadProbeId>
+    where
+        F: FnOnce(&Self) + Send + 'static,
+    {
+        let func = sync::Mutex::new(Some(func));
+        self.add_probe(PadProbeType::BLOCK_DOWNSTREAM, move |pad, _info| {
+            if let Some(func) = func.lock().unwrap().take() {
+                func(pad);
+            }
+
+            PadProbeReturn::Remove
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Drops all buffers on the pad until `func` returns `true` for a buffer.
+    ///
+    /// That buffer and all following buffers are passed through and the probe
+    /// is removed. Buffers of buffer lists are handled individually.
+    #[doc(alias = "gst_pad_add_probe")]
+    fn drop_buffers_until<F>(&self, func: F) -> Option<PadProbeId>
+    where
+        F: FnMut(&BufferRef) -> bool + Send + 'static,
+    {
+        let func = sync::Mutex::new(Some(func));
+        self.add_probe(
+            PadProbeType::BUFFER | PadProbeType::BUFFER_LIST,
+            move |_pad, info| {
+                let mut func_guard = func.lock().unwrap();
+                let Some(func) = func_guard.as_mut() else {
+                    return PadProbeReturn::Remove;
+                };
+
+                match info.data {
+                    Some(PadProbeData::Buffer(ref buffer)) => {
+                        if !func(buffer) {
+                            return PadProbeReturn::Drop;
+                        }
+                    }
+                    Some(PadProbeData::BufferList(ref mut list)) => {
+                        match list.iter().position(func) {
+                            None => return PadProbeReturn::Drop,
+                            Some(0) => (),
+                            Some(idx) => list.make_mut().remove(..idx),
+                        }
+                    }
+                    _ => return PadProbeReturn::Ok,
+                }
+
+                *func_guard = None;
+                PadProbeReturn::Remove
+            },
+        )
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Calls `func` for every event of type `T` on the pad.
+    ///
+    /// Similar to [`sticky_events_foreach()`](Self::sticky_events_foreach), the
+    /// return value of `func` decides whether the event is kept, dropped or
+    /// replaced, and whether `func` is called for further events. Once `func`
+    /// returns [`ControlFlow::Break`] the probe is removed.
+    ///
+    /// ```rust
+    /// # use gstreamer as gst;
+    /// # use gst::prelude::*;
+    /// # use std::ops::ControlFlow;
+    /// # gst::init().unwrap();
+    /// let pad = gst::Pad::builder(gst::PadDirection::Src).build();
+    /// pad.intercept_events::<gst::event::Eos>(|pad, _eos| {
+    ///     println!("{} is EOS", pad.name());
+    ///     ControlFlow::Break(gst::EventForeachAction::Keep)
+    /// });
+    /// ```
+    #[doc(alias = "gst_pad_add_probe")]
+    fn intercept_events<T: crate::event::ConcreteEventType + 'static>(
+        &self,
+        func: impl FnMut(&Self, &T) -> ControlFlow<EventForeachAction, EventForeachAction>
+            + Send
+            + 'static,
+    ) -> Option<PadProbeId> {
+        let mut mask = PadProbeType::empty();
+        if T::TYPE.is_upstream() {
+            mask |= PadProbeType::EVENT_UPSTREAM;
+        }
+        if T::TYPE.is_downstream() {
+            mask |= PadProbeType::EVENT_DOWNSTREAM;
+        }
+        if matches!(
+            T::TYPE,
+            crate::EventType::FlushStart | crate::EventType::FlushStop
+        ) {
+            mask |= PadProbeType::EVENT_FLUSH;
+        }
+
+        let func = sync::Mutex::new(Some(func));
+        self.add_probe(mask, move |pad, info| {
+            let res = {
+                let Some(event) = info.event().and_then(T::from_event_ref) else {
+                    return PadProbeReturn::Ok;
+                };
+
+                let mut func_guard = func.lock().unwrap();
+                let Some(func) = func_guard.as_mut() else {
+                    return PadProbeReturn::Ok;
+                };
+
+                let res = func(pad, event);
+                if res.is_break() {
+                    *func_guard = None;
+                }
+                res
+            };
+
+            let (remove, action) = match res {
+                ControlFlow::Continue(action) => (false, action),
+                ControlFlow::Break(action) => (true, action),
+            };
+
+            if remove {
+                if let Some(id) = info.id.take() {
+                    pad.remove_probe(id);
+                }
+            }
+
+            match action {
+                EventForeachAction::Keep => PadProbeReturn::Ok,
+                EventForeachAction::Remove => {
+                    info.take_event();
+                    PadProbeReturn::Drop
+                }
+                EventForeachAction::Replace(event) => {
+                    info.data = Some(PadProbeData::Event(event));
+                    PadProbeReturn::Ok
+                }
+            }
+        })
+    }
+
     #[doc(alias = "gst_pad_pull_range")]
     fn pull_range(&self, offset: u64, size: u32) -> Result<Buffer, FlowError> {
         unsafe {
@@ -2526,6 +2672,145 @@ mod tests {
         do_probe_with_return(crate::PadProbeReturn::Drop);
     }
 
+    #[test]
+    fn test_block_async() {
+        crate::init().unwrap();
+
+        let pad = crate::Pad::builder(crate::PadDirection::Src)
+            .name("src")
+            .build();
+        pad.set_active(true).unwrap();
+
+        let (sender, receiver) = channel();
+        pad.block_async(move |_| {
+            sender.send(()).unwrap();
+        })
+        .unwrap();
+
+        assert!(pad.push_event(crate::event::StreamStart::new("test")));
+        receiver.recv().unwrap();
+        assert!(!pad.is_blocked());
+
+        assert!(pad.push_event(crate::event::Caps::new(
+            &crate::Caps::builder("some/x-caps").build()
+        )));
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_drop_buffers_until() {
+        crate::init().unwrap();
+
+        let buffers = Arc::new(Mutex::new(Vec::new()));
+        let buffers_clone = buffers.clone();
+        let sinkpad = crate::Pad::builder(crate::PadDirection::Sink)
+            .name("sink")
+            .chain_function({
+                let buffers = buffers_clone.clone();
+                move |_, _, buffer| {
+                    buffers.lock().unwrap().push(buffer.offset());
+                    Ok(FlowSuccess::Ok)
+                }
+            })
+            .chain_list_function(move |_, _, list| {
+                let mut buffers = buffers_clone.lock().unwrap();
+                buffers.extend(list.iter().map(|buffer| buffer.offset()));
+                Ok(FlowSuccess::Ok)
+            })
+            .build();
+        let srcpad = crate::Pad::builder(crate::PadDirection::Src)
+            .name("src")
+            .build();
+        srcpad.link(&sinkpad).unwrap();
+        sinkpad.set_active(true).unwrap();
+        srcpad.set_active(true).unwrap();
+
+        srcpad
+            .drop_buffers_until(|buffer| buffer.flags().contains(crate::BufferFlags::HEADER))
+            .unwrap();
+
+        assert!(srcpad.push_event(crate::event::StreamStart::new("test")));
+        let segment = crate::FormattedSegment::<crate::ClockTime>::new();
+        assert!(srcpad.push_event(crate::event::Segment::new(segment.as_ref())));
+
+        let buffer = |offset, flags| {
+            let mut buffer = crate::Buffer::new();
+            let buffer_ref = buffer.get_mut().unwrap();
+            buffer_ref.set_offset(offset);
+            buffer_ref.set_flags(flags);
+            buffer
+        };
+
+        assert_eq!(
+            srcpad.push(buffer(0, crate::BufferFlags::empty())),
+            Ok(FlowSuccess::Ok)
+        );
+
+        let mut list = crate::BufferList::new();
+        {
+            let list = list.get_mut().unwrap();
+            list.add(buffer(1, crate::BufferFlags::empty()));
+            list.add(buffer(2, crate::BufferFlags::HEADER));
+            list.add(buffer(3, crate::BufferFlags::empty()));
+        }
+        assert_eq!(srcpad.push_list(list), Ok(FlowSuccess::Ok));
+
+        assert_eq!(
+            srcpad.push(buffer(4, crate::BufferFlags::empty())),
+            Ok(FlowSuccess::Ok)
+        );
+
+        assert_eq!(*buffers.lock().unwrap(), [2, 3, 4]);
+    }
+
+    #[test]
+    fn test_intercept_events() {
+        crate::init().unwrap();
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        let sinkpad = crate::Pad::builder(crate::PadDirection::Sink)
+            .name("sink")
+            .event_function(move |_, _, event| {
+                if let crate::EventView::StreamStart(stream_start) = event.view() {
+                    let mut received = received_clone.lock().unwrap();
+                    received.push(stream_start.stream_id().to_owned());
+                }
+                true
+            })
+            .build();
+        let pad = crate::Pad::builder(crate::PadDirection::Src)
+            .name("src")
+            .build();
+        pad.link(&sinkpad).unwrap();
+        sinkpad.set_active(true).unwrap();
+        pad.set_active(true).unwrap();
+
+        let stream_ids = Arc::new(Mutex::new(Vec::new()));
+        let stream_ids_clone = stream_ids.clone();
+        pad.intercept_events::<crate::event::StreamStart>(move |_, stream_start| {
+            let mut stream_ids = stream_ids_clone.lock().unwrap();
+            stream_ids.push(stream_start.stream_id().to_owned());
+            if stream_ids.len() == 1 {
+                ControlFlow::Continue(EventForeachAction::Replace(crate::event::StreamStart::new(
+                    "replaced",
+                )))
+            } else {
+                ControlFlow::Break(EventForeachAction::Remove)
+            }
+        })
+        .unwrap();
+
+        assert!(pad.push_event(crate::event::StreamStart::new("test")));
+        let caps = crate::Caps::builder("some/x-caps").build();
+        assert!(pad.push_event(crate::event::Caps::new(&caps)));
+        pad.push_event(crate::event::StreamStart::new("dropped"));
+        assert!(pad.push_event(crate::event::StreamStart::new("test2")));
+
+        assert_eq!(*stream_ids.lock().unwrap(), ["test", "dropped"]);
+        assert_eq!(*received.lock().unwrap(), ["replaced", "test2"]);
+    }
+
     #[test]
     fn test_sticky_events() {
         crate::init().unwrap();
@@ -2,7 +2,7 @@
 
 use glib::{prelude::*, translate::*};
 
-use crate::{ffi, prelude::*, Pipeline, PipelineFlags};
+use crate::{ffi, prelude::*, BinChildrenBuilder, Element, Pipeline, PipelineFlags};
 
 impl Pipeline {
     // rustdoc-stripper-ignore-next
@@ -211,4 +211,25 @@ impl<'a> PipelineBuilder<'a> {
     }
 
     impl_builder_gvalue_extra_setters!(property_and_name);
+
+    // rustdoc-stripper-ignore-next
+    /// Adds an element created from the factory `factory_name` with the
+    /// given `properties` to the [`Pipeline`].
+    ///
+    /// See [`BinChildrenBuilder::element()`].
+    pub fn element(
+        self,
+        factory_name: &'a str,
+        properties: impl IntoIterator<Item = (&'a str, glib::Value)>,
+    ) -> BinChildrenBuilder<'a, Pipeline> {
+        BinChildrenBuilder::new(self.builder).element(factory_name, properties)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds `element` to the [`Pipeline`].
+    ///
+    /// See [`BinChildrenBuilder::add()`].
+    pub fn add(self, element: &impl IsA<Element>) -> BinChildrenBuilder<'a, Pipeline> {
+        BinChildrenBuilder::new(self.builder).add(element)
+    }
 }
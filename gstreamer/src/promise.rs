@@ -149,6 +149,64 @@ impl Default for Promise {
 unsafe impl Send for Promise {}
 unsafe impl Sync for Promise {}
 
+// rustdoc-stripper-ignore-next
+/// A [`Promise`] that can be `.await`ed, e.g. after passing it to an API like the
+/// `create-offer` signal of `webrtcbin`.
+///
+/// Only promises created with a change function notify about their resolution, so other
+/// promises can't be awaited.
+///
+/// ```rust
+/// # use gstreamer as gst;
+/// # gst::init().unwrap();
+/// let promise = gst::AwaitablePromise::new();
+/// promise.reply(Some(gst::Structure::new_empty("reply")));
+///
+/// let reply = futures_executor::block_on(async { promise.await });
+/// assert_eq!(reply.unwrap().unwrap().name(), "reply");
+/// ```
+#[derive(Debug)]
+pub struct AwaitablePromise(PromiseFuture);
+
+impl AwaitablePromise {
+    #[doc(alias = "gst_promise_new_with_change_func")]
+    pub fn new() -> Self {
+        assert_initialized_main_thread!();
+        let (_, future) = Promise::new_future();
+        Self(future)
+    }
+
+    #[inline]
+    pub fn promise(&self) -> &Promise {
+        &self.0 .0
+    }
+}
+
+impl Default for AwaitablePromise {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for AwaitablePromise {
+    type Target = Promise;
+
+    #[inline]
+    fn deref(&self) -> &Promise {
+        self.promise()
+    }
+}
+
+impl std::future::IntoFuture for AwaitablePromise {
+    type Output = Result<Option<PromiseReply>, PromiseError>;
+    type IntoFuture = PromiseFuture;
+
+    #[inline]
+    fn into_future(self) -> PromiseFuture {
+        self.0
+    }
+}
+
 #[derive(Debug)]
 pub struct PromiseFuture(Promise, futures_channel::oneshot::Receiver<()>);
 
@@ -212,7 +270,7 @@ impl std::fmt::Debug for PromiseReply {
 
 #[cfg(test)]
 mod tests {
-    use std::{sync::mpsc::channel, thread};
+    use std::{future::IntoFuture, sync::mpsc::channel, thread};
 
     use super::*;
 
@@ -233,4 +291,40 @@ mod tests {
         let res = res.expect("promise failed").expect("promise returned None");
         assert_eq!(res.name(), "foo/bar");
     }
+
+    #[test]
+    fn test_awaitable() {
+        crate::init().unwrap();
+
+        let promise = AwaitablePromise::new();
+        let promise_clone = promise.promise().clone();
+        thread::spawn(move || {
+            promise_clone.reply(Some(crate::Structure::new_empty("foo/bar")));
+        });
+
+        let res = futures_executor::block_on(async { promise.await });
+        let res = res.expect("promise failed").expect("promise returned None");
+        assert_eq!(res.name(), "foo/bar");
+
+        let promise = AwaitablePromise::new();
+        promise.reply(None);
+        assert!(matches!(
+            futures_executor::block_on(promise.into_future()),
+            Ok(None)
+        ));
+
+        let promise = AwaitablePromise::new();
+        promise.interrupt();
+        assert_eq!(
+            futures_executor::block_on(promise.into_future()).unwrap_err(),
+            PromiseError::Interrupted
+        );
+
+        let promise = AwaitablePromise::new();
+        promise.expire();
+        assert_eq!(
+            futures_executor::block_on(promise.into_future()).unwrap_err(),
+            PromiseError::Expired
+        );
+    }
 }
@@ -0,0 +1,583 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! Map based serde representation of [`Caps`] and [`Structure`]s.
+//!
+//! The default serde implementations of [`Caps`] and [`Structure`] are based
+//! on tuples. The representation provided by this module is meant for
+//! self-describing formats like JSON instead, e.g. for configuration files or
+//! REST APIs, and can be selected for fields with
+//! `#[serde(with = "gst::serde_map::caps")]` and
+//! `#[serde(with = "gst::serde_map::structure")]`.
+//!
+//! A structure is a map of its `name` and its `fields`, where each field is a
+//! map from the type name of the field's value to the value:
+//!
+//! ```json
+//! {
+//!   "name": "video/x-raw",
+//!   "fields": {
+//!     "format": { "String": "I420" },
+//!     "width": { "i32": 1920 },
+//!     "framerate": { "Fraction": [30, 1] },
+//!     "views": { "List": [{ "i32": 1 }, { "i32": 2 }] }
+//!   }
+//! }
+//! ```
+//!
+//! Caps are either `"ANY"` or a sequence of structures, each of which
+//! optionally has `features`, which are either `"ANY"` or a sequence of
+//! feature names like `["memory:GLMemory"]`. Nested structures, caps, arrays
+//! and lists use the same representation.
+//!
+//! ```rust
+//! # use gstreamer as gst;
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Config {
+//!     #[serde(with = "gst::serde_map::caps")]
+//!     caps: gst::Caps,
+//! }
+//! ```
+
+#![allow(clippy::upper_case_acronyms)]
+
+use std::fmt;
+
+use glib::{prelude::*, Date};
+use serde::{
+    de,
+    de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor},
+    ser,
+    ser::{Serialize, SerializeMap, SerializeSeq, Serializer},
+};
+
+use crate::{
+    date_time_serde, value::*, value_serde::*, Buffer, Caps, CapsFeatures, CapsFeaturesRef,
+    CapsRef, DateTime, Sample, Structure, StructureRef,
+};
+
+const ANY: &str = "ANY";
+
+struct ValueSe<'a>(&'a glib::SendValue);
+impl Serialize for ValueSe<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+
+        let type_ = self.0.type_();
+        if type_ == *ARRAY_OTHER_TYPE_ID {
+            let array = self.0.get::<Array>().unwrap();
+            map.serialize_entry("Array", &ValuesSe(array.as_slice()))?;
+        } else if type_ == *LIST_OTHER_TYPE_ID {
+            let list = self.0.get::<List>().unwrap();
+            map.serialize_entry("List", &ValuesSe(list.as_slice()))?;
+        } else if type_ == *STRUCTURE_OTHER_TYPE_ID {
+            let structure = self.0.get::<Structure>().unwrap();
+            map.serialize_entry("Structure", &StructureSe(&structure))?;
+        } else if type_ == Caps::static_type() {
+            let caps = self.0.get::<Option<Caps>>().unwrap();
+            map.serialize_entry("Caps", &caps.as_deref().map(CapsSe))?;
+        } else {
+            ser_value!(self.0, |type_, value| map.serialize_entry(type_, &value))?;
+        }
+
+        map.end()
+    }
+}
+
+struct ValuesSe<'a>(&'a [glib::SendValue]);
+impl Serialize for ValuesSe<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for value in self.0 {
+            seq.serialize_element(&ValueSe(value))?;
+        }
+        seq.end()
+    }
+}
+
+struct FieldsSe<'a>(&'a StructureRef);
+impl Serialize for FieldsSe<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.n_fields()))?;
+        for (name, value) in self.0.iter() {
+            map.serialize_entry(name.as_str(), &ValueSe(value))?;
+        }
+        map.end()
+    }
+}
+
+struct StructureSe<'a>(&'a StructureRef);
+impl Serialize for StructureSe<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("name", self.0.name().as_str())?;
+        map.serialize_entry("fields", &FieldsSe(self.0))?;
+        map.end()
+    }
+}
+
+struct CapsFeaturesSe<'a>(&'a CapsFeaturesRef);
+impl Serialize for CapsFeaturesSe<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0.is_any() {
+            return serializer.serialize_str(ANY);
+        }
+
+        let mut seq = serializer.serialize_seq(Some(self.0.size()))?;
+        for feature in self.0.iter() {
+            seq.serialize_element(feature.as_str())?;
+        }
+        seq.end()
+    }
+}
+
+struct CapsStructureSe<'a>(&'a StructureRef, &'a CapsFeaturesRef);
+impl Serialize for CapsStructureSe<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let features = if !self.1.is_any()
+            && self
+                .1
+                .is_equal(crate::CAPS_FEATURES_MEMORY_SYSTEM_MEMORY.as_ref())
+        {
+            None
+        } else {
+            Some(self.1)
+        };
+
+        let mut map = serializer.serialize_map(Some(2 + features.is_some() as usize))?;
+        map.serialize_entry("name", self.0.name().as_str())?;
+        if let Some(features) = features {
+            map.serialize_entry("features", &CapsFeaturesSe(features))?;
+        }
+        map.serialize_entry("fields", &FieldsSe(self.0))?;
+        map.end()
+    }
+}
+
+struct CapsSe<'a>(&'a CapsRef);
+impl Serialize for CapsSe<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0.is_any() {
+            return serializer.serialize_str(ANY);
+        }
+
+        let mut seq = serializer.serialize_seq(Some(self.0.size()))?;
+        for (structure, features) in self.0.iter_with_features() {
+            seq.serialize_element(&CapsStructureSe(structure, features))?;
+        }
+        seq.end()
+    }
+}
+
+// Allows using `de_send_value!` for the value of a map entry
+struct MapValueAccess<'a, A>(&'a mut A);
+impl<'de, A: MapAccess<'de>> MapValueAccess<'_, A> {
+    fn next_element<T: Deserialize<'de>>(&mut self) -> Result<Option<T>, A::Error> {
+        self.0.next_value().map(Some)
+    }
+}
+
+struct ValueDe(glib::SendValue);
+
+struct ValueVisitor;
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = ValueDe;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of the type name of a `Value` to the value")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let type_name = map
+            .next_key::<String>()?
+            .ok_or_else(|| de::Error::custom("Expected a type name for `Value`"))?;
+
+        let value = match type_name.as_str() {
+            "Array" => {
+                let values = map.next_value::<Vec<ValueDe>>()?;
+                Array::from_values(values.into_iter().map(|value| value.0)).to_send_value()
+            }
+            "List" => {
+                let values = map.next_value::<Vec<ValueDe>>()?;
+                List::from_values(values.into_iter().map(|value| value.0)).to_send_value()
+            }
+            "Structure" => map.next_value::<StructureDe>()?.0.to_send_value(),
+            "Caps" => map
+                .next_value::<Option<CapsDe>>()?
+                .map(|caps| caps.0)
+                .to_send_value(),
+            _ => {
+                let mut access = MapValueAccess(&mut map);
+                de_send_value!(type_name, access)?
+                    .ok_or_else(|| de::Error::custom("Expected a value for `Value`"))?
+                    .into()
+            }
+        };
+
+        if map.next_key::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::custom("Expected a single type name for `Value`"));
+        }
+
+        Ok(ValueDe(value))
+    }
+}
+
+impl<'de> Deserialize<'de> for ValueDe {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        deserializer.deserialize_map(ValueVisitor)
+    }
+}
+
+struct FieldsDe(Vec<(String, glib::SendValue)>);
+
+struct FieldsVisitor;
+impl<'de> Visitor<'de> for FieldsVisitor {
+    type Value = FieldsDe;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of field names to `Value`s")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut fields = Vec::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some(name) = map.next_key::<String>()? {
+            let value = map.next_value::<ValueDe>()?;
+            fields.push((name, value.0));
+        }
+
+        Ok(FieldsDe(fields))
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldsDe {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        deserializer.deserialize_map(FieldsVisitor)
+    }
+}
+
+struct CapsFeaturesDe(CapsFeatures);
+
+struct CapsFeaturesVisitor;
+impl<'de> Visitor<'de> for CapsFeaturesVisitor {
+    type Value = CapsFeaturesDe;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("`\"ANY\"` or a sequence of `CapsFeatures` names")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        if value == ANY {
+            Ok(CapsFeaturesDe(CapsFeatures::new_any()))
+        } else {
+            Err(de::Error::invalid_value(de::Unexpected::Str(value), &self))
+        }
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut features = CapsFeatures::new_empty();
+        while let Some(feature) = seq.next_element::<String>()? {
+            features.add(feature.as_str());
+        }
+
+        Ok(CapsFeaturesDe(features))
+    }
+}
+
+impl<'de> Deserialize<'de> for CapsFeaturesDe {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        deserializer.deserialize_any(CapsFeaturesVisitor)
+    }
+}
+
+// Structure of `Caps` if `ALLOW_FEATURES`, otherwise a standalone `Structure`
+struct StructureDeImpl<const ALLOW_FEATURES: bool>(Structure, Option<CapsFeatures>);
+
+struct StructureVisitor<const ALLOW_FEATURES: bool>;
+impl<'de, const ALLOW_FEATURES: bool> Visitor<'de> for StructureVisitor<ALLOW_FEATURES> {
+    type Value = StructureDeImpl<ALLOW_FEATURES>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a `Structure` map with `name` and `fields`")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut name = None;
+        let mut fields = None;
+        let mut features = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            let duplicate = match key.as_str() {
+                "name" => name.replace(map.next_value::<String>()?).is_some(),
+                "fields" => fields.replace(map.next_value::<FieldsDe>()?.0).is_some(),
+                "features" if ALLOW_FEATURES => features
+                    .replace(map.next_value::<CapsFeaturesDe>()?.0)
+                    .is_some(),
+                _ => return Err(de::Error::custom(format!("Unexpected key `{key}`"))),
+            };
+
+            if duplicate {
+                return Err(de::Error::custom(format!("Duplicate key `{key}`")));
+            }
+        }
+
+        let name = name.ok_or_else(|| de::Error::custom("Expected a name for the `Structure`"))?;
+        let mut structure = Structure::new_empty(name);
+        for (name, value) in fields.unwrap_or_default() {
+            structure.set_value(name.as_str(), value);
+        }
+
+        Ok(StructureDeImpl(structure, features))
+    }
+}
+
+impl<'de, const ALLOW_FEATURES: bool> Deserialize<'de> for StructureDeImpl<ALLOW_FEATURES> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        deserializer.deserialize_map(StructureVisitor::<ALLOW_FEATURES>)
+    }
+}
+
+struct StructureDe(Structure);
+
+impl<'de> Deserialize<'de> for StructureDe {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        StructureDeImpl::<false>::deserialize(deserializer).map(|s| StructureDe(s.0))
+    }
+}
+
+struct CapsDe(Caps);
+
+struct CapsStructuresDe<'a>(&'a mut CapsRef);
+
+impl<'de> Visitor<'de> for CapsStructuresDe<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of `Structure` maps")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(StructureDeImpl(structure, features)) =
+            seq.next_element::<StructureDeImpl<true>>()?
+        {
+            self.0.append_structure_full(structure, features);
+        }
+
+        Ok(())
+    }
+}
+
+struct CapsVisitor;
+impl<'de> Visitor<'de> for CapsVisitor {
+    type Value = CapsDe;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("`\"ANY\"` or a sequence of `Structure` maps")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        if value == ANY {
+            Ok(CapsDe(Caps::new_any()))
+        } else {
+            Err(de::Error::invalid_value(de::Unexpected::Str(value), &self))
+        }
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        let mut caps = Caps::new_empty();
+        CapsStructuresDe(caps.get_mut().unwrap()).visit_seq(seq)?;
+
+        Ok(CapsDe(caps))
+    }
+}
+
+impl<'de> Deserialize<'de> for CapsDe {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        skip_assert_initialized!();
+        deserializer.deserialize_any(CapsVisitor)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Map based serde representation of [`Structure`]s for use with
+/// `#[serde(with = "gst::serde_map::structure")]`.
+pub mod structure {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{Structure, StructureRef};
+
+    pub fn serialize<S: Serializer>(
+        structure: &StructureRef,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::StructureSe(structure).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Structure, D::Error> {
+        skip_assert_initialized!();
+        super::StructureDe::deserialize(deserializer).map(|s| s.0)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Map based serde representation of [`Caps`] for use with
+/// `#[serde(with = "gst::serde_map::caps")]`.
+pub mod caps {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{Caps, CapsRef};
+
+    pub fn serialize<S: Serializer>(caps: &CapsRef, serializer: S) -> Result<S::Ok, S::Error> {
+        super::CapsSe(caps).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Caps, D::Error> {
+        skip_assert_initialized!();
+        super::CapsDe::deserialize(deserializer).map(|caps| caps.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{Array, Caps, CapsFeatures, Fraction, List, Structure};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Config {
+        #[serde(with = "super::caps")]
+        caps: Caps,
+        #[serde(with = "super::structure")]
+        structure: Structure,
+    }
+
+    #[test]
+    fn test_serialize() {
+        crate::init().unwrap();
+
+        let config = Config {
+            caps: Caps::builder("video/x-raw")
+                .field("width", 1920)
+                .field("framerate", Fraction::new(30, 1))
+                .features(["memory:GLMemory"])
+                .build(),
+            structure: Structure::builder("test")
+                .field("f1", "abc")
+                .field("f2", Option::<&str>::None)
+                .field("f3", 1u64)
+                .field("array", Array::new([1, 2]))
+                .field("list", List::new(["a"]))
+                .field(
+                    "nested",
+                    Structure::builder("nested").field("b", true).build(),
+                )
+                .field("caps", Caps::new_any())
+                .build(),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            concat!(
+                r#"{"caps":[{"name":"video/x-raw","features":["memory:GLMemory"],"#,
+                r#""fields":{"width":{"i32":1920},"framerate":{"Fraction":[30,1]}}}],"#,
+                r#""structure":{"name":"test","fields":{"f1":{"String":"abc"},"#,
+                r#""f2":{"String":null},"f3":{"u64":1},"#,
+                r#""array":{"Array":[{"i32":1},{"i32":2}]},"#,
+                r#""list":{"List":[{"String":"a"}]},"#,
+                r#""nested":{"Structure":{"name":"nested","fields":{"b":{"bool":true}}}},"#,
+                r#""caps":{"Caps":"ANY"}}}}"#,
+            )
+        );
+
+        let caps = Caps::builder("audio/x-raw").build();
+        let mut serializer = serde_json::Serializer::new(Vec::new());
+        super::caps::serialize(&caps, &mut serializer).unwrap();
+        assert_eq!(
+            serializer.into_inner(),
+            br#"[{"name":"audio/x-raw","fields":{}}]"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize() {
+        crate::init().unwrap();
+
+        let config: Config = serde_json::from_str(
+            r#"{
+                "caps": [
+                    {
+                        "fields": { "rate": { "i32": 48000 } },
+                        "name": "audio/x-raw"
+                    },
+                    {
+                        "name": "audio/x-raw",
+                        "features": "ANY"
+                    }
+                ],
+                "structure": {
+                    "name": "test",
+                    "fields": {
+                        "array": { "Array": [{ "Fraction": [1, 2] }] },
+                        "caps": { "Caps": [] }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let mut caps = Caps::builder("audio/x-raw").field("rate", 48000).build();
+        caps.get_mut().unwrap().append_structure_full(
+            Structure::new_empty("audio/x-raw"),
+            Some(CapsFeatures::new_any()),
+        );
+        assert_eq!(config.caps, caps);
+        assert_eq!(
+            config.structure,
+            Structure::builder("test")
+                .field("array", Array::new([Fraction::new(1, 2)]))
+                .field("caps", Caps::new_empty())
+                .build()
+        );
+
+        assert!(serde_json::from_str::<Config>(
+            r#"{"caps":"NONE","structure":{"name":"test","fields":{}}}"#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<Config>(
+            r#"{"caps":"ANY","structure":{"name":"test","fields":{"f":{"i32":1,"u32":1}}}}"#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<Config>(
+            r#"{"caps":"ANY","structure":{"name":"test","features":[]}}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        crate::init().unwrap();
+
+        let config = Config {
+            caps: Caps::builder("video/x-raw")
+                .field("format", crate::List::new(["I420", "NV12"]))
+                .field("width", crate::IntRange::new(16, 4096))
+                .field(
+                    "framerate",
+                    crate::FractionRange::new(Fraction::new(0, 1), Fraction::new(60, 1)),
+                )
+                .build(),
+            structure: Structure::builder("test")
+                .field("f", 1.5f64)
+                .field("b", crate::Bitmask::new(3))
+                .build(),
+        };
+
+        let json = serde_json::to_string(&config).unwrap();
+        let config_de: Config = serde_json::from_str(&json).unwrap();
+        assert_eq!(config_de, config);
+    }
+}
@@ -16,12 +16,50 @@ cfg_if::cfg_if! {
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// Defines a GStreamer plugin.
+///
+/// This creates the plugin descriptor that is necessary for loading the plugin dynamically, and
+/// a `plugin_desc` module with functions for registering the plugin statically, most notably
+/// `register_static()`. `plugin_desc::plugin_register_static()` is re-exported in the calling
+/// module.
+///
+/// To define multiple plugins in one module, e.g. when registering multiple static plugins from
+/// one binary, each one can be given its own module name instead:
+///
+/// ```ignore
+/// gst::plugin_define!(
+///     mod audio_plugin: audioplugin,
+///     env!("CARGO_PKG_DESCRIPTION"),
+///     audio_plugin_init,
+///     ...
+/// );
+///
+/// audio_plugin::register_static()?;
+/// ```
 #[macro_export]
 macro_rules! plugin_define(
+    (mod $mod:ident: $name:ident, $description:expr, $plugin_init:ident,
+     $version:expr, $license:expr, $source:expr,
+     $package:expr, $origin:expr $(, $release_datetime:expr)?) => {
+        $crate::plugin_define!(
+            @define $mod, $name, $description, $plugin_init,
+            $version, $license, $source, $package, $origin $(, $release_datetime)?
+        );
+    };
     ($name:ident, $description:expr, $plugin_init:ident,
      $version:expr, $license:expr, $source:expr,
      $package:expr, $origin:expr $(, $release_datetime:expr)?) => {
-        pub mod plugin_desc {
+        $crate::plugin_define!(
+            @define plugin_desc, $name, $description, $plugin_init,
+            $version, $license, $source, $package, $origin $(, $release_datetime)?
+        );
+        pub use self::plugin_desc::plugin_register_static;
+    };
+    (@define $mod:ident, $name:ident, $description:expr, $plugin_init:ident,
+     $version:expr, $license:expr, $source:expr,
+     $package:expr, $origin:expr $(, $release_datetime:expr)?) => {
+        pub mod $mod {
             #[repr(transparent)]
             pub struct GstPluginDesc($crate::ffi::GstPluginDesc);
             unsafe impl Send for GstPluginDesc {}
@@ -86,6 +124,74 @@ macro_rules! plugin_define(
                 }
             }
 
+            // Cause of the last failed initialization of the plugin, for reporting it from
+            // `register_static()`
+            static INIT_ERROR: std::sync::Mutex<Option<String>> = std::sync::Mutex::new(None);
+
+            // rustdoc-stripper-ignore-next
+            /// Registers the plugin statically.
+            ///
+            /// Unlike [`plugin_register_static()`], this fails if GStreamer is not initialized
+            /// yet, succeeds without doing anything if a plugin with the same name is already
+            /// registered, and reports why the plugin failed to initialize.
+            pub fn register_static() -> Result<(), $crate::glib::BoolError> {
+                let name = stringify!($name);
+
+                // GStreamer might have been initialized from C
+                if !$crate::INITIALIZED.load(std::sync::atomic::Ordering::SeqCst)
+                    && unsafe { $crate::ffi::gst_is_initialized() } != $crate::glib::ffi::GTRUE
+                {
+                    return Err($crate::glib::bool_error!(
+                        "Failed to register plugin '{}': GStreamer is not initialized",
+                        name
+                    ));
+                }
+
+                if $crate::Registry::get().find_plugin(name).is_some() {
+                    return Ok(());
+                }
+
+                INIT_ERROR.lock().unwrap().take();
+                plugin_register_static().map_err(|_| {
+                    match INIT_ERROR.lock().unwrap().take() {
+                        Some(err) => {
+                            $crate::glib::bool_error!("Failed to register plugin '{}': {}", name, err)
+                        }
+                        None => $crate::glib::bool_error!("Failed to register plugin '{}'", name),
+                    }
+                })
+            }
+
+            // rustdoc-stripper-ignore-next
+            /// Registers the plugin statically like [`register_static()`] and then overrides
+            /// the ranks of its features, e.g. depending on the configuration of the
+            /// application.
+            pub fn register_static_with_ranks(
+                ranks: &[(&str, $crate::Rank)],
+            ) -> Result<(), $crate::glib::BoolError> {
+                use $crate::prelude::*;
+
+                register_static()?;
+
+                let name = stringify!($name);
+                let registry = $crate::Registry::get();
+                for (feature_name, rank) in ranks {
+                    let feature = registry
+                        .lookup_feature(feature_name)
+                        .filter(|feature| feature.plugin_name().is_some_and(|n| n.as_str() == name))
+                        .ok_or_else(|| {
+                            $crate::glib::bool_error!(
+                                "Plugin '{}' has no feature '{}'",
+                                name,
+                                feature_name
+                            )
+                        })?;
+                    feature.set_rank(*rank);
+                }
+
+                Ok(())
+            }
+
             $crate::pastey::item! {
                 #[no_mangle]
                 #[allow(clippy::missing_safety_doc)]
@@ -111,6 +217,7 @@ macro_rules! plugin_define(
                         Ok(_) => $crate::glib::ffi::GTRUE,
                         Err(err) => {
                             $crate::error!($crate::CAT_PLUGIN_LOADING, "Failed to register plugin: {}", err);
+                            *INIT_ERROR.lock().unwrap() = Some(err.to_string());
                             $crate::glib::ffi::GFALSE
                         }
                     }
@@ -119,8 +226,10 @@ macro_rules! plugin_define(
                             .or_else(|| err.downcast_ref::<String>().map(|s| s.as_str()));
                         if let Some(cause) = cause {
                             $crate::error!($crate::CAT_PLUGIN_LOADING, "Failed to initialize plugin due to panic: {}", cause);
+                            *INIT_ERROR.lock().unwrap() = Some(format!("panic: {cause}"));
                         } else {
                             $crate::error!($crate::CAT_PLUGIN_LOADING, "Failed to initialize plugin due to panic");
+                            *INIT_ERROR.lock().unwrap() = Some(String::from("panic"));
                         }
 
                         $crate::glib::ffi::GFALSE
@@ -128,7 +237,6 @@ macro_rules! plugin_define(
                 }
             }
         }
-        pub use self::plugin_desc::plugin_register_static;
     };
 );
 
@@ -154,4 +262,71 @@ mod tests {
         crate::init().unwrap();
         plugin_register_static().unwrap();
     }
+
+    fn typefind_plugin_init(plugin: &crate::Plugin) -> Result<(), glib::BoolError> {
+        crate::TypeFind::register(
+            Some(plugin),
+            "gst_rs_plugin_test_typefind",
+            crate::Rank::NONE,
+            None,
+            None,
+            |_typefind| {},
+        )
+    }
+
+    crate::plugin_define!(
+        mod typefind_plugin: gst_rs_plugin_test_typefind,
+        env!("CARGO_PKG_DESCRIPTION"),
+        typefind_plugin_init,
+        env!("CARGO_PKG_VERSION"),
+        "MIT/X11",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_REPOSITORY")
+    );
+
+    fn failing_plugin_init(_plugin: &crate::Plugin) -> Result<(), glib::BoolError> {
+        Err(glib::bool_error!("broken"))
+    }
+
+    crate::plugin_define!(
+        mod failing_plugin: gst_rs_plugin_test_failing,
+        env!("CARGO_PKG_DESCRIPTION"),
+        failing_plugin_init,
+        env!("CARGO_PKG_VERSION"),
+        "MIT/X11",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_REPOSITORY")
+    );
+
+    #[test]
+    fn plugin_register_multiple() {
+        use crate::prelude::*;
+
+        crate::init().unwrap();
+
+        typefind_plugin::register_static_with_ranks(&[(
+            "gst_rs_plugin_test_typefind",
+            crate::Rank::MARGINAL,
+        )])
+        .unwrap();
+        // Registering again is a no-op
+        typefind_plugin::register_static().unwrap();
+
+        let feature = crate::Registry::get()
+            .lookup_feature("gst_rs_plugin_test_typefind")
+            .unwrap();
+        assert_eq!(feature.rank(), crate::Rank::MARGINAL);
+
+        let err = typefind_plugin::register_static_with_ranks(&[(
+            "gst_rs_plugin_test_missing",
+            crate::Rank::PRIMARY,
+        )])
+        .unwrap_err();
+        assert!(err.to_string().contains("no feature"));
+
+        let err = failing_plugin::register_static().unwrap_err();
+        assert!(err.to_string().contains("broken"));
+    }
 }
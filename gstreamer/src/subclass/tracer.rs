@@ -251,14 +251,38 @@ pub trait TracerImplExt: TracerImpl {
     /// Register a corresponding hook to be called for this tracer when certain events occur.
     ///
     /// Upon an event a corresponding method in `TracerImpl` will be called.
+    ///
+    /// Hooks are usually registered from `ObjectImpl::constructed()` and stay registered for
+    /// the remainder of the process' lifetime.
     fn register_hook(&self, hook: TracerHook);
+
+    // rustdoc-stripper-ignore-next
+    /// Register all `hooks` for this tracer.
+    ///
+    /// See [`register_hook()`](Self::register_hook) for details.
+    fn register_hooks(&self, hooks: impl IntoIterator<Item = TracerHook>) {
+        for hook in hooks {
+            self.register_hook(hook);
+        }
+    }
 }
 
 macro_rules! define_tracer_hooks {
     ($($(#[$attr:meta])* $name: ident($quark: literal) = |$this: ident, $ts: ident, $($cb_arg: ident: $cb_arg_ty: ty),*| $impl: block;)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
         pub enum TracerHook {
             $($(#[$attr])* $name),*
         }
+
+        impl TracerHook {
+            // rustdoc-stripper-ignore-next
+            /// The name of the hook as used by GStreamer, e.g. `pad-push-pre`.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $($(#[$attr])* Self::$name => $quark,)*
+                }
+            }
+        }
         impl<T: TracerImpl> TracerImplExt for T {
             fn register_hook(&self, hook: TracerHook) {
                 use TracerHook::*;
@@ -526,3 +550,84 @@ define_tracer_hooks! {
         this.pool_buffer_dequeued(ts, &pool, &b)
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::prelude::*;
+
+    pub mod imp {
+        use super::*;
+
+        #[derive(Default)]
+        pub struct TestTracer {
+            pub(super) buffers: AtomicU32,
+            pub(super) state_changes: AtomicU32,
+        }
+
+        impl ObjectImpl for TestTracer {
+            fn constructed(&self) {
+                self.parent_constructed();
+                self.register_hooks([TracerHook::PadPushPre, TracerHook::ElementChangeStatePre]);
+            }
+        }
+        impl GstObjectImpl for TestTracer {}
+        impl TracerImpl for TestTracer {
+            fn pad_push_pre(&self, _ts: u64, pad: &Pad, _buffer: &Buffer) {
+                if pad.parent().is_some_and(|p| p.name() == "tracer-test-src") {
+                    self.buffers.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+
+            fn element_change_state_pre(&self, _ts: u64, element: &Element, _change: StateChange) {
+                if element.name() == "tracer-test-sink" {
+                    self.state_changes.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for TestTracer {
+            const NAME: &'static str = "TestTracer";
+            type Type = super::TestTracer;
+            type ParentType = Tracer;
+        }
+    }
+
+    glib::wrapper! {
+        pub struct TestTracer(ObjectSubclass<imp::TestTracer>) @extends Tracer, crate::Object;
+    }
+
+    #[test]
+    fn test_hook_names() {
+        assert_eq!(TracerHook::PadPushPre.name(), "pad-push-pre");
+        assert_eq!(
+            TracerHook::ElementChangeStatePost.name(),
+            "element-change-state-post"
+        );
+    }
+
+    #[test]
+    fn test_tracer_hooks() {
+        crate::init().unwrap();
+
+        let tracer = glib::Object::new::<TestTracer>();
+
+        let pipeline = crate::parse::launch(
+            "fakesrc name=tracer-test-src num-buffers=5 ! fakesink name=tracer-test-sink",
+        )
+        .unwrap();
+        pipeline.set_state(crate::State::Playing).unwrap();
+        let bus = pipeline.bus().unwrap();
+        bus.timed_pop_filtered(crate::ClockTime::NONE, &[crate::MessageType::Eos])
+            .unwrap();
+        pipeline.set_state(crate::State::Null).unwrap();
+
+        let imp = tracer.imp();
+        assert_eq!(imp.buffers.load(Ordering::SeqCst), 5);
+        // NULL -> READY -> PAUSED -> PLAYING -> PAUSED -> READY -> NULL
+        assert_eq!(imp.state_changes.load(Ordering::SeqCst), 6);
+    }
+}
@@ -1,8 +1,11 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::ptr;
+use std::{
+    ptr,
+    sync::{Arc, Condvar, Mutex},
+};
 
-use glib::{ffi::gpointer, prelude::*, translate::*};
+use glib::{ffi::gpointer, prelude::*, subclass::prelude::*, translate::*};
 
 use crate::{ffi, TaskPool};
 
@@ -52,6 +55,25 @@ pub trait TaskPoolExtManual: IsA<TaskPool> + 'static {
 impl<O: IsA<TaskPool>> TaskPoolExtManual for O {}
 
 impl TaskPool {
+    // rustdoc-stripper-ignore-next
+    /// Creates a task pool that runs its tasks by calling `spawn`, e.g. to run them on a thread
+    /// pool of the application like `rayon` or the blocking pool of `tokio`.
+    ///
+    /// `spawn` must eventually call each task it receives. The tasks of elements usually run
+    /// for as long as the element is streaming, so the pool needs to provide enough threads for
+    /// all of them to run concurrently.
+    ///
+    /// Joining the handles of pushed tasks waits for the task to finish.
+    pub fn with_spawn_func<F>(spawn: F) -> Self
+    where
+        F: Fn(Box<dyn FnOnce() + Send + 'static>) + Send + Sync + 'static,
+    {
+        assert_initialized_main_thread!();
+        let pool = glib::Object::new::<SpawnTaskPool>();
+        let _ = pool.imp().spawn.set(Box::new(spawn));
+        pool.upcast()
+    }
+
     unsafe fn join(&self, id: ptr::NonNull<libc::c_void>) {
         ffi::gst_task_pool_join(self.to_glib_none().0, id.as_ptr())
     }
@@ -117,6 +139,84 @@ impl Drop for TaskPoolTaskHandle {
     }
 }
 
+#[derive(Debug, Default)]
+struct SpawnTask {
+    done: Mutex<bool>,
+    cond: Condvar,
+}
+
+// Marks the task as done once dropped, i.e. also if the task was not called or panicked
+struct SpawnTaskGuard(Arc<SpawnTask>);
+
+impl Drop for SpawnTaskGuard {
+    fn drop(&mut self) {
+        *self.0.done.lock().unwrap() = true;
+        self.0.cond.notify_all();
+    }
+}
+
+struct SpawnTaskHandle(Arc<SpawnTask>);
+
+impl TaskHandle for SpawnTaskHandle {
+    fn join(self) {
+        let mut done = self.0.done.lock().unwrap();
+        while !*done {
+            done = self.0.cond.wait(done).unwrap();
+        }
+    }
+}
+
+mod imp {
+    use std::sync::{Arc, OnceLock};
+
+    use super::{SpawnTask, SpawnTaskGuard, SpawnTaskHandle};
+    use crate::subclass::{prelude::*, TaskPoolFunction};
+
+    type SpawnFn = Box<dyn Fn(Box<dyn FnOnce() + Send + 'static>) + Send + Sync + 'static>;
+
+    #[derive(Default)]
+    pub struct SpawnTaskPool {
+        pub(super) spawn: OnceLock<SpawnFn>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for SpawnTaskPool {
+        const NAME: &'static str = "GstRsSpawnTaskPool";
+        type Type = super::SpawnTaskPool;
+        type ParentType = crate::TaskPool;
+    }
+
+    impl ObjectImpl for SpawnTaskPool {}
+
+    impl GstObjectImpl for SpawnTaskPool {}
+
+    impl TaskPoolImpl for SpawnTaskPool {
+        type Handle = SpawnTaskHandle;
+
+        fn push(&self, func: TaskPoolFunction) -> Result<Option<Self::Handle>, glib::Error> {
+            let Some(spawn) = self.spawn.get() else {
+                return Err(glib::Error::new(
+                    crate::CoreError::Failed,
+                    "No spawn function configured",
+                ));
+            };
+
+            let task = Arc::new(SpawnTask::default());
+            let guard = SpawnTaskGuard(task.clone());
+            spawn(Box::new(move || {
+                let _guard = guard;
+                func.call();
+            }));
+
+            Ok(Some(SpawnTaskHandle(task)))
+        }
+    }
+}
+
+glib::wrapper! {
+    struct SpawnTaskPool(ObjectSubclass<imp::SpawnTaskPool>) @extends TaskPool, crate::Object;
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::mpsc::{channel, RecvError};
@@ -150,4 +250,43 @@ mod tests {
 
         pool.cleanup();
     }
+
+    #[test]
+    fn test_with_spawn_func() {
+        use std::{
+            sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+            thread,
+            time::Duration,
+        };
+
+        crate::init().unwrap();
+
+        let spawned = Arc::new(AtomicUsize::new(0));
+        let pool = TaskPool::with_spawn_func({
+            let spawned = spawned.clone();
+            move |task| {
+                spawned.fetch_add(1, Ordering::SeqCst);
+                thread::spawn(task);
+            }
+        });
+        pool.prepare().unwrap();
+
+        let finished = Arc::new(AtomicBool::new(false));
+        let handle = pool
+            .push({
+                let finished = finished.clone();
+                move || {
+                    thread::sleep(Duration::from_millis(10));
+                    finished.store(true, Ordering::SeqCst);
+                }
+            })
+            .unwrap()
+            .unwrap();
+
+        handle.join();
+        assert!(finished.load(Ordering::SeqCst));
+        assert_eq!(spawned.load(Ordering::SeqCst), 1);
+
+        pool.cleanup();
+    }
 }